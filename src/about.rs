@@ -0,0 +1,71 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{CheckStatus, Error};
+use crate::models::{de_int64, ExtraFields, User};
+use crate::params::Params;
+use crate::Drive;
+
+/// Storage quota of the authenticated user, in bytes.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct StorageQuota {
+    /// Total storage. Missing for accounts with unlimited storage.
+    #[serde(deserialize_with = "de_int64")]
+    pub limit: Option<u64>,
+    /// Usage across all Google services.
+    #[serde(deserialize_with = "de_int64")]
+    pub usage: Option<u64>,
+    #[serde(deserialize_with = "de_int64")]
+    pub usage_in_drive: Option<u64>,
+    #[serde(deserialize_with = "de_int64")]
+    pub usage_in_drive_trash: Option<u64>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl StorageQuota {
+    /// Bytes left before hitting the limit, `None` if storage is unlimited.
+    pub fn remaining(&self) -> Option<u64> {
+        self.limit
+            .map(|limit| limit.saturating_sub(self.usage.unwrap_or_default()))
+    }
+
+    /// Whether a file of given size fits in the remaining quota.
+    pub fn fits(&self, bytes: u64) -> bool {
+        match self.remaining() {
+            Some(left) => bytes <= left,
+            None => true,
+        }
+    }
+}
+
+/// The authenticated user, their quota, and what Drive can convert to and from.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct About {
+    pub user: User,
+    pub storage_quota: StorageQuota,
+    /// Source MIME type -> Google-native types it can be imported as.
+    pub import_formats: HashMap<String, Vec<String>>,
+    /// Google-native MIME type -> formats it can be exported to.
+    pub export_formats: HashMap<String, Vec<String>>,
+    #[serde(deserialize_with = "de_int64")]
+    pub max_upload_size: Option<u64>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl Drive {
+    /// Get info about the authenticated user and their Drive.
+    pub fn about(&self) -> Result<About, Error> {
+        Ok(self
+            .get(
+                "/about",
+                Params::new().fields("user,storageQuota,importFormats,exportFormats,maxUploadSize"),
+            )?
+            .check_status()?
+            .json()?)
+    }
+}
@@ -0,0 +1,289 @@
+use std::time::SystemTime;
+
+use serde::Deserialize;
+
+use crate::error::{CheckStatus, Error};
+use crate::file_id::FileId;
+use crate::timestamp::format_rfc3339;
+use crate::Drive;
+
+const DRIVE_ACTIVITY_URL: &str = "https://driveactivity.googleapis.com/v2/activity:query";
+
+/// What happened in an activity.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ActivityAction {
+    Create,
+    Edit,
+    Move,
+    Rename,
+    Delete,
+    Restore,
+    PermissionChange,
+    Comment,
+    DlpChange,
+    Reference,
+    SettingsChange,
+    AppliedLabelChange,
+    /// An action type this crate doesn't know yet.
+    Other(String),
+}
+
+impl ActivityAction {
+    fn from_detail(detail: &serde_json::Value) -> ActivityAction {
+        let key = detail
+            .as_object()
+            .and_then(|d| d.keys().next())
+            .map(String::as_str)
+            .unwrap_or_default();
+        match key {
+            "create" => ActivityAction::Create,
+            "edit" => ActivityAction::Edit,
+            "move" => ActivityAction::Move,
+            "rename" => ActivityAction::Rename,
+            "delete" => ActivityAction::Delete,
+            "restore" => ActivityAction::Restore,
+            "permissionChange" => ActivityAction::PermissionChange,
+            "comment" => ActivityAction::Comment,
+            "dlpChange" => ActivityAction::DlpChange,
+            "reference" => ActivityAction::Reference,
+            "settingsChange" => ActivityAction::SettingsChange,
+            "appliedLabelChange" => ActivityAction::AppliedLabelChange,
+            other => ActivityAction::Other(other.to_string()),
+        }
+    }
+
+    /// Value for the API's `detail.action_detail_case` filter.
+    fn filter_name(&self) -> &str {
+        match self {
+            ActivityAction::Create => "CREATE",
+            ActivityAction::Edit => "EDIT",
+            ActivityAction::Move => "MOVE",
+            ActivityAction::Rename => "RENAME",
+            ActivityAction::Delete => "DELETE",
+            ActivityAction::Restore => "RESTORE",
+            ActivityAction::PermissionChange => "PERMISSION_CHANGE",
+            ActivityAction::Comment => "COMMENT",
+            ActivityAction::DlpChange => "DLP_CHANGE",
+            ActivityAction::Reference => "REFERENCE",
+            ActivityAction::SettingsChange => "SETTINGS_CHANGE",
+            ActivityAction::AppliedLabelChange => "APPLIED_LABEL_CHANGE",
+            ActivityAction::Other(name) => name,
+        }
+    }
+}
+
+/// Who did something.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Actor {
+    /// Resource name of a known user, `people/<account id>`. Look it up with the People API to
+    /// get a name or email.
+    pub person_name: Option<String>,
+    pub is_current_user: bool,
+    /// Set when the actor isn't a known user: "anonymous", "impersonation", "system",
+    /// "administrator" or "deleted".
+    pub other: Option<String>,
+}
+
+/// Item an activity happened to.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ActivityTarget {
+    pub file_id: String,
+    pub title: String,
+    pub mime_type: String,
+}
+
+/// One entry of a file or folder's activity history.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Activity {
+    /// The main thing that happened. `details` has every action when several were grouped.
+    pub action: ActivityAction,
+    pub details: Vec<ActivityAction>,
+    pub actors: Vec<Actor>,
+    pub targets: Vec<ActivityTarget>,
+    /// When it happened, or when it ended for activities spanning a time range.
+    pub time: Option<String>,
+    /// Full activity record, for details that aren't mapped above (e.g. the old and new
+    /// parents of a move).
+    pub raw: serde_json::Value,
+}
+
+impl Activity {
+    fn from_raw(raw: serde_json::Value) -> Activity {
+        let actors = raw["actors"]
+            .as_array()
+            .map(|actors| {
+                actors
+                    .iter()
+                    .map(|a| {
+                        let known = &a["user"]["knownUser"];
+                        Actor {
+                            person_name: known["personName"].as_str().map(String::from),
+                            is_current_user: known["isCurrentUser"].as_bool().unwrap_or_default(),
+                            other: a
+                                .as_object()
+                                .and_then(|a| a.keys().find(|k| *k != "user"))
+                                .cloned(),
+                        }
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let targets = raw["targets"]
+            .as_array()
+            .map(|targets| {
+                targets
+                    .iter()
+                    .filter_map(|t| t["driveItem"].as_object())
+                    .map(|item| ActivityTarget {
+                        file_id: item["name"]
+                            .as_str()
+                            .unwrap_or_default()
+                            .trim_start_matches("items/")
+                            .to_string(),
+                        title: item["title"].as_str().unwrap_or_default().to_string(),
+                        mime_type: item["mimeType"].as_str().unwrap_or_default().to_string(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let details = raw["actions"]
+            .as_array()
+            .map(|actions| {
+                actions
+                    .iter()
+                    .map(|a| ActivityAction::from_detail(&a["detail"]))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let time = raw["timestamp"]
+            .as_str()
+            .or_else(|| raw["timeRange"]["endTime"].as_str())
+            .map(String::from);
+        Activity {
+            action: ActivityAction::from_detail(&raw["primaryActionDetail"]),
+            details,
+            actors,
+            targets,
+            time,
+            raw,
+        }
+    }
+}
+
+/// What `Drive::activity` should look at.
+///
+/// ```no_run
+/// # use std::time::{Duration, SystemTime};
+/// # use google_api::*;
+/// # let drive = Drive::new(String::new(), String::new(), None)?;
+/// # let folder_id = FileId::unchecked("1aBc");
+/// let week_ago = SystemTime::now() - Duration::from_secs(7 * 24 * 60 * 60);
+/// let query = ActivityQuery::folder(folder_id).after(week_ago).action(ActivityAction::Move);
+/// for activity in drive.activity(&query)? {
+///     println!("{:?}", activity);
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct ActivityQuery {
+    item: String,
+    ancestor: bool,
+    after: Option<SystemTime>,
+    before: Option<SystemTime>,
+    actions: Vec<ActivityAction>,
+}
+
+impl ActivityQuery {
+    /// Activity on a single file or folder itself.
+    pub fn item(file_id: impl Into<FileId>) -> ActivityQuery {
+        ActivityQuery {
+            item: file_id.into().to_string(),
+            ancestor: false,
+            after: None,
+            before: None,
+            actions: vec![],
+        }
+    }
+
+    /// Activity on everything inside a folder, at any depth.
+    pub fn folder(folder_id: impl Into<FileId>) -> ActivityQuery {
+        ActivityQuery {
+            ancestor: true,
+            ..ActivityQuery::item(folder_id)
+        }
+    }
+
+    pub fn after(mut self, time: SystemTime) -> ActivityQuery {
+        self.after = Some(time);
+        self
+    }
+
+    pub fn before(mut self, time: SystemTime) -> ActivityQuery {
+        self.before = Some(time);
+        self
+    }
+
+    /// Only this kind of action. Can be given more than once.
+    pub fn action(mut self, action: ActivityAction) -> ActivityQuery {
+        self.actions.push(action);
+        self
+    }
+
+    fn filter(&self) -> String {
+        let mut clauses = vec![];
+        if let Some(after) = self.after {
+            clauses.push(format!("time >= \"{}\"", format_rfc3339(after)));
+        }
+        if let Some(before) = self.before {
+            clauses.push(format!("time < \"{}\"", format_rfc3339(before)));
+        }
+        if !self.actions.is_empty() {
+            let names: Vec<&str> = self.actions.iter().map(|a| a.filter_name()).collect();
+            clauses.push(format!("detail.action_detail_case:({})", names.join(" ")));
+        }
+        clauses.join(" AND ")
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct ActivityPage {
+    activities: Vec<serde_json::Value>,
+    next_page_token: Option<String>,
+}
+
+impl Drive {
+    /// Fetch activity history matching given query from the Drive Activity API, newest first.
+    /// Needs the `Scope::DriveActivityReadonly` scope.
+    pub fn activity(&self, query: &ActivityQuery) -> Result<Vec<Activity>, Error> {
+        let mut body = serde_json::json!({ "pageSize": 100 });
+        let item = format!("items/{}", query.item);
+        if query.ancestor {
+            body["ancestorName"] = item.into();
+        } else {
+            body["itemName"] = item.into();
+        }
+        let filter = query.filter();
+        if !filter.is_empty() {
+            body["filter"] = filter.into();
+        }
+
+        let mut activities = vec![];
+        loop {
+            let page: ActivityPage = self
+                .client
+                .post(DRIVE_ACTIVITY_URL)
+                .header("Authorization", self.auth.bearer()?)
+                .json(&body)
+                .send()?
+                .check_status()?
+                .json()?;
+            activities.extend(page.activities.into_iter().map(Activity::from_raw));
+            match page.next_page_token {
+                Some(token) => body["pageToken"] = token.into(),
+                None => break,
+            }
+        }
+        Ok(activities)
+    }
+}
@@ -0,0 +1,27 @@
+use reqwest::blocking::{Client, RequestBuilder};
+use reqwest::{Method, Url};
+
+use crate::auth::Auth;
+use crate::error::Error;
+
+/// Authorized request for the endpoint made of `base` and given path segments. Segments are
+/// percent-encoded, so ids, ranges and names can be passed as they are.
+pub(crate) fn request(
+    client: &Client,
+    auth: &Auth,
+    base: &str,
+    method: Method,
+    segments: &[&str],
+) -> Result<RequestBuilder, Error> {
+    let request = match Url::parse(base) {
+        Ok(mut url) => {
+            if let Ok(mut path) = url.path_segments_mut() {
+                path.pop_if_empty().extend(segments);
+            }
+            client.request(method, url)
+        }
+        // Left to fail when sent, with the reason.
+        Err(_) => client.request(method, base),
+    };
+    Ok(request.header("Authorization", auth.bearer()?))
+}
@@ -0,0 +1,61 @@
+use std::path::Path;
+
+use crate::error::{CheckStatus, Error};
+use crate::file_id::FileId;
+use crate::list::{escape_query, ListOptions};
+use crate::models::DriveFile;
+use crate::upload::UploadOptions;
+use crate::Drive;
+
+/// Alias for the hidden per-app folder, usable anywhere a folder id is expected. Needs the
+/// `Scope::DriveAppData` scope.
+pub const APP_DATA_FOLDER: &str = "appDataFolder";
+
+impl Drive {
+    /// List every file in the app data folder.
+    pub fn list_app_data(&self) -> Result<Vec<DriveFile>, Error> {
+        self.list_files(&ListOptions::new().spaces(APP_DATA_FOLDER))
+    }
+
+    /// Find a file in the app data folder by name.
+    pub fn find_app_data(&self, name: &str) -> Result<Option<DriveFile>, Error> {
+        let files = self.list_files(
+            &ListOptions::new()
+                .spaces(APP_DATA_FOLDER)
+                .query(&format!("name = '{}'", escape_query(name)))
+                .limit(1),
+        )?;
+        Ok(files.into_iter().next())
+    }
+
+    /// Write given content to a file in the app data folder, replacing it if it exists.
+    pub fn write_app_data(&self, name: &str, content: &[u8]) -> Result<DriveFile, Error> {
+        let existing = self.find_app_data(name)?;
+        match existing {
+            Some(file) => self.send_multipart(
+                Some(&file.id),
+                Path::new(name),
+                content,
+                &UploadOptions::new(),
+            ),
+            None => self.send_multipart(
+                None,
+                Path::new(name),
+                content,
+                &UploadOptions::new().parent(FileId::unchecked(APP_DATA_FOLDER)),
+            ),
+        }
+    }
+
+    /// Read a file from the app data folder, `None` if there isn't one with that name.
+    pub fn read_app_data(&self, name: &str) -> Result<Option<Vec<u8>>, Error> {
+        let file = match self.find_app_data(name)? {
+            Some(file) => file,
+            None => return Ok(None),
+        };
+        let mut resp = self.get_media(&file.id, None)?.check_status()?;
+        let mut content = vec![];
+        resp.copy_to(&mut content)?;
+        Ok(Some(content))
+    }
+}
@@ -0,0 +1,118 @@
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, SystemTime};
+
+use crate::error::Error;
+use crate::service_account::ServiceAccountKey;
+use crate::GoogleOAuthToken;
+
+/// Tokens this close to expiring are refreshed before use, so they don't run out mid-request.
+const EXPIRY_MARGIN: Duration = Duration::from_secs(60);
+
+/// How to get a new access token once the current one runs out.
+#[derive(Debug)]
+enum Renewal {
+    /// Refresh a user's token with the OAuth client it was granted to, saving it again.
+    OAuth {
+        client_id: String,
+        client_secret: String,
+    },
+    /// Ask for a new token with a fresh assertion signed by the key.
+    ServiceAccount {
+        key: ServiceAccountKey,
+        scopes: Vec<String>,
+        subject: Option<String>,
+    },
+}
+
+/// A client's access token, shared with its clones and refreshed before a request once it's
+/// about to expire, so long-running programs don't start failing after an hour.
+#[derive(Debug, Clone)]
+pub(crate) struct Auth {
+    token: Arc<Mutex<GoogleOAuthToken>>,
+    renewal: Arc<Renewal>,
+}
+
+impl Auth {
+    /// Sign in with given OAuth client, asking for consent if the saved token lacks any scope.
+    pub(crate) fn from_config(
+        client_id: &String,
+        client_secret: &String,
+        scopes: &[String],
+    ) -> Result<Auth, Error> {
+        Auth::from_config_at(client_id, client_secret, scopes, None)
+    }
+
+    /// `from_config`, keeping the token in given file rather than the current profile's.
+    pub(crate) fn from_config_at(
+        client_id: &String,
+        client_secret: &String,
+        scopes: &[String],
+        path: Option<PathBuf>,
+    ) -> Result<Auth, Error> {
+        let token = GoogleOAuthToken::from_config_at(client_id, client_secret, scopes, path)?;
+        Ok(Auth::new(
+            token,
+            Renewal::OAuth {
+                client_id: client_id.clone(),
+                client_secret: client_secret.clone(),
+            },
+        ))
+    }
+
+    /// Act as given service account, impersonating `subject` when set.
+    pub(crate) fn service_account(
+        key: &ServiceAccountKey,
+        scopes: &[String],
+        subject: Option<&str>,
+    ) -> Result<Auth, Error> {
+        let token = key.access_token(scopes, subject)?;
+        Ok(Auth::new(
+            token,
+            Renewal::ServiceAccount {
+                key: key.clone(),
+                scopes: scopes.to_vec(),
+                subject: subject.map(String::from),
+            },
+        ))
+    }
+
+    fn new(token: GoogleOAuthToken, renewal: Renewal) -> Auth {
+        Auth {
+            token: Arc::new(Mutex::new(token)),
+            renewal: Arc::new(renewal),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, GoogleOAuthToken> {
+        self.token.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// When the current access token expires.
+    pub(crate) fn expires(&self) -> SystemTime {
+        self.lock().expires
+    }
+
+    /// `Authorization` header value, refreshing the token first if it expires within a
+    /// minute. Clones waiting on the lock meanwhile get the new token.
+    pub(crate) fn bearer(&self) -> Result<String, Error> {
+        let mut token = self.lock();
+        if token.expires <= SystemTime::now() + EXPIRY_MARGIN {
+            log::debug!("access token about to expire, refreshing");
+            match &*self.renewal {
+                Renewal::OAuth {
+                    client_id,
+                    client_secret,
+                } => token
+                    .refresh_access_token(client_id, client_secret)
+                    .map_err(Error::Auth)?,
+                Renewal::ServiceAccount {
+                    key,
+                    scopes,
+                    subject,
+                } => *token = key.access_token(scopes, subject.as_deref())?,
+            }
+        }
+        Ok(format!("Bearer {}", token.token))
+    }
+}
@@ -0,0 +1,96 @@
+use std::collections::HashMap;
+
+use crate::concurrency::run_bounded;
+use crate::error::{CheckStatus, Error};
+use crate::file_id::FileId;
+use crate::models::{DriveFile, FILE_FIELDS};
+use crate::params::Params;
+use crate::retry::send_with_policy;
+use crate::Drive;
+
+/// Requests in flight at once for bulk operations, kept low to stay under Drive's per-user
+/// rate limit.
+const BATCH_JOBS: usize = 8;
+
+/// Outcome of a bulk operation like `Drive::trash_many`.
+#[derive(Debug, Clone, Default)]
+pub struct BulkReport {
+    pub succeeded: Vec<FileId>,
+    /// Items that failed, with the reason.
+    pub failed: Vec<(FileId, String)>,
+}
+
+impl BulkReport {
+    fn from_results(results: Vec<(FileId, Result<(), Error>)>) -> BulkReport {
+        let mut report = BulkReport::default();
+        for (id, result) in results {
+            match result {
+                Ok(()) => report.succeeded.push(id),
+                Err(e) => report.failed.push((id, e.to_string())),
+            }
+        }
+        report
+    }
+}
+
+impl Drive {
+    /// Fetch metadata for many files at once, a few requests at a time. Each id maps to its
+    /// file or to the error fetching it, so one missing file doesn't fail the rest.
+    pub fn get_metadata_batch<I>(&self, ids: I) -> HashMap<FileId, Result<DriveFile, Error>>
+    where
+        I: IntoIterator,
+        I::Item: Into<FileId>,
+    {
+        let ids: Vec<FileId> = ids.into_iter().map(Into::into).collect();
+        let results = run_bounded(ids, BATCH_JOBS, |id| {
+            let result = self
+                .get(
+                    format!("/files/{}", id).as_str(),
+                    Params::new().fields(FILE_FIELDS),
+                )
+                .and_then(|r| r.check_status())
+                .and_then(|r| Ok(r.json()?));
+            (id, result)
+        });
+        results.into_iter().collect()
+    }
+
+    /// Move many files to the trash, retrying any that get rate limited. Failures don't stop
+    /// the rest.
+    pub fn trash_many<I>(&self, ids: I) -> BulkReport
+    where
+        I: IntoIterator,
+        I::Item: Into<FileId>,
+    {
+        let ids: Vec<FileId> = ids.into_iter().map(Into::into).collect();
+        let results = run_bounded(ids, BATCH_JOBS, |id| {
+            let endpoint = format!("/files/{}", id);
+            let result = send_with_policy(&self.retry, || {
+                self.patch(
+                    endpoint.as_str(),
+                    Params::new().fields("id"),
+                    serde_json::json!({ "trashed": true }),
+                )
+            });
+            (id, result.map(|_| ()))
+        });
+        BulkReport::from_results(results)
+    }
+
+    /// Permanently delete many files, skipping the trash. Failures don't stop the rest.
+    pub fn delete_many<I>(&self, ids: I) -> BulkReport
+    where
+        I: IntoIterator,
+        I::Item: Into<FileId>,
+    {
+        let ids: Vec<FileId> = ids.into_iter().map(Into::into).collect();
+        let results = run_bounded(ids, BATCH_JOBS, |id| {
+            let endpoint = format!("/files/{}", id);
+            let result = send_with_policy(&self.retry, || {
+                self.delete(endpoint.as_str(), Params::new())
+            });
+            (id, result.map(|_| ()))
+        });
+        BulkReport::from_results(results)
+    }
+}
@@ -0,0 +1,54 @@
+//! `google-api auth ...`
+
+use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
+use google_api::{current_profile, profiles, switch_profile};
+
+use crate::output::{format, Format};
+use crate::CliResult;
+
+pub fn command() -> App<'static, 'static> {
+    SubCommand::with_name("auth")
+        .about("Google accounts signed in as, one per profile")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .subcommand(
+            SubCommand::with_name("list")
+                .about("List profiles with a saved sign in, the current one marked with *"),
+        )
+        .subcommand(
+            SubCommand::with_name("switch")
+                .about("Make a profile the current one. A new one asks to sign in on first use")
+                .arg(Arg::with_name("name").required(true)),
+        )
+}
+
+pub fn run(m: &ArgMatches) -> CliResult {
+    match m.subcommand() {
+        ("list", Some(m)) => list(m),
+        ("switch", Some(m)) => {
+            switch_profile(m.value_of("name").unwrap_or_default())?;
+            Ok(())
+        }
+        _ => unreachable!("subcommand required"),
+    }
+}
+
+/// `* name` for the current profile, `  name` for the others, which the completion scripts
+/// rely on.
+fn list(m: &ArgMatches) -> CliResult {
+    let current = current_profile();
+    let mut names = profiles()?;
+    if !names.contains(&current) {
+        names.push(current.clone());
+        names.sort();
+    }
+    if format(m) == Format::Json {
+        let json = serde_json::json!({ "current": current, "profiles": names });
+        println!("{}", serde_json::to_string_pretty(&json)?);
+        return Ok(());
+    }
+    for name in names {
+        let mark = if name == current { '*' } else { ' ' };
+        println!("{} {}", mark, name);
+    }
+    Ok(())
+}
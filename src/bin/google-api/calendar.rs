@@ -0,0 +1,459 @@
+//! `google-api calendar ...`
+//!
+//! Times are read in the local time zone, from `TZ` or the system's setting, at the offset
+//! in effect on the day in question.
+
+use std::time::SystemTime;
+
+use chrono::{
+    Datelike, Duration, Local, LocalResult, NaiveDate, NaiveTime, SecondsFormat, TimeZone, Utc,
+};
+use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
+use google_api::{Calendar, Event, EventQuery, EventTime, PRIMARY_CALENDAR};
+
+use crate::exit::AuthError;
+use crate::output::{format, parse_day, print_rows, Format};
+use crate::{credentials, CliResult};
+
+pub fn command() -> App<'static, 'static> {
+    let calendar = || {
+        Arg::with_name("calendar")
+            .long("calendar")
+            .short("c")
+            .value_name("ID")
+            .default_value(PRIMARY_CALENDAR)
+            .help("Calendar id, e.g. a shared calendar's address")
+    };
+    SubCommand::with_name("calendar")
+        .about("Google Calendar")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .subcommand(
+            SubCommand::with_name("agenda")
+                .about("List events from today on")
+                .arg(
+                    Arg::with_name("days")
+                        .long("days")
+                        .short("d")
+                        .value_name("N")
+                        .default_value("1")
+                        .validator(|v| match v.parse::<u64>() {
+                            Ok(n) if n >= 1 => Ok(()),
+                            _ => Err(String::from("expected a number of days, at least 1")),
+                        })
+                        .help("How many days to show, counting today"),
+                )
+                .arg(calendar()),
+        )
+        .subcommand(
+            SubCommand::with_name("add")
+                .about("Create an event")
+                .arg(
+                    Arg::with_name("summary")
+                        .required(true)
+                        .help("Title of the event"),
+                )
+                .arg(
+                    Arg::with_name("at")
+                        .long("at")
+                        .value_name("WHEN")
+                        .required(true)
+                        .help(
+                            "Start, e.g. \"tomorrow 9am\", \"friday 14:30\", \"next monday \
+                             noon\" or \"2024-05-01 10:15pm\". A day without a time makes an \
+                             all-day event",
+                        ),
+                )
+                .arg(
+                    Arg::with_name("duration")
+                        .long("duration")
+                        .value_name("DURATION")
+                        .default_value("1h")
+                        .validator(|v| parse_duration(&v).map(|_| ()))
+                        .help("Length, e.g. 30m, 1h30m or 2d"),
+                )
+                .arg(
+                    Arg::with_name("location")
+                        .long("location")
+                        .short("l")
+                        .value_name("PLACE"),
+                )
+                .arg(
+                    Arg::with_name("description")
+                        .long("description")
+                        .value_name("TEXT"),
+                )
+                .arg(
+                    Arg::with_name("attendee")
+                        .long("attendee")
+                        .value_name("ADDRESS")
+                        .multiple(true)
+                        .number_of_values(1)
+                        .help("Invite someone, can be given more than once"),
+                )
+                .arg(
+                    Arg::with_name("meet")
+                        .long("meet")
+                        .help("Add a Google Meet call"),
+                )
+                .arg(calendar()),
+        )
+}
+
+pub fn run(matches: &ArgMatches) -> CliResult {
+    let calendar = connect()?;
+    match matches.subcommand() {
+        ("agenda", Some(m)) => agenda(&calendar, m),
+        ("add", Some(m)) => add(&calendar, m),
+        _ => unreachable!("subcommand required"),
+    }
+}
+
+fn connect() -> Result<Calendar, Box<dyn std::error::Error>> {
+    let (client_id, client_secret) = credentials().map_err(AuthError)?;
+    Ok(Calendar::new(client_id, client_secret, None)?)
+}
+
+fn agenda(calendar: &Calendar, m: &ArgMatches) -> CliResult {
+    let zone = local_zone()?;
+    let days: i64 = m.value_of("days").unwrap_or("1").parse()?;
+    let today = today(&zone);
+    let start = start_of_day(&zone, today)?;
+    let end = start_of_day(&zone, today + Duration::days(days))?;
+    let events = calendar.list_events(
+        m.value_of("calendar").unwrap_or(PRIMARY_CALENDAR),
+        &EventQuery::between(start, end),
+    )?;
+
+    if format(m) == Format::Json {
+        println!("{}", serde_json::to_string_pretty(&events)?);
+        return Ok(());
+    }
+    let rows: Vec<Vec<String>> = events
+        .iter()
+        .map(|event| {
+            let start = event.start.clone().unwrap_or_default();
+            let end = event.end.clone().unwrap_or_default();
+            // Timed events come back in the calendar's zone, cut the clock out of them.
+            let clock = |t: &EventTime| {
+                t.date_time
+                    .as_deref()
+                    .and_then(|t| t.get(11..16))
+                    .map(String::from)
+            };
+            let (day, time) = match (&start.date_time, &start.date) {
+                (Some(date_time), _) => (
+                    date_time.get(..10).unwrap_or(date_time).to_string(),
+                    match (clock(&start), clock(&end)) {
+                        (Some(from), Some(to)) => format!("{}-{}", from, to),
+                        (from, _) => from.unwrap_or_default(),
+                    },
+                ),
+                (None, Some(date)) => (date.clone(), String::from("all day")),
+                (None, None) => (String::new(), String::new()),
+            };
+            vec![
+                day,
+                time,
+                event.summary.clone().unwrap_or_else(|| "(no title)".into()),
+                event.location.clone().unwrap_or_default(),
+            ]
+        })
+        .collect();
+    if rows.is_empty() && format(m) == Format::Table {
+        eprintln!("No events");
+    }
+    print_rows(&rows, format(m));
+    Ok(())
+}
+
+fn add(calendar: &Calendar, m: &ArgMatches) -> CliResult {
+    let zone = local_zone()?;
+    let when = parse_when(m.value_of("at").unwrap_or_default(), today(&zone))?;
+    let duration = parse_duration(m.value_of("duration").unwrap_or("1h"))?;
+    let (start, end) = match when.minute {
+        Some(minute) => (
+            EventTime::at_rfc3339(&local_time(&zone, when.day, minute)?),
+            EventTime::at_rfc3339(&local_time(&zone, when.day, minute + duration)?),
+        ),
+        None => {
+            let days = ((duration + 1439) / 1440).max(1);
+            (
+                EventTime::all_day(&when.day.to_string()),
+                EventTime::all_day(&(when.day + Duration::days(days)).to_string()),
+            )
+        }
+    };
+
+    let mut event = Event::new(m.value_of("summary").unwrap_or_default(), start, end);
+    if let Some(location) = m.value_of("location") {
+        event = event.location(location);
+    }
+    if let Some(description) = m.value_of("description") {
+        event = event.description(description);
+    }
+    for attendee in m.values_of("attendee").into_iter().flatten() {
+        event = event.attendee(attendee);
+    }
+    if m.is_present("meet") {
+        event = event.with_meet();
+    }
+    let created =
+        calendar.create_event(m.value_of("calendar").unwrap_or(PRIMARY_CALENDAR), &event)?;
+
+    if format(m) == Format::Json {
+        println!("{}", serde_json::to_string_pretty(&created)?);
+        return Ok(());
+    }
+    let start = created.start.clone().unwrap_or_default();
+    eprintln!(
+        "Created {} for {}",
+        created.summary.as_deref().unwrap_or_default(),
+        start.date_time.or(start.date).unwrap_or_default()
+    );
+    println!("{}", created.html_link);
+    Ok(())
+}
+
+/// The local time zone. Rather than guess UTC, fail when there's none to go by.
+fn local_zone() -> Result<Local, String> {
+    #[cfg(unix)]
+    {
+        let configured =
+            std::env::var_os("TZ").is_some() || std::path::Path::new("/etc/localtime").exists();
+        if !configured {
+            return Err(String::from(
+                "can't tell the local time zone, set TZ, e.g. TZ=Europe/Berlin",
+            ));
+        }
+    }
+    Ok(Local)
+}
+
+/// The local date.
+fn today(zone: &Local) -> NaiveDate {
+    Utc::now().with_timezone(zone).date_naive()
+}
+
+/// When given local day starts. Where the clocks skip midnight that's when they jump, at
+/// midnight by the offset before.
+fn start_of_day(zone: &Local, day: NaiveDate) -> Result<SystemTime, String> {
+    let midnight = day.and_time(NaiveTime::MIN);
+    let offset = zone
+        .offset_from_local_datetime(&midnight)
+        .earliest()
+        .or_else(|| {
+            zone.offset_from_local_datetime(&(midnight - Duration::days(1)))
+                .earliest()
+        })
+        .ok_or_else(|| format!("can't tell when {} starts in the local time zone", day))?;
+    Ok((midnight - offset).and_utc().into())
+}
+
+/// RFC 3339 timestamp of a local time given as a day and minutes into it, which may run
+/// past midnight. The offset is the one in effect at that time, and times the clocks skip
+/// or go through twice are refused.
+fn local_time(zone: &Local, day: NaiveDate, minute: i64) -> Result<String, String> {
+    let time = day.and_time(NaiveTime::MIN) + Duration::minutes(minute);
+    match zone.from_local_datetime(&time) {
+        LocalResult::Single(t) => Ok(t.to_rfc3339_opts(SecondsFormat::Secs, false)),
+        LocalResult::Ambiguous(..) => Err(format!(
+            "{} happens twice as the clocks go back, pick another time",
+            time.format("%Y-%m-%d %H:%M")
+        )),
+        LocalResult::None => Err(format!(
+            "{} doesn't exist as the clocks go forward, pick another time",
+            time.format("%Y-%m-%d %H:%M")
+        )),
+    }
+}
+
+/// When an event starts: a day, and minutes into it unless it's all day.
+struct When {
+    day: NaiveDate,
+    minute: Option<i64>,
+}
+
+/// Read times like `tomorrow 9am`, `friday 14:30`, `next monday noon`, `2024-05-01 10:15pm`
+/// or just `3pm`. The day defaults to today, a weekday means the coming one (today
+/// included, unless it's `next`).
+fn parse_when(s: &str, today: NaiveDate) -> Result<When, String> {
+    let invalid = || {
+        format!(
+            "can't read \"{}\" as a time, try e.g. \"tomorrow 9am\" or \"2024-05-01 14:30\"",
+            s
+        )
+    };
+    let text = s.to_lowercase().replace(" am", "am").replace(" pm", "pm");
+    let mut day = None;
+    let mut minute = None;
+    let mut next = false;
+    for word in text.split_whitespace() {
+        match word {
+            "today" => day = Some(today),
+            "tomorrow" => day = today.succ_opt(),
+            "next" => next = true,
+            "at" | "on" => {}
+            "noon" => minute = Some(12 * 60),
+            "midnight" => minute = Some(0),
+            w if w.contains('-') => day = Some(parse_day(w)?),
+            w => match weekday(w) {
+                Some(weekday) => {
+                    let from = i64::from(today.weekday().num_days_from_sunday());
+                    let ahead = (weekday - from).rem_euclid(7);
+                    let ahead = if ahead == 0 && next { 7 } else { ahead };
+                    day = Some(today + Duration::days(ahead));
+                }
+                None => minute = Some(parse_clock(w).ok_or_else(invalid)?),
+            },
+        }
+    }
+    if day.is_none() && minute.is_none() {
+        return Err(invalid());
+    }
+    Ok(When {
+        day: day.unwrap_or(today),
+        minute,
+    })
+}
+
+/// A weekday's number, Sunday being 0, from its name or the first three letters or more.
+fn weekday(word: &str) -> Option<i64> {
+    const DAYS: [&str; 7] = [
+        "sunday",
+        "monday",
+        "tuesday",
+        "wednesday",
+        "thursday",
+        "friday",
+        "saturday",
+    ];
+    if word.len() < 3 {
+        return None;
+    }
+    DAYS.iter()
+        .position(|day| day.starts_with(word))
+        .map(|i| i as i64)
+}
+
+/// `9am`, `9:30pm`, `14:00` or `14` as minutes after midnight.
+fn parse_clock(word: &str) -> Option<i64> {
+    let (clock, pm) = if let Some(clock) = word.strip_suffix("am") {
+        (clock, Some(false))
+    } else if let Some(clock) = word.strip_suffix("pm") {
+        (clock, Some(true))
+    } else {
+        (word, None)
+    };
+    let mut parts = clock.splitn(2, ':');
+    let hour: i64 = parts.next()?.parse().ok()?;
+    let minute: i64 = match parts.next() {
+        Some(minute) => minute.parse().ok()?,
+        None => 0,
+    };
+    if !(0..60).contains(&minute) {
+        return None;
+    }
+    let hour = match pm {
+        Some(pm) if (1..=12).contains(&hour) => hour % 12 + if pm { 12 } else { 0 },
+        None if (0..24).contains(&hour) => hour,
+        _ => return None,
+    };
+    Some(hour * 60 + minute)
+}
+
+/// `30m`, `1h`, `1h30m`, `2d` or a bare number of minutes, in minutes.
+fn parse_duration(s: &str) -> Result<i64, String> {
+    let invalid = || format!("invalid duration {}, expected e.g. 30m, 1h or 1h30m", s);
+    if let Ok(minutes) = s.parse::<i64>() {
+        return if minutes > 0 {
+            Ok(minutes)
+        } else {
+            Err(invalid())
+        };
+    }
+    let mut total = 0;
+    let mut number = String::new();
+    for c in s.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+        let n: i64 = number.parse().map_err(|_| invalid())?;
+        number.clear();
+        total += n * match c {
+            'd' => 1440,
+            'h' => 60,
+            'm' => 1,
+            _ => return Err(invalid()),
+        };
+    }
+    if !number.is_empty() || total == 0 {
+        return Err(invalid());
+    }
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A Wednesday.
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 5, 1).unwrap()
+    }
+
+    fn when(s: &str) -> (String, Option<i64>) {
+        let when = parse_when(s, today()).unwrap();
+        (when.day.to_string(), when.minute)
+    }
+
+    #[test]
+    fn reads_days_and_times() {
+        assert_eq!(when("tomorrow 9am"), ("2024-05-02".into(), Some(9 * 60)));
+        assert_eq!(when("Tomorrow 9 AM"), ("2024-05-02".into(), Some(9 * 60)));
+        assert_eq!(
+            when("friday 14:30"),
+            ("2024-05-03".into(), Some(14 * 60 + 30))
+        );
+        assert_eq!(when("wed"), ("2024-05-01".into(), None));
+        assert_eq!(
+            when("next wednesday noon"),
+            ("2024-05-08".into(), Some(12 * 60))
+        );
+        assert_eq!(when("monday at midnight"), ("2024-05-06".into(), Some(0)));
+        assert_eq!(
+            when("2024-05-01 10:15pm"),
+            ("2024-05-01".into(), Some(22 * 60 + 15))
+        );
+        assert_eq!(when("12am"), ("2024-05-01".into(), Some(0)));
+        assert_eq!(when("3pm"), ("2024-05-01".into(), Some(15 * 60)));
+    }
+
+    #[test]
+    fn rejects_what_isnt_a_time() {
+        for bad in &[
+            "",
+            "someday",
+            "at",
+            "25:00",
+            "13pm",
+            "9:60",
+            "2024-02-31 9am",
+            "2024-05-01T09:00",
+        ] {
+            assert!(parse_when(bad, today()).is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn reads_durations() {
+        assert_eq!(parse_duration("30m"), Ok(30));
+        assert_eq!(parse_duration("1h"), Ok(60));
+        assert_eq!(parse_duration("1h30m"), Ok(90));
+        assert_eq!(parse_duration("2d"), Ok(2 * 1440));
+        assert_eq!(parse_duration("45"), Ok(45));
+        for bad in &["", "0", "-5", "0m", "h", "1h30", "1x", "1.5h"] {
+            assert!(parse_duration(bad).is_err(), "{}", bad);
+        }
+    }
+}
@@ -0,0 +1,106 @@
+//! `google-api completions`
+
+use std::io::{self, Write};
+
+use clap::{App, Arg, ArgMatches, Shell, SubCommand};
+
+use crate::CliResult;
+
+pub fn command() -> App<'static, 'static> {
+    SubCommand::with_name("completions")
+        .about("Print a shell completion script")
+        .after_help(
+            "bash:  google-api completions bash > /etc/bash_completion.d/google-api\n\
+             zsh:   google-api completions zsh > \"${fpath[1]}/_google-api\"\n\
+             fish:  google-api completions fish > ~/.config/fish/completions/google-api.fish",
+        )
+        .arg(
+            Arg::with_name("shell")
+                .required(true)
+                .possible_values(&["bash", "zsh", "fish"]),
+        )
+}
+
+pub fn completions(m: &ArgMatches) -> CliResult {
+    let shell: Shell = m.value_of("shell").unwrap_or_default().parse()?;
+    io::stdout().write_all(script(shell)?.as_bytes())?;
+    Ok(())
+}
+
+/// Profile names, for `--profile`, aren't known to clap. The generated script is patched to
+/// ask `google-api auth list` for them when completing.
+fn script(shell: Shell) -> Result<String, Box<dyn std::error::Error>> {
+    let mut script = vec![];
+    crate::app().gen_completions_to("google-api", shell, &mut script);
+    let mut script = String::from_utf8(script)?;
+    match shell {
+        Shell::Bash => {
+            // Before looking at subcommands, their own cases only know about their own args.
+            let prev = "prev=\"${COMP_WORDS[COMP_CWORD-1]}\"\n";
+            hook_point(&script, prev)?;
+            script = script.replacen(
+                prev,
+                "prev=\"${COMP_WORDS[COMP_CWORD-1]}\"\n    if [[ \"${prev}\" == --profile ]]; then\n        \
+                 COMPREPLY=($(compgen -W \"$(google-api auth list 2>/dev/null | cut -c3-)\" -- \
+                 \"${cur}\"))\n        return 0\n    fi\n",
+                1,
+            )
+        }
+        Shell::Zsh => {
+            let profile = "\\[env: GOOGLE_API_PROFILE\\]]'";
+            hook_point(&script, profile)?;
+            script = script.replace(
+                profile,
+                "\\[env: GOOGLE_API_PROFILE\\]]:profile:{compadd -- ${(f)\"$(google-api auth \
+                 list 2>/dev/null | cut -c3-)\"}}'",
+            )
+        }
+        _ => script.push_str(
+            "complete -c google-api -l profile -x -a \"(google-api auth list 2>/dev/null | \
+             string sub -s 3)\"\n",
+        ),
+    }
+    Ok(script)
+}
+
+/// Fail rather than quietly leave profile names out if clap's script no longer has the
+/// snippet they're added at.
+fn hook_point(script: &str, snippet: &str) -> Result<(), String> {
+    if script.contains(snippet) {
+        Ok(())
+    } else {
+        Err(format!(
+            "can't add profile names to the completion script, it has no {}",
+            snippet.trim()
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bash_completes_profiles() {
+        let script = script(Shell::Bash).unwrap();
+        assert!(script.contains("if [[ \"${prev}\" == --profile ]]; then"));
+        assert!(script.contains("$(google-api auth list 2>/dev/null | cut -c3-)"));
+    }
+
+    #[test]
+    fn zsh_completes_profiles() {
+        let script = script(Shell::Zsh).unwrap();
+        assert!(script.contains(":profile:{compadd -- ${(f)\"$(google-api auth list"));
+    }
+
+    #[test]
+    fn fish_completes_profiles() {
+        let script = script(Shell::Fish).unwrap();
+        assert!(script.contains("complete -c google-api -l profile -x -a \"(google-api auth list"));
+    }
+
+    #[test]
+    fn missing_hook_point_is_an_error() {
+        assert!(hook_point("complete -F _google-api", "prev=").is_err());
+    }
+}
@@ -0,0 +1,191 @@
+//! `google-api config ...`, and the config file `credentials` falls back to.
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use clap::{App, AppSettings, ArgMatches, SubCommand};
+use dialoguer::theme::ColorfulTheme;
+use dialoguer::{Confirm, Input, MultiSelect, Password};
+use google_api::{GoogleClient, Scope};
+use serde::{Deserialize, Serialize};
+
+use crate::CliResult;
+
+/// The OAuth client to sign in with, saved by `config init`.
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Config {
+    pub client_id: String,
+    pub client_secret: String,
+}
+
+/// Access `config init` offers to grant up front, and what each is for.
+const SCOPES: &[(&str, Scope)] = &[
+    ("Drive (drive ...)", Scope::Drive),
+    ("Sheets (sheets ...)", Scope::Spreadsheets),
+    ("Gmail, send only (gmail send)", Scope::GmailSend),
+    ("Calendar (calendar ...)", Scope::Calendar),
+];
+
+pub fn command() -> App<'static, 'static> {
+    SubCommand::with_name("config")
+        .about("Set up the OAuth client to sign in with")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .subcommand(
+            SubCommand::with_name("init")
+                .about("Ask for the OAuth client and what to grant it, sign in and save it"),
+        )
+        .subcommand(
+            SubCommand::with_name("show").about("Print where the config is and what's in it"),
+        )
+}
+
+pub fn run(m: &ArgMatches) -> CliResult {
+    match m.subcommand() {
+        ("init", Some(_)) => init(),
+        ("show", Some(_)) => show(),
+        _ => unreachable!("subcommand required"),
+    }
+}
+
+/// `google-api.json` in the library's config directory, next to the saved tokens.
+pub fn path() -> Result<PathBuf, String> {
+    let dir = google_api::config_dir().map_err(|e| e.to_string())?;
+    Ok(dir.join("google-api.json"))
+}
+
+/// The saved config, `None` if `config init` hasn't been run.
+pub fn load() -> Result<Option<Config>, String> {
+    let path = path()?;
+    match fs::read_to_string(&path) {
+        Ok(text) => serde_json::from_str(&text)
+            .map(Some)
+            .map_err(|e| format!("error parsing {}: {}", path.display(), e)),
+        Err(_) => Ok(None),
+    }
+}
+
+fn save(config: &Config) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let path = path()?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&path, serde_json::to_string_pretty(config)?)?;
+    // It holds the client secret.
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o600))?;
+    }
+    Ok(path)
+}
+
+fn init() -> CliResult {
+    let theme = ColorfulTheme::default();
+    eprintln!(
+        "Create an OAuth client of type \"Desktop app\" at \
+         https://console.cloud.google.com/apis/credentials and download its JSON, or copy its \
+         id and secret."
+    );
+    let file: String = Input::with_theme(&theme)
+        .with_prompt("Path to the downloaded client_secret JSON (empty to type the id and secret)")
+        .allow_empty(true)
+        .interact_text()?;
+    let config = if file.trim().is_empty() {
+        Config {
+            client_id: Input::with_theme(&theme)
+                .with_prompt("Client id")
+                .interact_text()?,
+            client_secret: Password::with_theme(&theme)
+                .with_prompt("Client secret")
+                .interact()?,
+        }
+    } else {
+        from_client_secret_file(file.trim())?
+    };
+
+    let names: Vec<&str> = SCOPES.iter().map(|(name, _)| *name).collect();
+    let mut defaults = vec![false; SCOPES.len()];
+    defaults[0] = true;
+    let picked = MultiSelect::with_theme(&theme)
+        .with_prompt("Access to grant (space to toggle, enter to confirm)")
+        .items(&names)
+        .defaults(&defaults)
+        .interact()?;
+    if picked.is_empty() {
+        return Err("nothing picked to grant".into());
+    }
+    let scopes = picked
+        .into_iter()
+        .map(|i| SCOPES[i].1.clone().into())
+        .collect();
+
+    if let Some(existing) = load()? {
+        if existing.client_id != config.client_id
+            && !Confirm::with_theme(&theme)
+                .with_prompt("Replace the OAuth client saved before?")
+                .interact()?
+        {
+            return Ok(());
+        }
+    }
+    let path = save(&config)?;
+    eprintln!(
+        "Saved to {}. Signing in, check your browser.",
+        path.display()
+    );
+    GoogleClient::new(config.client_id, config.client_secret, scopes)?;
+    eprintln!("Signed in, all set.");
+    Ok(())
+}
+
+/// Read the client from the JSON the Cloud console gives to download, under `installed` for
+/// desktop clients or `web` for web ones.
+fn from_client_secret_file(path: &str) -> Result<Config, Box<dyn std::error::Error>> {
+    let json: serde_json::Value = serde_json::from_str(&fs::read_to_string(path)?)?;
+    let client = if json["installed"].is_object() {
+        &json["installed"]
+    } else {
+        &json["web"]
+    };
+    match (
+        client["client_id"].as_str(),
+        client["client_secret"].as_str(),
+    ) {
+        (Some(id), Some(secret)) => Ok(Config {
+            client_id: id.to_string(),
+            client_secret: secret.to_string(),
+        }),
+        _ => Err(format!("{} isn't an OAuth client JSON file", path).into()),
+    }
+}
+
+fn show() -> CliResult {
+    let path = path()?;
+    match load()? {
+        Some(config) => {
+            println!("{}", path.display());
+            println!("client id      {}", config.client_id);
+            println!("client secret  {}", mask(&config.client_secret));
+        }
+        None => println!(
+            "{} doesn't exist, run google-api config init",
+            path.display()
+        ),
+    }
+    if env::var("GOOGLE_CLIENT_ID").is_ok() {
+        println!("GOOGLE_CLIENT_ID and GOOGLE_CLIENT_SECRET are set and used instead");
+    }
+    Ok(())
+}
+
+/// Enough of a secret to recognise it.
+fn mask(secret: &str) -> String {
+    let shown: String = secret.chars().take(4).collect();
+    format!(
+        "{}{}",
+        shown,
+        "*".repeat(secret.chars().count().saturating_sub(4))
+    )
+}
@@ -0,0 +1,87 @@
+//! `google-api drive diff`
+
+use std::path::{Path, PathBuf};
+
+use clap::{App, Arg, ArgMatches, SubCommand};
+use google_api::{ConflictPolicy, Drive, SyncAction, SyncOptions};
+
+use crate::drive::{ignore_args, ignore_rules, resolve};
+use crate::output::{format, print_rows, Format};
+use crate::CliResult;
+
+pub fn command() -> App<'static, 'static> {
+    SubCommand::with_name("diff")
+        .about("Show how a local directory and a Drive folder differ, without changing either")
+        .after_help(
+            "One line per file: A added, M modified, D deleted or C changed on both sides, \
+             then which side changed. Files are compared by checksum and modified time against \
+             the last `drive sync`, deletions can only be seen for files synced before.",
+        )
+        .arg(
+            Arg::with_name("local")
+                .required(true)
+                .help("Local directory"),
+        )
+        .arg(
+            Arg::with_name("remote")
+                .required(true)
+                .help("Drive folder, by id, url or path"),
+        )
+        .args(&ignore_args())
+}
+
+pub fn diff(drive: &Drive, m: &ArgMatches) -> CliResult {
+    let local = Path::new(m.value_of("local").unwrap_or_default());
+    let remote = resolve(drive, m.value_of("remote").unwrap_or_default())?;
+    // What the most thorough sync would do: deletions included and conflicts left alone.
+    let options = SyncOptions::new()
+        .delete(true)
+        .conflict_policy(ConflictPolicy::Ask)
+        .ignore_rules(&ignore_rules(m));
+    let plan = drive.sync_plan(local, &remote, &options)?;
+
+    let changes: Vec<(&str, &str, &PathBuf)> = plan
+        .iter()
+        .map(|action| match action {
+            SyncAction::Upload {
+                path,
+                file_id: None,
+            } => ("A", "local", path),
+            SyncAction::Upload { path, .. } => ("M", "local", path),
+            SyncAction::Download { path, .. } if local.join(path).exists() => ("M", "drive", path),
+            SyncAction::Download { path, .. } => ("A", "drive", path),
+            SyncAction::DeleteRemote { path, .. } => ("D", "local", path),
+            SyncAction::DeleteLocal { path } => ("D", "drive", path),
+            SyncAction::KeepBoth { path, .. } | SyncAction::Conflict { path, .. } => {
+                ("C", "both", path)
+            }
+        })
+        .collect();
+
+    let format = format(m);
+    if format == Format::Json {
+        let json: Vec<_> = changes
+            .iter()
+            .map(|(status, side, path)| {
+                serde_json::json!({ "status": status, "changedOn": side, "path": path })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&json)?);
+        return Ok(());
+    }
+    if changes.is_empty() && format == Format::Table {
+        eprintln!("No differences, {} is in sync", local.display());
+    }
+    let rows: Vec<Vec<String>> = changes
+        .iter()
+        .map(|(status, side, path)| {
+            vec![
+                status.to_string(),
+                side.to_string(),
+                path.display().to_string(),
+            ]
+        })
+        .collect();
+    print_rows(&rows, format);
+    Ok(())
+}
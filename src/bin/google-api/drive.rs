@@ -0,0 +1,504 @@
+//! `google-api drive ...`
+
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+
+use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
+use google_api::{
+    local_file_name, ConflictPolicy, Drive, DriveFile, Error, FileId, IgnoreRules, ListOptions,
+    Query, SyncAction, SyncOptions, UploadOptions,
+};
+
+use crate::diff;
+use crate::exit::AuthError;
+use crate::export;
+use crate::find;
+use crate::link;
+use crate::organize;
+use crate::output::{format, human_size, print_rows, short_time, Format};
+use crate::pick;
+use crate::resume::{self, upload_state};
+use crate::sharing;
+use crate::transfer::{download_file, download_tree, upload_dir, upload_file, Progress};
+use crate::trash;
+use crate::usage;
+use crate::watch;
+use crate::{credentials, CliResult};
+
+pub fn command() -> App<'static, 'static> {
+    SubCommand::with_name("drive")
+        .about("Google Drive")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .subcommand(
+            SubCommand::with_name("ls")
+                .about("List files in a folder, or matching a search")
+                .arg(
+                    Arg::with_name("folder")
+                        .long("folder")
+                        .short("f")
+                        .value_name("FOLDER")
+                        .help("Folder to list, by id, url or path like /projects [default: My Drive]"),
+                )
+                .arg(
+                    Arg::with_name("query")
+                        .long("query")
+                        .short("q")
+                        .value_name("QUERY")
+                        .help("Drive search query, e.g. \"name contains 'report'\". Searches everywhere unless --folder is given"),
+                )
+                .arg(
+                    Arg::with_name("order-by")
+                        .long("order-by")
+                        .value_name("ORDER")
+                        .default_value("folder,name")
+                        .help("Sort order, e.g. \"modifiedTime desc\""),
+                )
+                .args(&listing_args()),
+        )
+        .subcommand(
+            SubCommand::with_name("upload")
+                .about("Upload a file, or a directory with -r. Prints the new file's id")
+                .arg(
+                    Arg::with_name("path")
+                        .required(true)
+                        .help("Local file or directory, or - to read from stdin"),
+                )
+                .arg(
+                    Arg::with_name("name")
+                        .long("name")
+                        .value_name("NAME")
+                        .conflicts_with("recursive")
+                        .required_if("path", "-")
+                        .help("Name on Drive [default: the local file name]"),
+                )
+                .arg(
+                    Arg::with_name("to")
+                        .long("to")
+                        .value_name("FOLDER")
+                        .help("Folder to upload into, by id, url or path [default: My Drive]"),
+                )
+                .arg(
+                    Arg::with_name("recursive")
+                        .long("recursive")
+                        .short("r")
+                        .help("Upload a directory and everything in it"),
+                )
+                .args(&ignore_args())
+                .arg(
+                    Arg::with_name("resume")
+                        .long("resume")
+                        .conflicts_with("recursive")
+                        .help("Continue an interrupted upload of the same file to the same folder"),
+                )
+                .arg(jobs_arg()),
+        )
+        .subcommand(
+            SubCommand::with_name("download")
+                .about("Download a file, or a folder with -r")
+                .arg(
+                    Arg::with_name("file")
+                        .required(true)
+                        .help("File or folder, by id, url or path"),
+                )
+                .arg(
+                    Arg::with_name("out")
+                        .long("out")
+                        .short("o")
+                        .value_name("PATH")
+                        .help("Where to save it, a directory to download into or a file name [default: .]"),
+                )
+                .arg(
+                    Arg::with_name("recursive")
+                        .long("recursive")
+                        .short("r")
+                        .help("Download a folder and everything in it"),
+                )
+                .arg(
+                    Arg::with_name("resume")
+                        .long("resume")
+                        .help("Complete partly downloaded files instead of starting over, and only check finished ones"),
+                )
+                .arg(jobs_arg()),
+        )
+        .subcommand(
+            SubCommand::with_name("cat")
+                .about("Write a file's content to stdout")
+                .arg(
+                    Arg::with_name("file")
+                        .required(true)
+                        .help("File, by id, url or path"),
+                ),
+        )
+        .subcommand(organize::mv_command())
+        .subcommand(organize::cp_command())
+        .subcommand(organize::mkdir_command())
+        .subcommand(organize::rename_command())
+        .subcommand(export::command())
+        .subcommand(find::command())
+        .subcommand(pick::command())
+        .subcommand(link::link_command())
+        .subcommand(link::open_command())
+        .subcommand(trash::rm_command())
+        .subcommand(trash::trash_command())
+        .subcommand(trash::restore_command())
+        .subcommand(usage::quota_command())
+        .subcommand(usage::du_command())
+        .subcommand(sharing::share_command())
+        .subcommand(sharing::permissions_command())
+        .subcommand(watch::command())
+        .subcommand(resume::command())
+        .subcommand(diff::command())
+        .subcommand(
+            SubCommand::with_name("sync")
+                .about("Two-way sync between a local directory and a Drive folder")
+                .arg(Arg::with_name("local").required(true).help("Local directory"))
+                .arg(
+                    Arg::with_name("remote")
+                        .required(true)
+                        .help("Drive folder, by id, url or path"),
+                )
+                .arg(
+                    Arg::with_name("delete")
+                        .long("delete")
+                        .help("Propagate deletions, files removed on Drive go to its trash"),
+                )
+                .arg(
+                    Arg::with_name("dry-run")
+                        .long("dry-run")
+                        .short("n")
+                        .help("Only print what would be done"),
+                )
+                .arg(
+                    Arg::with_name("conflict")
+                        .long("conflict")
+                        .value_name("POLICY")
+                        .possible_values(&["newest", "keep-both"])
+                        .default_value("newest")
+                        .help("Files changed on both sides: keep the newest, or keep both with the local one renamed"),
+                )
+                .args(&ignore_args())
+                .arg(jobs_arg()),
+        )
+}
+
+/// `--jobs`, for commands transferring many files.
+fn jobs_arg() -> Arg<'static, 'static> {
+    Arg::with_name("jobs")
+        .long("jobs")
+        .short("j")
+        .value_name("N")
+        .default_value("4")
+        .validator(|v| match v.parse::<usize>() {
+            Ok(0) => Err(String::from("must be at least 1")),
+            Ok(_) => Ok(()),
+            Err(e) => Err(e.to_string()),
+        })
+        .help("Files to transfer at once")
+}
+
+fn jobs(m: &ArgMatches) -> usize {
+    m.value_of("jobs").and_then(|j| j.parse().ok()).unwrap_or(1)
+}
+
+/// `--exclude` and `--include`, for commands walking a local directory. They come on top of
+/// the directory's `.driveignore`.
+pub fn ignore_args() -> Vec<Arg<'static, 'static>> {
+    vec![
+        Arg::with_name("exclude")
+            .long("exclude")
+            .value_name("PATTERN")
+            .multiple(true)
+            .number_of_values(1)
+            .help("Leave out paths matching a .gitignore style pattern, e.g. target/ or *.log. Can be given more than once"),
+        Arg::with_name("include")
+            .long("include")
+            .value_name("PATTERN")
+            .multiple(true)
+            .number_of_values(1)
+            .help("Keep paths matching a pattern that --exclude or .driveignore leaves out. Can be given more than once"),
+    ]
+}
+
+pub fn ignore_rules(m: &ArgMatches) -> IgnoreRules {
+    let excluded = m
+        .values_of("exclude")
+        .into_iter()
+        .flatten()
+        .fold(IgnoreRules::new(), IgnoreRules::exclude);
+    m.values_of("include")
+        .into_iter()
+        .flatten()
+        .fold(excluded, IgnoreRules::include)
+}
+
+/// `--long` and `--json`, for commands listing files with `print_files`.
+pub fn listing_args() -> Vec<Arg<'static, 'static>> {
+    vec![
+        Arg::with_name("long")
+            .long("long")
+            .short("l")
+            .help("Also show size, last modified time and owner"),
+        Arg::with_name("json")
+            .long("json")
+            .conflicts_with("long")
+            .help("Print the files as JSON"),
+    ]
+}
+
+pub fn run(matches: &ArgMatches) -> CliResult {
+    // Only looks at local state, no need to sign in.
+    if let ("transfers", Some(m)) = matches.subcommand() {
+        return resume::transfers(m);
+    }
+    let drive = connect()?;
+    match matches.subcommand() {
+        ("ls", Some(m)) => ls(&drive, m),
+        ("upload", Some(m)) => upload(&drive, m),
+        ("download", Some(m)) => download(&drive, m),
+        ("cat", Some(m)) => cat(&drive, m),
+        ("mv", Some(m)) => organize::mv(&drive, m),
+        ("cp", Some(m)) => organize::cp(&drive, m),
+        ("mkdir", Some(m)) => organize::mkdir(&drive, m),
+        ("rename", Some(m)) => organize::rename(&drive, m),
+        ("export", Some(m)) => export::export(&drive, m),
+        ("find", Some(m)) => find::find(&drive, m),
+        ("pick", Some(m)) => pick::pick(&drive, m),
+        ("link", Some(m)) => link::link(&drive, m),
+        ("open", Some(m)) => link::open(&drive, m),
+        ("rm", Some(m)) => trash::rm(&drive, m),
+        ("trash", Some(m)) => trash::trash(&drive, m),
+        ("restore", Some(m)) => trash::restore(&drive, m),
+        ("quota", Some(m)) => usage::quota(&drive, m),
+        ("du", Some(m)) => usage::du(&drive, m),
+        ("share", Some(m)) => sharing::share(&drive, m),
+        ("permissions", Some(m)) => sharing::permissions(&drive, m),
+        ("diff", Some(m)) => diff::diff(&drive, m),
+        ("sync", Some(m)) => sync(&drive, m),
+        ("watch", Some(m)) => watch::watch(&drive, m),
+        _ => unreachable!("subcommand required"),
+    }
+}
+
+fn connect() -> Result<Drive, Box<dyn std::error::Error>> {
+    let (client_id, client_secret) = credentials().map_err(AuthError)?;
+    Ok(Drive::new(client_id, client_secret, None)?)
+}
+
+/// A file or folder given on the command line: an id, a Drive url, or a `/` separated path
+/// from the root of My Drive.
+pub fn resolve(drive: &Drive, arg: &str) -> Result<FileId, Box<dyn std::error::Error>> {
+    if arg.contains('/') && !arg.contains("://") || arg == "My Drive" {
+        Ok(drive.resolve_path(arg)?)
+    } else {
+        Ok(FileId::parse(arg)?)
+    }
+}
+
+fn ls(drive: &Drive, m: &ArgMatches) -> CliResult {
+    let mut query = Query::new().trashed(false);
+    match (m.value_of("folder"), m.value_of("query")) {
+        (Some(folder), _) => query = query.in_folder(resolve(drive, folder)?),
+        (None, None) => query = query.in_folder(FileId::root()),
+        (None, Some(_)) => {}
+    }
+    if let Some(q) = m.value_of("query") {
+        query = query.raw(&format!("({})", q));
+    }
+    let options = ListOptions::new()
+        .query(&query.to_string())
+        .order_by(m.value_of("order-by").unwrap_or_default());
+    print_files(&drive.list_files(&options)?, m)
+}
+
+/// Print files as `ls` does, honouring its `--long` and `--json` flags and `--output`.
+pub fn print_files(files: &[DriveFile], m: &ArgMatches) -> CliResult {
+    let format = format(m);
+    if format == Format::Json {
+        println!("{}", serde_json::to_string_pretty(&files)?);
+        return Ok(());
+    }
+    let rows: Vec<Vec<String>> = files
+        .iter()
+        .map(|file| {
+            if m.is_present("long") {
+                vec![
+                    file.id.clone(),
+                    file.size.map(human_size).unwrap_or_else(|| "-".into()),
+                    file.modified_time
+                        .map(|t| short_time(&t.to_string()))
+                        .unwrap_or_default(),
+                    owner(file),
+                    display_name(file),
+                ]
+            } else {
+                vec![file.id.clone(), display_name(file)]
+            }
+        })
+        .collect();
+    print_rows(&rows, format);
+    Ok(())
+}
+
+fn upload(drive: &Drive, m: &ArgMatches) -> CliResult {
+    let path = Path::new(m.value_of("path").unwrap_or_default());
+    let parent = match m.value_of("to") {
+        Some(folder) => resolve(drive, folder)?,
+        None => FileId::root(),
+    };
+    let mut options = UploadOptions::new().parent(&parent);
+    if let Some(name) = m.value_of("name") {
+        options = options.name(name);
+    }
+    if path == Path::new("-") {
+        let name = m.value_of("name").unwrap_or_default();
+        let file = drive.upload_from_reader(io::stdin().lock(), name, &options)?;
+        println!("{}", file.id);
+    } else if path.is_dir() {
+        if !m.is_present("recursive") {
+            return Err(format!("{} is a directory, use -r to upload it", path.display()).into());
+        }
+        let (folder_id, progress) = upload_dir(drive, path, &parent, &ignore_rules(m), jobs(m))?;
+        println!("{}", folder_id);
+        progress.finish("Uploaded")?;
+    } else {
+        let progress = Progress::new(1, fs::metadata(path)?.len());
+        let state = upload_state(path, &parent)?;
+        let resume = m.is_present("resume");
+        if let Some(file) = upload_file(drive, path, &options, Some(&state), resume, &progress) {
+            println!("{}", file.id);
+        }
+        progress.finish("Uploaded")?;
+    }
+    Ok(())
+}
+
+fn download(drive: &Drive, m: &ArgMatches) -> CliResult {
+    let id = resolve(drive, m.value_of("file").unwrap_or_default())?;
+    let out = PathBuf::from(m.value_of("out").unwrap_or("."));
+    let file = metadata(drive, &id)?;
+    if file.is_folder() {
+        if !m.is_present("recursive") {
+            return Err(format!("{} is a folder, use -r to download it", file.name).into());
+        }
+        let tree = drive.tree(&id, None)?;
+        let progress = Progress::new(tree.file_count, tree.size);
+        download_tree(
+            drive,
+            &tree,
+            &out,
+            &progress,
+            jobs(m),
+            m.is_present("resume"),
+        );
+        progress.finish("Downloaded")?;
+    } else {
+        let dest = if out.is_dir() {
+            out.join(local_file_name(&file.name))
+        } else {
+            out
+        };
+        let progress = Progress::new(1, file.size.unwrap_or_default());
+        download_file(drive, &file, &dest, m.is_present("resume"), &progress);
+        progress.finish("Downloaded")?;
+    }
+    Ok(())
+}
+
+/// Stream a file to stdout. The reader going away early, like `head` does, isn't an error.
+fn cat(drive: &Drive, m: &ArgMatches) -> CliResult {
+    let id = resolve(drive, m.value_of("file").unwrap_or_default())?;
+    match drive.download_to_writer(&id, &mut io::stdout().lock()) {
+        Err(Error::Io(e)) if e.kind() == ErrorKind::BrokenPipe => Ok(()),
+        Err(e) => Err(e.into()),
+        Ok(_) => Ok(()),
+    }
+}
+
+fn sync(drive: &Drive, m: &ArgMatches) -> CliResult {
+    let local = Path::new(m.value_of("local").unwrap_or_default());
+    let remote = resolve(drive, m.value_of("remote").unwrap_or_default())?;
+    let policy = match m.value_of("conflict") {
+        Some("keep-both") => ConflictPolicy::KeepBoth,
+        _ => ConflictPolicy::NewestWins,
+    };
+    let options = SyncOptions::new()
+        .delete(m.is_present("delete"))
+        .conflict_policy(policy)
+        .ignore_rules(&ignore_rules(m))
+        .jobs(jobs(m));
+
+    if m.is_present("dry-run") {
+        let plan = drive.sync_plan(local, &remote, &options)?;
+        if plan.is_empty() {
+            println!("Nothing to do, {} is in sync", local.display());
+        }
+        for action in &plan {
+            println!("{}", describe(action));
+        }
+        return Ok(());
+    }
+
+    let report = drive.sync(local, &remote, &options)?;
+    for action in report.done.iter().chain(&report.conflicts) {
+        println!("{}", describe(action));
+    }
+    for (action, reason) in &report.failed {
+        eprintln!("failed: {}: {}", describe(action), reason);
+    }
+    eprintln!(
+        "{} done, {} conflicts, {} failed",
+        report.done.len(),
+        report.conflicts.len(),
+        report.failed.len()
+    );
+    if report.failed.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("{} action(s) failed", report.failed.len()).into())
+    }
+}
+
+/// One line saying what a sync action does, for plans and reports.
+fn describe(action: &SyncAction) -> String {
+    match action {
+        SyncAction::Upload {
+            path,
+            file_id: None,
+        } => format!("upload    {}", path.display()),
+        SyncAction::Upload { path, .. } => format!("update    {} on Drive", path.display()),
+        SyncAction::Download { path, .. } => format!("download  {}", path.display()),
+        SyncAction::DeleteLocal { path } => format!("delete    {} locally", path.display()),
+        SyncAction::DeleteRemote { path, .. } => format!("trash     {} on Drive", path.display()),
+        SyncAction::KeepBoth { path, copy, .. } => format!(
+            "keep both {}, local version kept as {}",
+            path.display(),
+            copy.display()
+        ),
+        SyncAction::Conflict { path, .. } => format!("conflict  {}, left alone", path.display()),
+    }
+}
+
+pub fn metadata(drive: &Drive, id: &FileId) -> Result<DriveFile, Error> {
+    Ok(serde_json::from_value(drive.file_metadata(id)?)?)
+}
+
+/// Name with a trailing `/` for folders.
+fn display_name(file: &DriveFile) -> String {
+    if file.is_folder() {
+        format!("{}/", file.name)
+    } else {
+        file.name.clone()
+    }
+}
+
+fn owner(file: &DriveFile) -> String {
+    file.owners
+        .first()
+        .map(|o| {
+            o.email_address
+                .clone()
+                .unwrap_or_else(|| o.display_name.clone())
+        })
+        .unwrap_or_else(|| "-".into())
+}
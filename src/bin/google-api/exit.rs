@@ -0,0 +1,71 @@
+//! Exit codes, so scripts can tell why a command failed.
+
+use std::error::Error;
+use std::fmt;
+
+use reqwest::StatusCode;
+
+/// Anything else.
+pub const FAILURE: i32 = 1;
+/// Signing in failed, or the account isn't allowed to do that.
+pub const AUTH: i32 = 3;
+/// No such file, folder or permission.
+pub const NOT_FOUND: i32 = 4;
+/// Out of storage, or rate limited even after retrying.
+pub const QUOTA: i32 = 5;
+/// Google couldn't be reached.
+pub const NETWORK: i32 = 6;
+
+/// The OAuth client isn't set up, so signing in couldn't even start.
+#[derive(Debug)]
+pub struct AuthError(pub String);
+
+impl fmt::Display for AuthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "signing in failed: {}", self.0)
+    }
+}
+
+impl Error for AuthError {}
+
+/// Exit code for a command's error.
+pub fn code(e: &(dyn Error + 'static)) -> i32 {
+    if e.is::<AuthError>() {
+        return AUTH;
+    }
+    if let Some(e) = e.downcast_ref::<reqwest::Error>() {
+        return http_code(e);
+    }
+    match e.downcast_ref::<google_api::Error>() {
+        Some(google_api::Error::Http(e)) => http_code(e),
+        Some(google_api::Error::NotFound(_)) => NOT_FOUND,
+        Some(google_api::Error::Auth(_)) => AUTH,
+        Some(e @ google_api::Error::Api { status, .. }) => status_code(*status, e.reason()),
+        Some(google_api::Error::Context { source, .. }) => code(source.as_ref()),
+        _ => FAILURE,
+    }
+}
+
+fn http_code(e: &reqwest::Error) -> i32 {
+    match e.status() {
+        Some(status) => status_code(status, None),
+        None if e.is_connect() || e.is_timeout() || e.is_request() => NETWORK,
+        None => FAILURE,
+    }
+}
+
+/// Drive gives 403 for running out of storage and most rate limits too, the reason says
+/// which.
+fn status_code(status: StatusCode, reason: Option<&str>) -> i32 {
+    let quota = matches!(
+        reason,
+        Some("rateLimitExceeded") | Some("userRateLimitExceeded") | Some("storageQuotaExceeded")
+    );
+    match status {
+        StatusCode::NOT_FOUND => NOT_FOUND,
+        StatusCode::TOO_MANY_REQUESTS => QUOTA,
+        StatusCode::FORBIDDEN if quota => QUOTA,
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => AUTH,
+        _ => FAILURE,
+    }
+}
@@ -0,0 +1,66 @@
+//! `google-api drive export`
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use clap::{App, Arg, ArgMatches, SubCommand};
+use google_api::{local_file_name, Drive, ExportFormat};
+
+use crate::drive::{metadata, resolve};
+use crate::CliResult;
+
+pub fn command() -> App<'static, 'static> {
+    SubCommand::with_name("export")
+        .about("Export a Google Doc, Sheet, Slides deck or drawing to a regular file")
+        .arg(
+            Arg::with_name("file")
+                .required(true)
+                .help("File, by id, url or path"),
+        )
+        .arg(
+            Arg::with_name("format")
+                .long("format")
+                .short("f")
+                .value_name("FORMAT")
+                .validator(|v| v.parse::<ExportFormat>().map(|_| ()))
+                .help(
+                    "pdf, docx, odt, rtf, txt, html, epub, xlsx, ods, csv, tsv, pptx, odp, png, \
+                     jpg, svg or json [default: docx for Docs, xlsx for Sheets, pptx for \
+                     Slides, png for drawings]",
+                ),
+        )
+        .arg(
+            Arg::with_name("out")
+                .long("out")
+                .short("o")
+                .value_name("PATH")
+                .help("File or directory to save to, - for stdout [default: ./<name>.<format>]"),
+        )
+}
+
+pub fn export(drive: &Drive, m: &ArgMatches) -> CliResult {
+    let id = resolve(drive, m.value_of("file").unwrap_or_default())?;
+    let file = metadata(drive, &id)?;
+    let format = match m.value_of("format") {
+        Some(format) => format.parse()?,
+        None => ExportFormat::default_for(&file.mime_type).ok_or_else(|| {
+            format!(
+                "{} isn't a Google Docs, Sheets or Slides file, use drive download",
+                file.name
+            )
+        })?,
+    };
+    let content = drive.export_file(&id, format)?;
+
+    let file_name = format!("{}.{}", local_file_name(&file.name), format.extension());
+    let out = match m.value_of("out") {
+        Some("-") => return Ok(io::stdout().lock().write_all(&content)?),
+        Some(out) if PathBuf::from(out).is_dir() => PathBuf::from(out).join(file_name),
+        Some(out) => PathBuf::from(out),
+        None => PathBuf::from(file_name),
+    };
+    fs::write(&out, content)?;
+    eprintln!("Exported {} to {}", file.name, out.display());
+    Ok(())
+}
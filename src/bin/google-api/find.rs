@@ -0,0 +1,142 @@
+//! `google-api drive find`
+
+use std::io::{self, Write};
+
+use clap::{App, Arg, ArgMatches, SubCommand};
+use google_api::{glob_match, Drive, DriveFile, ListOptions, MimeType, Query};
+
+use crate::drive::{listing_args, print_files, resolve};
+use crate::output::{format, parse_date, Format};
+use crate::CliResult;
+
+/// Folders whose ids fit in one `in parents` query.
+const FOLDERS_PER_QUERY: usize = 50;
+
+pub fn command() -> App<'static, 'static> {
+    let date = |v: String| parse_date(&v).map(|_| ());
+    SubCommand::with_name("find")
+        .about("Search Drive, printing one id per line for xargs")
+        .arg(
+            Arg::with_name("name")
+                .long("name")
+                .value_name("GLOB")
+                .help("Name matching a glob, e.g. \"*.pdf\" or \"report-202?-*\""),
+        )
+        .arg(
+            Arg::with_name("text")
+                .long("text")
+                .value_name("TEXT")
+                .help("Name, description or content contains TEXT"),
+        )
+        .arg(
+            Arg::with_name("in")
+                .long("in")
+                .value_name("FOLDER")
+                .help("Only look in this folder and below it, by id, url or path"),
+        )
+        .arg(
+            Arg::with_name("modified-after")
+                .long("modified-after")
+                .value_name("DATE")
+                .validator(date)
+                .help("Modified after the start of DATE (YYYY-MM-DD, UTC)"),
+        )
+        .arg(
+            Arg::with_name("modified-before")
+                .long("modified-before")
+                .value_name("DATE")
+                .validator(date)
+                .help("Modified before the start of DATE (YYYY-MM-DD, UTC)"),
+        )
+        .arg(
+            Arg::with_name("type")
+                .long("type")
+                .value_name("TYPE")
+                .possible_values(&["file", "folder"])
+                .help("Only files or only folders"),
+        )
+        .arg(
+            Arg::with_name("mime")
+                .long("mime")
+                .value_name("MIME")
+                .conflicts_with("type")
+                .help("Only this MIME type, e.g. application/pdf"),
+        )
+        .arg(
+            Arg::with_name("print0")
+                .long("print0")
+                .short("0")
+                .conflicts_with_all(&["long", "json"])
+                .help("End ids with a NUL instead of a newline, for xargs -0"),
+        )
+        .args(&listing_args())
+}
+
+/// Server side part of a name glob: an exact name if it has no wildcards, else the literal
+/// text before the first wildcard, which Drive's `contains` matches as a prefix.
+fn name_clause(query: Query, glob: &str) -> Query {
+    match glob.find(['*', '?']) {
+        None => query.name_is(glob),
+        Some(0) => query,
+        Some(i) => query.name_contains(&glob[..i]),
+    }
+}
+
+pub fn find(drive: &Drive, m: &ArgMatches) -> CliResult {
+    let mut query = Query::new().trashed(false);
+    if let Some(glob) = m.value_of("name") {
+        query = name_clause(query, glob);
+    }
+    if let Some(text) = m.value_of("text") {
+        query = query.full_text(text);
+    }
+    if let Some(date) = m.value_of("modified-after") {
+        query = query.modified_after(parse_date(date)?);
+    }
+    if let Some(date) = m.value_of("modified-before") {
+        query = query.modified_before(parse_date(date)?);
+    }
+    match m.value_of("type") {
+        Some("folder") => query = query.folders(),
+        Some(_) => query = query.not_mime_type(MimeType::FOLDER.as_str()),
+        None => {}
+    }
+    if let Some(mime) = m.value_of("mime") {
+        query = query.mime_type(mime);
+    }
+
+    let mut files = match m.value_of("in") {
+        None => drive.search(&query)?,
+        Some(folder) => {
+            let tree = drive.tree(resolve(drive, folder)?, None)?;
+            let folder_ids: Vec<String> = tree
+                .walk()
+                .into_iter()
+                .map(|(_, t)| format!("'{}' in parents", t.folder.id))
+                .collect();
+            let mut files = vec![];
+            for parents in folder_ids.chunks(FOLDERS_PER_QUERY) {
+                let scoped = query.clone().raw(&format!("({})", parents.join(" or ")));
+                files.extend(drive.list_files(&ListOptions::new().query(&scoped.to_string()))?);
+            }
+            files
+        }
+    };
+    if let Some(glob) = m.value_of("name") {
+        files.retain(|f| glob_match(glob, &f.name));
+    }
+
+    if m.is_present("long") || format(m) == Format::Json {
+        return print_files(&files, m);
+    }
+    print_ids(&files, if m.is_present("print0") { '\0' } else { '\n' })
+}
+
+fn print_ids(files: &[DriveFile], end: char) -> CliResult {
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    for file in files {
+        write!(out, "{}{}", file.id, end)?;
+    }
+    Ok(out.flush()?)
+}
@@ -0,0 +1,146 @@
+//! `google-api gmail ...`
+
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+
+use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
+use google_api::{Gmail, Message, Scope};
+
+use crate::exit::AuthError;
+use crate::output::{format, Format};
+use crate::{credentials, CliResult};
+
+pub fn command() -> App<'static, 'static> {
+    let addresses = |name: &'static str, help: &'static str| {
+        Arg::with_name(name)
+            .long(name)
+            .value_name("ADDRESS")
+            .multiple(true)
+            .number_of_values(1)
+            .help(help)
+    };
+    SubCommand::with_name("gmail")
+        .about("Gmail")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .subcommand(
+            SubCommand::with_name("send")
+                .about("Send an email from your account")
+                .arg(addresses("to", "Recipient, can be given more than once").required(true))
+                .arg(addresses("cc", "Copy to, can be given more than once"))
+                .arg(addresses(
+                    "bcc",
+                    "Blind copy to, can be given more than once",
+                ))
+                .arg(
+                    Arg::with_name("from")
+                        .long("from")
+                        .value_name("ADDRESS")
+                        .help("Sender, one of your send-as addresses [default: your address]"),
+                )
+                .arg(
+                    Arg::with_name("subject")
+                        .long("subject")
+                        .short("s")
+                        .value_name("SUBJECT")
+                        .default_value(""),
+                )
+                .arg(
+                    Arg::with_name("body")
+                        .long("body")
+                        .short("b")
+                        .value_name("TEXT")
+                        .conflicts_with("body-file")
+                        .help("Message text"),
+                )
+                .arg(
+                    Arg::with_name("body-file")
+                        .long("body-file")
+                        .value_name("FILE")
+                        .help("File with the message text, - for stdin"),
+                )
+                .arg(
+                    Arg::with_name("html")
+                        .long("html")
+                        .help("The message text is HTML"),
+                )
+                .arg(
+                    Arg::with_name("attach")
+                        .long("attach")
+                        .short("a")
+                        .value_name("FILE")
+                        .multiple(true)
+                        .number_of_values(1)
+                        .validator(|v| {
+                            if Path::new(&v).is_file() {
+                                Ok(())
+                            } else {
+                                Err(format!("{} isn't a file", v))
+                            }
+                        })
+                        .help("File to attach, can be given more than once"),
+                ),
+        )
+}
+
+pub fn run(matches: &ArgMatches) -> CliResult {
+    match matches.subcommand() {
+        ("send", Some(m)) => send(m),
+        _ => unreachable!("subcommand required"),
+    }
+}
+
+/// Only asks for the send scope, so a token saved for cron jobs can't read the mailbox.
+fn connect() -> Result<Gmail, Box<dyn std::error::Error>> {
+    let (client_id, client_secret) = credentials().map_err(AuthError)?;
+    Ok(Gmail::new(
+        client_id,
+        client_secret,
+        Some(vec![Scope::GmailSend.into()]),
+    )?)
+}
+
+fn send(m: &ArgMatches) -> CliResult {
+    let body = match m.value_of("body-file") {
+        Some("-") => {
+            let mut body = String::new();
+            io::stdin().read_to_string(&mut body)?;
+            body
+        }
+        Some(path) => fs::read_to_string(path)?,
+        None => m.value_of("body").unwrap_or_default().to_string(),
+    };
+
+    let mut message = Message::new().subject(m.value_of("subject").unwrap_or_default());
+    if let Some(from) = m.value_of("from") {
+        message = message.from(from);
+    }
+    for to in m.values_of("to").into_iter().flatten() {
+        message = message.to(to);
+    }
+    for cc in m.values_of("cc").into_iter().flatten() {
+        message = message.cc(cc);
+    }
+    for bcc in m.values_of("bcc").into_iter().flatten() {
+        message = message.bcc(bcc);
+    }
+    message = if m.is_present("html") {
+        message.html(&body)
+    } else {
+        message.text(&body)
+    };
+    for path in m.values_of("attach").into_iter().flatten() {
+        message = message.attach_file(path);
+    }
+
+    let sent = connect()?.send(&message)?;
+    if format(m) == Format::Json {
+        println!(
+            "{}",
+            serde_json::json!({ "id": sent.id, "threadId": sent.thread_id })
+        );
+    } else {
+        println!("{}", sent.id);
+    }
+    Ok(())
+}
@@ -0,0 +1,66 @@
+//! `google-api drive link` and `google-api drive open`
+
+use clap::{App, Arg, ArgMatches, SubCommand};
+use google_api::{Drive, DriveFile};
+
+use crate::drive::{metadata, resolve};
+use crate::CliResult;
+
+pub fn link_command() -> App<'static, 'static> {
+    SubCommand::with_name("link")
+        .about("Print the link to view files in the browser")
+        .arg(
+            Arg::with_name("file")
+                .required(true)
+                .multiple(true)
+                .help("Files or folders, by id, url or path"),
+        )
+        .arg(
+            Arg::with_name("download")
+                .long("download")
+                .help("Print the direct download link instead, for files other than Google Docs"),
+        )
+}
+
+pub fn open_command() -> App<'static, 'static> {
+    SubCommand::with_name("open")
+        .about("Open a file or folder in the browser")
+        .arg(
+            Arg::with_name("file")
+                .required(true)
+                .help("File or folder, by id, url or path"),
+        )
+}
+
+pub fn link(drive: &Drive, m: &ArgMatches) -> CliResult {
+    for arg in m.values_of("file").unwrap_or_default() {
+        let file = metadata(drive, &resolve(drive, arg)?)?;
+        if m.is_present("download") {
+            let url = file
+                .web_content_link
+                .as_deref()
+                .ok_or_else(|| format!("{} has no download link, use drive export", file.name))?;
+            println!("{}", url);
+        } else {
+            println!("{}", view_link(&file));
+        }
+    }
+    Ok(())
+}
+
+pub fn open(drive: &Drive, m: &ArgMatches) -> CliResult {
+    let file = metadata(
+        drive,
+        &resolve(drive, m.value_of("file").unwrap_or_default())?,
+    )?;
+    let url = view_link(&file);
+    open::that(&url).map_err(|e| format!("couldn't open {}: {}", url, e))?;
+    Ok(())
+}
+
+/// Drive leaves `webViewLink` out for some files, the generic link works for any.
+fn view_link(file: &DriveFile) -> String {
+    file.web_view_link
+        .clone()
+        .unwrap_or_else(|| format!("https://drive.google.com/open?id={}", file.id))
+}
@@ -0,0 +1,119 @@
+//! `google-api`, a command line tool on top of the google_api crate.
+//!
+//! Signs in with the OAuth client saved by `google-api config init`, or the one in
+//! `GOOGLE_CLIENT_ID` and `GOOGLE_CLIENT_SECRET`, sharing the saved token with any program
+//! using the library.
+
+use std::env;
+use std::error::Error;
+use std::process;
+
+use clap::{App, AppSettings, Arg};
+use google_api::PROFILE_ENV;
+
+mod auth;
+mod calendar;
+mod completions;
+mod config;
+mod diff;
+mod drive;
+mod exit;
+mod export;
+mod find;
+mod gmail;
+mod link;
+mod organize;
+mod output;
+mod pick;
+mod resume;
+mod sharing;
+mod sheets;
+mod transfer;
+mod trash;
+mod usage;
+mod watch;
+
+/// What every command returns. Errors are printed and exit with a status from `exit`.
+pub type CliResult = Result<(), Box<dyn Error>>;
+
+/// OAuth client id and secret from the environment, or else the config file.
+pub fn credentials() -> Result<(String, String), String> {
+    if let (Ok(id), Ok(secret)) = (
+        env::var("GOOGLE_CLIENT_ID"),
+        env::var("GOOGLE_CLIENT_SECRET"),
+    ) {
+        return Ok((id, secret));
+    }
+    match config::load()? {
+        Some(config) => Ok((config.client_id, config.client_secret)),
+        None => Err(String::from(
+            "no OAuth client set up, run `google-api config init` or see `google-api --help`",
+        )),
+    }
+}
+
+/// The whole command line, also what completion scripts are generated from.
+pub fn app() -> App<'static, 'static> {
+    App::new("google-api")
+        .version(env!("CARGO_PKG_VERSION"))
+        .about("Google Drive and friends from the command line")
+        .after_help(
+            "Run `google-api config init` to set up an OAuth client of yours, or set \
+             GOOGLE_CLIENT_ID and GOOGLE_CLIENT_SECRET to one. The first command opens a \
+             consent page in the browser, the token is saved after that.\n\n\
+             Exit codes: 0 success, 1 failure, 3 signing in failed or access denied, 4 not \
+             found, 5 out of storage or rate limited, 6 network error.",
+        )
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .global_setting(AppSettings::VersionlessSubcommands)
+        .arg(
+            Arg::with_name("profile")
+                .long("profile")
+                .value_name("PROFILE")
+                .global(true)
+                .help("Sign in as this profile's account, see `google-api auth` [env: GOOGLE_API_PROFILE]"),
+        )
+        .arg(
+            Arg::with_name("output")
+                .long("output")
+                .value_name("FORMAT")
+                .global(true)
+                .possible_values(&["table", "plain", "json"])
+                .help("How to print listings: aligned columns, tab separated, or JSON. Errors are JSON too with json [default: table]"),
+        )
+        .subcommand(drive::command())
+        .subcommand(sheets::command())
+        .subcommand(gmail::command())
+        .subcommand(calendar::command())
+        .subcommand(auth::command())
+        .subcommand(config::command())
+        .subcommand(completions::command())
+}
+
+fn main() {
+    let matches = app().get_matches();
+    // The library reads it when signing in.
+    if let Some(profile) = matches.value_of("profile") {
+        env::set_var(PROFILE_ENV, profile);
+    }
+    let result = match matches.subcommand() {
+        ("drive", Some(m)) => drive::run(m),
+        ("sheets", Some(m)) => sheets::run(m),
+        ("gmail", Some(m)) => gmail::run(m),
+        ("calendar", Some(m)) => calendar::run(m),
+        ("auth", Some(m)) => auth::run(m),
+        ("config", Some(m)) => config::run(m),
+        ("completions", Some(m)) => completions::completions(m),
+        _ => unreachable!("subcommand required"),
+    };
+    if let Err(e) = result {
+        let code = exit::code(e.as_ref());
+        if matches.value_of("output") == Some("json") {
+            let error = serde_json::json!({ "error": e.to_string(), "exitCode": code });
+            eprintln!("{}", error);
+        } else {
+            eprintln!("google-api: {}", e);
+        }
+        process::exit(code);
+    }
+}
@@ -0,0 +1,182 @@
+//! `google-api drive mv`, `cp`, `mkdir` and `rename`
+
+use clap::{App, Arg, ArgMatches, SubCommand};
+use google_api::{Drive, Error, FileId};
+use reqwest::StatusCode;
+
+use crate::drive::{metadata, resolve};
+use crate::CliResult;
+
+pub fn mv_command() -> App<'static, 'static> {
+    SubCommand::with_name("mv")
+        .about("Move files into a folder, or move and rename one file")
+        .arg(
+            Arg::with_name("file")
+                .required(true)
+                .multiple(true)
+                .help("Files or folders to move, by id, url or path"),
+        )
+        .arg(dest_arg())
+}
+
+pub fn cp_command() -> App<'static, 'static> {
+    SubCommand::with_name("cp")
+        .about(
+            "Copy files into a folder, or copy one file under a new name. Prints the copies' ids",
+        )
+        .arg(
+            Arg::with_name("file")
+                .required(true)
+                .multiple(true)
+                .help("Files to copy, by id, url or path. Drive can't copy folders"),
+        )
+        .arg(dest_arg())
+}
+
+fn dest_arg() -> Arg<'static, 'static> {
+    Arg::with_name("dest").required(true).help(
+        "Existing folder, by id, url or path, or a path that doesn't exist yet to name a single \
+         file. A bare name keeps the file in its folder",
+    )
+}
+
+pub fn mkdir_command() -> App<'static, 'static> {
+    SubCommand::with_name("mkdir")
+        .about("Create a folder. Prints its id")
+        .arg(
+            Arg::with_name("path")
+                .required(true)
+                .help("Path of the new folder, e.g. /projects/2024"),
+        )
+        .arg(
+            Arg::with_name("parents")
+                .long("parents")
+                .short("p")
+                .help("Create missing folders along the way, and don't fail if it already exists"),
+        )
+}
+
+pub fn rename_command() -> App<'static, 'static> {
+    SubCommand::with_name("rename")
+        .about("Rename a file or folder in place")
+        .arg(
+            Arg::with_name("file")
+                .required(true)
+                .help("File or folder, by id, url or path"),
+        )
+        .arg(Arg::with_name("name").required(true).help("New name"))
+}
+
+/// Where `mv` and `cp` put files.
+enum Target {
+    /// Into an existing folder, keeping their names.
+    Into(FileId),
+    /// Into a folder under a new name.
+    As(FileId, String),
+    /// Into the folder it's in, under a new name.
+    Renamed(String),
+}
+
+/// The folder `dest` names or, for a single file, where it goes and what it's called when
+/// `dest` doesn't exist yet. A bare name is a new name in the file's own folder.
+fn target(drive: &Drive, dest: &str, files: usize) -> Result<Target, Box<dyn std::error::Error>> {
+    let existing = match resolve(drive, dest) {
+        Ok(id) => match metadata(drive, &id) {
+            Ok(file) => Some((id, file)),
+            // A name that happens to look like an id.
+            Err(e) if e.status() == Some(StatusCode::NOT_FOUND) => None,
+            Err(e) => return Err(e.into()),
+        },
+        Err(e) => match e.downcast_ref::<Error>() {
+            Some(Error::NotFound(_)) => None,
+            // Neither an id nor a url, so a name.
+            None if !dest.contains('/') => None,
+            _ => return Err(e),
+        },
+    };
+    match existing {
+        Some((id, file)) if file.is_folder() => Ok(Target::Into(id)),
+        Some(_) => Err(format!("{} already exists", dest).into()),
+        None if files > 1 => Err(format!("no folder {}", dest).into()),
+        None if !dest.contains('/') => Ok(Target::Renamed(dest.to_string())),
+        None => {
+            let dest = dest.trim_end_matches('/');
+            let (parent, name) = dest.rsplit_once('/').unwrap_or(("", dest));
+            let parent = drive.resolve_path(parent)?;
+            Ok(Target::As(parent, name.to_string()))
+        }
+    }
+}
+
+pub fn mv(drive: &Drive, m: &ArgMatches) -> CliResult {
+    let files: Vec<&str> = m.values_of("file").unwrap_or_default().collect();
+    let target = target(drive, m.value_of("dest").unwrap_or_default(), files.len())?;
+    for arg in files {
+        let file = metadata(drive, &resolve(drive, arg)?)?;
+        let (folder, name) = match &target {
+            Target::Into(folder) => (Some(folder), None),
+            Target::As(folder, name) => (Some(folder), Some(name)),
+            Target::Renamed(name) => (None, Some(name)),
+        };
+        // Drive refuses to move a file into the folder it's already in.
+        if let Some(folder) = folder {
+            if !file.parents.iter().any(|p| p == folder.as_str()) {
+                drive.move_file(FileId::unchecked(&file.id), folder)?;
+            }
+        }
+        if let Some(name) = name {
+            drive.rename_file(FileId::unchecked(&file.id), name)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn cp(drive: &Drive, m: &ArgMatches) -> CliResult {
+    let files: Vec<&str> = m.values_of("file").unwrap_or_default().collect();
+    let target = target(drive, m.value_of("dest").unwrap_or_default(), files.len())?;
+    for arg in files {
+        let file = metadata(drive, &resolve(drive, arg)?)?;
+        if file.is_folder() {
+            return Err(format!("{} is a folder, Drive can only copy files", file.name).into());
+        }
+        let copy = match &target {
+            Target::Into(folder) => drive.copy_file(
+                FileId::unchecked(&file.id),
+                Some(&file.name),
+                Some(folder.clone()),
+            )?,
+            Target::As(folder, name) => drive.copy_file(
+                FileId::unchecked(&file.id),
+                Some(name),
+                Some(folder.clone()),
+            )?,
+            // Copies stay in the original's folder unless told otherwise.
+            Target::Renamed(name) => {
+                drive.copy_file(FileId::unchecked(&file.id), Some(name), None)?
+            }
+        };
+        println!("{}", copy.id);
+    }
+    Ok(())
+}
+
+pub fn mkdir(drive: &Drive, m: &ArgMatches) -> CliResult {
+    let path = m.value_of("path").unwrap_or_default().trim_end_matches('/');
+    let id = if m.is_present("parents") {
+        drive.create_folder_path(path)?
+    } else {
+        let (parent, name) = path.rsplit_once('/').unwrap_or(("", path));
+        if drive.resolve_path(path).is_ok() {
+            return Err(format!("{} already exists", path).into());
+        }
+        FileId::unchecked(drive.create_folder(name, drive.resolve_path(parent)?)?.id)
+    };
+    println!("{}", id);
+    Ok(())
+}
+
+pub fn rename(drive: &Drive, m: &ArgMatches) -> CliResult {
+    let id = resolve(drive, m.value_of("file").unwrap_or_default())?;
+    drive.rename_file(&id, m.value_of("name").unwrap_or_default())?;
+    Ok(())
+}
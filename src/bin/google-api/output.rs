@@ -0,0 +1,125 @@
+//! Formatting and parsing shared by the commands.
+
+use chrono::NaiveDate;
+use clap::ArgMatches;
+use google_api::Timestamp;
+
+/// How listings are printed, from the global `--output` flag.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    /// Aligned columns, for people.
+    Table,
+    /// Tab separated columns, for `cut` and friends.
+    Plain,
+    Json,
+}
+
+/// `--output` as given to the command. The `--json` flag some commands have means `json` too.
+pub fn format(m: &ArgMatches) -> Format {
+    match m.value_of("output") {
+        _ if m.is_present("json") => Format::Json,
+        Some("json") => Format::Json,
+        Some("plain") => Format::Plain,
+        _ => Format::Table,
+    }
+}
+
+/// Size in bytes as a short human readable string, e.g. `4.2 MB`.
+pub fn human_size(bytes: u64) -> String {
+    const UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1000.0 && unit < UNITS.len() - 1 {
+        size /= 1000.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+/// An RFC 3339 timestamp from Google cut down to `YYYY-MM-DD HH:MM`, in UTC.
+pub fn short_time(timestamp: &str) -> String {
+    timestamp
+        .get(..16)
+        .unwrap_or(timestamp)
+        .replacen('T', " ", 1)
+}
+
+/// Print rows as left aligned columns separated by two spaces. The last column isn't padded,
+/// so long names don't leave trailing whitespace.
+pub fn print_table(rows: &[Vec<String>]) {
+    let columns = rows.iter().map(Vec::len).max().unwrap_or_default();
+    let widths: Vec<usize> = (0..columns)
+        .map(|i| {
+            rows.iter()
+                .filter_map(|row| row.get(i))
+                .map(|cell| cell.chars().count())
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+    for row in rows {
+        let mut line = String::new();
+        for (i, cell) in row.iter().enumerate() {
+            if i + 1 < row.len() {
+                line.push_str(&format!("{:width$}  ", cell, width = widths[i]));
+            } else {
+                line.push_str(cell);
+            }
+        }
+        println!("{}", line);
+    }
+}
+
+/// Print rows in given format, `Table` or `Plain`. JSON is up to the command, as it has
+/// more to say than the columns shown.
+pub fn print_rows(rows: &[Vec<String>], format: Format) {
+    if format != Format::Plain {
+        return print_table(rows);
+    }
+    for row in rows {
+        println!("{}", row.join("\t"));
+    }
+}
+
+/// Parse a `YYYY-MM-DD` date as midnight UTC.
+pub fn parse_date(date: &str) -> Result<Timestamp, String> {
+    format!("{}T00:00:00Z", date)
+        .parse()
+        .map_err(|_| format!("invalid date {}, expected YYYY-MM-DD", date))
+}
+
+/// Parse a `YYYY-MM-DD` date.
+pub fn parse_day(date: &str) -> Result<NaiveDate, String> {
+    parse_date(date).map(|t| t.date_time().date_naive())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_dates() {
+        assert_eq!(
+            parse_date("2024-02-29").unwrap().to_string(),
+            "2024-02-29T00:00:00Z"
+        );
+        assert_eq!(
+            parse_day("1969-07-20"),
+            Ok(NaiveDate::from_ymd_opt(1969, 7, 20).unwrap())
+        );
+        for bad in &[
+            "2024-02-31",
+            "2023-02-29",
+            "2024-13-01",
+            "2024-05",
+            "05/01/2024",
+            "",
+        ] {
+            assert!(parse_date(bad).is_err(), "{}", bad);
+        }
+    }
+}
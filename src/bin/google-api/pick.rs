@@ -0,0 +1,82 @@
+//! `google-api drive pick`
+
+use clap::{App, Arg, ArgMatches, SubCommand};
+use dialoguer::console::Term;
+use dialoguer::theme::ColorfulTheme;
+use dialoguer::FuzzySelect;
+use google_api::{Drive, DriveFile, ListOptions, Query};
+
+use crate::drive::resolve;
+use crate::output::short_time;
+use crate::CliResult;
+
+pub fn command() -> App<'static, 'static> {
+    SubCommand::with_name("pick")
+        .about("Pick a file from a fuzzy-searchable list and print its id, e.g. for $(google-api drive pick)")
+        .arg(
+            Arg::with_name("folder")
+                .long("folder")
+                .short("f")
+                .value_name("FOLDER")
+                .help("Only pick from this folder, by id, url or path [default: all of Drive]"),
+        )
+        .arg(
+            Arg::with_name("limit")
+                .long("limit")
+                .short("n")
+                .value_name("N")
+                .default_value("1000")
+                .validator(|v| v.parse::<usize>().map(|_| ()).map_err(|e| e.to_string()))
+                .help("Most recently modified files to offer"),
+        )
+}
+
+/// The list is drawn on stderr, so only the picked id goes to stdout. Cancelling with Esc
+/// prints nothing and fails, for `&&` chains to stop.
+pub fn pick(drive: &Drive, m: &ArgMatches) -> CliResult {
+    let term = Term::stderr();
+    if !term.is_term() {
+        return Err("drive pick needs a terminal".into());
+    }
+    let mut query = Query::new().trashed(false);
+    if let Some(folder) = m.value_of("folder") {
+        query = query.in_folder(resolve(drive, folder)?);
+    }
+    let limit = m.value_of("limit").unwrap_or_default().parse()?;
+    let options = ListOptions::new()
+        .query(&query.to_string())
+        .order_by("modifiedTime desc")
+        .limit(limit);
+    let files = drive.list_files(&options)?;
+    if files.is_empty() {
+        return Err("no files to pick from".into());
+    }
+
+    let items: Vec<String> = files.iter().map(item).collect();
+    let picked = FuzzySelect::with_theme(&ColorfulTheme::default())
+        .with_prompt("File")
+        .items(&items)
+        .default(0)
+        .max_length(term.size().0.saturating_sub(2).max(5) as usize)
+        .interact_on_opt(&term)?;
+    match picked {
+        Some(i) => {
+            println!("{}", files[i].id);
+            Ok(())
+        }
+        None => Err("nothing picked".into()),
+    }
+}
+
+/// What the list shows for a file: its name, `/` ended for folders, and when it last changed.
+fn item(file: &DriveFile) -> String {
+    let modified = file
+        .modified_time
+        .map(|t| short_time(&t.to_string()))
+        .unwrap_or_default();
+    if file.is_folder() {
+        format!("{}/  {}", file.name, modified)
+    } else {
+        format!("{}  {}", file.name, modified)
+    }
+}
@@ -0,0 +1,169 @@
+//! State of interrupted transfers, for `--resume` and `google-api drive transfers`.
+//!
+//! Single file uploads keep their resumable session in
+//! `~/.config/cameron-williams/transfers/upload-<key>.json`, downloads a note of the file
+//! they're writing in `download-<key>.json`. Both are removed once the transfer completes,
+//! so whatever is left was interrupted.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use clap::{App, AppSettings, ArgMatches, SubCommand};
+use google_api::{Drive, DriveFile, FileId, ResumableUpload};
+use serde::{Deserialize, Serialize};
+
+use crate::output::{format, human_size, print_rows, Format};
+use crate::CliResult;
+
+/// A download in progress: which Drive file is being written where.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PartialDownload {
+    file_id: String,
+    path: PathBuf,
+    size: u64,
+}
+
+pub fn command() -> App<'static, 'static> {
+    SubCommand::with_name("transfers")
+        .about("Interrupted uploads and downloads, to continue with --resume")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .subcommand(SubCommand::with_name("ls").about("List interrupted transfers"))
+        .subcommand(
+            SubCommand::with_name("clear")
+                .about("Forget interrupted transfers, so --resume starts them over"),
+        )
+}
+
+pub fn transfers(m: &ArgMatches) -> CliResult {
+    match m.subcommand() {
+        ("ls", Some(m)) => ls(m),
+        ("clear", Some(_)) => clear(),
+        _ => unreachable!("subcommand required"),
+    }
+}
+
+fn dir() -> Result<PathBuf, String> {
+    let dir = google_api::config_dir().map_err(|e| e.to_string())?;
+    Ok(dir.join("transfers"))
+}
+
+/// Absolute form of a local path, so the same file gets the same key from any directory.
+fn absolute(path: &Path) -> PathBuf {
+    fs::canonicalize(path)
+        .or_else(|_| env::current_dir().map(|dir| dir.join(path)))
+        .unwrap_or_else(|_| path.to_path_buf())
+}
+
+fn state_file(kind: &str, parts: &[&str]) -> Result<PathBuf, String> {
+    let key = md5::compute(parts.join("\n"));
+    Ok(dir()?.join(format!("{}-{:x}.json", kind, key)))
+}
+
+/// Where the session of an upload of `path` into `parent` is kept.
+pub fn upload_state(path: &Path, parent: &FileId) -> Result<PathBuf, String> {
+    let path = absolute(path);
+    state_file("upload", &[&path.to_string_lossy(), parent.as_str()])
+}
+
+/// The saved session for an upload, if there is one for the file as it is now and Drive
+/// still has it. Brings its offset up to what Drive actually received.
+pub fn saved_upload(drive: &Drive, state: &Path, path: &Path) -> Option<ResumableUpload> {
+    let mut upload = ResumableUpload::load(state.to_path_buf()).ok()?;
+    if upload.size != fs::metadata(path).ok()?.len() {
+        return None;
+    }
+    if drive.upload_status(&mut upload).ok()?.is_some() {
+        // Done before the state was removed: asking for the rest gets the file.
+        upload.offset = upload.size;
+    }
+    Some(upload)
+}
+
+/// Note that `file` is being downloaded to `dest`. Returns the note, to remove with
+/// `finish_download` once done.
+pub fn start_download(file: &DriveFile, dest: &Path) -> Option<PathBuf> {
+    let path = absolute(dest);
+    let state = state_file("download", &[&path.to_string_lossy()]).ok()?;
+    let partial = PartialDownload {
+        file_id: file.id.clone(),
+        path,
+        size: file.size.unwrap_or_default(),
+    };
+    fs::create_dir_all(state.parent()?).ok()?;
+    fs::write(&state, serde_json::to_vec_pretty(&partial).ok()?).ok()?;
+    Some(state)
+}
+
+pub fn finish_download(state: Option<PathBuf>) {
+    if let Some(state) = state {
+        let _ = fs::remove_file(state);
+    }
+}
+
+fn ls(m: &ArgMatches) -> CliResult {
+    let mut transfers = vec![];
+    let mut states: Vec<PathBuf> = match fs::read_dir(dir()?) {
+        Ok(entries) => entries.filter_map(|e| e.ok().map(|e| e.path())).collect(),
+        Err(_) => vec![],
+    };
+    states.sort();
+    for state in states {
+        let name = state.file_name().unwrap_or_default().to_string_lossy();
+        if name.starts_with("upload-") {
+            if let Ok(upload) = ResumableUpload::load(state.clone()) {
+                transfers.push(("upload", upload.path, upload.offset, upload.size));
+            }
+        } else if name.starts_with("download-") {
+            let partial = fs::read(&state)
+                .ok()
+                .and_then(|json| serde_json::from_slice::<PartialDownload>(&json).ok());
+            if let Some(partial) = partial {
+                let have = fs::metadata(&partial.path).map(|m| m.len()).unwrap_or(0);
+                transfers.push(("download", partial.path, have, partial.size));
+            }
+        }
+    }
+
+    if format(m) == Format::Json {
+        let json: Vec<serde_json::Value> = transfers
+            .iter()
+            .map(|(kind, path, done, size)| {
+                serde_json::json!({ "kind": kind, "path": path, "done": done, "size": size })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&json)?);
+        return Ok(());
+    }
+    let rows: Vec<Vec<String>> = transfers
+        .iter()
+        .map(|(kind, path, done, size)| {
+            let percent = if *size == 0 { 0 } else { done * 100 / size };
+            vec![
+                kind.to_string(),
+                format!("{}%", percent),
+                human_size(*size),
+                path.display().to_string(),
+            ]
+        })
+        .collect();
+    print_rows(&rows, format(m));
+    Ok(())
+}
+
+fn clear() -> CliResult {
+    let mut cleared = 0;
+    if let Ok(entries) = fs::read_dir(dir()?) {
+        for entry in entries.filter_map(Result::ok) {
+            fs::remove_file(entry.path())?;
+            cleared += 1;
+        }
+    }
+    eprintln!(
+        "Forgot {} transfer{}",
+        cleared,
+        if cleared == 1 { "" } else { "s" }
+    );
+    Ok(())
+}
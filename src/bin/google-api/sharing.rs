@@ -0,0 +1,176 @@
+//! `google-api drive share` and `google-api drive permissions ...`
+
+use clap::{App, AppSettings, Arg, ArgGroup, ArgMatches, SubCommand};
+use google_api::{Drive, Grantee, Permission, Role, ShareOptions};
+
+use crate::drive::resolve;
+use crate::output::{format, print_rows, Format};
+use crate::CliResult;
+
+const ROLES: &[&str] = &["reader", "commenter", "writer", "organizer", "owner"];
+
+pub fn share_command() -> App<'static, 'static> {
+    SubCommand::with_name("share")
+        .about("Give someone access to a file or folder. Prints the permission id")
+        .arg(
+            Arg::with_name("file")
+                .required(true)
+                .help("File or folder, by id, url or path"),
+        )
+        .arg(
+            Arg::with_name("with")
+                .long("with")
+                .value_name("EMAIL")
+                .help("Share with this Google account"),
+        )
+        .arg(
+            Arg::with_name("group")
+                .long("group")
+                .value_name("EMAIL")
+                .help("Share with this Google group"),
+        )
+        .arg(
+            Arg::with_name("domain")
+                .long("domain")
+                .value_name("DOMAIN")
+                .help("Share with everyone in a Workspace domain"),
+        )
+        .arg(
+            Arg::with_name("anyone")
+                .long("anyone")
+                .help("Share with anyone who has the link"),
+        )
+        .group(
+            ArgGroup::with_name("grantee")
+                .args(&["with", "group", "domain", "anyone"])
+                .required(true),
+        )
+        .arg(
+            Arg::with_name("role")
+                .long("role")
+                .value_name("ROLE")
+                .possible_values(ROLES)
+                .default_value("reader"),
+        )
+        .arg(
+            Arg::with_name("no-notify")
+                .long("no-notify")
+                .help("Don't email the people shared with"),
+        )
+        .arg(
+            Arg::with_name("message")
+                .long("message")
+                .value_name("TEXT")
+                .conflicts_with("no-notify")
+                .help("Text to include in the notification email"),
+        )
+}
+
+pub fn permissions_command() -> App<'static, 'static> {
+    SubCommand::with_name("permissions")
+        .about("List or revoke who has access to a file")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .subcommand(
+            SubCommand::with_name("ls")
+                .about("List a file's permissions")
+                .arg(Arg::with_name("file").required(true))
+                .arg(
+                    Arg::with_name("json")
+                        .long("json")
+                        .help("Print the permissions as JSON"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("rm")
+                .about("Revoke a permission")
+                .arg(Arg::with_name("file").required(true))
+                .arg(
+                    Arg::with_name("permission")
+                        .required(true)
+                        .help("Permission id, or the email or domain it was granted to"),
+                ),
+        )
+}
+
+fn role(name: &str) -> Role {
+    match name {
+        "commenter" => Role::Commenter,
+        "writer" => Role::Writer,
+        "organizer" => Role::Organizer,
+        "owner" => Role::Owner,
+        _ => Role::Reader,
+    }
+}
+
+pub fn share(drive: &Drive, m: &ArgMatches) -> CliResult {
+    let file = resolve(drive, m.value_of("file").unwrap_or_default())?;
+    let grantee = if let Some(email) = m.value_of("with") {
+        Grantee::User(email.to_string())
+    } else if let Some(email) = m.value_of("group") {
+        Grantee::Group(email.to_string())
+    } else if let Some(domain) = m.value_of("domain") {
+        Grantee::Domain(domain.to_string())
+    } else {
+        Grantee::Anyone
+    };
+    let mut options = ShareOptions::new();
+    if m.is_present("no-notify") {
+        options = options.send_notification_email(false);
+    }
+    if let Some(message) = m.value_of("message") {
+        options = options.message(message);
+    }
+    let permission = drive.share(
+        &file,
+        grantee,
+        role(m.value_of("role").unwrap_or_default()),
+        &options,
+    )?;
+    println!("{}", permission.id);
+    Ok(())
+}
+
+pub fn permissions(drive: &Drive, m: &ArgMatches) -> CliResult {
+    match m.subcommand() {
+        ("ls", Some(m)) => {
+            let file = resolve(drive, m.value_of("file").unwrap_or_default())?;
+            let permissions = drive.list_permissions(&file)?;
+            if format(m) == Format::Json {
+                println!("{}", serde_json::to_string_pretty(&permissions)?);
+                return Ok(());
+            }
+            let rows: Vec<Vec<String>> = permissions
+                .iter()
+                .map(|p| vec![p.id.clone(), p.role.clone(), p.kind.clone(), grantee(p)])
+                .collect();
+            print_rows(&rows, format(m));
+            Ok(())
+        }
+        ("rm", Some(m)) => {
+            let file = resolve(drive, m.value_of("file").unwrap_or_default())?;
+            let wanted = m.value_of("permission").unwrap_or_default();
+            let id = if wanted.contains('@') || wanted.contains('.') {
+                drive
+                    .list_permissions(&file)?
+                    .into_iter()
+                    .find(|p| grantee(p).eq_ignore_ascii_case(wanted))
+                    .map(|p| p.id)
+                    .ok_or_else(|| format!("{} has no access to this file", wanted))?
+            } else {
+                wanted.to_string()
+            };
+            drive.remove_permission(&file, &id)?;
+            Ok(())
+        }
+        _ => unreachable!("subcommand required"),
+    }
+}
+
+/// Who a permission is for: an email, a domain, or `anyone`.
+fn grantee(permission: &Permission) -> String {
+    permission
+        .email_address
+        .clone()
+        .or_else(|| permission.domain.clone())
+        .unwrap_or_else(|| permission.kind.clone())
+}
@@ -0,0 +1,292 @@
+//! `google-api sheets ...`
+
+use std::fs;
+use std::io::{self, Read, Write};
+
+use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
+use google_api::{a1_range, FileId, Sheets, ValueInputOption};
+
+use crate::exit::AuthError;
+use crate::output::{format, print_rows, Format};
+use crate::{credentials, CliResult};
+
+pub fn command() -> App<'static, 'static> {
+    let spreadsheet = || {
+        Arg::with_name("spreadsheet")
+            .required(true)
+            .help("Spreadsheet, by id or url")
+    };
+    SubCommand::with_name("sheets")
+        .about("Google Sheets")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .subcommand(
+            SubCommand::with_name("get")
+                .about("Print the values in a range, as shown in the spreadsheet")
+                .arg(spreadsheet())
+                .arg(
+                    Arg::with_name("range")
+                        .required(true)
+                        .help("Range in A1 notation, e.g. Sheet1!A1:C10, or a sheet's name"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("append")
+                .about("Append CSV rows after the table in a range")
+                .arg(spreadsheet())
+                .arg(
+                    Arg::with_name("range")
+                        .required(true)
+                        .help("Range the table is in, in A1 notation, e.g. Sheet1!A:C"),
+                )
+                .arg(
+                    Arg::with_name("csv")
+                        .long("csv")
+                        .value_name("FILE")
+                        .required(true)
+                        .help("CSV file with the rows to append, - for stdin"),
+                )
+                .arg(
+                    Arg::with_name("raw").long("raw").help(
+                        "Store values as given, instead of parsing numbers, dates and formulas",
+                    ),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("export")
+                .about("Print a whole sheet as CSV or TSV")
+                .arg(spreadsheet())
+                .arg(
+                    Arg::with_name("sheet")
+                        .long("sheet")
+                        .short("s")
+                        .value_name("NAME")
+                        .help("Sheet to export [default: the first one]"),
+                )
+                .arg(
+                    Arg::with_name("format")
+                        .long("format")
+                        .short("f")
+                        .value_name("FORMAT")
+                        .possible_values(&["csv", "tsv"])
+                        .default_value("csv"),
+                )
+                .arg(
+                    Arg::with_name("out")
+                        .long("out")
+                        .short("o")
+                        .value_name("FILE")
+                        .help("File to save to [default: stdout]"),
+                ),
+        )
+}
+
+pub fn run(matches: &ArgMatches) -> CliResult {
+    let sheets = connect()?;
+    match matches.subcommand() {
+        ("get", Some(m)) => get(&sheets, m),
+        ("append", Some(m)) => append(&sheets, m),
+        ("export", Some(m)) => export(&sheets, m),
+        _ => unreachable!("subcommand required"),
+    }
+}
+
+fn connect() -> Result<Sheets, Box<dyn std::error::Error>> {
+    let (client_id, client_secret) = credentials().map_err(AuthError)?;
+    Ok(Sheets::new(client_id, client_secret, None)?)
+}
+
+fn spreadsheet_id(m: &ArgMatches) -> Result<FileId, String> {
+    FileId::parse(m.value_of("spreadsheet").unwrap_or_default())
+}
+
+fn get(sheets: &Sheets, m: &ArgMatches) -> CliResult {
+    let id = spreadsheet_id(m)?;
+    let values = sheets.get_values(id.as_str(), m.value_of("range").unwrap_or_default())?;
+    if format(m) == Format::Json {
+        println!("{}", serde_json::to_string_pretty(&values)?);
+        return Ok(());
+    }
+    print_rows(&cells(&values.values), format(m));
+    Ok(())
+}
+
+fn append(sheets: &Sheets, m: &ArgMatches) -> CliResult {
+    let id = spreadsheet_id(m)?;
+    let csv = match m.value_of("csv").unwrap_or_default() {
+        "-" => {
+            let mut csv = String::new();
+            io::stdin().read_to_string(&mut csv)?;
+            csv
+        }
+        path => fs::read_to_string(path)?,
+    };
+    let rows: Vec<Vec<serde_json::Value>> = parse_csv(&csv)
+        .into_iter()
+        .map(|row| row.into_iter().map(serde_json::Value::String).collect())
+        .collect();
+    if rows.is_empty() {
+        return Err("no rows to append".into());
+    }
+    let input = if m.is_present("raw") {
+        ValueInputOption::Raw
+    } else {
+        ValueInputOption::UserEntered
+    };
+    let appended = sheets.append_values(
+        id.as_str(),
+        m.value_of("range").unwrap_or_default(),
+        &rows,
+        input,
+    )?;
+    let rows = appended.updates.updated_rows;
+    let plural = if rows == 1 { "" } else { "s" };
+    eprintln!(
+        "Appended {} row{} to {}",
+        rows, plural, appended.updates.updated_range
+    );
+    Ok(())
+}
+
+fn export(sheets: &Sheets, m: &ArgMatches) -> CliResult {
+    let id = spreadsheet_id(m)?;
+    let sheet = match m.value_of("sheet") {
+        Some(sheet) => sheet.to_string(),
+        None => sheets
+            .get_spreadsheet(id.as_str())?
+            .sheets
+            .into_iter()
+            .min_by_key(|s| s.properties.index)
+            .map(|s| s.properties.title)
+            .ok_or("spreadsheet has no sheets")?,
+    };
+    let values = sheets.get_values(id.as_str(), &a1_range(&sheet, ""))?;
+
+    let tsv = m.value_of("format") == Some("tsv");
+    let mut out = String::new();
+    for row in cells(&values.values) {
+        let line: Vec<String> = if tsv {
+            row.iter().map(|c| c.replace(['\t', '\n'], " ")).collect()
+        } else {
+            row.iter().map(|c| csv_field(c)).collect()
+        };
+        out.push_str(&line.join(if tsv { "\t" } else { "," }));
+        out.push('\n');
+    }
+    match m.value_of("out") {
+        Some(path) => fs::write(path, out)?,
+        None => io::stdout().lock().write_all(out.as_bytes())?,
+    }
+    Ok(())
+}
+
+/// Cell values as text: strings as they are, numbers and booleans as JSON writes them.
+fn cells(values: &[Vec<serde_json::Value>]) -> Vec<Vec<String>> {
+    values
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|v| match v {
+                    serde_json::Value::String(s) => s.clone(),
+                    serde_json::Value::Null => String::new(),
+                    other => other.to_string(),
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// A CSV field, quoted when it has a comma, quote or line break in it.
+fn csv_field(cell: &str) -> String {
+    if cell.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
+
+/// Rows of a CSV text, RFC 4180 style: fields may be quoted, with `""` for a quote, and
+/// quoted fields may span lines. Blank lines are skipped.
+fn parse_csv(text: &str) -> Vec<Vec<String>> {
+    let mut rows = vec![];
+    let mut row = vec![];
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            '"' if quoted => quoted = false,
+            '"' if field.is_empty() => quoted = true,
+            ',' if !quoted => row.push(std::mem::take(&mut field)),
+            '\r' if !quoted && chars.peek() == Some(&'\n') => {}
+            '\n' if !quoted => {
+                row.push(std::mem::take(&mut field));
+                if row.len() > 1 || !row[0].is_empty() {
+                    rows.push(std::mem::take(&mut row));
+                } else {
+                    row.clear();
+                }
+            }
+            c => field.push(c),
+        }
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(rows: &[&[&str]]) -> Vec<Vec<String>> {
+        rows.iter()
+            .map(|row| row.iter().map(|cell| cell.to_string()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn parses_plain_and_quoted_fields() {
+        assert_eq!(
+            parse_csv("name,age\r\nAda,36\n\n\"Lovelace, Ada\",\"\"\n"),
+            rows(&[&["name", "age"], &["Ada", "36"], &["Lovelace, Ada", ""]])
+        );
+        assert_eq!(parse_csv("a,,b"), rows(&[&["a", "", "b"]]));
+        assert_eq!(parse_csv(""), rows(&[]));
+    }
+
+    #[test]
+    fn parses_escaped_quotes_and_line_breaks() {
+        assert_eq!(
+            parse_csv("\"say \"\"hi\"\"\",\"two\nlines\",\"a,b\"\nnext"),
+            rows(&[&["say \"hi\"", "two\nlines", "a,b"], &["next"]])
+        );
+    }
+
+    #[test]
+    fn quotes_fields_that_need_it() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+        assert_eq!(csv_field("cr\r"), "\"cr\r\"");
+    }
+
+    #[test]
+    fn fields_round_trip() {
+        let table = rows(&[&["a,b", "\"q\"", "x\ny"], &["", "plain", "\r\n"]]);
+        let text: String = table
+            .iter()
+            .map(|row| {
+                let fields: Vec<String> = row.iter().map(|cell| csv_field(cell)).collect();
+                fields.join(",") + "\n"
+            })
+            .collect();
+        assert_eq!(parse_csv(&text), table);
+    }
+}
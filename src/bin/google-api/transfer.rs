@@ -0,0 +1,336 @@
+//! Uploads and downloads with progress bars, for `drive upload` and `drive download`.
+
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard};
+use std::time::Instant;
+
+use google_api::{
+    local_file_name, run_bounded, DownloadOptions, Drive, DriveFile, Error, FileId, FolderTree,
+    FolderUploadEvent, FolderUploadOptions, IgnoreRules, UploadOptions,
+};
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+
+use crate::output::human_size;
+use crate::resume::{finish_download, saved_upload, start_download};
+
+fn style(template: &str) -> ProgressStyle {
+    ProgressStyle::with_template(template)
+        .unwrap_or_else(|_| ProgressStyle::default_bar())
+        .progress_chars("=> ")
+}
+
+/// Progress of a batch of transfers: one bar for the whole batch and one per file in flight,
+/// drawn on stderr (and not at all when it isn't a terminal). Shared by the transfer threads.
+pub struct Progress {
+    bars: MultiProgress,
+    overall: ProgressBar,
+    started: Instant,
+    tally: Mutex<Tally>,
+}
+
+#[derive(Default)]
+struct Tally {
+    total_files: usize,
+    files: usize,
+    bytes: u64,
+    failed: Vec<(String, String)>,
+    skipped: Vec<(String, String)>,
+}
+
+impl Progress {
+    pub fn new(total_files: usize, total_bytes: u64) -> Progress {
+        let bars = MultiProgress::new();
+        let overall = bars.add(ProgressBar::new(total_bytes));
+        overall.set_style(style(
+            "{msg:>12} [{wide_bar}] {bytes}/{total_bytes} {binary_bytes_per_sec} eta {eta}",
+        ));
+        overall.set_message(format!("0/{} files", total_files));
+        Progress {
+            bars,
+            overall,
+            started: Instant::now(),
+            tally: Mutex::new(Tally {
+                total_files,
+                ..Tally::default()
+            }),
+        }
+    }
+
+    /// Set the batch size once it's known.
+    fn set_totals(&self, total_files: usize, total_bytes: u64) {
+        self.overall.set_length(total_bytes);
+        let mut tally = self.tally();
+        tally.total_files = total_files;
+        self.update_count(&tally);
+    }
+
+    /// Bar for one file, shown under the overall one until it's done.
+    fn file_bar(&self, name: &str, size: u64) -> ProgressBar {
+        let bar = self.bars.add(ProgressBar::new(size));
+        bar.set_style(style("  {msg:30!} [{bar:30}] {bytes}/{total_bytes}"));
+        bar.set_message(name.to_string());
+        bar
+    }
+
+    fn tally(&self) -> MutexGuard<'_, Tally> {
+        self.tally.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn done(&self, name: &str, result: Result<u64, Error>) {
+        match result {
+            Ok(bytes) => {
+                let mut tally = self.tally();
+                tally.files += 1;
+                tally.bytes += bytes;
+                self.update_count(&tally);
+            }
+            Err(e) => self.fail(name, &e),
+        }
+    }
+
+    fn fail(&self, name: &str, error: &Error) {
+        let mut tally = self.tally();
+        tally.failed.push((name.to_string(), error.to_string()));
+        self.update_count(&tally);
+    }
+
+    fn update_count(&self, tally: &Tally) {
+        let handled = tally.files + tally.failed.len() + tally.skipped.len();
+        self.overall
+            .set_message(format!("{}/{} files", handled, tally.total_files));
+    }
+
+    /// Record a file left out, with why.
+    pub fn skip(&self, name: &str, reason: &str) {
+        let mut tally = self.tally();
+        tally.skipped.push((name.to_string(), reason.to_string()));
+        self.update_count(&tally);
+    }
+
+    /// Clear the bars and print what happened. Errors if any file failed.
+    pub fn finish(self, verb: &str) -> Result<(), String> {
+        self.overall.finish_and_clear();
+        let tally = self.tally.into_inner().unwrap_or_else(|e| e.into_inner());
+        eprintln!(
+            "{} {} file{} ({}) in {:.1}s",
+            verb,
+            tally.files,
+            if tally.files == 1 { "" } else { "s" },
+            human_size(tally.bytes),
+            self.started.elapsed().as_secs_f64()
+        );
+        for (name, reason) in &tally.skipped {
+            eprintln!("  skipped {}: {}", name, reason);
+        }
+        if tally.failed.is_empty() {
+            return Ok(());
+        }
+        for (name, reason) in &tally.failed {
+            eprintln!("  failed {}: {}", name, reason);
+        }
+        Err(format!("{} file(s) failed", tally.failed.len()))
+    }
+}
+
+/// Writer moving a file's bar and the overall one along as bytes go through.
+struct ProgressWriter<'a, W> {
+    inner: W,
+    bar: &'a ProgressBar,
+    overall: &'a ProgressBar,
+}
+
+impl<W: Write> Write for ProgressWriter<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.bar.inc(n as u64);
+        self.overall.inc(n as u64);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Upload one local file with the resumable protocol, a chunk at a time so the bars move.
+/// With a `state` file the session is saved there until the upload completes, and with
+/// `resume` a session saved by an earlier run carries on where it stopped.
+pub fn upload_file(
+    drive: &Drive,
+    path: &Path,
+    options: &UploadOptions,
+    state: Option<&Path>,
+    resume: bool,
+    progress: &Progress,
+) -> Option<DriveFile> {
+    let name = path.display().to_string();
+    let saved = match state {
+        Some(state) if resume => saved_upload(drive, state, path),
+        _ => None,
+    };
+    let upload = match saved {
+        Some(upload) => Ok(upload),
+        None => drive
+            .start_resumable_upload(path, options)
+            .and_then(|upload| match state {
+                Some(state) => upload.persist_to(state.to_path_buf()),
+                None => Ok(upload),
+            }),
+    };
+    let mut upload = match upload {
+        Ok(upload) => upload,
+        Err(e) => {
+            progress.done(&name, Err(e));
+            return None;
+        }
+    };
+    let bar = progress.file_bar(&name, upload.size);
+    let mut sent = upload.offset;
+    progress.overall.inc(sent);
+    bar.set_position(sent);
+    loop {
+        match drive.upload_chunk(&mut upload) {
+            Ok(None) => {
+                progress.overall.inc(upload.offset - sent);
+                sent = upload.offset;
+                bar.set_position(sent);
+            }
+            Ok(Some(file)) => {
+                progress.overall.inc(upload.size - sent);
+                bar.finish_and_clear();
+                progress.done(&name, Ok(upload.size));
+                return Some(file);
+            }
+            Err(e) => {
+                bar.finish_and_clear();
+                progress.done(&name, Err(e));
+                return None;
+            }
+        }
+    }
+}
+
+/// Mirror a local directory into a new folder inside `parent`, uploading `jobs` files at a
+/// time and leaving out what the directory's `.driveignore` and then `ignore` say. Returns the
+/// new folder's id and how the files went. Failing to create a folder stops everything, a file
+/// failing doesn't.
+pub fn upload_dir(
+    drive: &Drive,
+    dir: &Path,
+    parent: &FileId,
+    ignore: &IgnoreRules,
+    jobs: usize,
+) -> Result<(String, Progress), Error> {
+    let options = FolderUploadOptions::new().jobs(jobs).ignore_rules(ignore);
+    let progress = Progress::new(0, 0);
+    let bars = Mutex::new(HashMap::new());
+    let bars = || bars.lock().unwrap_or_else(|e| e.into_inner());
+    let report = drive.upload_folder_with_progress(dir, parent, &options, |event| match event {
+        FolderUploadEvent::Started { files, bytes } => progress.set_totals(files, bytes),
+        FolderUploadEvent::Sent { path, sent, size } => {
+            let mut bars = bars();
+            let bar = bars
+                .entry(path.to_path_buf())
+                .or_insert_with(|| progress.file_bar(&path.display().to_string(), size));
+            progress.overall.inc(sent.saturating_sub(bar.position()));
+            bar.set_position(sent);
+        }
+        FolderUploadEvent::Finished { path, result } => {
+            let bar = bars().remove(path);
+            let sent = bar.as_ref().map_or(0, |bar| bar.position());
+            if let Some(bar) = bar {
+                bar.finish_and_clear();
+            }
+            let name = path.display().to_string();
+            match result {
+                Ok(_) => progress.done(&name, Ok(sent)),
+                Err(e) => progress.fail(&name, e),
+            }
+        }
+    })?;
+    Ok((report.root_id, progress))
+}
+
+/// Download one file to given local path, noting it under the transfer state until it's done.
+/// With `resume`, a partial local file is completed instead of downloaded again, and a
+/// complete one only checked.
+pub fn download_file(
+    drive: &Drive,
+    file: &DriveFile,
+    dest: &Path,
+    resume: bool,
+    progress: &Progress,
+) {
+    let name = dest.display().to_string();
+    if file.is_google_native() {
+        progress.skip(&name, "Google Docs, Sheets and Slides can only be exported");
+        return;
+    }
+    let size = file.size.unwrap_or_default();
+    let state = start_download(file, dest);
+    let have = dest.metadata().map(|m| m.len()).unwrap_or(0);
+    let result = if resume && have > 0 && file.md5_checksum.is_some() {
+        progress.overall.inc(have.min(size));
+        let options = DownloadOptions::new().resume(true);
+        let result = drive
+            .download(FileId::unchecked(&file.id), dest, &options)
+            .map(|_| size);
+        progress.overall.inc(size.saturating_sub(have));
+        result
+    } else {
+        let bar = progress.file_bar(&name, size);
+        let result = File::create(dest).map_err(Error::from).and_then(|f| {
+            let mut writer = ProgressWriter {
+                inner: f,
+                bar: &bar,
+                overall: &progress.overall,
+            };
+            drive.download_to_writer(FileId::unchecked(&file.id), &mut writer)
+        });
+        bar.finish_and_clear();
+        result
+    };
+    if result.is_ok() {
+        finish_download(state);
+    }
+    progress.done(&name, result);
+}
+
+/// Download a folder and everything in it into a new directory inside `out`, `jobs` files at
+/// a time.
+pub fn download_tree(
+    drive: &Drive,
+    tree: &FolderTree,
+    out: &Path,
+    progress: &Progress,
+    jobs: usize,
+    resume: bool,
+) {
+    let root = out.join(local_file_name(&tree.folder.name));
+    let mut downloads = vec![];
+    for (path, folder) in tree.walk() {
+        let dir = path.split('/').fold(root.clone(), |dir, name| {
+            if name.is_empty() {
+                dir
+            } else {
+                dir.join(local_file_name(name))
+            }
+        });
+        if let Err(e) = fs::create_dir_all(&dir) {
+            for file in &folder.files {
+                let name = dir.join(local_file_name(&file.name)).display().to_string();
+                progress.done(&name, Err(io::Error::new(e.kind(), e.to_string()).into()));
+            }
+            continue;
+        }
+        for file in &folder.files {
+            downloads.push((file, dir.join(local_file_name(&file.name))));
+        }
+    }
+    run_bounded(downloads, jobs, |(file, dest)| {
+        download_file(drive, file, &dest, resume, progress)
+    });
+}
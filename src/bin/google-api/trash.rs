@@ -0,0 +1,126 @@
+//! `google-api drive trash|restore|rm`
+
+use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
+use google_api::{BulkReport, Drive, FileId};
+
+use crate::drive::{listing_args, print_files, resolve};
+use crate::CliResult;
+
+pub fn trash_command() -> App<'static, 'static> {
+    SubCommand::with_name("trash")
+        .about("Move files to the trash, or list or empty it")
+        .settings(&[
+            AppSettings::ArgsNegateSubcommands,
+            AppSettings::SubcommandsNegateReqs,
+        ])
+        .arg(
+            Arg::with_name("files")
+                .required(true)
+                .multiple(true)
+                .help("Files or folders, by id, url or path"),
+        )
+        .subcommand(
+            SubCommand::with_name("ls")
+                .about("List trashed files")
+                .args(&listing_args()),
+        )
+        .subcommand(
+            SubCommand::with_name("empty")
+                .about("Permanently delete everything in the trash")
+                .arg(
+                    Arg::with_name("yes")
+                        .long("yes")
+                        .help("Really do it, there's no way back"),
+                ),
+        )
+}
+
+pub fn restore_command() -> App<'static, 'static> {
+    SubCommand::with_name("restore")
+        .about("Take files back out of the trash")
+        .arg(
+            Arg::with_name("files")
+                .required(true)
+                .multiple(true)
+                .help("Trashed files or folders, by id or url"),
+        )
+}
+
+pub fn rm_command() -> App<'static, 'static> {
+    SubCommand::with_name("rm")
+        .about("Delete files. They go to the trash unless --permanent is given")
+        .arg(
+            Arg::with_name("files")
+                .required(true)
+                .multiple(true)
+                .help("Files or folders, by id, url or path"),
+        )
+        .arg(
+            Arg::with_name("permanent")
+                .long("permanent")
+                .help("Skip the trash, the files can't be recovered"),
+        )
+}
+
+fn resolve_all(drive: &Drive, m: &ArgMatches) -> Result<Vec<FileId>, Box<dyn std::error::Error>> {
+    m.values_of("files")
+        .into_iter()
+        .flatten()
+        .map(|f| resolve(drive, f))
+        .collect()
+}
+
+/// Print failures from a bulk operation, erroring if there were any.
+fn check(report: BulkReport) -> CliResult {
+    for (id, reason) in &report.failed {
+        eprintln!("failed: {}: {}", id, reason);
+    }
+    if report.failed.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("{} file(s) failed", report.failed.len()).into())
+    }
+}
+
+pub fn trash(drive: &Drive, m: &ArgMatches) -> CliResult {
+    match m.subcommand() {
+        ("ls", Some(m)) => print_files(&drive.trashed()?, m),
+        ("empty", Some(m)) => {
+            if !m.is_present("yes") {
+                let count = drive.trashed()?.len();
+                return Err(format!(
+                    "this permanently deletes {} trashed file(s), pass --yes to go ahead",
+                    count
+                )
+                .into());
+            }
+            drive.empty_trash()?;
+            Ok(())
+        }
+        _ => check(drive.trash_many(resolve_all(drive, m)?)),
+    }
+}
+
+pub fn restore(drive: &Drive, m: &ArgMatches) -> CliResult {
+    let mut failed = 0;
+    for id in resolve_all(drive, m)? {
+        if let Err(e) = drive.set_trashed(&id, false) {
+            eprintln!("failed: {}: {}", id, e);
+            failed += 1;
+        }
+    }
+    if failed == 0 {
+        Ok(())
+    } else {
+        Err(format!("{} file(s) failed", failed).into())
+    }
+}
+
+pub fn rm(drive: &Drive, m: &ArgMatches) -> CliResult {
+    let ids = resolve_all(drive, m)?;
+    if m.is_present("permanent") {
+        check(drive.delete_many(ids))
+    } else {
+        check(drive.trash_many(ids))
+    }
+}
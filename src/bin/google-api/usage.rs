@@ -0,0 +1,119 @@
+//! `google-api drive quota` and `google-api drive du`
+
+use clap::{App, Arg, ArgMatches, SubCommand};
+use google_api::{Drive, FileId};
+
+use crate::drive::resolve;
+use crate::output::{format, human_size, print_rows, Format};
+use crate::CliResult;
+
+pub fn quota_command() -> App<'static, 'static> {
+    SubCommand::with_name("quota")
+        .about("Show how much storage is used and left")
+        .arg(
+            Arg::with_name("json")
+                .long("json")
+                .help("Print the quota as JSON, in bytes"),
+        )
+}
+
+pub fn du_command() -> App<'static, 'static> {
+    SubCommand::with_name("du")
+        .about("Show storage used by a folder and each folder below it, biggest first")
+        .arg(Arg::with_name("folder").help("Folder, by id, url or path [default: My Drive]"))
+        .arg(
+            Arg::with_name("depth")
+                .long("depth")
+                .short("d")
+                .value_name("N")
+                .validator(|v| v.parse::<usize>().map(|_| ()).map_err(|e| e.to_string()))
+                .help("Only show folders up to N levels below the folder, 0 for just its total"),
+        )
+        .arg(
+            Arg::with_name("json")
+                .long("json")
+                .help("Print the usage as JSON, in bytes"),
+        )
+}
+
+pub fn quota(drive: &Drive, m: &ArgMatches) -> CliResult {
+    let about = drive.about()?;
+    let quota = &about.storage_quota;
+    if format(m) == Format::Json {
+        let json = serde_json::json!({
+            "user": about.user.email_address,
+            "storageQuota": quota,
+            "remaining": quota.remaining(),
+        });
+        println!("{}", serde_json::to_string_pretty(&json)?);
+        return Ok(());
+    }
+
+    let used = quota.usage.unwrap_or_default();
+    let size = |bytes: Option<u64>| bytes.map(human_size).unwrap_or_else(|| "-".into());
+    let mut rows = vec![];
+    if let Some(email) = &about.user.email_address {
+        rows.push(vec!["Account".into(), email.clone()]);
+    }
+    match quota.limit {
+        Some(limit) if limit > 0 => rows.push(vec![
+            "Used".into(),
+            format!(
+                "{} of {} ({:.0}%)",
+                human_size(used),
+                human_size(limit),
+                used as f64 * 100.0 / limit as f64
+            ),
+        ]),
+        _ => rows.push(vec![
+            "Used".into(),
+            format!("{} (unlimited)", human_size(used)),
+        ]),
+    }
+    rows.push(vec!["In Drive".into(), size(quota.usage_in_drive)]);
+    rows.push(vec!["In trash".into(), size(quota.usage_in_drive_trash)]);
+    rows.push(vec!["Left".into(), size(quota.remaining())]);
+    print_rows(&rows, format(m));
+    Ok(())
+}
+
+pub fn du(drive: &Drive, m: &ArgMatches) -> CliResult {
+    let folder = match m.value_of("folder") {
+        Some(folder) => resolve(drive, folder)?,
+        None => FileId::root(),
+    };
+    let depth: Option<usize> = m.value_of("depth").and_then(|d| d.parse().ok());
+    let usage: Vec<_> = drive
+        .folder_usage(&folder)?
+        .into_iter()
+        .filter(|u| {
+            let level = if u.path.is_empty() {
+                0
+            } else {
+                u.path.split('/').count()
+            };
+            depth.map_or(true, |d| level <= d)
+        })
+        .collect();
+    if format(m) == Format::Json {
+        println!("{}", serde_json::to_string_pretty(&usage)?);
+        return Ok(());
+    }
+    let rows: Vec<Vec<String>> = usage
+        .iter()
+        .map(|u| {
+            let files = if u.native_count > 0 {
+                format!(
+                    "{} files ({} Google Docs etc.)",
+                    u.file_count, u.native_count
+                )
+            } else {
+                format!("{} files", u.file_count)
+            };
+            let path = if u.path.is_empty() { "." } else { &u.path };
+            vec![human_size(u.bytes), files, path.to_string()]
+        })
+        .collect();
+    print_rows(&rows, format(m));
+    Ok(())
+}
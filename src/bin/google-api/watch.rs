@@ -0,0 +1,124 @@
+//! `google-api drive watch`
+
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::Duration;
+
+use clap::{App, Arg, ArgMatches, SubCommand};
+use google_api::{Change, ChangeCursor, Drive, FileId};
+
+use crate::drive::resolve;
+use crate::CliResult;
+
+pub fn command() -> App<'static, 'static> {
+    SubCommand::with_name("watch")
+        .about("Poll Drive for changes and print them as JSON lines, or run a command for each")
+        .after_help(
+            "With --exec the command runs through the shell once per change, with the change \
+             as JSON on stdin and GOOGLE_DRIVE_FILE_ID, GOOGLE_DRIVE_FILE_NAME and \
+             GOOGLE_DRIVE_REMOVED (true or false) set. It failing is reported but doesn't stop \
+             the watch.",
+        )
+        .arg(
+            Arg::with_name("exec")
+                .long("exec")
+                .short("e")
+                .value_name("COMMAND")
+                .help("Command to run for each change, instead of printing it"),
+        )
+        .arg(
+            Arg::with_name("folder")
+                .long("folder")
+                .short("f")
+                .value_name("FOLDER")
+                .help("Only changes to files directly in this folder, by id, url or path"),
+        )
+        .arg(
+            Arg::with_name("interval")
+                .long("interval")
+                .short("i")
+                .value_name("SECONDS")
+                .default_value("30")
+                .validator(|v| v.parse::<u64>().map(|_| ()).map_err(|e| e.to_string()))
+                .help("Time between polls"),
+        )
+        .arg(
+            Arg::with_name("cursor")
+                .long("cursor")
+                .value_name("FILE")
+                .help("Remember the position in the change feed in this file, to pick up where the last run stopped instead of from now"),
+        )
+}
+
+/// Poll until killed. Errors polling, a network blip say, are reported and retried next
+/// interval rather than ending the watch.
+pub fn watch(drive: &Drive, m: &ArgMatches) -> CliResult {
+    let folder = match m.value_of("folder") {
+        Some(folder) => Some(resolve(drive, folder)?),
+        None => None,
+    };
+    let interval = Duration::from_secs(m.value_of("interval").unwrap_or_default().parse()?);
+    let mut cursor = match m.value_of("cursor") {
+        Some(path) => ChangeCursor::load(PathBuf::from(path))?,
+        None => ChangeCursor::in_memory(),
+    };
+
+    loop {
+        match drive.changes_since(&mut cursor) {
+            Ok(changes) => {
+                for change in changes.iter().filter(|c| in_folder(c, folder.as_ref())) {
+                    match m.value_of("exec") {
+                        Some(cmd) => run_command(cmd, change),
+                        None => println!("{}", serde_json::to_string(change)?),
+                    }
+                }
+                cursor.save()?;
+            }
+            Err(e) => eprintln!("google-api: polling changes failed, retrying: {}", e),
+        }
+        thread::sleep(interval);
+    }
+}
+
+/// Removed files have no metadata left to tell where they were, so they only pass without a
+/// folder given.
+fn in_folder(change: &Change, folder: Option<&FileId>) -> bool {
+    match (folder, &change.file) {
+        (None, _) => true,
+        (Some(folder), Some(file)) => file.parents.iter().any(|p| p == folder.as_str()),
+        (Some(_), None) => false,
+    }
+}
+
+fn run_command(cmd: &str, change: &Change) {
+    let (shell, flag) = if cfg!(windows) {
+        ("cmd", "/C")
+    } else {
+        ("sh", "-c")
+    };
+    let name = change.file.as_ref().map(|f| f.name.as_str());
+    let child = Command::new(shell)
+        .args([flag, cmd])
+        .env(
+            "GOOGLE_DRIVE_FILE_ID",
+            change.file_id.as_deref().unwrap_or_default(),
+        )
+        .env("GOOGLE_DRIVE_FILE_NAME", name.unwrap_or_default())
+        .env("GOOGLE_DRIVE_REMOVED", change.removed.to_string())
+        .stdin(Stdio::piped())
+        .spawn();
+    let result = child.and_then(|mut child| {
+        if let Some(mut stdin) = child.stdin.take() {
+            // The command not reading its input is fine.
+            let _ = stdin.write_all(serde_json::to_string(change)?.as_bytes());
+        }
+        child.wait()
+    });
+    match result {
+        Ok(status) if status.success() => {}
+        Ok(status) => eprintln!("google-api: {} exited with {}", cmd, status),
+        Err(e) => eprintln!("google-api: couldn't run {}: {}", cmd, e),
+    }
+}
@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::auth::Auth;
+use crate::error::Error;
+use crate::retry::RetryPolicy;
+use crate::{Drive, DEFAULT_DRIVE_SCOPE};
+
+/// Step by step configuration of a `Drive` client, from `Drive::builder`. Only the OAuth
+/// client id and secret are required.
+///
+/// ```no_run
+/// # use std::time::Duration;
+/// # use google_api::*;
+/// # let (client_id, client_secret) = (String::new(), String::new());
+/// let drive = Drive::builder()
+///     .client_id(client_id)
+///     .client_secret(client_secret)
+///     .scope(Scope::DriveFile)
+///     .config_path("/var/lib/backup/token.json")
+///     .timeout(Duration::from_secs(30))
+///     .retry_policy(RetryPolicy::new(3, Duration::from_secs(1)))
+///     .build()?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct DriveBuilder {
+    client_id: Option<String>,
+    client_secret: Option<String>,
+    scopes: Vec<String>,
+    config_path: Option<PathBuf>,
+    timeout: Option<Duration>,
+    retry: RetryPolicy,
+}
+
+impl DriveBuilder {
+    pub fn new() -> DriveBuilder {
+        DriveBuilder::default()
+    }
+
+    pub fn client_id(mut self, client_id: impl Into<String>) -> DriveBuilder {
+        self.client_id = Some(client_id.into());
+        self
+    }
+
+    pub fn client_secret(mut self, client_secret: impl Into<String>) -> DriveBuilder {
+        self.client_secret = Some(client_secret.into());
+        self
+    }
+
+    /// Scopes to ask for, replacing any given before. Defaults to full Drive access.
+    pub fn scopes<S: Into<String>>(mut self, scopes: impl IntoIterator<Item = S>) -> DriveBuilder {
+        self.scopes = scopes.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Ask for one more scope.
+    pub fn scope(mut self, scope: impl Into<String>) -> DriveBuilder {
+        self.scopes.push(scope.into());
+        self
+    }
+
+    /// File to keep the token in instead of the current profile's, e.g. for a service running
+    /// as its own user. It's created on first sign in.
+    pub fn config_path(mut self, path: impl Into<PathBuf>) -> DriveBuilder {
+        self.config_path = Some(path.into());
+        self
+    }
+
+    /// Give up on a request that takes longer than this, from connecting to reading the end
+    /// of the response. Applies to whole downloads too, so raise it for big files. 30 seconds
+    /// by default.
+    pub fn timeout(mut self, timeout: Duration) -> DriveBuilder {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// How rate limits, server errors and failed upload chunks are retried.
+    pub fn retry_policy(mut self, retry: RetryPolicy) -> DriveBuilder {
+        self.retry = retry;
+        self
+    }
+
+    /// Sign in and create the client. The user is asked for consent if the token file lacks
+    /// any of the scopes.
+    pub fn build(self) -> Result<Drive, Error> {
+        let client_id = self
+            .client_id
+            .ok_or_else(|| Error::Auth(String::from("no OAuth client id given")))?;
+        let client_secret = self
+            .client_secret
+            .ok_or_else(|| Error::Auth(String::from("no OAuth client secret given")))?;
+        let scopes = if self.scopes.is_empty() {
+            vec![String::from(DEFAULT_DRIVE_SCOPE)]
+        } else {
+            self.scopes
+        };
+        let mut client = reqwest::blocking::Client::builder();
+        if let Some(timeout) = self.timeout {
+            client = client.timeout(timeout);
+        }
+        let client = client.build()?;
+
+        Ok(Drive {
+            auth: Auth::from_config_at(&client_id, &client_secret, &scopes, self.config_path)?,
+            client,
+            paths: Arc::new(Mutex::new(HashMap::new())),
+            retry: self.retry,
+        })
+    }
+}
@@ -0,0 +1,219 @@
+use std::collections::HashMap;
+
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+
+use super::events::ReminderOverride;
+use super::Calendar;
+use crate::error::{CheckStatus, Error};
+use crate::models::ExtraFields;
+
+/// How the user hears about a kind of change on a calendar.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct CalendarNotification {
+    /// `eventCreation`, `eventChange`, `eventCancellation`, `eventResponse` or `agenda`.
+    #[serde(rename = "type")]
+    pub notification_type: String,
+    /// Only `email` is supported.
+    pub method: String,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct NotificationSettings {
+    pub notifications: Vec<CalendarNotification>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+/// A calendar in the user's list, with their own settings for it. Only fields that are set
+/// are sent, so it doubles as the changes for `Calendar::update_calendar_entry`.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CalendarListEntry {
+    #[serde(skip_serializing)]
+    pub id: String,
+    #[serde(skip_serializing)]
+    pub summary: String,
+    #[serde(skip_serializing)]
+    pub description: Option<String>,
+    #[serde(skip_serializing)]
+    pub time_zone: Option<String>,
+    /// `owner`, `writer`, `reader` or `freeBusyReader`.
+    #[serde(skip_serializing)]
+    pub access_role: String,
+    /// Set on the user's primary calendar.
+    #[serde(skip_serializing)]
+    pub primary: bool,
+    /// The user's own name for the calendar.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary_override: Option<String>,
+    /// Key into `CalendarColors::calendar`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color_id: Option<String>,
+    /// `#rrggbb`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub background_color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub foreground_color: Option<String>,
+    /// Shown in the UI's calendar list.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub selected: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hidden: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_reminders: Option<Vec<ReminderOverride>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notification_settings: Option<NotificationSettings>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct ColorDefinition {
+    pub background: String,
+    pub foreground: String,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+/// The palettes behind `color_id`s, by id.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct CalendarColors {
+    pub calendar: HashMap<String, ColorDefinition>,
+    pub event: HashMap<String, ColorDefinition>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct CalendarList {
+    items: Vec<CalendarListEntry>,
+    next_page_token: Option<String>,
+}
+
+impl Calendar {
+    /// Calendars in the user's list: their own and the ones they subscribed to.
+    pub fn list_calendars(&self) -> Result<Vec<CalendarListEntry>, Error> {
+        let mut calendars = vec![];
+        let mut page_token: Option<String> = None;
+        loop {
+            let mut params = vec![("maxResults", "250")];
+            if let Some(token) = &page_token {
+                params.push(("pageToken", token));
+            }
+            let page: CalendarList = self
+                .request(Method::GET, &["users", "me", "calendarList"])?
+                .query(&params)
+                .send()?
+                .check_status()?
+                .json()?;
+            calendars.extend(page.items);
+
+            match page.next_page_token {
+                Some(token) => page_token = Some(token),
+                None => break,
+            }
+        }
+        Ok(calendars)
+    }
+
+    /// Subscribe to an existing calendar (someone's address, a shared calendar's id).
+    pub fn add_calendar(&self, calendar_id: &str) -> Result<CalendarListEntry, Error> {
+        Ok(self
+            .request(Method::POST, &["users", "me", "calendarList"])?
+            .json(&serde_json::json!({ "id": calendar_id }))
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    /// Create a new calendar owned by the user. It's added to their list.
+    pub fn create_calendar(
+        &self,
+        summary: &str,
+        time_zone: Option<&str>,
+    ) -> Result<CalendarListEntry, Error> {
+        let mut body = serde_json::json!({ "summary": summary });
+        if let Some(time_zone) = time_zone {
+            body["timeZone"] = serde_json::json!(time_zone);
+        }
+        Ok(self
+            .request(Method::POST, &["calendars"])?
+            .json(&body)
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    /// Take a calendar out of the user's list. The calendar itself is left alone.
+    pub fn remove_calendar(&self, calendar_id: &str) -> Result<(), Error> {
+        self.request(
+            Method::DELETE,
+            &["users", "me", "calendarList", calendar_id],
+        )?
+        .send()?
+        .check_status()?;
+        Ok(())
+    }
+
+    /// Change the user's settings for a calendar in their list: the fields set in `changes`.
+    pub fn update_calendar_entry(
+        &self,
+        calendar_id: &str,
+        changes: &CalendarListEntry,
+    ) -> Result<CalendarListEntry, Error> {
+        let mut request =
+            self.request(Method::PATCH, &["users", "me", "calendarList", calendar_id])?;
+        if changes.background_color.is_some() || changes.foreground_color.is_some() {
+            request = request.query(&[("colorRgbFormat", "true")]);
+        }
+        Ok(request.json(changes).send()?.check_status()?.json()?)
+    }
+
+    /// Show a calendar in given `#rrggbb` colors.
+    pub fn set_calendar_color(
+        &self,
+        calendar_id: &str,
+        background: &str,
+        foreground: &str,
+    ) -> Result<CalendarListEntry, Error> {
+        let changes = CalendarListEntry {
+            background_color: Some(background.to_string()),
+            foreground_color: Some(foreground.to_string()),
+            ..CalendarListEntry::default()
+        };
+        self.update_calendar_entry(calendar_id, &changes)
+    }
+
+    /// Replace the email notifications the user gets for a calendar. Empty turns them off.
+    pub fn set_calendar_notifications(
+        &self,
+        calendar_id: &str,
+        notifications: Vec<CalendarNotification>,
+    ) -> Result<CalendarListEntry, Error> {
+        let changes = CalendarListEntry {
+            notification_settings: Some(NotificationSettings {
+                notifications,
+                ..NotificationSettings::default()
+            }),
+            ..CalendarListEntry::default()
+        };
+        self.update_calendar_entry(calendar_id, &changes)
+    }
+
+    /// The color palettes for calendars and events.
+    pub fn colors(&self) -> Result<CalendarColors, Error> {
+        Ok(self
+            .request(Method::GET, &["colors"])?
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+}
@@ -0,0 +1,436 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+
+use super::recurrence::{ical_time, Recurrence};
+use super::Calendar;
+use crate::error::{CheckStatus, Error};
+use crate::models::ExtraFields;
+use crate::timestamp::{format_rfc3339, parse_rfc3339};
+
+/// When an event starts or ends: a moment for timed events, a date for all-day ones.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct EventTime {
+    /// RFC 3339 timestamp.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_time: Option<String>,
+    /// `yyyy-mm-dd`, for all-day events. The end date is exclusive.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+    /// IANA zone, e.g. `Europe/Paris`. Needed for recurring events.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_zone: Option<String>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl EventTime {
+    pub fn at(time: SystemTime) -> EventTime {
+        EventTime {
+            date_time: Some(format_rfc3339(time)),
+            ..EventTime::default()
+        }
+    }
+
+    /// An RFC 3339 timestamp, e.g. `2024-05-01T09:00:00+02:00`.
+    pub fn at_rfc3339(timestamp: &str) -> EventTime {
+        EventTime {
+            date_time: Some(timestamp.to_string()),
+            ..EventTime::default()
+        }
+    }
+
+    /// A whole day, `yyyy-mm-dd`.
+    pub fn all_day(date: &str) -> EventTime {
+        EventTime {
+            date: Some(date.to_string()),
+            ..EventTime::default()
+        }
+    }
+
+    pub fn in_zone(mut self, time_zone: &str) -> EventTime {
+        self.time_zone = Some(time_zone.to_string());
+        self
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Attendee {
+    pub email: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub optional: bool,
+    /// `needsAction`, `declined`, `tentative` or `accepted`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_status: Option<String>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ReminderOverride {
+    /// `popup` or `email`.
+    pub method: String,
+    pub minutes: u32,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+/// Reminders for an event: the calendar's defaults, or the overrides given.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Reminders {
+    pub use_default: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub overrides: Vec<ReminderOverride>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+/// A way into the event's video call.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct EntryPoint {
+    /// `video`, `phone`, `sip` or `more`.
+    pub entry_point_type: String,
+    pub uri: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+/// Video conference attached to an event.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ConferenceData {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conference_id: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub entry_points: Vec<EntryPoint>,
+    /// Asks for a new conference to be created. Only sent, never read back.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub create_request: Option<serde_json::Value>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl ConferenceData {
+    /// Ask for a new Google Meet call.
+    pub fn new_meet() -> ConferenceData {
+        let request_id = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        ConferenceData {
+            create_request: Some(serde_json::json!({
+                "requestId": format!("{:x}", request_id),
+                "conferenceSolutionKey": { "type": "hangoutsMeet" },
+            })),
+            ..ConferenceData::default()
+        }
+    }
+
+    /// Link to join the video call, once the conference has been created.
+    pub fn video_link(&self) -> Option<&str> {
+        self.entry_points
+            .iter()
+            .find(|e| e.entry_point_type == "video")
+            .map(|e| e.uri.as_str())
+    }
+}
+
+/// A calendar event. Only fields that are set are sent, so the same type serves to create
+/// events and to patch some of their fields.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Event {
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub id: String,
+    /// `confirmed`, `tentative` or `cancelled`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start: Option<EventTime>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end: Option<EventTime>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub attendees: Vec<Attendee>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reminders: Option<Reminders>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conference_data: Option<ConferenceData>,
+    /// `RRULE`, `EXRULE`, `RDATE` and `EXDATE` lines of a recurring event.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub recurrence: Vec<String>,
+    /// For an instance of a recurring event, the id of its series.
+    #[serde(skip_serializing)]
+    pub recurring_event_id: Option<String>,
+    /// For an instance of a recurring event, when the series would have had it start.
+    #[serde(skip_serializing)]
+    pub original_start_time: Option<EventTime>,
+    /// Link to the event in the Calendar web UI.
+    #[serde(skip_serializing)]
+    pub html_link: String,
+    #[serde(skip_serializing)]
+    pub created: Option<String>,
+    #[serde(skip_serializing)]
+    pub updated: Option<String>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl Event {
+    pub fn new(summary: &str, start: EventTime, end: EventTime) -> Event {
+        Event {
+            summary: Some(summary.to_string()),
+            start: Some(start),
+            end: Some(end),
+            ..Event::default()
+        }
+    }
+
+    pub fn description(mut self, description: &str) -> Event {
+        self.description = Some(description.to_string());
+        self
+    }
+
+    pub fn location(mut self, location: &str) -> Event {
+        self.location = Some(location.to_string());
+        self
+    }
+
+    /// Invite someone. Can be given more than once.
+    pub fn attendee(mut self, email: &str) -> Event {
+        self.attendees.push(Attendee {
+            email: email.to_string(),
+            ..Attendee::default()
+        });
+        self
+    }
+
+    /// Remind `minutes` before the start, by `popup` or `email`. Replaces the calendar's
+    /// default reminders. Can be given more than once.
+    pub fn reminder(mut self, method: &str, minutes: u32) -> Event {
+        self.reminders
+            .get_or_insert_with(Reminders::default)
+            .overrides
+            .push(ReminderOverride {
+                method: method.to_string(),
+                minutes,
+                ..ReminderOverride::default()
+            });
+        self
+    }
+
+    /// Create a Google Meet call for the event.
+    pub fn with_meet(mut self) -> Event {
+        self.conference_data = Some(ConferenceData::new_meet());
+        self
+    }
+
+    /// Repeat the event. Start and end need a time zone (`EventTime::in_zone`) so the
+    /// series keeps its local time across daylight saving changes.
+    pub fn repeat(mut self, rule: &Recurrence) -> Event {
+        self.recurrence.push(rule.to_string());
+        self
+    }
+
+    /// Leave out the occurrence starting at given time (or on given day, for all-day events).
+    pub fn except(mut self, start: &EventTime) -> Event {
+        let line = match (&start.date_time, &start.date) {
+            (Some(date_time), _) => match parse_rfc3339(date_time) {
+                Some(time) => format!("EXDATE:{}", ical_time(time)),
+                None => return self,
+            },
+            (None, Some(date)) => format!("EXDATE;VALUE=DATE:{}", date.replace('-', "")),
+            (None, None) => return self,
+        };
+        self.recurrence.push(line);
+        self
+    }
+
+    /// Id of the series this event belongs to: its own id unless it's an instance.
+    pub fn series_id(&self) -> &str {
+        self.recurring_event_id.as_deref().unwrap_or(&self.id)
+    }
+}
+
+/// Which events `Calendar::list_events` returns.
+///
+/// ```
+/// # use std::time::{Duration, SystemTime};
+/// # use google_api::*;
+/// # let now = SystemTime::now();
+/// let query = EventQuery::between(now, now + Duration::from_secs(7 * 86400)).search("review");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventQuery {
+    time_min: Option<SystemTime>,
+    time_max: Option<SystemTime>,
+    single_events: bool,
+    search: Option<String>,
+    show_deleted: bool,
+}
+
+impl Default for EventQuery {
+    fn default() -> EventQuery {
+        EventQuery {
+            time_min: None,
+            time_max: None,
+            single_events: true,
+            search: None,
+            show_deleted: false,
+        }
+    }
+}
+
+impl EventQuery {
+    /// Every event, with recurring events expanded.
+    pub fn new() -> EventQuery {
+        EventQuery::default()
+    }
+
+    /// Events overlapping the window from `start` to `end`.
+    pub fn between(start: SystemTime, end: SystemTime) -> EventQuery {
+        EventQuery {
+            time_min: Some(start),
+            time_max: Some(end),
+            ..EventQuery::default()
+        }
+    }
+
+    /// Expand recurring events into their instances, ordered by start time. On by default,
+    /// off lists each series once.
+    pub fn single_events(mut self, single: bool) -> EventQuery {
+        self.single_events = single;
+        self
+    }
+
+    /// Free text search over summary, description, location and attendees.
+    pub fn search(mut self, text: &str) -> EventQuery {
+        self.search = Some(text.to_string());
+        self
+    }
+
+    /// Include cancelled events.
+    pub fn show_deleted(mut self, show: bool) -> EventQuery {
+        self.show_deleted = show;
+        self
+    }
+
+    fn params(&self) -> Vec<(&'static str, String)> {
+        let mut params = vec![("maxResults", String::from("2500"))];
+        if let Some(time) = self.time_min {
+            params.push(("timeMin", format_rfc3339(time)));
+        }
+        if let Some(time) = self.time_max {
+            params.push(("timeMax", format_rfc3339(time)));
+        }
+        if self.single_events {
+            params.push(("singleEvents", String::from("true")));
+            params.push(("orderBy", String::from("startTime")));
+        }
+        if let Some(text) = &self.search {
+            params.push(("q", text.clone()));
+        }
+        if self.show_deleted {
+            params.push(("showDeleted", String::from("true")));
+        }
+        params
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub(super) struct EventList {
+    pub(super) items: Vec<Event>,
+    pub(super) next_page_token: Option<String>,
+}
+
+impl Calendar {
+    /// Events on a calendar matching the query, following every page.
+    pub fn list_events(&self, calendar_id: &str, query: &EventQuery) -> Result<Vec<Event>, Error> {
+        let params = query.params();
+        let mut events = vec![];
+        let mut page_token: Option<String> = None;
+        loop {
+            let mut page_params = params.clone();
+            if let Some(token) = &page_token {
+                page_params.push(("pageToken", token.clone()));
+            }
+            let page: EventList = self
+                .request(Method::GET, &["calendars", calendar_id, "events"])?
+                .query(&page_params)
+                .send()?
+                .check_status()?
+                .json()?;
+            events.extend(page.items);
+
+            match page.next_page_token {
+                Some(token) => page_token = Some(token),
+                None => break,
+            }
+        }
+        Ok(events)
+    }
+
+    pub fn get_event(&self, calendar_id: &str, event_id: &str) -> Result<Event, Error> {
+        Ok(self
+            .request(Method::GET, &["calendars", calendar_id, "events", event_id])?
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    /// Add an event to a calendar. Attendees aren't emailed about it.
+    pub fn create_event(&self, calendar_id: &str, event: &Event) -> Result<Event, Error> {
+        Ok(self
+            .request(Method::POST, &["calendars", calendar_id, "events"])?
+            .query(&[("conferenceDataVersion", "1")])
+            .json(event)
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    /// Change the fields that are set in `changes`, leaving the rest of the event alone.
+    pub fn patch_event(
+        &self,
+        calendar_id: &str,
+        event_id: &str,
+        changes: &Event,
+    ) -> Result<Event, Error> {
+        Ok(self
+            .request(
+                Method::PATCH,
+                &["calendars", calendar_id, "events", event_id],
+            )?
+            .query(&[("conferenceDataVersion", "1")])
+            .json(changes)
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    pub fn delete_event(&self, calendar_id: &str, event_id: &str) -> Result<(), Error> {
+        self.request(
+            Method::DELETE,
+            &["calendars", calendar_id, "events", event_id],
+        )?
+        .send()?
+        .check_status()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,123 @@
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime};
+
+use reqwest::Method;
+use serde::Deserialize;
+
+use super::Calendar;
+use crate::error::{CheckStatus, Error};
+use crate::models::ExtraFields;
+use crate::timestamp::{format_rfc3339, parse_rfc3339};
+
+/// A stretch of time a calendar is busy, as RFC 3339 timestamps.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct BusyPeriod {
+    pub start: String,
+    pub end: String,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct FreeBusyError {
+    pub domain: String,
+    /// e.g. `notFound`, or `groupTooBig` for a group with too many members to expand.
+    pub reason: String,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+/// Busy times of one calendar. A calendar that couldn't be read has `errors` and no busy
+/// times, which doesn't mean it's free.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct CalendarBusy {
+    pub busy: Vec<BusyPeriod>,
+    pub errors: Vec<FreeBusyError>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+/// Answer to `Calendar::free_busy`.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct FreeBusy {
+    pub time_min: String,
+    pub time_max: String,
+    /// Busy times by calendar id.
+    pub calendars: HashMap<String, CalendarBusy>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl FreeBusy {
+    /// Gaps of at least `min_length` in the queried window where none of the calendars is
+    /// busy, in order.
+    pub fn free_slots(&self, min_length: Duration) -> Vec<(SystemTime, SystemTime)> {
+        let (window_start, window_end) =
+            match (parse_rfc3339(&self.time_min), parse_rfc3339(&self.time_max)) {
+                (Some(start), Some(end)) => (start, end),
+                _ => return vec![],
+            };
+        let mut busy: Vec<(SystemTime, SystemTime)> = self
+            .calendars
+            .values()
+            .flat_map(|c| &c.busy)
+            .filter_map(|p| Some((parse_rfc3339(&p.start)?, parse_rfc3339(&p.end)?)))
+            .collect();
+        busy.sort();
+
+        let mut slots = vec![];
+        let mut free_from = window_start;
+        for (start, end) in busy {
+            if start > free_from {
+                push_slot(&mut slots, free_from, start.min(window_end), min_length);
+            }
+            free_from = free_from.max(end);
+        }
+        push_slot(&mut slots, free_from, window_end, min_length);
+        slots
+    }
+}
+
+fn push_slot(
+    slots: &mut Vec<(SystemTime, SystemTime)>,
+    start: SystemTime,
+    end: SystemTime,
+    min_length: Duration,
+) {
+    let long_enough = end
+        .duration_since(start)
+        .is_ok_and(|length| length >= min_length && !length.is_zero());
+    if long_enough {
+        slots.push((start, end));
+    }
+}
+
+impl Calendar {
+    /// When each of given calendars is busy between `start` and `end`. Takes calendar ids,
+    /// which for people in the same organization are their email addresses.
+    pub fn free_busy(
+        &self,
+        calendar_ids: &[&str],
+        start: SystemTime,
+        end: SystemTime,
+    ) -> Result<FreeBusy, Error> {
+        let items: Vec<_> = calendar_ids
+            .iter()
+            .map(|id| serde_json::json!({ "id": id }))
+            .collect();
+        Ok(self
+            .request(Method::POST, &["freeBusy"])?
+            .json(&serde_json::json!({
+                "timeMin": format_rfc3339(start),
+                "timeMax": format_rfc3339(end),
+                "items": items,
+            }))
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+}
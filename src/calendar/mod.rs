@@ -0,0 +1,75 @@
+use reqwest::blocking::RequestBuilder;
+use reqwest::Method;
+
+use crate::api;
+use crate::auth::Auth;
+use crate::error::Error;
+use crate::scope::Scope;
+
+mod calendars;
+mod events;
+mod freebusy;
+mod recurrence;
+
+pub use calendars::{
+    CalendarColors, CalendarListEntry, CalendarNotification, ColorDefinition, NotificationSettings,
+};
+pub use events::{
+    Attendee, ConferenceData, EntryPoint, Event, EventQuery, EventTime, ReminderOverride, Reminders,
+};
+pub use freebusy::{BusyPeriod, CalendarBusy, FreeBusy, FreeBusyError};
+pub use recurrence::{Frequency, Recurrence};
+
+const CALENDAR_BASE_URL: &str = "https://www.googleapis.com/calendar/v3";
+
+/// Alias for the user's main calendar, usable anywhere a calendar id is expected.
+pub const PRIMARY_CALENDAR: &str = "primary";
+
+/// Google Calendar client. Shares its saved token with the other clients.
+///
+/// ```no_run
+/// # use std::time::{Duration, SystemTime};
+/// # use google_api::*;
+/// # let (client_id, client_secret) = (String::new(), String::new());
+/// # let start = SystemTime::now();
+/// # let end = start + Duration::from_secs(15 * 60);
+/// let calendar = Calendar::new(client_id, client_secret, None)?;
+/// let standup = Event::new("Standup", EventTime::at(start), EventTime::at(end))
+///     .attendee("team@example.com")
+///     .with_meet();
+/// calendar.create_event(PRIMARY_CALENDAR, &standup)?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug)]
+pub struct Calendar {
+    auth: Auth,
+    client: reqwest::blocking::Client,
+}
+
+impl Calendar {
+    /// Authenticate with given OAuth client. `scopes` defaults to `Scope::Calendar`.
+    pub fn new(
+        client_id: String,
+        client_secret: String,
+        scopes: Option<Vec<String>>,
+    ) -> Result<Calendar, Error> {
+        let scopes = scopes.unwrap_or_else(|| vec![Scope::Calendar.into()]);
+
+        Ok(Calendar {
+            auth: Auth::from_config(&client_id, &client_secret, &scopes)?,
+            client: reqwest::blocking::Client::new(),
+        })
+    }
+
+    /// Authorized request for the endpoint made of given path segments, e.g.
+    /// `["calendars", id, "events"]`.
+    fn request(&self, method: Method, segments: &[&str]) -> Result<RequestBuilder, Error> {
+        api::request(
+            &self.client,
+            &self.auth,
+            CALENDAR_BASE_URL,
+            method,
+            segments,
+        )
+    }
+}
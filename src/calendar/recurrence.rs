@@ -0,0 +1,175 @@
+use std::fmt;
+use std::time::SystemTime;
+
+use reqwest::Method;
+
+use super::events::{Event, EventList};
+use super::Calendar;
+use crate::error::{CheckStatus, Error};
+use crate::timestamp::format_rfc3339;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+impl Frequency {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Frequency::Daily => "DAILY",
+            Frequency::Weekly => "WEEKLY",
+            Frequency::Monthly => "MONTHLY",
+            Frequency::Yearly => "YEARLY",
+        }
+    }
+}
+
+/// An RFC 5545 `RRULE`, for `Event::repeat`.
+///
+/// ```
+/// # use google_api::*;
+/// // Every other Monday and Wednesday, ten times.
+/// let rule = Recurrence::new(Frequency::Weekly).interval(2).on(&["MO", "WE"]).count(10);
+/// assert_eq!(rule.to_string(), "RRULE:FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,WE;COUNT=10");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Recurrence {
+    frequency: Frequency,
+    interval: u32,
+    by_day: Vec<String>,
+    count: Option<u32>,
+    until: Option<SystemTime>,
+}
+
+impl Recurrence {
+    pub fn new(frequency: Frequency) -> Recurrence {
+        Recurrence {
+            frequency,
+            interval: 1,
+            by_day: vec![],
+            count: None,
+            until: None,
+        }
+    }
+
+    /// Repeat every `n` periods instead of every one.
+    pub fn interval(mut self, n: u32) -> Recurrence {
+        self.interval = n;
+        self
+    }
+
+    /// Days the event falls on, as `MO`..`SU`. Monthly rules take an ordinal too, e.g. `1MO`
+    /// for the first Monday or `-1FR` for the last Friday.
+    pub fn on(mut self, days: &[&str]) -> Recurrence {
+        self.by_day.extend(days.iter().map(|d| d.to_uppercase()));
+        self
+    }
+
+    /// Stop after `n` occurrences.
+    pub fn count(mut self, n: u32) -> Recurrence {
+        self.count = Some(n);
+        self.until = None;
+        self
+    }
+
+    /// Stop at given time. An occurrence starting exactly then is still included.
+    pub fn until(mut self, time: SystemTime) -> Recurrence {
+        self.until = Some(time);
+        self.count = None;
+        self
+    }
+}
+
+impl fmt::Display for Recurrence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "RRULE:FREQ={}", self.frequency.as_str())?;
+        if self.interval > 1 {
+            write!(f, ";INTERVAL={}", self.interval)?;
+        }
+        if !self.by_day.is_empty() {
+            write!(f, ";BYDAY={}", self.by_day.join(","))?;
+        }
+        if let Some(count) = self.count {
+            write!(f, ";COUNT={}", count)?;
+        }
+        if let Some(until) = self.until {
+            write!(f, ";UNTIL={}", ical_time(until))?;
+        }
+        Ok(())
+    }
+}
+
+/// A UTC time in iCalendar's basic format, e.g. `20240501T090000Z`.
+pub(crate) fn ical_time(time: SystemTime) -> String {
+    format_rfc3339(time).replace(['-', ':'], "")
+}
+
+impl Calendar {
+    /// Occurrences of a recurring event, optionally only those overlapping the window from
+    /// `start` to `end`. Each has its own id, which `patch_event` and `delete_event` take to
+    /// change or cancel that one occurrence.
+    pub fn list_instances(
+        &self,
+        calendar_id: &str,
+        event_id: &str,
+        window: Option<(SystemTime, SystemTime)>,
+    ) -> Result<Vec<Event>, Error> {
+        let mut params = vec![("maxResults", String::from("2500"))];
+        if let Some((start, end)) = window {
+            params.push(("timeMin", format_rfc3339(start)));
+            params.push(("timeMax", format_rfc3339(end)));
+        }
+        let mut instances = vec![];
+        let mut page_token: Option<String> = None;
+        loop {
+            let mut page_params = params.clone();
+            if let Some(token) = &page_token {
+                page_params.push(("pageToken", token.clone()));
+            }
+            let page: EventList = self
+                .request(
+                    Method::GET,
+                    &["calendars", calendar_id, "events", event_id, "instances"],
+                )?
+                .query(&page_params)
+                .send()?
+                .check_status()?
+                .json()?;
+            instances.extend(page.items);
+
+            match page.next_page_token {
+                Some(token) => page_token = Some(token),
+                None => break,
+            }
+        }
+        Ok(instances)
+    }
+
+    /// Cancel one occurrence of a recurring event, leaving the rest of the series.
+    pub fn cancel_instance(&self, calendar_id: &str, instance_id: &str) -> Result<Event, Error> {
+        let cancelled = Event {
+            status: Some(String::from("cancelled")),
+            ..Event::default()
+        };
+        self.patch_event(calendar_id, instance_id, &cancelled)
+    }
+
+    /// Apply changes to every occurrence of the series `event` (the series itself or any of
+    /// its instances) belongs to. Occurrences changed one by one keep those changes.
+    pub fn patch_series(
+        &self,
+        calendar_id: &str,
+        event: &Event,
+        changes: &Event,
+    ) -> Result<Event, Error> {
+        self.patch_event(calendar_id, event.series_id(), changes)
+    }
+
+    /// Delete the whole series `event` belongs to.
+    pub fn delete_series(&self, calendar_id: &str, event: &Event) -> Result<(), Error> {
+        self.delete_event(calendar_id, event.series_id())
+    }
+}
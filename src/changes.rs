@@ -0,0 +1,153 @@
+use std::fs::{create_dir_all, File};
+use std::io::{BufReader, BufWriter};
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{CheckStatus, Context, Error};
+use crate::models::{DriveFile, ExtraFields, FILE_FIELDS};
+use crate::params::Params;
+use crate::Drive;
+
+/// A single entry in the Drive change feed.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Change {
+    /// Either "file" or "drive".
+    pub change_type: String,
+    pub time: Option<String>,
+    /// File was deleted or access to it was lost.
+    pub removed: bool,
+    pub file_id: Option<String>,
+    /// Current file metadata, missing when `removed` is set.
+    pub file: Option<DriveFile>,
+    pub drive_id: Option<String>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct ChangeList {
+    changes: Vec<Change>,
+    next_page_token: Option<String>,
+    new_start_page_token: Option<String>,
+}
+
+/// Position in the change feed, persisted to a local file between runs or kept in memory.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ChangeCursor {
+    #[serde(skip)]
+    path: Option<PathBuf>,
+    token: Option<String>,
+}
+
+impl ChangeCursor {
+    /// Load cursor from given path. A missing file gives a fresh cursor that starts at the
+    /// current state of the Drive.
+    pub fn load(path: PathBuf) -> Result<ChangeCursor, Error> {
+        if !path.exists() {
+            return Ok(ChangeCursor {
+                path: Some(path),
+                token: None,
+            });
+        }
+        let reading = || format!("reading change cursor {}", path.display());
+        let f = File::open(&path).with_context(reading)?;
+        let cursor: ChangeCursor =
+            serde_json::from_reader(BufReader::new(f)).with_context(reading)?;
+        Ok(ChangeCursor {
+            path: Some(path),
+            token: cursor.token,
+        })
+    }
+
+    /// A fresh cursor without a file, which `save` leaves alone. It lasts as long as the
+    /// process does.
+    pub fn in_memory() -> ChangeCursor {
+        ChangeCursor {
+            path: None,
+            token: None,
+        }
+    }
+
+    /// Page token the next `changes_since` call will start from.
+    pub fn token(&self) -> Option<&str> {
+        self.token.as_deref()
+    }
+
+    /// Write the cursor to its file, if it has one. Call this once the changes it was advanced
+    /// past have been handled, so a crash halfway through replays them instead of losing them.
+    pub fn save(&self) -> Result<(), Error> {
+        let path = match &self.path {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+        let writing = || format!("writing change cursor {}", path.display());
+        if let Some(dir) = path.parent() {
+            create_dir_all(dir).with_context(writing)?;
+        }
+        let f = File::create(path).with_context(writing)?;
+        serde_json::to_writer_pretty(BufWriter::new(f), &self).with_context(writing)
+    }
+}
+
+impl Drive {
+    /// Get a page token pointing at the current end of the change feed.
+    pub fn start_page_token(&self) -> Result<String, Error> {
+        let resp: serde_json::Value = self
+            .get("/changes/startPageToken", Params::new())?
+            .check_status()?
+            .json()?;
+        Ok(resp["startPageToken"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string())
+    }
+
+    /// Get every change since given page token. Returns the changes and the token to pass next time.
+    pub fn list_changes(&self, page_token: &str) -> Result<(Vec<Change>, String), Error> {
+        let fields = format!(
+            "nextPageToken,newStartPageToken,changes(changeType,time,removed,fileId,driveId,file({}))",
+            FILE_FIELDS
+        );
+        let mut changes = vec![];
+        let mut page_token = page_token.to_string();
+        loop {
+            let page: ChangeList = self
+                .get(
+                    "/changes",
+                    Params::new()
+                        .page_token(page_token.as_str())
+                        .page_size(1000)
+                        .param("includeItemsFromAllDrives", "true")
+                        .fields(fields.as_str()),
+                )?
+                .check_status()?
+                .json()?;
+            changes.extend(page.changes);
+            match (page.next_page_token, page.new_start_page_token) {
+                (Some(next), _) => page_token = next,
+                (None, Some(start)) => return Ok((changes, start)),
+                // Shouldn't happen, but don't lose our place if it does.
+                (None, None) => return Ok((changes, page_token)),
+            }
+        }
+    }
+
+    /// Get every change since the cursor's position and advance it. A fresh cursor is pointed at
+    /// the current end of the feed and no changes are returned.
+    /// The cursor is only updated in memory, `ChangeCursor::save` persists it.
+    pub fn changes_since(&self, cursor: &mut ChangeCursor) -> Result<Vec<Change>, Error> {
+        let token = match &cursor.token {
+            Some(t) => t.clone(),
+            None => {
+                cursor.token = Some(self.start_page_token()?);
+                return Ok(vec![]);
+            }
+        };
+        let (changes, next) = self.list_changes(&token)?;
+        cursor.token = Some(next);
+        Ok(changes)
+    }
+}
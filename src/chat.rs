@@ -0,0 +1,306 @@
+use reqwest::blocking::RequestBuilder;
+use reqwest::Method;
+use serde::Deserialize;
+
+use crate::api;
+use crate::auth::Auth;
+use crate::error::{CheckStatus, Error};
+use crate::models::ExtraFields;
+use crate::scope::Scope;
+use crate::service_account::ServiceAccountKey;
+
+const CHAT_BASE_URL: &str = "https://chat.googleapis.com/v1";
+
+/// Something a card shows, one per row.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Widget {
+    /// A paragraph of text. Chat's basic HTML (`<b>`, `<a href>`, ...) works.
+    Text(String),
+    /// A value with a small label above it, e.g. `("Status", "Failing")`.
+    Labeled { label: String, text: String },
+    /// A button opening a link.
+    Button { text: String, url: String },
+    /// Any widget the API takes, as JSON.
+    Raw(serde_json::Value),
+}
+
+impl Widget {
+    fn to_json(&self) -> serde_json::Value {
+        match self {
+            Widget::Text(text) => serde_json::json!({ "textParagraph": { "text": text } }),
+            Widget::Labeled { label, text } => {
+                serde_json::json!({ "decoratedText": { "topLabel": label, "text": text } })
+            }
+            Widget::Button { text, url } => serde_json::json!({
+                "buttonList": { "buttons": [{
+                    "text": text,
+                    "onClick": { "openLink": { "url": url } },
+                }] }
+            }),
+            Widget::Raw(value) => value.clone(),
+        }
+    }
+}
+
+/// A card with a header and sections of widgets.
+///
+/// ```
+/// # use google_api::*;
+/// # let logs_url = String::from("https://ci.example.com/builds/42");
+/// let card = Card::new("Deploy failed")
+///     .subtitle("api-server, main")
+///     .section(None, vec![Widget::Labeled { label: "Step".into(), text: "migrate".into() }])
+///     .section(None, vec![Widget::Button { text: "Logs".into(), url: logs_url }]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Card {
+    title: String,
+    subtitle: Option<String>,
+    sections: Vec<(Option<String>, Vec<Widget>)>,
+}
+
+impl Card {
+    pub fn new(title: &str) -> Card {
+        Card {
+            title: title.to_string(),
+            ..Card::default()
+        }
+    }
+
+    pub fn subtitle(mut self, subtitle: &str) -> Card {
+        self.subtitle = Some(subtitle.to_string());
+        self
+    }
+
+    /// Add a section, optionally with its own header.
+    pub fn section(mut self, header: Option<&str>, widgets: Vec<Widget>) -> Card {
+        self.sections.push((header.map(String::from), widgets));
+        self
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        let mut header = serde_json::json!({ "title": self.title });
+        if let Some(subtitle) = &self.subtitle {
+            header["subtitle"] = serde_json::json!(subtitle);
+        }
+        let sections: Vec<serde_json::Value> = self
+            .sections
+            .iter()
+            .map(|(section_header, widgets)| {
+                let widgets: Vec<serde_json::Value> = widgets.iter().map(Widget::to_json).collect();
+                let mut section = serde_json::json!({ "widgets": widgets });
+                if let Some(section_header) = section_header {
+                    section["header"] = serde_json::json!(section_header);
+                }
+                section
+            })
+            .collect();
+        serde_json::json!({ "header": header, "sections": sections })
+    }
+}
+
+/// A message to post, text and/or cards.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChatMessage {
+    text: Option<String>,
+    cards: Vec<Card>,
+    thread_key: Option<String>,
+}
+
+impl ChatMessage {
+    /// Plain text message. Supports Chat's markup, e.g. `*bold*` and `<users/all>`.
+    pub fn text(text: &str) -> ChatMessage {
+        ChatMessage {
+            text: Some(text.to_string()),
+            ..ChatMessage::default()
+        }
+    }
+
+    /// Message made of a card. Cards can only be posted by apps and webhooks, not as a user.
+    pub fn card(card: Card) -> ChatMessage {
+        ChatMessage::default().with_card(card)
+    }
+
+    pub fn with_card(mut self, card: Card) -> ChatMessage {
+        self.cards.push(card);
+        self
+    }
+
+    /// Post into the thread with this key, starting it if there's none yet. Handy to group
+    /// every alert about the same incident.
+    pub fn thread(mut self, key: &str) -> ChatMessage {
+        self.thread_key = Some(key.to_string());
+        self
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        let mut message = serde_json::json!({});
+        if let Some(text) = &self.text {
+            message["text"] = serde_json::json!(text);
+        }
+        if !self.cards.is_empty() {
+            let cards: Vec<serde_json::Value> = self
+                .cards
+                .iter()
+                .enumerate()
+                .map(|(i, card)| {
+                    serde_json::json!({ "cardId": format!("card{}", i), "card": card.to_json() })
+                })
+                .collect();
+            message["cardsV2"] = serde_json::json!(cards);
+        }
+        if let Some(key) = &self.thread_key {
+            message["thread"] = serde_json::json!({ "threadKey": key });
+        }
+        message
+    }
+
+    /// Query parameters posting this message needs.
+    fn params(&self) -> Vec<(&str, &str)> {
+        match self.thread_key {
+            Some(_) => vec![("messageReplyOption", "REPLY_MESSAGE_FALLBACK_TO_NEW_THREAD")],
+            None => vec![],
+        }
+    }
+}
+
+/// A message as Chat stored it.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct SentChatMessage {
+    /// `spaces/<space>/messages/<message>`, which `Chat::delete_message` takes.
+    pub name: String,
+    pub text: String,
+    pub create_time: String,
+    pub thread: ChatThread,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct ChatThread {
+    pub name: String,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+/// Google Chat client, posting as a Chat app (service account) or as the signed in user.
+///
+/// ```no_run
+/// # use std::path::Path;
+/// # use google_api::*;
+/// let key = ServiceAccountKey::from_file(Path::new("chat-app.json"))?;
+/// let chat = Chat::with_service_account(&key)?;
+/// chat.send_message("spaces/AAAAxyz", &ChatMessage::text("Backups done").thread("backups"))?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug)]
+pub struct Chat {
+    auth: Auth,
+    client: reqwest::blocking::Client,
+}
+
+impl Chat {
+    /// Authenticate with given OAuth client, posting as the user. `scopes` defaults to
+    /// `Scope::ChatMessages`.
+    pub fn new(
+        client_id: String,
+        client_secret: String,
+        scopes: Option<Vec<String>>,
+    ) -> Result<Chat, Error> {
+        let scopes = scopes.unwrap_or_else(|| vec![Scope::ChatMessages.into()]);
+
+        Ok(Chat {
+            auth: Auth::from_config(&client_id, &client_secret, &scopes)?,
+            client: reqwest::blocking::Client::new(),
+        })
+    }
+
+    /// Post as the Chat app the service account belongs to. The app has to be added to a
+    /// space before it can post there. Its token is renewed as it runs out.
+    pub fn with_service_account(key: &ServiceAccountKey) -> Result<Chat, Error> {
+        Ok(Chat {
+            auth: Auth::service_account(key, &[Scope::ChatBot.into()], None)?,
+            client: reqwest::blocking::Client::new(),
+        })
+    }
+
+    /// Request for a resource by name, e.g. `spaces/AAA/messages`. Names have slashes that
+    /// have to stay path separators.
+    fn request(&self, method: Method, name: &str) -> Result<RequestBuilder, Error> {
+        let segments: Vec<&str> = name.split('/').collect();
+        api::request(&self.client, &self.auth, CHAT_BASE_URL, method, &segments)
+    }
+
+    /// Post a message to a space, given as `spaces/<id>`.
+    pub fn send_message(
+        &self,
+        space: &str,
+        message: &ChatMessage,
+    ) -> Result<SentChatMessage, Error> {
+        Ok(self
+            .request(Method::POST, &format!("{}/messages", space))?
+            .query(&message.params())
+            .json(&message.to_json())
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    /// Replace the text and cards of a message posted earlier.
+    pub fn update_message(
+        &self,
+        name: &str,
+        message: &ChatMessage,
+    ) -> Result<SentChatMessage, Error> {
+        Ok(self
+            .request(Method::PATCH, name)?
+            .query(&[("updateMask", "text,cardsV2")])
+            .json(&message.to_json())
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    pub fn delete_message(&self, name: &str) -> Result<(), Error> {
+        self.request(Method::DELETE, name)?.send()?.check_status()?;
+        Ok(())
+    }
+}
+
+/// A space's incoming webhook, which posts without any sign-in. The URL carries its own key,
+/// so keep it secret.
+///
+/// ```no_run
+/// # use google_api::*;
+/// # let webhook_url = String::from("https://chat.googleapis.com/v1/spaces/AAAAxyz/messages");
+/// let hook = ChatWebhook::new(webhook_url);
+/// hook.send(&ChatMessage::card(Card::new("Disk almost full").subtitle("db-1, 93%")))?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug)]
+pub struct ChatWebhook {
+    url: String,
+    client: reqwest::blocking::Client,
+}
+
+impl ChatWebhook {
+    pub fn new(url: String) -> ChatWebhook {
+        ChatWebhook {
+            url,
+            client: reqwest::blocking::Client::new(),
+        }
+    }
+
+    pub fn send(&self, message: &ChatMessage) -> Result<SentChatMessage, Error> {
+        Ok(self
+            .client
+            .post(self.url.as_str())
+            .query(&message.params())
+            .json(&message.to_json())
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+}
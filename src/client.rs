@@ -0,0 +1,128 @@
+use reqwest::blocking::RequestBuilder;
+use reqwest::Method;
+use serde::de::DeserializeOwned;
+
+use crate::auth::Auth;
+use crate::error::Error;
+use crate::retry::{send_with_policy, RetryPolicy};
+use crate::timestamp::Timestamp;
+
+/// Client for any Google API this crate doesn't wrap (yet). It shares the saved token with
+/// the other clients and takes full endpoint URLs.
+///
+/// ```no_run
+/// # use reqwest::Method;
+/// # use google_api::*;
+/// # let (client_id, client_secret) = (String::new(), String::new());
+/// # let posts_url = "https://www.googleapis.com/blogger/v3/blogs/123/posts";
+/// let google = GoogleClient::new(client_id, client_secret, vec![Scope::Custom(
+///     "https://www.googleapis.com/auth/blogger".into(),
+/// ).into()])?;
+/// let blog: serde_json::Value = google.call(
+///     Method::GET,
+///     "https://www.googleapis.com/blogger/v3/blogs/byurl",
+///     &[("url", "https://example.blogspot.com")],
+///     None,
+/// )?;
+/// let posts: Vec<serde_json::Value> = google.get_all(&posts_url, &[], "items")?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug)]
+pub struct GoogleClient {
+    auth: Auth,
+    client: reqwest::blocking::Client,
+    retry: RetryPolicy,
+}
+
+impl GoogleClient {
+    /// Authenticate with given OAuth client. There's no default scope, ask for whichever the
+    /// API needs.
+    pub fn new(
+        client_id: String,
+        client_secret: String,
+        scopes: Vec<String>,
+    ) -> Result<GoogleClient, Error> {
+        Ok(GoogleClient {
+            auth: Auth::from_config(&client_id, &client_secret, &scopes)?,
+            client: reqwest::blocking::Client::new(),
+            retry: RetryPolicy::default(),
+        })
+    }
+
+    /// Set how rate limited and failing calls are retried.
+    pub fn retry_policy(mut self, retry: RetryPolicy) -> GoogleClient {
+        self.retry = retry;
+        self
+    }
+
+    /// When the current access token expires. It's refreshed before a request once it's about
+    /// to, so this moves on as the client is used.
+    pub fn token_expires(&self) -> Timestamp {
+        Timestamp::from(self.auth.expires())
+    }
+
+    /// Authorized request to given URL, to finish and send yourself, e.g. for uploads.
+    pub fn request(&self, method: Method, url: &str) -> Result<RequestBuilder, Error> {
+        Ok(self
+            .client
+            .request(method, url)
+            .header("Authorization", self.auth.bearer()?))
+    }
+
+    /// Send a request with given query parameters and optional JSON body, retrying rate limits
+    /// and server errors, and parse the JSON answer. Error statuses come back as `Error::Api`
+    /// with Google's message. An empty answer, as most deletes give, reads as JSON `null`, so
+    /// ask for `()` or `serde_json::Value` then.
+    pub fn call<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        url: &str,
+        query: &[(&str, &str)],
+        body: Option<&serde_json::Value>,
+    ) -> Result<T, Error> {
+        let resp = send_with_policy(&self.retry, || {
+            let req = self.request(method.clone(), url)?.query(query);
+            match body {
+                Some(body) => req.json(body),
+                None => req,
+            }
+            .send()
+            .map_err(Error::from)
+        })?;
+        let text = resp.text()?;
+        if text.trim().is_empty() {
+            return Ok(serde_json::from_value(serde_json::Value::Null)?);
+        }
+        Ok(serde_json::from_str(&text)?)
+    }
+
+    /// GET every page of a list endpoint, following `nextPageToken`, and collect the items
+    /// under `items_field` (e.g. `items`, `files`, `messages`) of each page.
+    pub fn get_all<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        query: &[(&str, &str)],
+        items_field: &str,
+    ) -> Result<Vec<T>, Error> {
+        let mut items = vec![];
+        let mut page_token: Option<String> = None;
+        loop {
+            let mut params = query.to_vec();
+            if let Some(token) = &page_token {
+                params.push(("pageToken", token));
+            }
+            let mut page: serde_json::Value = self.call(Method::GET, url, &params, None)?;
+            if let serde_json::Value::Array(page_items) = page[items_field].take() {
+                for item in page_items {
+                    items.push(serde_json::from_value(item)?);
+                }
+            }
+
+            match page["nextPageToken"].as_str() {
+                Some(token) => page_token = Some(token.to_string()),
+                None => break,
+            }
+        }
+        Ok(items)
+    }
+}
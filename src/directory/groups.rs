@@ -0,0 +1,206 @@
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+
+use super::Directory;
+use crate::error::{CheckStatus, Error};
+use crate::models::{de_int64, ExtraFields};
+
+/// A Workspace group. Only non-empty fields are sent, so the same type creates and updates
+/// groups.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Group {
+    #[serde(skip_serializing)]
+    pub id: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub email: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub name: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    #[serde(skip_serializing, deserialize_with = "de_int64")]
+    pub direct_members_count: Option<u64>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl Group {
+    pub fn new(email: &str, name: &str) -> Group {
+        Group {
+            email: email.to_string(),
+            name: name.to_string(),
+            ..Group::default()
+        }
+    }
+
+    pub fn description(mut self, description: &str) -> Group {
+        self.description = description.to_string();
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum MemberRole {
+    Owner,
+    Manager,
+    #[default]
+    Member,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Member {
+    pub id: String,
+    pub email: String,
+    pub role: MemberRole,
+    /// `USER`, `GROUP` or `CUSTOMER`.
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// `ACTIVE`, `SUSPENDED`, ...
+    pub status: String,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct GroupList {
+    groups: Vec<Group>,
+    next_page_token: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct MemberList {
+    members: Vec<Member>,
+    next_page_token: Option<String>,
+}
+
+impl Directory {
+    /// Every group of given domain, e.g. `example.com`.
+    pub fn list_groups(&self, domain: &str) -> Result<Vec<Group>, Error> {
+        let mut groups = vec![];
+        let mut page_token: Option<String> = None;
+        loop {
+            let mut params = vec![("domain", domain), ("maxResults", "200")];
+            if let Some(token) = &page_token {
+                params.push(("pageToken", token));
+            }
+            let page: GroupList = self
+                .request(Method::GET, &["groups"])?
+                .query(&params)
+                .send()?
+                .check_status()?
+                .json()?;
+            groups.extend(page.groups);
+
+            match page.next_page_token {
+                Some(token) => page_token = Some(token),
+                None => break,
+            }
+        }
+        Ok(groups)
+    }
+
+    /// Fetch a group by email, alias or id.
+    pub fn get_group(&self, group_key: &str) -> Result<Group, Error> {
+        Ok(self
+            .request(Method::GET, &["groups", group_key])?
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    pub fn insert_group(&self, group: &Group) -> Result<Group, Error> {
+        Ok(self
+            .request(Method::POST, &["groups"])?
+            .json(group)
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    /// Change the fields of a group that `changes` has values for.
+    pub fn update_group(&self, group_key: &str, changes: &Group) -> Result<Group, Error> {
+        Ok(self
+            .request(Method::PUT, &["groups", group_key])?
+            .json(changes)
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    /// Direct members of a group. Members of nested groups aren't expanded.
+    pub fn list_members(&self, group_key: &str) -> Result<Vec<Member>, Error> {
+        let mut members = vec![];
+        let mut page_token: Option<String> = None;
+        loop {
+            let mut params = vec![("maxResults", "200")];
+            if let Some(token) = &page_token {
+                params.push(("pageToken", token));
+            }
+            let page: MemberList = self
+                .request(Method::GET, &["groups", group_key, "members"])?
+                .query(&params)
+                .send()?
+                .check_status()?
+                .json()?;
+            members.extend(page.members);
+
+            match page.next_page_token {
+                Some(token) => page_token = Some(token),
+                None => break,
+            }
+        }
+        Ok(members)
+    }
+
+    pub fn get_member(&self, group_key: &str, member_key: &str) -> Result<Member, Error> {
+        Ok(self
+            .request(Method::GET, &["groups", group_key, "members", member_key])?
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    /// Add a user or group, by email, to a group.
+    pub fn insert_member(
+        &self,
+        group_key: &str,
+        email: &str,
+        role: MemberRole,
+    ) -> Result<Member, Error> {
+        Ok(self
+            .request(Method::POST, &["groups", group_key, "members"])?
+            .json(&serde_json::json!({ "email": email, "role": role }))
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    /// Change a member's role in a group.
+    pub fn update_member(
+        &self,
+        group_key: &str,
+        member_key: &str,
+        role: MemberRole,
+    ) -> Result<Member, Error> {
+        Ok(self
+            .request(Method::PUT, &["groups", group_key, "members", member_key])?
+            .json(&serde_json::json!({ "role": role }))
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    pub fn remove_member(&self, group_key: &str, member_key: &str) -> Result<(), Error> {
+        self.request(
+            Method::DELETE,
+            &["groups", group_key, "members", member_key],
+        )?
+        .send()?
+        .check_status()?;
+        Ok(())
+    }
+}
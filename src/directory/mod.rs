@@ -0,0 +1,88 @@
+use reqwest::blocking::RequestBuilder;
+use reqwest::Method;
+
+use crate::api;
+use crate::auth::Auth;
+use crate::error::Error;
+use crate::scope::Scope;
+use crate::service_account::ServiceAccountKey;
+
+mod groups;
+mod users;
+
+pub use groups::{Group, Member, MemberRole};
+pub use users::{DirectoryUser, UserName};
+
+const DIRECTORY_BASE_URL: &str = "https://admin.googleapis.com/admin/directory/v1";
+
+/// Admin SDK Directory client, for managing a Workspace domain's users and groups. Sign in as
+/// a domain admin with `new`, or have a service account act as one with
+/// `with_service_account`, which is what unattended provisioning tools want.
+///
+/// ```no_run
+/// # use std::path::Path;
+/// # use google_api::*;
+/// let key = ServiceAccountKey::from_file(Path::new("provisioner.json"))?;
+/// let directory = Directory::with_service_account(&key, "admin@example.com", None)?;
+/// let new_user = DirectoryUser::new("jo@example.com", "Jo", "Doe", "hunter22");
+/// let user = directory.insert_user(&new_user)?;
+/// directory.insert_member("staff@example.com", &user.primary_email, MemberRole::Member)?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug)]
+pub struct Directory {
+    auth: Auth,
+    client: reqwest::blocking::Client,
+}
+
+fn default_scopes() -> Vec<String> {
+    vec![
+        Scope::AdminDirectoryUser.into(),
+        Scope::AdminDirectoryGroup.into(),
+    ]
+}
+
+impl Directory {
+    /// Authenticate with given OAuth client, signing in as a domain admin. `scopes` defaults
+    /// to `Scope::AdminDirectoryUser` and `Scope::AdminDirectoryGroup`.
+    pub fn new(
+        client_id: String,
+        client_secret: String,
+        scopes: Option<Vec<String>>,
+    ) -> Result<Directory, Error> {
+        let scopes = scopes.unwrap_or_else(default_scopes);
+
+        Ok(Directory {
+            auth: Auth::from_config(&client_id, &client_secret, &scopes)?,
+            client: reqwest::blocking::Client::new(),
+        })
+    }
+
+    /// Authenticate as a service account acting on behalf of `admin_email`. The account needs
+    /// domain-wide delegation for the scopes in the Admin console. Its token is renewed with
+    /// the key as it runs out.
+    pub fn with_service_account(
+        key: &ServiceAccountKey,
+        admin_email: &str,
+        scopes: Option<Vec<String>>,
+    ) -> Result<Directory, Error> {
+        let scopes = scopes.unwrap_or_else(default_scopes);
+
+        Ok(Directory {
+            auth: Auth::service_account(key, &scopes, Some(admin_email))?,
+            client: reqwest::blocking::Client::new(),
+        })
+    }
+
+    /// Authorized request for the endpoint made of given path segments, e.g.
+    /// `["users", email]`.
+    fn request(&self, method: Method, segments: &[&str]) -> Result<RequestBuilder, Error> {
+        api::request(
+            &self.client,
+            &self.auth,
+            DIRECTORY_BASE_URL,
+            method,
+            segments,
+        )
+    }
+}
@@ -0,0 +1,132 @@
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+
+use super::Directory;
+use crate::error::{CheckStatus, Error};
+use crate::models::ExtraFields;
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct UserName {
+    pub given_name: String,
+    pub family_name: String,
+    #[serde(skip_serializing)]
+    pub full_name: String,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+/// A Workspace user account. Only fields with values are sent, so a user with just the
+/// fields to change can be passed to `update_user`.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct DirectoryUser {
+    #[serde(skip_serializing)]
+    pub id: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub primary_email: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<UserName>,
+    /// Only ever sent, Google doesn't give passwords back.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    /// Make the user pick a new password at next sign-in.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub change_password_at_next_login: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suspended: Option<bool>,
+    /// Organizational unit, e.g. `/Engineering`. `/` is the top of the domain.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub org_unit_path: Option<String>,
+    #[serde(skip_serializing)]
+    pub is_admin: bool,
+    #[serde(skip_serializing)]
+    pub creation_time: String,
+    #[serde(skip_serializing)]
+    pub last_login_time: String,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl DirectoryUser {
+    /// A new account, ready for `insert_user`. The password needs at least 8 characters.
+    pub fn new(email: &str, given_name: &str, family_name: &str, password: &str) -> DirectoryUser {
+        DirectoryUser {
+            primary_email: email.to_string(),
+            name: Some(UserName {
+                given_name: given_name.to_string(),
+                family_name: family_name.to_string(),
+                ..UserName::default()
+            }),
+            password: Some(password.to_string()),
+            ..DirectoryUser::default()
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct UserList {
+    users: Vec<DirectoryUser>,
+    next_page_token: Option<String>,
+}
+
+impl Directory {
+    /// Every user of given domain, e.g. `example.com`.
+    pub fn list_users(&self, domain: &str) -> Result<Vec<DirectoryUser>, Error> {
+        let mut users = vec![];
+        let mut page_token: Option<String> = None;
+        loop {
+            let mut params = vec![("domain", domain), ("maxResults", "500")];
+            if let Some(token) = &page_token {
+                params.push(("pageToken", token));
+            }
+            let page: UserList = self
+                .request(Method::GET, &["users"])?
+                .query(&params)
+                .send()?
+                .check_status()?
+                .json()?;
+            users.extend(page.users);
+
+            match page.next_page_token {
+                Some(token) => page_token = Some(token),
+                None => break,
+            }
+        }
+        Ok(users)
+    }
+
+    /// Fetch a user by primary email, alias or id.
+    pub fn get_user(&self, user_key: &str) -> Result<DirectoryUser, Error> {
+        Ok(self
+            .request(Method::GET, &["users", user_key])?
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    pub fn insert_user(&self, user: &DirectoryUser) -> Result<DirectoryUser, Error> {
+        Ok(self
+            .request(Method::POST, &["users"])?
+            .json(user)
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    /// Change the fields of a user that `changes` has values for, e.g. to suspend them or
+    /// move them to another organizational unit.
+    pub fn update_user(
+        &self,
+        user_key: &str,
+        changes: &DirectoryUser,
+    ) -> Result<DirectoryUser, Error> {
+        Ok(self
+            .request(Method::PUT, &["users", user_key])?
+            .json(changes)
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+}
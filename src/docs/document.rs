@@ -0,0 +1,277 @@
+use std::collections::HashMap;
+
+use reqwest::Method;
+use serde::Deserialize;
+
+use super::requests::TextStyle;
+use super::Docs;
+use crate::error::{CheckStatus, Error};
+use crate::models::ExtraFields;
+
+/// A document as the Docs API returns it. Positions in the body are UTF-16 code unit indexes,
+/// which is what `DocRequest`s take.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Document {
+    pub document_id: String,
+    pub title: String,
+    pub revision_id: String,
+    pub body: Body,
+    /// Images and other embedded objects, by the id `InlineObjectElement`s refer to.
+    pub inline_objects: HashMap<String, InlineObject>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl Document {
+    /// The body's text, tables included, without any formatting.
+    pub fn text(&self) -> String {
+        self.body.content.iter().map(|e| e.text()).collect()
+    }
+
+    /// Paragraphs at the top level of the body, i.e. not inside tables.
+    pub fn paragraphs(&self) -> Vec<&Paragraph> {
+        self.body
+            .content
+            .iter()
+            .filter_map(|e| e.paragraph.as_ref())
+            .collect()
+    }
+
+    /// Tables at the top level of the body.
+    pub fn tables(&self) -> Vec<&Table> {
+        self.body
+            .content
+            .iter()
+            .filter_map(|e| e.table.as_ref())
+            .collect()
+    }
+
+    /// Index just before the body's final newline, where appended content goes.
+    pub fn end_index(&self) -> u32 {
+        self.body
+            .content
+            .last()
+            .map(|e| e.end_index.saturating_sub(1).max(1))
+            .unwrap_or(1)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct Body {
+    pub content: Vec<StructuralElement>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+/// One block of a body or table cell. Exactly one of the content fields is set, and section
+/// breaks and tables of contents are left as JSON.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct StructuralElement {
+    pub start_index: u32,
+    pub end_index: u32,
+    pub paragraph: Option<Paragraph>,
+    pub table: Option<Table>,
+    pub section_break: Option<serde_json::Value>,
+    pub table_of_contents: Option<serde_json::Value>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl StructuralElement {
+    pub fn text(&self) -> String {
+        match (&self.paragraph, &self.table) {
+            (Some(paragraph), _) => paragraph.text(),
+            (None, Some(table)) => table
+                .table_rows
+                .iter()
+                .flat_map(|row| &row.table_cells)
+                .map(|cell| cell.text())
+                .collect(),
+            (None, None) => String::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ParagraphStyle {
+    /// `NORMAL_TEXT`, `TITLE`, `SUBTITLE` or `HEADING_1` to `HEADING_6`.
+    pub named_style_type: String,
+    /// `START`, `CENTER`, `END` or `JUSTIFIED`.
+    pub alignment: Option<String>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Paragraph {
+    pub elements: Vec<ParagraphElement>,
+    pub paragraph_style: ParagraphStyle,
+    /// Set for list items.
+    pub bullet: Option<serde_json::Value>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl Paragraph {
+    /// The paragraph's text, ending in a newline.
+    pub fn text(&self) -> String {
+        self.elements
+            .iter()
+            .filter_map(|e| e.text_run.as_ref())
+            .map(|run| run.content.as_str())
+            .collect()
+    }
+
+    /// Whether the paragraph is a heading of any level.
+    pub fn is_heading(&self) -> bool {
+        self.paragraph_style
+            .named_style_type
+            .starts_with("HEADING_")
+    }
+}
+
+/// A piece of a paragraph: a run of text in one style, or an embedded object.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ParagraphElement {
+    pub start_index: u32,
+    pub end_index: u32,
+    pub text_run: Option<TextRun>,
+    pub inline_object_element: Option<InlineObjectElement>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct TextRun {
+    pub content: String,
+    pub text_style: TextStyle,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct InlineObjectElement {
+    /// Key into `Document::inline_objects`.
+    pub inline_object_id: String,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Table {
+    pub rows: u32,
+    pub columns: u32,
+    pub table_rows: Vec<TableRow>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl Table {
+    /// Text of each cell, row by row, with the trailing newline of each cell trimmed.
+    pub fn cell_texts(&self) -> Vec<Vec<String>> {
+        self.table_rows
+            .iter()
+            .map(|row| {
+                row.table_cells
+                    .iter()
+                    .map(|cell| cell.text().trim_end_matches('\n').to_string())
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct TableRow {
+    pub start_index: u32,
+    pub end_index: u32,
+    pub table_cells: Vec<TableCell>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct TableCell {
+    pub start_index: u32,
+    pub end_index: u32,
+    pub content: Vec<StructuralElement>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl TableCell {
+    pub fn text(&self) -> String {
+        self.content.iter().map(|e| e.text()).collect()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct InlineObject {
+    pub object_id: String,
+    pub inline_object_properties: InlineObjectProperties,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct InlineObjectProperties {
+    pub embedded_object: EmbeddedObject,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct EmbeddedObject {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    /// Set when the object is an image.
+    pub image_properties: Option<ImageProperties>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ImageProperties {
+    /// Short-lived link to the image content.
+    pub content_uri: String,
+    /// Where the image was inserted from, if it came from a URL.
+    pub source_uri: Option<String>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl Docs {
+    /// Create an empty document with given title, in the root of the user's Drive.
+    pub fn create_document(&self, title: &str) -> Result<Document, Error> {
+        Ok(self
+            .request(Method::POST, &[])?
+            .json(&serde_json::json!({ "title": title }))
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    /// Fetch a document with its whole body.
+    pub fn get_document(&self, document_id: &str) -> Result<Document, Error> {
+        Ok(self
+            .request(Method::GET, &[document_id])?
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+}
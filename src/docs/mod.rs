@@ -0,0 +1,64 @@
+use reqwest::blocking::RequestBuilder;
+use reqwest::Method;
+
+use crate::api;
+use crate::auth::Auth;
+use crate::error::Error;
+use crate::scope::Scope;
+
+mod document;
+mod requests;
+mod template;
+
+pub use document::{
+    Body, Document, EmbeddedObject, ImageProperties, InlineObject, InlineObjectElement,
+    InlineObjectProperties, Paragraph, ParagraphElement, ParagraphStyle, StructuralElement, Table,
+    TableCell, TableRow, TextRun,
+};
+pub use requests::{
+    Dimension, DocRequest, DocumentUpdateResponse, Link, OptionalColor, RgbColor, TextStyle,
+};
+pub use template::{FilledTemplate, TemplateOptions};
+
+const DOCS_BASE_URL: &str = "https://docs.googleapis.com/v1/documents";
+
+/// Google Docs client. Shares its saved token with the other clients.
+///
+/// ```no_run
+/// # use google_api::*;
+/// # let (client_id, client_secret) = (String::new(), String::new());
+/// let docs = Docs::new(client_id, client_secret, None)?;
+/// let doc = docs.create_document("Minutes")?;
+/// docs.batch_update(&doc.document_id, &[
+///     DocRequest::AppendText { text: String::from("Attendees\n") },
+///     DocRequest::NamedStyle { start: 1, end: 11, style: String::from("HEADING_1") },
+/// ])?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug)]
+pub struct Docs {
+    auth: Auth,
+    client: reqwest::blocking::Client,
+}
+
+impl Docs {
+    /// Authenticate with given OAuth client. `scopes` defaults to `Scope::Documents`.
+    pub fn new(
+        client_id: String,
+        client_secret: String,
+        scopes: Option<Vec<String>>,
+    ) -> Result<Docs, Error> {
+        let scopes = scopes.unwrap_or_else(|| vec![Scope::Documents.into()]);
+
+        Ok(Docs {
+            auth: Auth::from_config(&client_id, &client_secret, &scopes)?,
+            client: reqwest::blocking::Client::new(),
+        })
+    }
+
+    /// Authorized request for the endpoint below `/documents` made of given path segments,
+    /// e.g. `[id]`.
+    fn request(&self, method: Method, segments: &[&str]) -> Result<RequestBuilder, Error> {
+        api::request(&self.client, &self.auth, DOCS_BASE_URL, method, segments)
+    }
+}
@@ -0,0 +1,342 @@
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+
+use super::Docs;
+use crate::error::{CheckStatus, Error};
+use crate::models::ExtraFields;
+use crate::sheets::Color;
+
+/// A length, e.g. a font size in points.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Dimension {
+    pub magnitude: f32,
+    /// `PT`.
+    pub unit: String,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct RgbColor {
+    pub rgb_color: Color,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+/// A colour that may be unset, as the Docs API wraps them.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct OptionalColor {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<RgbColor>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Link {
+    pub url: String,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+/// Character formatting, read from text runs or built up with chained setters to apply
+/// with `DocRequest::StyleText`. Only what's set is changed.
+///
+/// ```
+/// # use google_api::*;
+/// let style = TextStyle::new().bold().font_size(14.0).color(Color::rgb(200, 0, 0));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct TextStyle {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bold: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub italic: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub underline: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strikethrough: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub font_size: Option<Dimension>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub foreground_color: Option<OptionalColor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link: Option<Link>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl TextStyle {
+    pub fn new() -> TextStyle {
+        TextStyle::default()
+    }
+
+    pub fn bold(mut self) -> TextStyle {
+        self.bold = Some(true);
+        self
+    }
+
+    pub fn italic(mut self) -> TextStyle {
+        self.italic = Some(true);
+        self
+    }
+
+    pub fn underline(mut self) -> TextStyle {
+        self.underline = Some(true);
+        self
+    }
+
+    pub fn strikethrough(mut self) -> TextStyle {
+        self.strikethrough = Some(true);
+        self
+    }
+
+    /// Font size in points.
+    pub fn font_size(mut self, points: f32) -> TextStyle {
+        self.font_size = Some(Dimension {
+            magnitude: points,
+            unit: String::from("PT"),
+            ..Dimension::default()
+        });
+        self
+    }
+
+    pub fn color(mut self, color: Color) -> TextStyle {
+        self.foreground_color = Some(OptionalColor {
+            color: Some(RgbColor {
+                rgb_color: color,
+                ..RgbColor::default()
+            }),
+            ..OptionalColor::default()
+        });
+        self
+    }
+
+    /// Make the text a link to given URL.
+    pub fn link(mut self, url: &str) -> TextStyle {
+        self.link = Some(Link {
+            url: url.to_string(),
+            ..Link::default()
+        });
+        self
+    }
+
+    /// Field mask naming only what's set, so the rest of the text's style is left alone.
+    fn fields(&self) -> String {
+        let mut fields = vec![];
+        if self.bold.is_some() {
+            fields.push("bold");
+        }
+        if self.italic.is_some() {
+            fields.push("italic");
+        }
+        if self.underline.is_some() {
+            fields.push("underline");
+        }
+        if self.strikethrough.is_some() {
+            fields.push("strikethrough");
+        }
+        if self.font_size.is_some() {
+            fields.push("fontSize");
+        }
+        if self.foreground_color.is_some() {
+            fields.push("foregroundColor");
+        }
+        if self.link.is_some() {
+            fields.push("link");
+        }
+        fields.join(",")
+    }
+}
+
+/// One edit to a document, sent with `Docs::batch_update`. Indexes count UTF-16 code units
+/// from the start of the body (which is at 1) and ranges are `start..end`. `Raw` takes any
+/// request the API supports that isn't covered here.
+///
+/// Edits are applied in order and each shifts the indexes after it, so when making several
+/// insertions by index it's easiest to go from the end of the document backwards.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DocRequest {
+    InsertText {
+        index: u32,
+        text: String,
+    },
+    /// Add text at the end of the body.
+    AppendText {
+        text: String,
+    },
+    DeleteRange {
+        start: u32,
+        end: u32,
+    },
+    /// Replace every occurrence of `find` in the document, e.g. a `{{name}}` placeholder.
+    ReplaceAllText {
+        find: String,
+        replace: String,
+        match_case: bool,
+    },
+    StyleText {
+        start: u32,
+        end: u32,
+        style: TextStyle,
+    },
+    /// Give the paragraphs overlapping the range a named style such as `HEADING_1`, `TITLE`
+    /// or `NORMAL_TEXT`.
+    NamedStyle {
+        start: u32,
+        end: u32,
+        style: String,
+    },
+    /// Turn the paragraphs overlapping the range into a bulleted list.
+    Bullets {
+        start: u32,
+        end: u32,
+    },
+    /// Insert an empty table. The document gets a newline before it.
+    InsertTable {
+        index: u32,
+        rows: u32,
+        columns: u32,
+    },
+    /// Insert an image from a public URL. PNG, JPEG and GIF under 50 MB are supported.
+    InsertImage {
+        index: u32,
+        uri: String,
+    },
+    InsertPageBreak {
+        index: u32,
+    },
+    Raw(serde_json::Value),
+}
+
+impl DocRequest {
+    fn to_json(&self) -> serde_json::Value {
+        match self {
+            DocRequest::InsertText { index, text } => serde_json::json!({
+                "insertText": { "location": { "index": index }, "text": text }
+            }),
+            DocRequest::AppendText { text } => serde_json::json!({
+                "insertText": { "endOfSegmentLocation": {}, "text": text }
+            }),
+            DocRequest::DeleteRange { start, end } => serde_json::json!({
+                "deleteContentRange": { "range": range(*start, *end) }
+            }),
+            DocRequest::ReplaceAllText {
+                find,
+                replace,
+                match_case,
+            } => serde_json::json!({
+                "replaceAllText": {
+                    "containsText": { "text": find, "matchCase": match_case },
+                    "replaceText": replace,
+                }
+            }),
+            DocRequest::StyleText { start, end, style } => serde_json::json!({
+                "updateTextStyle": {
+                    "range": range(*start, *end),
+                    "textStyle": style,
+                    "fields": style.fields(),
+                }
+            }),
+            DocRequest::NamedStyle { start, end, style } => serde_json::json!({
+                "updateParagraphStyle": {
+                    "range": range(*start, *end),
+                    "paragraphStyle": { "namedStyleType": style },
+                    "fields": "namedStyleType",
+                }
+            }),
+            DocRequest::Bullets { start, end } => serde_json::json!({
+                "createParagraphBullets": {
+                    "range": range(*start, *end),
+                    "bulletPreset": "BULLET_DISC_CIRCLE_SQUARE",
+                }
+            }),
+            DocRequest::InsertTable {
+                index,
+                rows,
+                columns,
+            } => serde_json::json!({
+                "insertTable": { "location": { "index": index }, "rows": rows, "columns": columns }
+            }),
+            DocRequest::InsertImage { index, uri } => serde_json::json!({
+                "insertInlineImage": { "location": { "index": index }, "uri": uri }
+            }),
+            DocRequest::InsertPageBreak { index } => serde_json::json!({
+                "insertPageBreak": { "location": { "index": index } }
+            }),
+            DocRequest::Raw(request) => request.clone(),
+        }
+    }
+}
+
+fn range(start: u32, end: u32) -> serde_json::Value {
+    serde_json::json!({ "startIndex": start, "endIndex": end })
+}
+
+/// Result of a batch update, one reply per request. Most replies are empty,
+/// `ReplaceAllText`'s counts the replacements.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct DocumentUpdateResponse {
+    pub document_id: String,
+    pub replies: Vec<serde_json::Value>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl DocumentUpdateResponse {
+    /// How many occurrences the `ReplaceAllText` requests replaced in total.
+    pub fn occurrences_changed(&self) -> u64 {
+        self.replies
+            .iter()
+            .filter_map(|r| r["replaceAllText"]["occurrencesChanged"].as_u64())
+            .sum()
+    }
+}
+
+impl Docs {
+    /// Apply the edits to the document in order, as one change. If any of them fails, none
+    /// are applied.
+    pub fn batch_update(
+        &self,
+        document_id: &str,
+        requests: &[DocRequest],
+    ) -> Result<DocumentUpdateResponse, Error> {
+        let requests: Vec<serde_json::Value> = requests.iter().map(|r| r.to_json()).collect();
+        let endpoint = format!("{}:batchUpdate", document_id);
+        Ok(self
+            .request(Method::POST, &[&endpoint])?
+            .json(&serde_json::json!({ "requests": requests }))
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    /// Replace each `find` with its `replace` throughout the document, matching case.
+    /// Returns how many occurrences were replaced.
+    pub fn replace_all(
+        &self,
+        document_id: &str,
+        replacements: &[(&str, &str)],
+    ) -> Result<u64, Error> {
+        if replacements.is_empty() {
+            return Ok(0);
+        }
+        let requests: Vec<DocRequest> = replacements
+            .iter()
+            .map(|(find, replace)| DocRequest::ReplaceAllText {
+                find: find.to_string(),
+                replace: replace.to_string(),
+                match_case: true,
+            })
+            .collect();
+        Ok(self
+            .batch_update(document_id, &requests)?
+            .occurrences_changed())
+    }
+}
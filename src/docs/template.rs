@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use super::requests::DocRequest;
+use super::Docs;
+use crate::error::Error;
+use crate::export::ExportFormat;
+use crate::file_id::FileId;
+use crate::models::DriveFile;
+use crate::Drive;
+
+/// Options for `Docs::fill_template`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TemplateOptions {
+    name: Option<String>,
+    pdf_path: Option<PathBuf>,
+}
+
+impl TemplateOptions {
+    pub fn new() -> TemplateOptions {
+        TemplateOptions::default()
+    }
+
+    /// Name for the filled document. Defaults to Drive's "Copy of <template>".
+    pub fn name(mut self, name: &str) -> TemplateOptions {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    /// Also export the filled document as a PDF to given path.
+    pub fn export_pdf(mut self, path: impl Into<PathBuf>) -> TemplateOptions {
+        self.pdf_path = Some(path.into());
+        self
+    }
+}
+
+/// A document made by `Docs::fill_template`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FilledTemplate {
+    pub document: DriveFile,
+    /// How many placeholders were replaced in total.
+    pub replaced: u64,
+    /// Where the PDF was written, if one was asked for.
+    pub pdf_path: Option<PathBuf>,
+}
+
+impl Docs {
+    /// Mail merge: copy a template document into `target_folder` and replace each
+    /// `{{key}}` in it with its value from `placeholders`. Keys match case, and placeholders
+    /// without a value are left in place. The copy is made with `drive`, which needs a scope
+    /// that can read the template.
+    ///
+    /// ```no_run
+    /// # use std::collections::HashMap;
+    /// # use google_api::*;
+    /// # let drive = Drive::new(String::new(), String::new(), None)?;
+    /// # let docs = Docs::new(String::new(), String::new(), None)?;
+    /// # let template_id = FileId::unchecked("1aBc");
+    /// # let invoices_folder = FileId::unchecked("1dEf");
+    /// let mut fields = HashMap::new();
+    /// fields.insert("name", "Ada Lovelace");
+    /// fields.insert("amount", "£120.00");
+    /// let invoice = docs.fill_template(
+    ///     &drive,
+    ///     template_id,
+    ///     &fields,
+    ///     invoices_folder,
+    ///     &TemplateOptions::new().name("Invoice 042").export_pdf("invoice-042.pdf"),
+    /// )?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn fill_template<K, V>(
+        &self,
+        drive: &Drive,
+        template_id: impl Into<FileId>,
+        placeholders: &HashMap<K, V>,
+        target_folder: impl Into<FileId>,
+        options: &TemplateOptions,
+    ) -> Result<FilledTemplate, Error>
+    where
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let document = drive.copy_file(
+            template_id,
+            options.name.as_deref(),
+            Some(target_folder.into()),
+        )?;
+
+        let requests: Vec<DocRequest> = placeholders
+            .iter()
+            .map(|(key, value)| DocRequest::ReplaceAllText {
+                find: format!("{{{{{}}}}}", key.as_ref()),
+                replace: value.as_ref().to_string(),
+                match_case: true,
+            })
+            .collect();
+        let replaced = if requests.is_empty() {
+            0
+        } else {
+            match self.batch_update(&document.id, &requests) {
+                Ok(response) => response.occurrences_changed(),
+                Err(e) => {
+                    // Don't leave a half-made copy behind.
+                    let _ = drive.delete_file(FileId::unchecked(&document.id));
+                    return Err(e);
+                }
+            }
+        };
+
+        let pdf_path = match &options.pdf_path {
+            Some(path) => {
+                let pdf = drive.export_file(FileId::unchecked(&document.id), ExportFormat::Pdf)?;
+                fs::write(path, pdf)?;
+                Some(path.clone())
+            }
+            None => None,
+        };
+
+        Ok(FilledTemplate {
+            document,
+            replaced,
+            pdf_path,
+        })
+    }
+}
@@ -0,0 +1,350 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use reqwest::StatusCode;
+
+use crate::concurrency::run_bounded;
+use crate::error::{CheckStatus, Context, Error};
+use crate::file_id::FileId;
+use crate::models::DriveFile;
+use crate::params::Params;
+use crate::retry::send_with_policy;
+use crate::sync::md5_file;
+use crate::Drive;
+
+/// Files smaller than this aren't worth splitting into ranges.
+const MIN_PARALLEL_SIZE: u64 = 16 * 1024 * 1024;
+
+/// Options for `Drive::download`.
+#[derive(Debug, Clone)]
+pub struct DownloadOptions {
+    connections: usize,
+    preserve_times: bool,
+    resume: bool,
+}
+
+impl Default for DownloadOptions {
+    fn default() -> Self {
+        DownloadOptions {
+            connections: 1,
+            preserve_times: false,
+            resume: false,
+        }
+    }
+}
+
+impl DownloadOptions {
+    pub fn new() -> DownloadOptions {
+        DownloadOptions::default()
+    }
+
+    /// Number of concurrent range requests for big files.
+    pub fn connections(mut self, connections: usize) -> DownloadOptions {
+        self.connections = connections.max(1);
+        self
+    }
+
+    /// Set the local file's modification time to the one recorded on Drive.
+    pub fn preserve_times(mut self, preserve: bool) -> DownloadOptions {
+        self.preserve_times = preserve;
+        self
+    }
+
+    /// Continue an interrupted download from the end of the partial local file instead of
+    /// starting over. The finished file is checked against Drive's checksum and downloaded
+    /// again from scratch if it doesn't match.
+    pub fn resume(mut self, resume: bool) -> DownloadOptions {
+        self.resume = resume;
+        self
+    }
+}
+
+/// Turn a Drive file name into a local file name that stays inside the folder it's joined to.
+/// Drive allows any name, so path separators become `_`, and empty names and `.` or `..`
+/// are replaced too.
+pub fn local_file_name(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | '\0' => '_',
+            ':' if cfg!(windows) => '_',
+            c => c,
+        })
+        .collect();
+    match name.as_str() {
+        "" => String::from("_"),
+        "." | ".." => name.replace('.', "_"),
+        _ => name,
+    }
+}
+
+/// Temporary file next to `path` that a download is written to before it's renamed over
+/// `path`.
+fn partial_path(path: &Path) -> PathBuf {
+    let mut name = path
+        .file_name()
+        .map(|n| n.to_os_string())
+        .unwrap_or_default();
+    name.push(".part");
+    path.with_file_name(name)
+}
+
+/// Have `write` fill a temporary file next to `path` and move it over `path` once it has
+/// succeeded, so a failed download leaves an existing file alone instead of truncated.
+pub(crate) fn write_replacing<F>(path: &Path, write: F) -> Result<(), Error>
+where
+    F: FnOnce(&Path) -> Result<(), Error>,
+{
+    let partial = partial_path(path);
+    match write(&partial) {
+        Ok(()) => Ok(fs::rename(&partial, path)?),
+        Err(e) => {
+            let _ = fs::remove_file(&partial);
+            Err(e)
+        }
+    }
+}
+
+/// Set a local file's modification time from Drive metadata, if it has one.
+pub(crate) fn restore_modified_time(path: &Path, file: &DriveFile) -> io::Result<()> {
+    if let Some(time) = file.modified_time {
+        OpenOptions::new()
+            .write(true)
+            .open(path)?
+            .set_modified(time.system_time())?;
+    }
+    Ok(())
+}
+
+impl Drive {
+    /// Start a media download of given file, optionally for a byte range (inclusive).
+    pub(crate) fn get_media(
+        &self,
+        file_id: &str,
+        range: Option<(u64, u64)>,
+    ) -> Result<reqwest::blocking::Response, Error> {
+        let url = Drive::build_url(
+            format!("/files/{}", file_id).as_str(),
+            Params::new().alt("media"),
+        );
+        send_with_policy(&self.retry, || -> Result<_, Error> {
+            let mut req = self
+                .client
+                .get(&url)
+                .header("Authorization", self.auth.bearer()?);
+            if let Some((start, end)) = range {
+                req = req.header("Range", format!("bytes={}-{}", start, end));
+            }
+            Ok(req.send()?)
+        })
+    }
+
+    /// Download given file to given path, which may be a directory to download into.
+    pub fn download(
+        &self,
+        file_id: impl Into<FileId>,
+        path: &Path,
+        options: &DownloadOptions,
+    ) -> Result<PathBuf, Error> {
+        let file_id = file_id.into();
+        let meta: DriveFile = self
+            .get(
+                format!("/files/{}", file_id).as_str(),
+                Params::new().fields("id,name,mimeType,size,md5Checksum,modifiedTime"),
+            )
+            .and_then(|resp| Ok(resp.check_status()?.json()?))
+            .with_context(|| format!("getting metadata of {}", file_id))?;
+        if meta.is_google_native() {
+            return Err(Error::UnexpectedResponse(format!(
+                "{} is a {} file, it has to be exported instead",
+                meta.name, meta.mime_type
+            )));
+        }
+        let path = if path.is_dir() {
+            path.join(local_file_name(&meta.name))
+        } else {
+            path.to_path_buf()
+        };
+        let resumable = options.resume
+            && meta.md5_checksum.is_some()
+            && path.metadata().map(|m| m.len() > 0).unwrap_or(false);
+        let downloading = || format!("downloading {} to {}", file_id, path.display());
+        let path = if resumable {
+            self.download_resume(file_id.as_str(), &meta, path.clone())
+                .with_context(downloading)?
+        } else {
+            self.download_to(file_id.as_str(), &meta, path.clone(), options.connections)
+                .with_context(downloading)?
+        };
+        if options.preserve_times {
+            restore_modified_time(&path, &meta)
+                .with_context(|| format!("setting modified time of {}", path.display()))?;
+        }
+        Ok(path)
+    }
+
+    /// Stream given file's content into `writer`, e.g. stdout or a hashing writer, without
+    /// touching the disk. Returns the number of bytes written.
+    pub fn download_to_writer<W: Write + ?Sized>(
+        &self,
+        file_id: impl Into<FileId>,
+        writer: &mut W,
+    ) -> Result<u64, Error> {
+        let file_id = file_id.into();
+        self.get_media(file_id.as_str(), None)
+            .and_then(|resp| resp.check_status())
+            .and_then(|mut resp| Ok(io::copy(&mut resp, writer)?))
+            .with_context(|| format!("downloading {}", file_id))
+    }
+
+    /// Download given file to given path with up to `connections` concurrent range requests,
+    /// each writing straight into its slice of a preallocated file. Falls back to a single
+    /// sequential request for small files or when ranges aren't honoured.
+    pub fn download_file_parallel(
+        &self,
+        file_id: impl Into<FileId>,
+        path: &Path,
+        connections: usize,
+    ) -> Result<PathBuf, Error> {
+        self.download(
+            file_id,
+            path,
+            &DownloadOptions::new().connections(connections),
+        )
+    }
+
+    fn download_to(
+        &self,
+        file_id: &str,
+        meta: &DriveFile,
+        path: PathBuf,
+        connections: usize,
+    ) -> Result<PathBuf, Error> {
+        let size = match meta.size {
+            Some(size) if size >= MIN_PARALLEL_SIZE && connections > 1 => size,
+            _ => return self.download_sequential(file_id, path),
+        };
+        // Check the server actually honours ranges before committing to them.
+        let probe = self.get_media(file_id, Some((0, 0)))?.check_status()?;
+        if probe.status() != StatusCode::PARTIAL_CONTENT {
+            log::debug!("range requests not supported, downloading sequentially");
+            return self.download_sequential(file_id, path);
+        }
+
+        write_replacing(&path, |partial| {
+            File::create(partial)?.set_len(size)?;
+            let chunk = size.div_ceil(connections as u64);
+            let ranges: Vec<(u64, u64)> = (0..connections as u64)
+                .map(|i| (i * chunk, ((i + 1) * chunk).min(size) - 1))
+                .filter(|(start, end)| start <= end)
+                .collect();
+            let results = run_bounded(ranges, connections, |(start, end)| {
+                self.download_range(file_id, partial, start, end)
+            });
+            results.into_iter().collect()
+        })?;
+        Ok(path)
+    }
+
+    /// Fetch one byte range and write it at its offset in the local file.
+    fn download_range(
+        &self,
+        file_id: &str,
+        path: &Path,
+        start: u64,
+        end: u64,
+    ) -> Result<(), Error> {
+        let mut resp = self
+            .get_media(file_id, Some((start, end)))?
+            .check_status()?;
+        if resp.status() != StatusCode::PARTIAL_CONTENT {
+            return Err(Error::UnexpectedResponse(format!(
+                "expected partial content for bytes {}-{}, got {}",
+                start,
+                end,
+                resp.status()
+            )));
+        }
+        let mut file = OpenOptions::new().write(true).open(path)?;
+        file.seek(SeekFrom::Start(start))?;
+        let written = io::copy(&mut resp, &mut file)?;
+        if written != end - start + 1 {
+            return Err(Error::UnexpectedResponse(format!(
+                "got {} bytes for range {}-{}",
+                written, start, end
+            )));
+        }
+        Ok(())
+    }
+
+    /// Append whatever is missing from a partial local file, then verify the whole file.
+    fn download_resume(
+        &self,
+        file_id: &str,
+        meta: &DriveFile,
+        path: PathBuf,
+    ) -> Result<PathBuf, Error> {
+        let size = meta.size.unwrap_or_default();
+        let have = path.metadata()?.len();
+        if have < size {
+            log::debug!("resuming download at byte {} of {}", have, size);
+            let mut resp = self
+                .get_media(file_id, Some((have, size - 1)))?
+                .check_status()?;
+            if resp.status() == StatusCode::PARTIAL_CONTENT {
+                let mut file = OpenOptions::new().append(true).open(&path)?;
+                io::copy(&mut resp, &mut file)?;
+            } else {
+                // Range ignored, so this is the whole file.
+                write_replacing(&path, |partial| {
+                    io::copy(&mut resp, &mut File::create(partial)?)?;
+                    Ok(())
+                })?;
+            }
+        }
+        if meta.md5_checksum.as_deref() == Some(md5_file(&path)?.as_str()) {
+            return Ok(path);
+        }
+        log::debug!("checksum mismatch after resuming, downloading again");
+        let path = self.download_sequential(file_id, path)?;
+        if meta.md5_checksum.as_deref() != Some(md5_file(&path)?.as_str()) {
+            return Err(Error::UnexpectedResponse(format!(
+                "checksum mismatch for {}",
+                path.display()
+            )));
+        }
+        Ok(path)
+    }
+
+    /// Stream the whole file to given path in one request.
+    fn download_sequential(&self, file_id: &str, path: PathBuf) -> Result<PathBuf, Error> {
+        let mut resp = self.get_media(file_id, None)?.check_status()?;
+        write_replacing(&path, |partial| {
+            resp.copy_to(&mut File::create(partial)?)?;
+            Ok(())
+        })?;
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn local_file_name_stays_in_folder() {
+        assert_eq!(local_file_name("report.pdf"), "report.pdf");
+        assert_eq!(local_file_name("a/b"), "a_b");
+        assert_eq!(local_file_name("../../etc/passwd"), ".._.._etc_passwd");
+        assert_eq!(local_file_name("/etc/passwd"), "_etc_passwd");
+        assert_eq!(local_file_name("..\\evil"), ".._evil");
+        assert_eq!(local_file_name(".."), "__");
+        assert_eq!(local_file_name("."), "_");
+        assert_eq!(local_file_name(""), "_");
+        for name in &["..", "a/../..", "/abs", "\\\\server\\share"] {
+            let joined = Path::new("out").join(local_file_name(name));
+            assert_eq!(joined.parent(), Some(Path::new("out")));
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::{CheckStatus, Error};
+use crate::models::ExtraFields;
+use crate::params::Params;
+use crate::timestamp::Timestamp;
+use crate::Drive;
+
+const DRIVE_FIELDS: &str = "id,name,colorRgb,createdTime,hidden";
+
+/// A shared drive (formerly Team Drive).
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct SharedDrive {
+    pub id: String,
+    pub name: String,
+    pub color_rgb: Option<String>,
+    pub created_time: Option<Timestamp>,
+    pub hidden: bool,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct DriveList {
+    drives: Vec<SharedDrive>,
+    next_page_token: Option<String>,
+}
+
+impl Drive {
+    /// Create a shared drive with given name. `request_id` makes the call idempotent, retrying
+    /// with the same id won't create a second drive.
+    pub fn create_shared_drive(&self, request_id: &str, name: &str) -> Result<SharedDrive, Error> {
+        Ok(self
+            .post(
+                "/drives",
+                Params::new()
+                    .param("requestId", request_id)
+                    .fields(DRIVE_FIELDS),
+                serde_json::json!({ "name": name }),
+            )?
+            .check_status()?
+            .json()?)
+    }
+
+    /// List every shared drive the user is a member of.
+    pub fn list_shared_drives(&self) -> Result<Vec<SharedDrive>, Error> {
+        let fields = format!("nextPageToken,drives({})", DRIVE_FIELDS);
+        let mut drives = vec![];
+        let mut page_token: Option<String> = None;
+        loop {
+            let mut params = Params::new().page_size(100).fields(&fields);
+            if let Some(token) = &page_token {
+                params = params.page_token(token);
+            }
+            let page: DriveList = self.get("/drives", params)?.check_status()?.json()?;
+            drives.extend(page.drives);
+            match page.next_page_token {
+                Some(token) => page_token = Some(token),
+                None => return Ok(drives),
+            }
+        }
+    }
+
+    /// Get shared drive with given id.
+    pub fn shared_drive(&self, id: &str) -> Result<SharedDrive, Error> {
+        Ok(self
+            .get(
+                format!("/drives/{}", id).as_str(),
+                Params::new().fields(DRIVE_FIELDS),
+            )?
+            .check_status()?
+            .json()?)
+    }
+
+    /// Rename and/or recolour given shared drive.
+    pub fn update_shared_drive(
+        &self,
+        id: &str,
+        name: Option<&str>,
+        color_rgb: Option<&str>,
+    ) -> Result<SharedDrive, Error> {
+        let mut body = serde_json::json!({});
+        if let Some(name) = name {
+            body["name"] = serde_json::json!(name);
+        }
+        if let Some(color) = color_rgb {
+            body["colorRgb"] = serde_json::json!(color);
+        }
+        Ok(self
+            .patch(
+                format!("/drives/{}", id).as_str(),
+                Params::new().fields(DRIVE_FIELDS),
+                body,
+            )?
+            .check_status()?
+            .json()?)
+    }
+
+    /// Delete given shared drive. Drive only allows this once the shared drive is empty.
+    pub fn delete_shared_drive(&self, id: &str) -> Result<(), Error> {
+        self.delete(format!("/drives/{}", id).as_str(), Params::new())?
+            .check_status()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,70 @@
+use std::collections::HashMap;
+
+use crate::error::Error;
+use crate::file_id::FileId;
+use crate::models::DriveFile;
+use crate::query::Query;
+use crate::Drive;
+
+/// Files with the same content, as found by `Drive::find_duplicates`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DuplicateSet {
+    pub md5_checksum: String,
+    pub size: u64,
+    /// Oldest first.
+    pub files: Vec<DriveFile>,
+}
+
+impl DuplicateSet {
+    /// The file created first, usually the one worth keeping. `None` only for a set built
+    /// by hand with no files.
+    pub fn oldest(&self) -> Option<&DriveFile> {
+        self.files.first()
+    }
+
+    /// Bytes freed by removing all but one copy.
+    pub fn wasted(&self) -> u64 {
+        self.size * (self.files.len() as u64).saturating_sub(1)
+    }
+}
+
+impl Drive {
+    /// Group files matching given query by checksum and size, returning every group with more
+    /// than one file, most wasted space first. Google-native files have no checksum and are
+    /// never reported.
+    pub fn find_duplicates(&self, scope: &Query) -> Result<Vec<DuplicateSet>, Error> {
+        let mut groups: HashMap<(String, u64), Vec<DriveFile>> = HashMap::new();
+        for file in self.search(&scope.clone().trashed(false))? {
+            if let (Some(md5), Some(size)) = (file.md5_checksum.clone(), file.size) {
+                groups.entry((md5, size)).or_default().push(file);
+            }
+        }
+        let mut sets: Vec<DuplicateSet> = groups
+            .into_iter()
+            .filter(|(_, files)| files.len() > 1)
+            .map(|((md5_checksum, size), mut files)| {
+                files.sort_by_key(|f| f.created_time);
+                DuplicateSet {
+                    md5_checksum,
+                    size,
+                    files,
+                }
+            })
+            .collect();
+        sets.sort_by_key(|s| std::cmp::Reverse(s.wasted()));
+        Ok(sets)
+    }
+
+    /// Move every file in the set except `keep_id` to the trash. Returns the trashed files.
+    pub fn resolve_duplicates(
+        &self,
+        set: &DuplicateSet,
+        keep_id: &str,
+    ) -> Result<Vec<DriveFile>, Error> {
+        set.files
+            .iter()
+            .filter(|f| f.id != keep_id)
+            .map(|f| self.set_trashed(FileId::unchecked(&f.id), true))
+            .collect()
+    }
+}
@@ -0,0 +1,250 @@
+use std::fmt;
+use std::io;
+
+use reqwest::blocking::Response;
+use reqwest::StatusCode;
+
+/// Errors from operations that touch both Drive and the local filesystem.
+#[derive(Debug)]
+pub enum Error {
+    /// Request to Google failed or returned an error status.
+    Http(reqwest::Error),
+    /// Reading or writing a local file failed.
+    Io(std::io::Error),
+    /// Local state file couldn't be (de)serialized.
+    Json(serde_json::Error),
+    /// Google answered with something this crate doesn't know how to handle.
+    UnexpectedResponse(String),
+    /// Nothing on Drive matches the given path or name.
+    NotFound(String),
+    /// Signing in failed: no consent, a refused or unreadable token, missing client details.
+    Auth(String),
+    /// Google answered with an error status, and said why.
+    Api {
+        status: StatusCode,
+        /// Path of the URL asked for, e.g. `/drive/v3/files/1a2b3c`.
+        endpoint: String,
+        message: String,
+        /// Google's code for the error when it gave one, e.g. `rateLimitExceeded`,
+        /// `userRateLimitExceeded` or `storageQuotaExceeded`.
+        reason: Option<String>,
+    },
+    /// One of the other errors, with what was being done at the time, e.g. which file was
+    /// being downloaded.
+    Context { context: String, source: Box<Error> },
+}
+
+impl Error {
+    /// Error for a response that wasn't the one expected.
+    pub(crate) fn from_status(resp: Response) -> Error {
+        let status = resp.status();
+        if status.is_client_error() || status.is_server_error() {
+            Error::from_api(resp)
+        } else {
+            Error::UnexpectedResponse(format!("unexpected response status {}", status))
+        }
+    }
+
+    /// `Error::Api` for an error response, with the message Google put in the body.
+    pub(crate) fn from_api(resp: Response) -> Error {
+        let status = resp.status();
+        let endpoint = resp.url().path().to_string();
+        Error::api(status, endpoint, resp.text().unwrap_or_default())
+    }
+
+    /// `Error::Api` from an error response's status, URL path and body, which is Google's
+    /// error JSON or, from proxies and the like, anything.
+    pub(crate) fn api(status: StatusCode, endpoint: String, body: String) -> Error {
+        let json: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+        let error = &json["error"];
+        let reason = error["errors"]
+            .as_array()
+            .and_then(|errors| errors.iter().find_map(|e| e["reason"].as_str()))
+            .map(String::from);
+        let message = error["message"].as_str().map(String::from).unwrap_or(body);
+        Error::Api {
+            status,
+            endpoint,
+            message,
+            reason,
+        }
+    }
+
+    /// Wrap this error with what was being done, e.g. `downloading 1a2b3c`.
+    pub fn context(self, context: impl Into<String>) -> Error {
+        Error::Context {
+            context: context.into(),
+            source: Box::new(self),
+        }
+    }
+
+    /// The HTTP status Google answered with, if it got that far.
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            Error::Http(e) => e.status(),
+            Error::Api { status, .. } => Some(*status),
+            Error::Context { source, .. } => source.status(),
+            _ => None,
+        }
+    }
+
+    /// Google's code for an API error, e.g. `rateLimitExceeded`, if it gave one.
+    pub fn reason(&self) -> Option<&str> {
+        match self {
+            Error::Api { reason, .. } => reason.as_deref(),
+            Error::Context { source, .. } => source.reason(),
+            _ => None,
+        }
+    }
+
+    /// Whether Google refused the request for going over a rate limit.
+    pub fn is_rate_limited(&self) -> bool {
+        self.status() == Some(StatusCode::TOO_MANY_REQUESTS)
+            || matches!(
+                self.reason(),
+                Some("rateLimitExceeded") | Some("userRateLimitExceeded")
+            )
+    }
+
+    fn io_kind(&self) -> io::ErrorKind {
+        match self {
+            Error::Io(e) => e.kind(),
+            Error::Json(_) => io::ErrorKind::InvalidData,
+            Error::NotFound(_) => io::ErrorKind::NotFound,
+            Error::Auth(_) => io::ErrorKind::PermissionDenied,
+            Error::Http(e) if e.is_timeout() => io::ErrorKind::TimedOut,
+            Error::Context { source, .. } => source.io_kind(),
+            _ => match self.status() {
+                Some(StatusCode::NOT_FOUND) => io::ErrorKind::NotFound,
+                Some(StatusCode::UNAUTHORIZED) | Some(StatusCode::FORBIDDEN) => {
+                    io::ErrorKind::PermissionDenied
+                }
+                _ => io::ErrorKind::Other,
+            },
+        }
+    }
+}
+
+/// Pass a successful response on, turn an error status into `Error::Api`.
+pub(crate) fn check_status(resp: Response) -> Result<Response, Error> {
+    if resp.status().is_success() {
+        Ok(resp)
+    } else {
+        Err(Error::from_api(resp))
+    }
+}
+
+/// `check_status` as a method, for the middle of a request chain.
+pub(crate) trait CheckStatus {
+    fn check_status(self) -> Result<Response, Error>;
+}
+
+impl CheckStatus for Response {
+    fn check_status(self) -> Result<Response, Error> {
+        check_status(self)
+    }
+}
+
+/// `Error::context` for results, taking a closure so the message is only built on failure.
+pub(crate) trait Context<T> {
+    fn with_context<C: Into<String>>(self, context: impl FnOnce() -> C) -> Result<T, Error>;
+}
+
+impl<T, E: Into<Error>> Context<T> for Result<T, E> {
+    fn with_context<C: Into<String>>(self, context: impl FnOnce() -> C) -> Result<T, Error> {
+        self.map_err(|e| e.into().context(context()))
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Http(e) => write!(f, "http error: {}", e),
+            Error::Io(e) => write!(f, "io error: {}", e),
+            Error::Json(e) => write!(f, "json error: {}", e),
+            Error::UnexpectedResponse(msg) => write!(f, "unexpected response: {}", msg),
+            Error::NotFound(what) => write!(f, "not found: {}", what),
+            Error::Auth(msg) => write!(f, "signing in failed: {}", msg),
+            Error::Api {
+                status,
+                endpoint,
+                message,
+                ..
+            } => write!(f, "{} from {}: {}", status, endpoint, message),
+            Error::Context { context, source } => write!(f, "{}: {}", context, source),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Http(e) => Some(e),
+            Error::Io(e) => Some(e),
+            Error::Json(e) => Some(e),
+            Error::Context { source, .. } => Some(&**source),
+            Error::UnexpectedResponse(_)
+            | Error::NotFound(_)
+            | Error::Auth(_)
+            | Error::Api { .. } => None,
+        }
+    }
+}
+
+/// For `std::io::Read`/`Write` implementations and other code speaking `io::Result`. Local
+/// file errors come back as they were, with any context added to the message, and not found,
+/// permission and timeout errors from Google get the matching `io::ErrorKind`.
+impl From<Error> for io::Error {
+    fn from(e: Error) -> Self {
+        match e {
+            Error::Io(e) => e,
+            e => io::Error::new(e.io_kind(), e),
+        }
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(e: reqwest::Error) -> Self {
+        Error::Http(e)
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        Error::Json(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn api_errors_keep_googles_reason() {
+        let body = r#"{"error": {"code": 403, "message": "User Rate Limit Exceeded",
+            "errors": [{"domain": "usageLimits", "reason": "userRateLimitExceeded"}]}}"#;
+        let e = Error::api(
+            StatusCode::FORBIDDEN,
+            String::from("/drive/v3/files"),
+            body.into(),
+        );
+        assert_eq!(e.reason(), Some("userRateLimitExceeded"));
+        assert!(e.is_rate_limited());
+        assert_eq!(
+            e.to_string(),
+            "403 Forbidden from /drive/v3/files: User Rate Limit Exceeded"
+        );
+        assert_eq!(e.context("listing").reason(), Some("userRateLimitExceeded"));
+
+        let e = Error::api(StatusCode::FORBIDDEN, String::new(), String::from("<html>"));
+        assert_eq!(e.reason(), None);
+        assert!(!e.is_rate_limited());
+        assert_eq!(e.to_string(), "403 Forbidden from : <html>");
+    }
+}
@@ -0,0 +1,160 @@
+use std::str::FromStr;
+
+use crate::error::{CheckStatus, Error};
+use crate::file_id::FileId;
+use crate::params::Params;
+use crate::Drive;
+
+/// Formats Google Docs, Sheets, Slides, Drawings and Apps Script files can be exported to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExportFormat {
+    Pdf,
+    Docx,
+    Odt,
+    Rtf,
+    Txt,
+    /// Zipped html with images.
+    Html,
+    Epub,
+    Xlsx,
+    Ods,
+    /// First sheet only.
+    Csv,
+    /// First sheet only.
+    Tsv,
+    Pptx,
+    Odp,
+    Png,
+    Jpeg,
+    Svg,
+    /// Apps Script projects.
+    Json,
+}
+
+impl ExportFormat {
+    const ALL: [ExportFormat; 17] = [
+        ExportFormat::Pdf,
+        ExportFormat::Docx,
+        ExportFormat::Odt,
+        ExportFormat::Rtf,
+        ExportFormat::Txt,
+        ExportFormat::Html,
+        ExportFormat::Epub,
+        ExportFormat::Xlsx,
+        ExportFormat::Ods,
+        ExportFormat::Csv,
+        ExportFormat::Tsv,
+        ExportFormat::Pptx,
+        ExportFormat::Odp,
+        ExportFormat::Png,
+        ExportFormat::Jpeg,
+        ExportFormat::Svg,
+        ExportFormat::Json,
+    ];
+
+    /// MIME type to request from the export endpoint.
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Pdf => "application/pdf",
+            ExportFormat::Docx => {
+                "application/vnd.openxmlformats-officedocument.wordprocessingml.document"
+            }
+            ExportFormat::Odt => "application/vnd.oasis.opendocument.text",
+            ExportFormat::Rtf => "application/rtf",
+            ExportFormat::Txt => "text/plain",
+            ExportFormat::Html => "application/zip",
+            ExportFormat::Epub => "application/epub+zip",
+            ExportFormat::Xlsx => {
+                "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
+            }
+            ExportFormat::Ods => "application/x-vnd.oasis.opendocument.spreadsheet",
+            ExportFormat::Csv => "text/csv",
+            ExportFormat::Tsv => "text/tab-separated-values",
+            ExportFormat::Pptx => {
+                "application/vnd.openxmlformats-officedocument.presentationml.presentation"
+            }
+            ExportFormat::Odp => "application/vnd.oasis.opendocument.presentation",
+            ExportFormat::Png => "image/png",
+            ExportFormat::Jpeg => "image/jpeg",
+            ExportFormat::Svg => "image/svg+xml",
+            ExportFormat::Json => "application/vnd.google-apps.script+json",
+        }
+    }
+
+    /// File extension (without the dot) for exported files.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Pdf => "pdf",
+            ExportFormat::Docx => "docx",
+            ExportFormat::Odt => "odt",
+            ExportFormat::Rtf => "rtf",
+            ExportFormat::Txt => "txt",
+            ExportFormat::Html => "zip",
+            ExportFormat::Epub => "epub",
+            ExportFormat::Xlsx => "xlsx",
+            ExportFormat::Ods => "ods",
+            ExportFormat::Csv => "csv",
+            ExportFormat::Tsv => "tsv",
+            ExportFormat::Pptx => "pptx",
+            ExportFormat::Odp => "odp",
+            ExportFormat::Png => "png",
+            ExportFormat::Jpeg => "jpg",
+            ExportFormat::Svg => "svg",
+            ExportFormat::Json => "json",
+        }
+    }
+
+    /// Default export format for a Google-native source mimeType. `None` for anything that
+    /// can't be exported (and should be downloaded instead).
+    pub fn default_for(source_mime_type: &str) -> Option<ExportFormat> {
+        match source_mime_type {
+            "application/vnd.google-apps.document" => Some(ExportFormat::Docx),
+            "application/vnd.google-apps.spreadsheet" => Some(ExportFormat::Xlsx),
+            "application/vnd.google-apps.presentation" => Some(ExportFormat::Pptx),
+            "application/vnd.google-apps.drawing" => Some(ExportFormat::Png),
+            "application/vnd.google-apps.script" => Some(ExportFormat::Json),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    /// Parse a format by its extension, e.g. `pdf`, `xlsx` or `jpg`. `jpeg` and `html` work
+    /// too.
+    fn from_str(s: &str) -> Result<ExportFormat, String> {
+        let s = s.trim_start_matches('.').to_ascii_lowercase();
+        match s.as_str() {
+            "jpeg" => return Ok(ExportFormat::Jpeg),
+            "html" => return Ok(ExportFormat::Html),
+            _ => {}
+        }
+        ExportFormat::ALL
+            .iter()
+            .find(|f| f.extension() == s)
+            .copied()
+            .ok_or_else(|| format!("unknown export format {}", s))
+    }
+}
+
+impl Drive {
+    /// Export given Google-native file to given format and return its contents.
+    /// Drive limits exported content to 10MB.
+    pub fn export_file(
+        &self,
+        file_id: impl Into<FileId>,
+        format: ExportFormat,
+    ) -> Result<Vec<u8>, Error> {
+        let file_id = file_id.into();
+        let mut resp = self
+            .get(
+                format!("/files/{}/export", file_id).as_str(),
+                Params::new().param("mimeType", format.mime_type()),
+            )?
+            .check_status()?;
+        let mut buf: Vec<u8> = vec![];
+        resp.copy_to(&mut buf)?;
+        Ok(buf)
+    }
+}
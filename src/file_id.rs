@@ -0,0 +1,167 @@
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+use reqwest::Url;
+
+/// Id of a Drive file or folder, parsed from any of the URL formats Drive hands out or a bare
+/// id. Methods taking a file want one of these, so input that isn't an id is turned away
+/// before it ends up in a request URL.
+///
+/// ```no_run
+/// # use std::convert::TryFrom;
+/// # use std::path::Path;
+/// # use google_api::*;
+/// # let drive = Drive::new(String::new(), String::new(), None)?;
+/// # let folder_id_from_config = String::from("1aBc");
+/// let id: FileId = "https://docs.google.com/document/d/1aBc.../edit".parse()?;
+/// let id = FileId::try_from(folder_id_from_config)?;
+/// drive.download(&id, Path::new("report.pdf"), &DownloadOptions::new())?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FileId(String);
+
+impl FileId {
+    /// Parse a file id out of given string. Understands:
+    ///
+    /// - `drive.google.com/file/d/<id>/view` and the `docs.google.com/<type>/d/<id>/edit` links
+    ///   for Docs, Sheets, Slides and Forms
+    /// - folder links, `drive.google.com/drive/folders/<id>` (also under `/drive/u/<n>/`)
+    /// - links with an `id` param, like `open?id=<id>` and `uc?id=<id>&export=download`
+    /// - bare ids
+    pub fn parse(s: &str) -> Result<FileId, String> {
+        let s = s.trim();
+        if is_id(s) {
+            return Ok(FileId(s.to_string()));
+        }
+        let url = Url::parse(s).map_err(|e| format!("invalid url {}: {}", s, e))?;
+        if let Some((_, id)) = url.query_pairs().find(|(k, _)| k == "id") {
+            if is_id(&id) {
+                return Ok(FileId(id.into_owned()));
+            }
+        }
+        let segments: Vec<&str> = url.path_segments().map(|s| s.collect()).unwrap_or_default();
+        segments
+            .windows(2)
+            .find(|w| (w[0] == "d" || w[0] == "folders") && is_id(w[1]))
+            .map(|w| FileId(w[1].to_string()))
+            .ok_or_else(|| format!("no file id in url {}", s))
+    }
+
+    /// The top of the user's My Drive, by its `root` alias.
+    pub fn root() -> FileId {
+        FileId(String::from("root"))
+    }
+
+    /// Take given id as it is, without checking it. For ids that came from Drive, such as a
+    /// `DriveFile`'s, and aliases like `appDataFolder`.
+    pub fn unchecked(id: impl Into<String>) -> FileId {
+        FileId(id.into())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Drive ids are made of letters, digits, `-` and `_`.
+fn is_id(s: &str) -> bool {
+    !s.is_empty()
+        && s.chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+impl FromStr for FileId {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<FileId, String> {
+        FileId::parse(s)
+    }
+}
+
+impl fmt::Display for FileId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl AsRef<str> for FileId {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+/// `FileId::parse`, for `FileId::try_from(url)?` and `url.try_into()?`.
+impl TryFrom<&str> for FileId {
+    type Error = String;
+
+    fn try_from(s: &str) -> Result<FileId, String> {
+        FileId::parse(s)
+    }
+}
+
+impl TryFrom<&String> for FileId {
+    type Error = String;
+
+    fn try_from(s: &String) -> Result<FileId, String> {
+        FileId::parse(s)
+    }
+}
+
+impl TryFrom<String> for FileId {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<FileId, String> {
+        FileId::parse(&s)
+    }
+}
+
+impl From<&FileId> for FileId {
+    fn from(id: &FileId) -> FileId {
+        id.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ID: &str = "1aBc-D_e2";
+
+    #[test]
+    fn parses_links_and_bare_ids() {
+        for s in &[
+            "1aBc-D_e2",
+            "  1aBc-D_e2\n",
+            "https://drive.google.com/file/d/1aBc-D_e2/view?usp=sharing",
+            "https://docs.google.com/document/d/1aBc-D_e2/edit",
+            "https://docs.google.com/spreadsheets/d/1aBc-D_e2/edit#gid=0",
+            "https://docs.google.com/presentation/d/1aBc-D_e2",
+            "https://drive.google.com/drive/folders/1aBc-D_e2",
+            "https://drive.google.com/drive/u/1/folders/1aBc-D_e2?usp=sharing",
+            "https://drive.google.com/open?id=1aBc-D_e2",
+            "https://drive.google.com/uc?id=1aBc-D_e2&export=download",
+        ] {
+            assert_eq!(FileId::parse(s), Ok(FileId::unchecked(ID)), "{}", s);
+        }
+    }
+
+    #[test]
+    fn rejects_garbage() {
+        for s in &[
+            "",
+            "   ",
+            "not an id",
+            "../etc/passwd",
+            "id?q=x",
+            "https://drive.google.com/",
+            "https://drive.google.com/drive/my-drive",
+            "https://drive.google.com/open?id=a/b",
+            "https://docs.google.com/document/d/",
+            "https://example.com/d/",
+        ] {
+            assert!(FileId::parse(s).is_err(), "{} parsed", s);
+        }
+    }
+}
@@ -0,0 +1,245 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::concurrency::run_bounded;
+use crate::error::{CheckStatus, Error};
+use crate::file_id::FileId;
+use crate::ignore::IgnoreRules;
+use crate::mime::MimeType;
+use crate::models::{DriveFile, FILE_FIELDS};
+use crate::params::Params;
+use crate::resumable::RESUMABLE_THRESHOLD;
+use crate::upload::UploadOptions;
+use crate::Drive;
+
+/// Options for `Drive::upload_folder`.
+#[derive(Debug, Clone)]
+pub struct FolderUploadOptions {
+    jobs: usize,
+    ignore: IgnoreRules,
+}
+
+impl Default for FolderUploadOptions {
+    fn default() -> Self {
+        FolderUploadOptions {
+            jobs: 1,
+            ignore: IgnoreRules::new(),
+        }
+    }
+}
+
+impl FolderUploadOptions {
+    pub fn new() -> FolderUploadOptions {
+        FolderUploadOptions::default()
+    }
+
+    /// Number of files to upload at once.
+    pub fn jobs(mut self, jobs: usize) -> FolderUploadOptions {
+        self.jobs = jobs.max(1);
+        self
+    }
+
+    /// Skip files and folders matching given pattern, e.g. `target/`, `*.tmp` or
+    /// `build/**/*.o`, on top of the directory's `DRIVE_IGNORE_FILE`. See `IgnoreRules`.
+    pub fn ignore(mut self, pattern: &str) -> FolderUploadOptions {
+        self.ignore = self.ignore.exclude(pattern);
+        self
+    }
+
+    /// Upload files and folders matching given pattern even if an ignore rule before it
+    /// leaves them out.
+    pub fn include(mut self, pattern: &str) -> FolderUploadOptions {
+        self.ignore = self.ignore.include(pattern);
+        self
+    }
+
+    /// Add a whole set of ignore rules.
+    pub fn ignore_rules(mut self, rules: &IgnoreRules) -> FolderUploadOptions {
+        self.ignore = self.ignore.extend(rules);
+        self
+    }
+}
+
+/// What `Drive::upload_folder` did.
+#[derive(Debug, Clone, Default)]
+pub struct FolderUploadReport {
+    /// Id of the Drive folder mirroring the local directory.
+    pub root_id: String,
+    /// Local folder -> id of the Drive folder created for it.
+    pub folders: Vec<(PathBuf, String)>,
+    /// Local file -> id of the uploaded Drive file.
+    pub files: Vec<(PathBuf, String)>,
+    /// Paths matching an ignore pattern.
+    pub skipped: Vec<PathBuf>,
+    /// Files that failed to upload, with the reason.
+    pub failed: Vec<(PathBuf, String)>,
+}
+
+/// What `Drive::upload_folder_with_progress` reports as it goes. Files upload concurrently,
+/// so events for different files interleave.
+#[derive(Debug)]
+pub enum FolderUploadEvent<'a> {
+    /// The folders are created and this many files, of this many bytes in all, are about to be
+    /// uploaded.
+    Started { files: usize, bytes: u64 },
+    /// `sent` of the file's `size` bytes have been uploaded so far.
+    Sent {
+        path: &'a Path,
+        sent: u64,
+        size: u64,
+    },
+    /// The file is done, uploaded or failed.
+    Finished {
+        path: &'a Path,
+        result: Result<&'a DriveFile, &'a Error>,
+    },
+}
+
+impl Drive {
+    /// Create a folder with given name inside given parent folder (`FileId::root()` for My Drive).
+    pub fn create_folder(
+        &self,
+        name: &str,
+        parent_id: impl Into<FileId>,
+    ) -> Result<DriveFile, Error> {
+        let parent_id = parent_id.into();
+        Ok(self
+            .post(
+                "/files",
+                Params::new().fields(FILE_FIELDS),
+                serde_json::json!({
+                    "name": name,
+                    "mimeType": MimeType::FOLDER,
+                    "parents": [parent_id.as_str()],
+                }),
+            )?
+            .check_status()?
+            .json()?)
+    }
+
+    /// Mirror the local directory at given path into a new folder inside given parent folder.
+    /// Folders are created first, then files are uploaded. A file failing to upload doesn't
+    /// stop the rest, check the report's `failed` list.
+    pub fn upload_folder(
+        &self,
+        local_path: &Path,
+        parent_id: impl Into<FileId>,
+        options: &FolderUploadOptions,
+    ) -> Result<FolderUploadReport, Error> {
+        self.upload_folder_with_progress(local_path, parent_id, options, |_| {})
+    }
+
+    /// `upload_folder`, calling `progress` as files go up, e.g. to draw progress bars. It's
+    /// called from the upload threads.
+    pub fn upload_folder_with_progress<F>(
+        &self,
+        local_path: &Path,
+        parent_id: impl Into<FileId>,
+        options: &FolderUploadOptions,
+        progress: F,
+    ) -> Result<FolderUploadReport, Error>
+    where
+        F: Fn(FolderUploadEvent) + Sync,
+    {
+        let parent_id = parent_id.into();
+        let name = local_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("upload");
+        let ignore = IgnoreRules::load(local_path)?.extend(&options.ignore);
+        let root = self.create_folder(name, parent_id)?;
+
+        let mut report = FolderUploadReport {
+            root_id: root.id.clone(),
+            folders: vec![(local_path.to_path_buf(), root.id.clone())],
+            ..FolderUploadReport::default()
+        };
+        let mut files = vec![];
+        let mut pending = vec![(local_path.to_path_buf(), root.id)];
+        while let Some((dir, dir_id)) = pending.pop() {
+            let mut entries: Vec<PathBuf> = fs::read_dir(&dir)?
+                .filter_map(|e| e.ok().map(|e| e.path()))
+                .collect();
+            entries.sort();
+            for entry in entries {
+                let relative = entry.strip_prefix(local_path).unwrap_or(&entry);
+                if ignore.is_ignored(relative, entry.is_dir()) {
+                    report.skipped.push(entry);
+                    continue;
+                }
+                if entry.is_dir() {
+                    let name = entry
+                        .file_name()
+                        .and_then(|n| n.to_str())
+                        .unwrap_or_default();
+                    let folder = self.create_folder(name, FileId::unchecked(&dir_id))?;
+                    report.folders.push((entry.clone(), folder.id.clone()));
+                    pending.push((entry, folder.id));
+                } else {
+                    let size = entry.metadata().map(|m| m.len()).unwrap_or_default();
+                    files.push((entry, size, dir_id.clone()));
+                }
+            }
+        }
+
+        progress(FolderUploadEvent::Started {
+            files: files.len(),
+            bytes: files.iter().map(|(_, size, _)| size).sum(),
+        });
+        let results = run_bounded(files, options.jobs, |(path, size, parent)| {
+            let result = self.upload_into(&path, size, &parent, &progress);
+            progress(FolderUploadEvent::Finished {
+                path: &path,
+                result: result.as_ref(),
+            });
+            (path, result)
+        });
+        for (path, result) in results {
+            match result {
+                Ok(file) => report.files.push((path, file.id)),
+                Err(e) => report.failed.push((path, e.to_string())),
+            }
+        }
+        Ok(report)
+    }
+
+    /// Upload one file into given folder, resumably and reporting each chunk if it's big.
+    fn upload_into<F>(
+        &self,
+        path: &Path,
+        size: u64,
+        parent_id: &str,
+        progress: &F,
+    ) -> Result<DriveFile, Error>
+    where
+        F: Fn(FolderUploadEvent),
+    {
+        let options = UploadOptions::new().parent(FileId::unchecked(parent_id));
+        if size <= RESUMABLE_THRESHOLD {
+            let file = self.send_file(None, path, &options)?;
+            progress(FolderUploadEvent::Sent {
+                path,
+                sent: size,
+                size,
+            });
+            return Ok(file);
+        }
+        let mut upload = self.start_resumable_upload(path, &options)?;
+        loop {
+            let done = self.upload_chunk(&mut upload)?;
+            let sent = if done.is_some() {
+                upload.size
+            } else {
+                upload.offset
+            };
+            progress(FolderUploadEvent::Sent {
+                path,
+                sent,
+                size: upload.size,
+            });
+            if let Some(file) = done {
+                return Ok(file);
+            }
+        }
+    }
+}
@@ -0,0 +1,354 @@
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+
+use super::Forms;
+use crate::error::{CheckStatus, Error};
+use crate::models::ExtraFields;
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct FormInfo {
+    /// Title shown to respondents.
+    pub title: String,
+    /// Name of the form's file in Drive.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub document_title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+/// A form and its items.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct GoogleForm {
+    pub form_id: String,
+    pub info: FormInfo,
+    pub items: Vec<FormItem>,
+    pub revision_id: String,
+    /// Link to fill in the form.
+    pub responder_uri: String,
+    /// Spreadsheet responses are also written to, if one is linked.
+    pub linked_sheet_id: Option<String>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl GoogleForm {
+    /// The question with given id, which is what answers are keyed by.
+    pub fn question(&self, question_id: &str) -> Option<&FormItem> {
+        self.items.iter().find(|item| {
+            item.question_item
+                .as_ref()
+                .is_some_and(|q| q.question.question_id == question_id)
+        })
+    }
+}
+
+/// One item of a form. Questions have `question_item` set, other items (page breaks, text,
+/// images) only a title and description. Build new questions with the constructors and add
+/// them with `Forms::add_questions`.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct FormItem {
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub item_id: String,
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub question_item: Option<QuestionItem>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl FormItem {
+    fn question(title: &str, question: Question) -> FormItem {
+        FormItem {
+            title: title.to_string(),
+            question_item: Some(QuestionItem {
+                question,
+                ..QuestionItem::default()
+            }),
+            ..FormItem::default()
+        }
+    }
+
+    /// A question answered with one line of text.
+    pub fn short_answer(title: &str) -> FormItem {
+        FormItem::question(
+            title,
+            Question {
+                text_question: Some(TextQuestion {
+                    paragraph: false,
+                    ..TextQuestion::default()
+                }),
+                ..Question::default()
+            },
+        )
+    }
+
+    /// A question answered with free text over several lines.
+    pub fn paragraph(title: &str) -> FormItem {
+        FormItem::question(
+            title,
+            Question {
+                text_question: Some(TextQuestion {
+                    paragraph: true,
+                    ..TextQuestion::default()
+                }),
+                ..Question::default()
+            },
+        )
+    }
+
+    /// A multiple choice question, with radio buttons, checkboxes or a dropdown.
+    pub fn choice(title: &str, kind: ChoiceType, options: &[&str]) -> FormItem {
+        let options = options
+            .iter()
+            .map(|value| ChoiceOption {
+                value: value.to_string(),
+                ..ChoiceOption::default()
+            })
+            .collect();
+        FormItem::question(
+            title,
+            Question {
+                choice_question: Some(ChoiceQuestion {
+                    kind,
+                    options,
+                    shuffle: false,
+                    ..ChoiceQuestion::default()
+                }),
+                ..Question::default()
+            },
+        )
+    }
+
+    /// A linear scale from `low` (0 or 1) to `high` (2 to 10).
+    pub fn scale(title: &str, low: u32, high: u32) -> FormItem {
+        FormItem::question(
+            title,
+            Question {
+                scale_question: Some(ScaleQuestion {
+                    low,
+                    high,
+                    ..ScaleQuestion::default()
+                }),
+                ..Question::default()
+            },
+        )
+    }
+
+    pub fn description(mut self, description: &str) -> FormItem {
+        self.description = Some(description.to_string());
+        self
+    }
+
+    /// Respondents have to answer the question.
+    pub fn required(mut self) -> FormItem {
+        if let Some(item) = &mut self.question_item {
+            item.question.required = true;
+        }
+        self
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct QuestionItem {
+    pub question: Question,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+/// A question. One of the kinds is set.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Question {
+    /// Key of the question's answers in a `FormResponse`.
+    #[serde(skip_serializing)]
+    pub question_id: String,
+    pub required: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub choice_question: Option<ChoiceQuestion>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text_question: Option<TextQuestion>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scale_question: Option<ScaleQuestion>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ChoiceType {
+    /// Pick one.
+    #[default]
+    Radio,
+    /// Pick any number.
+    Checkbox,
+    /// Pick one from a dropdown.
+    DropDown,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ChoiceQuestion {
+    #[serde(rename = "type")]
+    pub kind: ChoiceType,
+    pub options: Vec<ChoiceOption>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub shuffle: bool,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ChoiceOption {
+    pub value: String,
+    /// The "Other:" option with a text field.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub is_other: bool,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct TextQuestion {
+    pub paragraph: bool,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ScaleQuestion {
+    pub low: u32,
+    pub high: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub low_label: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub high_label: Option<String>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+/// One change to a form, sent with `Forms::batch_update`. `Raw` takes any request the API
+/// supports that isn't covered here.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FormRequest {
+    /// Insert an item at given position, counting from 0.
+    CreateItem {
+        item: Box<FormItem>,
+        index: u32,
+    },
+    DeleteItem {
+        index: u32,
+    },
+    /// Change the title and/or description shown to respondents.
+    UpdateInfo {
+        title: Option<String>,
+        description: Option<String>,
+    },
+    Raw(serde_json::Value),
+}
+
+impl FormRequest {
+    fn to_json(&self) -> serde_json::Value {
+        match self {
+            FormRequest::CreateItem { item, index } => serde_json::json!({
+                "createItem": { "item": item, "location": { "index": index } }
+            }),
+            FormRequest::DeleteItem { index } => serde_json::json!({
+                "deleteItem": { "location": { "index": index } }
+            }),
+            FormRequest::UpdateInfo { title, description } => {
+                let mut info = serde_json::json!({});
+                let mut fields = vec![];
+                if let Some(title) = title {
+                    info["title"] = serde_json::json!(title);
+                    fields.push("title");
+                }
+                if let Some(description) = description {
+                    info["description"] = serde_json::json!(description);
+                    fields.push("description");
+                }
+                serde_json::json!({
+                    "updateFormInfo": { "info": info, "updateMask": fields.join(",") }
+                })
+            }
+            FormRequest::Raw(request) => request.clone(),
+        }
+    }
+}
+
+/// Result of a batch update, one reply per request. `CreateItem`'s holds the new item's id.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct FormUpdateResponse {
+    pub replies: Vec<serde_json::Value>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl Forms {
+    /// Create an empty form with given title. Questions are added afterwards.
+    pub fn create_form(&self, title: &str) -> Result<GoogleForm, Error> {
+        Ok(self
+            .request(Method::POST, &[])?
+            .json(&serde_json::json!({ "info": { "title": title } }))
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    pub fn get_form(&self, form_id: &str) -> Result<GoogleForm, Error> {
+        Ok(self
+            .request(Method::GET, &[form_id])?
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    /// Apply the requests to the form in order, as one change. If any of them fails, none
+    /// are applied.
+    pub fn batch_update(
+        &self,
+        form_id: &str,
+        requests: &[FormRequest],
+    ) -> Result<FormUpdateResponse, Error> {
+        let requests: Vec<serde_json::Value> = requests.iter().map(|r| r.to_json()).collect();
+        let endpoint = format!("{}:batchUpdate", form_id);
+        Ok(self
+            .request(Method::POST, &[&endpoint])?
+            .json(&serde_json::json!({ "requests": requests }))
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    /// Add questions (or any items) after the form's existing items. Returns the new items'
+    /// ids.
+    pub fn add_questions(&self, form_id: &str, items: &[FormItem]) -> Result<Vec<String>, Error> {
+        let start = self.get_form(form_id)?.items.len() as u32;
+        let requests: Vec<FormRequest> = items
+            .iter()
+            .zip(start..)
+            .map(|(item, index)| FormRequest::CreateItem {
+                item: Box::new(item.clone()),
+                index,
+            })
+            .collect();
+        let resp = self.batch_update(form_id, &requests)?;
+        Ok(resp
+            .replies
+            .iter()
+            .filter_map(|r| r["createItem"]["itemId"].as_str())
+            .map(String::from)
+            .collect())
+    }
+}
@@ -0,0 +1,70 @@
+use reqwest::blocking::RequestBuilder;
+use reqwest::Method;
+
+use crate::api;
+use crate::auth::Auth;
+use crate::error::Error;
+use crate::scope::Scope;
+
+mod form;
+mod responses;
+mod watches;
+
+pub use form::{
+    ChoiceOption, ChoiceQuestion, ChoiceType, FormInfo, FormItem, FormRequest, FormUpdateResponse,
+    GoogleForm, Question, QuestionItem, ScaleQuestion, TextQuestion,
+};
+pub use responses::{Answer, FormResponse, TextAnswer, TextAnswers};
+pub use watches::{FormWatch, FormWatchEvent};
+
+const FORMS_BASE_URL: &str = "https://forms.googleapis.com/v1/forms";
+
+/// Google Forms client. Shares its saved token with the other clients.
+///
+/// ```no_run
+/// # use google_api::*;
+/// # let (client_id, client_secret) = (String::new(), String::new());
+/// let forms = Forms::new(client_id, client_secret, None)?;
+/// let form = forms.create_form("Team lunch")?;
+/// forms.add_questions(&form.form_id, &[
+///     FormItem::choice("Where?", ChoiceType::Radio, &["Pizza", "Sushi"]).required(),
+///     FormItem::paragraph("Anything else?"),
+/// ])?;
+/// for response in forms.list_responses(&form.form_id, None)? {
+///     println!("{:?}", response.answers);
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug)]
+pub struct Forms {
+    auth: Auth,
+    client: reqwest::blocking::Client,
+}
+
+impl Forms {
+    /// Authenticate with given OAuth client. `scopes` defaults to `Scope::FormsBody` and
+    /// `Scope::FormsResponsesReadonly`.
+    pub fn new(
+        client_id: String,
+        client_secret: String,
+        scopes: Option<Vec<String>>,
+    ) -> Result<Forms, Error> {
+        let scopes = scopes.unwrap_or_else(|| {
+            vec![
+                Scope::FormsBody.into(),
+                Scope::FormsResponsesReadonly.into(),
+            ]
+        });
+
+        Ok(Forms {
+            auth: Auth::from_config(&client_id, &client_secret, &scopes)?,
+            client: reqwest::blocking::Client::new(),
+        })
+    }
+
+    /// Authorized request for the endpoint below `/forms` made of given path segments, e.g.
+    /// `[id, "responses"]`.
+    fn request(&self, method: Method, segments: &[&str]) -> Result<RequestBuilder, Error> {
+        api::request(&self.client, &self.auth, FORMS_BASE_URL, method, segments)
+    }
+}
@@ -0,0 +1,123 @@
+use std::collections::HashMap;
+use std::time::SystemTime;
+
+use reqwest::Method;
+use serde::Deserialize;
+
+use super::Forms;
+use crate::error::{CheckStatus, Error};
+use crate::models::ExtraFields;
+use crate::timestamp::format_rfc3339;
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct TextAnswer {
+    pub value: String,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct TextAnswers {
+    pub answers: Vec<TextAnswer>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+/// A respondent's answer to one question. Choices, scales and dates come as text too.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Answer {
+    pub question_id: String,
+    pub text_answers: Option<TextAnswers>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl Answer {
+    /// The answer's values: one, or several for checkbox questions.
+    pub fn values(&self) -> Vec<&str> {
+        self.text_answers
+            .iter()
+            .flat_map(|t| &t.answers)
+            .map(|a| a.value.as_str())
+            .collect()
+    }
+}
+
+/// One submission of a form.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct FormResponse {
+    pub response_id: String,
+    /// RFC 3339 timestamps.
+    pub create_time: String,
+    pub last_submitted_time: String,
+    /// Only set when the form collects email addresses.
+    pub respondent_email: Option<String>,
+    /// Answers by question id. Unanswered questions are missing.
+    pub answers: HashMap<String, Answer>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl FormResponse {
+    /// Values given for a question, empty if it wasn't answered.
+    pub fn answer(&self, question_id: &str) -> Vec<&str> {
+        self.answers
+            .get(question_id)
+            .map(|a| a.values())
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct ResponseList {
+    responses: Vec<FormResponse>,
+    next_page_token: Option<String>,
+}
+
+impl Forms {
+    /// Every response to a form, or only those submitted (or edited) after `since`.
+    pub fn list_responses(
+        &self,
+        form_id: &str,
+        since: Option<SystemTime>,
+    ) -> Result<Vec<FormResponse>, Error> {
+        let filter = since.map(|time| format!("timestamp > {}", format_rfc3339(time)));
+        let mut responses = vec![];
+        let mut page_token: Option<String> = None;
+        loop {
+            let mut params = vec![("pageSize", "5000")];
+            if let Some(filter) = &filter {
+                params.push(("filter", filter));
+            }
+            if let Some(token) = &page_token {
+                params.push(("pageToken", token));
+            }
+            let page: ResponseList = self
+                .request(Method::GET, &[form_id, "responses"])?
+                .query(&params)
+                .send()?
+                .check_status()?
+                .json()?;
+            responses.extend(page.responses);
+
+            match page.next_page_token {
+                Some(token) => page_token = Some(token),
+                None => break,
+            }
+        }
+        Ok(responses)
+    }
+
+    pub fn get_response(&self, form_id: &str, response_id: &str) -> Result<FormResponse, Error> {
+        Ok(self
+            .request(Method::GET, &[form_id, "responses", response_id])?
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+}
@@ -0,0 +1,92 @@
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+
+use super::Forms;
+use crate::error::{CheckStatus, Error};
+use crate::models::ExtraFields;
+
+/// What a `FormWatch` reports.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum FormWatchEvent {
+    /// A response was submitted.
+    #[default]
+    Responses,
+    /// The form's content or settings changed.
+    Schema,
+}
+
+/// Notifications about a form, published to a Pub/Sub topic. Watches expire after 7 days
+/// unless renewed with `Forms::renew_watch`.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct FormWatch {
+    pub id: String,
+    pub event_type: FormWatchEvent,
+    /// Pub/Sub topic, as `projects/<project>/topics/<topic>`.
+    pub target: serde_json::Value,
+    /// RFC 3339 timestamps.
+    pub create_time: String,
+    pub expire_time: String,
+    /// `ACTIVE` or `SUSPENDED`, e.g. when the topic can't be published to.
+    pub state: String,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct WatchList {
+    watches: Vec<FormWatch>,
+}
+
+impl Forms {
+    /// Publish an event to given Pub/Sub topic (`projects/<project>/topics/<topic>`) each
+    /// time it happens on the form. Forms' service account has to be allowed to publish to
+    /// the topic.
+    pub fn watch(
+        &self,
+        form_id: &str,
+        topic: &str,
+        event: FormWatchEvent,
+    ) -> Result<FormWatch, Error> {
+        Ok(self
+            .request(Method::POST, &[form_id, "watches"])?
+            .json(&serde_json::json!({
+                "watch": {
+                    "target": { "topic": { "topicName": topic } },
+                    "eventType": event,
+                }
+            }))
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    /// The form's watches created by this OAuth client.
+    pub fn list_watches(&self, form_id: &str) -> Result<Vec<FormWatch>, Error> {
+        let list: WatchList = self
+            .request(Method::GET, &[form_id, "watches"])?
+            .send()?
+            .check_status()?
+            .json()?;
+        Ok(list.watches)
+    }
+
+    /// Extend a watch for another 7 days.
+    pub fn renew_watch(&self, form_id: &str, watch_id: &str) -> Result<FormWatch, Error> {
+        let endpoint = format!("{}:renew", watch_id);
+        Ok(self
+            .request(Method::POST, &[form_id, "watches", &endpoint])?
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    pub fn delete_watch(&self, form_id: &str, watch_id: &str) -> Result<(), Error> {
+        self.request(Method::DELETE, &[form_id, "watches", watch_id])?
+            .send()?
+            .check_status()?;
+        Ok(())
+    }
+}
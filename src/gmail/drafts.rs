@@ -0,0 +1,100 @@
+use reqwest::Method;
+use serde::Deserialize;
+
+use super::message::{Message, MessageRef};
+use super::read::GmailMessage;
+use super::Gmail;
+use crate::error::{CheckStatus, Error};
+use crate::models::ExtraFields;
+
+/// An unsent message. Listing only fills in the message's id and thread, fetch it with
+/// `Gmail::get_message` for the rest.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct GmailDraft {
+    pub id: String,
+    pub message: GmailMessage,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct DraftList {
+    drafts: Vec<GmailDraft>,
+    next_page_token: Option<String>,
+}
+
+impl Gmail {
+    /// Save a message as a draft, for someone to review and send from Gmail, or to send
+    /// later with `send_draft`.
+    pub fn create_draft(&self, message: &Message) -> Result<GmailDraft, Error> {
+        Ok(self
+            .request(Method::POST, &["drafts"])?
+            .json(&serde_json::json!({ "message": message.to_json()? }))
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    /// Replace a draft's content.
+    pub fn update_draft(&self, draft_id: &str, message: &Message) -> Result<GmailDraft, Error> {
+        Ok(self
+            .request(Method::PUT, &["drafts", draft_id])?
+            .json(&serde_json::json!({ "id": draft_id, "message": message.to_json()? }))
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    /// Send a draft as it is. The draft is removed once sent.
+    pub fn send_draft(&self, draft_id: &str) -> Result<MessageRef, Error> {
+        Ok(self
+            .request(Method::POST, &["drafts", "send"])?
+            .json(&serde_json::json!({ "id": draft_id }))
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    pub fn get_draft(&self, draft_id: &str) -> Result<GmailDraft, Error> {
+        Ok(self
+            .request(Method::GET, &["drafts", draft_id])?
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    /// Every draft in the mailbox.
+    pub fn list_drafts(&self) -> Result<Vec<GmailDraft>, Error> {
+        let mut drafts = vec![];
+        let mut page_token: Option<String> = None;
+        loop {
+            let mut params = vec![("maxResults", "500")];
+            if let Some(token) = &page_token {
+                params.push(("pageToken", token));
+            }
+            let page: DraftList = self
+                .request(Method::GET, &["drafts"])?
+                .query(&params)
+                .send()?
+                .check_status()?
+                .json()?;
+            drafts.extend(page.drafts);
+
+            match page.next_page_token {
+                Some(token) => page_token = Some(token),
+                None => break,
+            }
+        }
+        Ok(drafts)
+    }
+
+    /// Permanently delete a draft.
+    pub fn delete_draft(&self, draft_id: &str) -> Result<(), Error> {
+        self.request(Method::DELETE, &["drafts", draft_id])?
+            .send()?
+            .check_status()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,159 @@
+use reqwest::{Method, StatusCode};
+use serde::Deserialize;
+
+use super::message::MessageRef;
+use super::Gmail;
+use crate::error::{CheckStatus, Error};
+use crate::models::{de_int64, ExtraFields};
+
+/// An active `Gmail::watch`. It has to be renewed before `expiration`, at least once a week.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct GmailWatch {
+    /// Mailbox history id when the watch started. Changes after it can be fetched with
+    /// `Gmail::list_history`.
+    pub history_id: String,
+    /// When the watch stops, in milliseconds since the epoch.
+    #[serde(deserialize_with = "de_int64")]
+    pub expiration: Option<u64>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+/// What Gmail publishes to the Pub/Sub topic when the mailbox changes. Only says that
+/// something changed, `Gmail::list_history` from the last history id seen says what.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct GmailNotification {
+    pub email_address: String,
+    #[serde(deserialize_with = "de_int64")]
+    pub history_id: Option<u64>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl GmailNotification {
+    /// Parse the body of a Pub/Sub push request, as posted to a push subscription's endpoint.
+    pub fn from_push(body: &[u8]) -> Result<GmailNotification, Error> {
+        let push: serde_json::Value = serde_json::from_slice(body)?;
+        let data = push["message"]["data"]
+            .as_str()
+            .and_then(|d| base64::decode(d).ok())
+            .ok_or_else(|| {
+                Error::UnexpectedResponse(String::from("push message had no base64 data"))
+            })?;
+        Ok(serde_json::from_slice(&data)?)
+    }
+}
+
+/// A message a history record is about.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct HistoryMessage {
+    pub message: MessageRef,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+/// Labels added to or removed from a message.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct HistoryLabels {
+    pub message: MessageRef,
+    pub label_ids: Vec<String>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+/// One change to the mailbox.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct HistoryRecord {
+    pub id: String,
+    pub messages_added: Vec<HistoryMessage>,
+    pub messages_deleted: Vec<HistoryMessage>,
+    pub labels_added: Vec<HistoryLabels>,
+    pub labels_removed: Vec<HistoryLabels>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+/// Changes since a history id, and the id to ask from next time.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GmailHistory {
+    pub records: Vec<HistoryRecord>,
+    pub history_id: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct HistoryList {
+    history: Vec<HistoryRecord>,
+    next_page_token: Option<String>,
+    history_id: String,
+}
+
+impl Gmail {
+    /// Have Gmail publish to a Cloud Pub/Sub topic (`projects/<project>/topics/<topic>`)
+    /// whenever the mailbox changes, or only messages with given labels when any are given.
+    /// Gmail's service account needs publish rights on the topic.
+    pub fn watch(&self, topic_name: &str, label_ids: &[&str]) -> Result<GmailWatch, Error> {
+        let mut body = serde_json::json!({ "topicName": topic_name });
+        if !label_ids.is_empty() {
+            body["labelIds"] = serde_json::json!(label_ids);
+            body["labelFilterBehavior"] = serde_json::json!("include");
+        }
+        Ok(self
+            .request(Method::POST, &["watch"])?
+            .json(&body)
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    /// Stop publishing mailbox changes.
+    pub fn stop_watch(&self) -> Result<(), Error> {
+        self.request(Method::POST, &["stop"])?
+            .send()?
+            .check_status()?;
+        Ok(())
+    }
+
+    /// Every change to the mailbox after given history id (from a `GmailWatch`, a
+    /// notification, a message, or the last call). Gmail keeps about a week of history, older
+    /// ids give `Error::NotFound` and the mailbox has to be synced in full again.
+    pub fn list_history(&self, start_history_id: &str) -> Result<GmailHistory, Error> {
+        let mut history = GmailHistory {
+            records: vec![],
+            history_id: start_history_id.to_string(),
+        };
+        let mut page_token: Option<String> = None;
+        loop {
+            let mut params = vec![("startHistoryId", start_history_id), ("maxResults", "500")];
+            if let Some(token) = &page_token {
+                params.push(("pageToken", token));
+            }
+            let resp = self
+                .request(Method::GET, &["history"])?
+                .query(&params)
+                .send()?;
+            if resp.status() == StatusCode::NOT_FOUND {
+                return Err(Error::NotFound(format!(
+                    "history from {} has expired",
+                    start_history_id
+                )));
+            }
+            let page: HistoryList = resp.check_status()?.json()?;
+            history.records.extend(page.history);
+            if !page.history_id.is_empty() {
+                history.history_id = page.history_id;
+            }
+
+            match page.next_page_token {
+                Some(token) => page_token = Some(token),
+                None => break,
+            }
+        }
+        Ok(history)
+    }
+}
@@ -0,0 +1,185 @@
+use reqwest::Method;
+use serde::Deserialize;
+
+use super::message::MessageRef;
+use super::Gmail;
+use crate::error::{CheckStatus, Error};
+use crate::models::ExtraFields;
+
+/// Most ids a single batch modify can take.
+const BATCH_MODIFY_LIMIT: usize = 1000;
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct LabelColor {
+    /// Hex colour, from the fixed palette Gmail allows.
+    pub text_color: String,
+    pub background_color: String,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+/// A Gmail label. System labels (`INBOX`, `UNREAD`, ...) have their name as id, user
+/// labels get a generated one.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct GmailLabel {
+    pub id: String,
+    pub name: String,
+    /// `system` or `user`.
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub messages_total: u64,
+    pub messages_unread: u64,
+    pub threads_total: u64,
+    pub threads_unread: u64,
+    pub color: Option<LabelColor>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl GmailLabel {
+    pub const INBOX: &'static str = "INBOX";
+    pub const UNREAD: &'static str = "UNREAD";
+    pub const STARRED: &'static str = "STARRED";
+    pub const IMPORTANT: &'static str = "IMPORTANT";
+    pub const SENT: &'static str = "SENT";
+    pub const DRAFT: &'static str = "DRAFT";
+    pub const SPAM: &'static str = "SPAM";
+    pub const TRASH: &'static str = "TRASH";
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct LabelList {
+    labels: Vec<GmailLabel>,
+}
+
+impl Gmail {
+    /// Every label in the mailbox, system and user. Message counts aren't filled in here,
+    /// fetch a single label with `get_label` for those.
+    pub fn list_labels(&self) -> Result<Vec<GmailLabel>, Error> {
+        let list: LabelList = self
+            .request(Method::GET, &["labels"])?
+            .send()?
+            .check_status()?
+            .json()?;
+        Ok(list.labels)
+    }
+
+    pub fn get_label(&self, label_id: &str) -> Result<GmailLabel, Error> {
+        Ok(self
+            .request(Method::GET, &["labels", label_id])?
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    /// Find a label by its name, ignoring case.
+    pub fn find_label(&self, name: &str) -> Result<Option<GmailLabel>, Error> {
+        Ok(self
+            .list_labels()?
+            .into_iter()
+            .find(|l| l.name.eq_ignore_ascii_case(name)))
+    }
+
+    /// Create a user label. Nest it under another with a `/` in the name, e.g.
+    /// `Reports/Nightly`.
+    pub fn create_label(&self, name: &str) -> Result<GmailLabel, Error> {
+        Ok(self
+            .request(Method::POST, &["labels"])?
+            .json(&serde_json::json!({
+                "name": name,
+                "labelListVisibility": "labelShow",
+                "messageListVisibility": "show",
+            }))
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    /// Delete a user label. Messages keep everything but the label.
+    pub fn delete_label(&self, label_id: &str) -> Result<(), Error> {
+        self.request(Method::DELETE, &["labels", label_id])?
+            .send()?
+            .check_status()?;
+        Ok(())
+    }
+
+    /// Add and remove labels on a message, by label id.
+    pub fn modify_message(
+        &self,
+        message_id: &str,
+        add: &[&str],
+        remove: &[&str],
+    ) -> Result<MessageRef, Error> {
+        Ok(self
+            .request(Method::POST, &["messages", message_id, "modify"])?
+            .json(&serde_json::json!({ "addLabelIds": add, "removeLabelIds": remove }))
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    /// Add and remove labels on many messages, a thousand per request.
+    pub fn modify_messages(
+        &self,
+        message_ids: &[&str],
+        add: &[&str],
+        remove: &[&str],
+    ) -> Result<(), Error> {
+        for ids in message_ids.chunks(BATCH_MODIFY_LIMIT) {
+            self.request(Method::POST, &["messages", "batchModify"])?
+                .json(&serde_json::json!({
+                    "ids": ids,
+                    "addLabelIds": add,
+                    "removeLabelIds": remove,
+                }))
+                .send()?
+                .check_status()?;
+        }
+        Ok(())
+    }
+
+    /// Take a message out of the inbox. It stays under All Mail and its other labels.
+    pub fn archive(&self, message_id: &str) -> Result<MessageRef, Error> {
+        self.modify_message(message_id, &[], &[GmailLabel::INBOX])
+    }
+
+    pub fn mark_read(&self, message_id: &str) -> Result<MessageRef, Error> {
+        self.modify_message(message_id, &[], &[GmailLabel::UNREAD])
+    }
+
+    pub fn mark_unread(&self, message_id: &str) -> Result<MessageRef, Error> {
+        self.modify_message(message_id, &[GmailLabel::UNREAD], &[])
+    }
+
+    pub fn set_message_starred(
+        &self,
+        message_id: &str,
+        starred: bool,
+    ) -> Result<MessageRef, Error> {
+        if starred {
+            self.modify_message(message_id, &[GmailLabel::STARRED], &[])
+        } else {
+            self.modify_message(message_id, &[], &[GmailLabel::STARRED])
+        }
+    }
+
+    /// Move a message to the trash, where it's deleted after 30 days.
+    pub fn trash_message(&self, message_id: &str) -> Result<MessageRef, Error> {
+        Ok(self
+            .request(Method::POST, &["messages", message_id, "trash"])?
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    pub fn untrash_message(&self, message_id: &str) -> Result<MessageRef, Error> {
+        Ok(self
+            .request(Method::POST, &["messages", message_id, "untrash"])?
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+}
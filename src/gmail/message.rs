@@ -0,0 +1,320 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use reqwest::Method;
+use serde::Deserialize;
+
+use super::read::GmailMessage;
+use super::Gmail;
+use crate::error::{CheckStatus, Error};
+use crate::mime::MimeType;
+use crate::models::ExtraFields;
+
+/// Base64 lines in MIME bodies are kept to this length.
+const LINE_LENGTH: usize = 76;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Attachment {
+    File(PathBuf),
+    Data {
+        name: String,
+        mime_type: String,
+        content: Vec<u8>,
+    },
+}
+
+/// An email to send, built up with chained setters. Give it a plain text body, an HTML body,
+/// or both (clients show the HTML and fall back on the text).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Message {
+    from: Option<String>,
+    to: Vec<String>,
+    cc: Vec<String>,
+    bcc: Vec<String>,
+    subject: String,
+    text: Option<String>,
+    html: Option<String>,
+    attachments: Vec<Attachment>,
+    thread_id: Option<String>,
+    in_reply_to: Option<String>,
+    references: Vec<String>,
+}
+
+impl Message {
+    pub fn new() -> Message {
+        Message::default()
+    }
+
+    /// Sender, e.g. `Reports <reports@example.com>`. Defaults to the account's address, and
+    /// has to be that or one of its configured send-as aliases.
+    pub fn from(mut self, address: &str) -> Message {
+        self.from = Some(address.to_string());
+        self
+    }
+
+    /// Add a recipient. Can be given more than once.
+    pub fn to(mut self, address: &str) -> Message {
+        self.to.push(address.to_string());
+        self
+    }
+
+    pub fn cc(mut self, address: &str) -> Message {
+        self.cc.push(address.to_string());
+        self
+    }
+
+    pub fn bcc(mut self, address: &str) -> Message {
+        self.bcc.push(address.to_string());
+        self
+    }
+
+    pub fn subject(mut self, subject: &str) -> Message {
+        self.subject = subject.to_string();
+        self
+    }
+
+    /// Plain text body.
+    pub fn text(mut self, body: &str) -> Message {
+        self.text = Some(body.to_string());
+        self
+    }
+
+    /// HTML body.
+    pub fn html(mut self, body: &str) -> Message {
+        self.html = Some(body.to_string());
+        self
+    }
+
+    /// Attach the file at given path, read when the message is sent. The MIME type is
+    /// guessed from the extension.
+    pub fn attach_file(mut self, path: impl AsRef<Path>) -> Message {
+        self.attachments
+            .push(Attachment::File(path.as_ref().to_path_buf()));
+        self
+    }
+
+    /// Attach content from memory under given file name.
+    pub fn attach(mut self, name: &str, mime_type: &str, content: Vec<u8>) -> Message {
+        self.attachments.push(Attachment::Data {
+            name: name.to_string(),
+            mime_type: mime_type.to_string(),
+            content,
+        });
+        self
+    }
+
+    /// A reply to given message (fetched with `MessageFormat::Full` or `Metadata`), addressed
+    /// to its sender and threaded under it, with the subject prefixed by `Re:`. Add a body
+    /// and send it, or save it as a draft.
+    pub fn reply_to(original: &GmailMessage) -> Message {
+        let recipient = original
+            .header("Reply-To")
+            .or_else(|| original.from())
+            .unwrap_or_default();
+        let subject = original.subject().unwrap_or_default();
+        let already_reply = subject
+            .get(..3)
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case("re:"));
+        let subject = if already_reply {
+            subject.to_string()
+        } else {
+            format!("Re: {}", subject)
+        };
+        let mut reply = Message::new()
+            .to(recipient)
+            .subject(&subject)
+            .thread(&original.thread_id);
+        if let Some(message_id) = original.header("Message-ID") {
+            reply.references = original
+                .header("References")
+                .map(|r| r.split_whitespace().map(String::from).collect())
+                .unwrap_or_default();
+            reply.references.push(message_id.to_string());
+            reply.in_reply_to = Some(message_id.to_string());
+        }
+        reply
+    }
+
+    /// Gmail thread to put the message in. Gmail only threads it there if the subject
+    /// matches and it references a message in the thread, `reply_to` sets all three.
+    pub fn thread(mut self, thread_id: &str) -> Message {
+        self.thread_id = Some(thread_id.to_string());
+        self
+    }
+
+    /// `Message-ID` header of the message this one answers, for `In-Reply-To`.
+    pub fn in_reply_to(mut self, message_id: &str) -> Message {
+        self.in_reply_to = Some(message_id.to_string());
+        self
+    }
+
+    /// Add a `Message-ID` to the `References` header. Can be given more than once.
+    pub fn reference(mut self, message_id: &str) -> Message {
+        self.references.push(message_id.to_string());
+        self
+    }
+
+    /// The message as the API takes it: base64url encoded, with its thread if it has one.
+    pub(crate) fn to_json(&self) -> Result<serde_json::Value, std::io::Error> {
+        let raw = base64::encode_config(self.to_rfc2822()?, base64::URL_SAFE);
+        let mut json = serde_json::json!({ "raw": raw });
+        if let Some(thread_id) = &self.thread_id {
+            json["threadId"] = serde_json::json!(thread_id);
+        }
+        Ok(json)
+    }
+
+    /// The message in RFC 2822 format, reading any attached files.
+    pub fn to_rfc2822(&self) -> Result<Vec<u8>, std::io::Error> {
+        let mut out = String::new();
+        if let Some(from) = &self.from {
+            header(&mut out, "From", from);
+        }
+        for (name, addresses) in &[("To", &self.to), ("Cc", &self.cc), ("Bcc", &self.bcc)] {
+            if !addresses.is_empty() {
+                header(&mut out, name, &addresses.join(", "));
+            }
+        }
+        header(&mut out, "Subject", &encode_word(&self.subject));
+        if let Some(message_id) = &self.in_reply_to {
+            header(&mut out, "In-Reply-To", message_id);
+        }
+        if !self.references.is_empty() {
+            header(&mut out, "References", &self.references.join(" "));
+        }
+        header(&mut out, "MIME-Version", "1.0");
+
+        if self.attachments.is_empty() {
+            out.push_str(&self.body_part());
+            return Ok(out.into_bytes());
+        }
+        let mixed = boundary("mixed");
+        header(
+            &mut out,
+            "Content-Type",
+            &format!("multipart/mixed; boundary=\"{}\"", mixed),
+        );
+        out.push_str(&format!("\r\n--{}\r\n{}", mixed, self.body_part()));
+        for attachment in &self.attachments {
+            let (name, mime_type, content) = match attachment {
+                Attachment::File(path) => {
+                    let name = path
+                        .file_name()
+                        .and_then(|n| n.to_str())
+                        .unwrap_or("attachment")
+                        .to_string();
+                    (name, MimeType::from_path(path).to_string(), fs::read(path)?)
+                }
+                Attachment::Data {
+                    name,
+                    mime_type,
+                    content,
+                } => (name.clone(), mime_type.clone(), content.clone()),
+            };
+            let name = encode_word(&name).replace('"', "");
+            out.push_str(&format!("\r\n--{}\r\n", mixed));
+            header(
+                &mut out,
+                "Content-Type",
+                &format!("{}; name=\"{}\"", mime_type, name),
+            );
+            header(
+                &mut out,
+                "Content-Disposition",
+                &format!("attachment; filename=\"{}\"", name),
+            );
+            header(&mut out, "Content-Transfer-Encoding", "base64");
+            out.push_str("\r\n");
+            out.push_str(&base64_lines(&content));
+        }
+        out.push_str(&format!("\r\n--{}--\r\n", mixed));
+        Ok(out.into_bytes())
+    }
+
+    /// Headers and content of the body: one part, or text and HTML as alternatives.
+    fn body_part(&self) -> String {
+        let text = self.text.as_deref();
+        let html = self.html.as_deref();
+        match (text, html) {
+            (Some(text), Some(html)) => {
+                let alternative = boundary("alt");
+                format!(
+                    "Content-Type: multipart/alternative; boundary=\"{b}\"\r\n\r\n--{b}\r\n{}\r\n--{b}\r\n{}\r\n--{b}--\r\n",
+                    text_part("plain", text),
+                    text_part("html", html),
+                    b = alternative
+                )
+            }
+            (None, Some(html)) => text_part("html", html),
+            (text, None) => text_part("plain", text.unwrap_or_default()),
+        }
+    }
+}
+
+/// A message as Gmail identifies it.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct MessageRef {
+    pub id: String,
+    pub thread_id: String,
+    pub label_ids: Vec<String>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+/// Append a header line, dropping line breaks so a value can't inject headers of its own.
+fn header(out: &mut String, name: &str, value: &str) {
+    let value: String = value.chars().filter(|c| *c != '\r' && *c != '\n').collect();
+    out.push_str(&format!("{}: {}\r\n", name, value));
+}
+
+/// RFC 2047 encoded word for header values that aren't plain ASCII.
+fn encode_word(value: &str) -> String {
+    if value.is_ascii() {
+        value.to_string()
+    } else {
+        format!("=?UTF-8?B?{}?=", base64::encode(value))
+    }
+}
+
+/// A UTF-8 text part of given subtype, base64 encoded so any content survives transport.
+fn text_part(subtype: &str, body: &str) -> String {
+    format!(
+        "Content-Type: text/{}; charset=\"UTF-8\"\r\nContent-Transfer-Encoding: base64\r\n\r\n{}",
+        subtype,
+        base64_lines(body.as_bytes())
+    )
+}
+
+/// Base64 broken into lines of the length MIME allows.
+fn base64_lines(content: &[u8]) -> String {
+    let encoded = base64::encode(content);
+    let mut out = String::with_capacity(encoded.len() + encoded.len() / LINE_LENGTH * 2 + 2);
+    for line in encoded.as_bytes().chunks(LINE_LENGTH) {
+        out.push_str(&String::from_utf8_lossy(line));
+        out.push_str("\r\n");
+    }
+    out
+}
+
+/// A MIME boundary that won't appear in base64 content.
+fn boundary(kind: &str) -> String {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+    format!("=_{}_{:x}", kind, nanos)
+}
+
+impl Gmail {
+    /// Send a message from the user's account. It lands in their Sent folder like any other.
+    pub fn send(&self, message: &Message) -> Result<MessageRef, Error> {
+        Ok(self
+            .request(Method::POST, &["messages", "send"])?
+            .json(&message.to_json()?)
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+}
@@ -0,0 +1,74 @@
+use reqwest::blocking::RequestBuilder;
+use reqwest::Method;
+
+use crate::api;
+use crate::auth::Auth;
+use crate::error::Error;
+use crate::scope::Scope;
+
+mod drafts;
+mod history;
+mod labels;
+mod message;
+mod read;
+mod threads;
+
+pub use drafts::GmailDraft;
+pub use history::{
+    GmailHistory, GmailNotification, GmailWatch, HistoryLabels, HistoryMessage, HistoryRecord,
+};
+pub use labels::{GmailLabel, LabelColor};
+pub use message::{Message, MessageRef};
+pub use read::{GmailMessage, MessageFormat, MessageHeader, MessagePart, MessagePartBody};
+pub use threads::GmailThread;
+
+const GMAIL_BASE_URL: &str = "https://gmail.googleapis.com/gmail/v1/users/me";
+
+/// Decode Gmail's base64url data, which may or may not be padded.
+fn decode_base64url(data: &str) -> Option<Vec<u8>> {
+    base64::decode_config(data.trim_end_matches('='), base64::URL_SAFE_NO_PAD).ok()
+}
+
+/// Gmail client for the authenticated user's mailbox. Shares its saved token with the other
+/// clients.
+///
+/// ```no_run
+/// # use google_api::*;
+/// # let (client_id, client_secret) = (String::new(), String::new());
+/// let gmail = Gmail::new(client_id, client_secret, None)?;
+/// let report = Message::new()
+///     .to("ops@example.com")
+///     .subject("Nightly report")
+///     .html("<h1>All good</h1>")
+///     .attach_file("report.pdf");
+/// gmail.send(&report)?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug)]
+pub struct Gmail {
+    auth: Auth,
+    client: reqwest::blocking::Client,
+}
+
+impl Gmail {
+    /// Authenticate with given OAuth client. `scopes` defaults to `Scope::GmailModify`, pass
+    /// `Scope::GmailSend` for an app that only sends.
+    pub fn new(
+        client_id: String,
+        client_secret: String,
+        scopes: Option<Vec<String>>,
+    ) -> Result<Gmail, Error> {
+        let scopes = scopes.unwrap_or_else(|| vec![Scope::GmailModify.into()]);
+
+        Ok(Gmail {
+            auth: Auth::from_config(&client_id, &client_secret, &scopes)?,
+            client: reqwest::blocking::Client::new(),
+        })
+    }
+
+    /// Authorized request for the endpoint below `/users/me` made of given path segments,
+    /// e.g. `["messages", id]`.
+    fn request(&self, method: Method, segments: &[&str]) -> Result<RequestBuilder, Error> {
+        api::request(&self.client, &self.auth, GMAIL_BASE_URL, method, segments)
+    }
+}
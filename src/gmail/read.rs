@@ -0,0 +1,278 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use reqwest::Method;
+use serde::Deserialize;
+
+use super::message::MessageRef;
+use super::{decode_base64url, Gmail};
+use crate::error::{CheckStatus, Error};
+use crate::models::{de_int64, ExtraFields};
+
+/// How much of a message `Gmail::get_message` fetches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageFormat {
+    /// Headers and the decoded body parts. Attachments only by id.
+    Full,
+    /// Headers only.
+    Metadata,
+    /// Ids, labels and snippet.
+    Minimal,
+    /// The whole RFC 2822 message in `raw`.
+    Raw,
+}
+
+impl MessageFormat {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            MessageFormat::Full => "full",
+            MessageFormat::Metadata => "metadata",
+            MessageFormat::Minimal => "minimal",
+            MessageFormat::Raw => "raw",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct MessageHeader {
+    pub name: String,
+    pub value: String,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+/// Content of a message part. Small content comes inline in `data`, anything else has an
+/// `attachment_id` to fetch it with.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct MessagePartBody {
+    pub attachment_id: Option<String>,
+    pub size: u64,
+    /// Base64url encoded content.
+    pub data: Option<String>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+/// One part of a message's MIME tree.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct MessagePart {
+    pub part_id: String,
+    pub mime_type: String,
+    /// Set for attachments.
+    pub filename: String,
+    pub headers: Vec<MessageHeader>,
+    pub body: MessagePartBody,
+    pub parts: Vec<MessagePart>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl MessagePart {
+    /// Value of the first header with given name, ignoring case.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|h| h.name.eq_ignore_ascii_case(name))
+            .map(|h| h.value.as_str())
+    }
+
+    /// This part and every part below it, depth first.
+    pub fn walk(&self) -> Vec<&MessagePart> {
+        let mut parts = vec![self];
+        for part in &self.parts {
+            parts.extend(part.walk());
+        }
+        parts
+    }
+
+    /// Inline content, decoded. `None` when the content has to be fetched as an attachment.
+    pub fn data(&self) -> Option<Vec<u8>> {
+        self.body.data.as_deref().and_then(decode_base64url)
+    }
+}
+
+/// A message in the user's mailbox. What's filled in depends on the `MessageFormat` it was
+/// fetched with.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct GmailMessage {
+    pub id: String,
+    pub thread_id: String,
+    pub label_ids: Vec<String>,
+    /// Short plain text excerpt of the body.
+    pub snippet: String,
+    pub history_id: String,
+    /// When Gmail received the message, in milliseconds since the epoch.
+    #[serde(deserialize_with = "de_int64")]
+    pub internal_date: Option<u64>,
+    pub size_estimate: u64,
+    pub payload: MessagePart,
+    /// Base64url encoded RFC 2822 message, with `MessageFormat::Raw`.
+    pub raw: Option<String>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl GmailMessage {
+    /// Value of a top-level header, ignoring case.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.payload.header(name)
+    }
+
+    pub fn subject(&self) -> Option<&str> {
+        self.header("Subject")
+    }
+
+    pub fn from(&self) -> Option<&str> {
+        self.header("From")
+    }
+
+    pub fn to(&self) -> Option<&str> {
+        self.header("To")
+    }
+
+    pub fn date(&self) -> Option<&str> {
+        self.header("Date")
+    }
+
+    /// The first plain text body part, decoded.
+    pub fn text_body(&self) -> Option<String> {
+        self.body_of_type("text/plain")
+    }
+
+    /// The first HTML body part, decoded.
+    pub fn html_body(&self) -> Option<String> {
+        self.body_of_type("text/html")
+    }
+
+    fn body_of_type(&self, mime_type: &str) -> Option<String> {
+        self.payload
+            .walk()
+            .into_iter()
+            .filter(|p| p.mime_type == mime_type && p.filename.is_empty())
+            .find_map(|p| p.data())
+            .map(|data| String::from_utf8_lossy(&data).into_owned())
+    }
+
+    /// Parts that are attachments, i.e. have a file name.
+    pub fn attachments(&self) -> Vec<&MessagePart> {
+        self.payload
+            .walk()
+            .into_iter()
+            .filter(|p| !p.filename.is_empty())
+            .collect()
+    }
+
+    /// The decoded RFC 2822 message, with `MessageFormat::Raw`.
+    pub fn raw_bytes(&self) -> Option<Vec<u8>> {
+        self.raw.as_deref().and_then(decode_base64url)
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct MessageList {
+    messages: Vec<MessageRef>,
+    next_page_token: Option<String>,
+}
+
+impl Gmail {
+    /// Messages matching a Gmail search query (`from:alice has:attachment newer_than:7d`,
+    /// empty for everything), newest first. Every page is fetched, so narrow the query on
+    /// big mailboxes. Spam and trash are left out unless the query asks for them.
+    pub fn list_messages(&self, query: &str) -> Result<Vec<MessageRef>, Error> {
+        let mut messages = vec![];
+        let mut page_token: Option<String> = None;
+        loop {
+            let mut params = vec![("maxResults", "500")];
+            if !query.is_empty() {
+                params.push(("q", query));
+            }
+            if let Some(token) = &page_token {
+                params.push(("pageToken", token));
+            }
+            let page: MessageList = self
+                .request(Method::GET, &["messages"])?
+                .query(&params)
+                .send()?
+                .check_status()?
+                .json()?;
+            messages.extend(page.messages);
+
+            match page.next_page_token {
+                Some(token) => page_token = Some(token),
+                None => break,
+            }
+        }
+        Ok(messages)
+    }
+
+    /// Fetch a message in given format.
+    pub fn get_message(
+        &self,
+        message_id: &str,
+        format: MessageFormat,
+    ) -> Result<GmailMessage, Error> {
+        Ok(self
+            .request(Method::GET, &["messages", message_id])?
+            .query(&[("format", format.as_str())])
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    /// Search with a Gmail query and fetch every match's headers and body.
+    pub fn search(&self, query: &str) -> Result<Vec<GmailMessage>, Error> {
+        self.list_messages(query)?
+            .iter()
+            .map(|m| self.get_message(&m.id, MessageFormat::Full))
+            .collect()
+    }
+
+    /// Fetch an attachment's content.
+    pub fn get_attachment(&self, message_id: &str, attachment_id: &str) -> Result<Vec<u8>, Error> {
+        let body: MessagePartBody = self
+            .request(
+                Method::GET,
+                &["messages", message_id, "attachments", attachment_id],
+            )?
+            .send()?
+            .check_status()?
+            .json()?;
+        body.data
+            .as_deref()
+            .and_then(decode_base64url)
+            .ok_or_else(|| Error::UnexpectedResponse(String::from("attachment had no data")))
+    }
+
+    /// Save an attachment (one of `GmailMessage::attachments`) into given directory under its
+    /// file name. Returns the path written.
+    pub fn save_attachment(
+        &self,
+        message_id: &str,
+        part: &MessagePart,
+        dir: &Path,
+    ) -> Result<PathBuf, Error> {
+        let content = match (part.data(), &part.body.attachment_id) {
+            (Some(data), _) => data,
+            (None, Some(attachment_id)) => self.get_attachment(message_id, attachment_id)?,
+            (None, None) => {
+                return Err(Error::UnexpectedResponse(format!(
+                    "attachment {} has no content",
+                    part.filename
+                )))
+            }
+        };
+        // Only the last path component, a crafted file name mustn't write outside `dir`.
+        let name = Path::new(&part.filename)
+            .file_name()
+            .map(|n| n.to_os_string())
+            .unwrap_or_else(|| "attachment".into());
+        let path = dir.join(name);
+        fs::write(&path, content)?;
+        Ok(path)
+    }
+}
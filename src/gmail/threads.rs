@@ -0,0 +1,100 @@
+use reqwest::Method;
+use serde::Deserialize;
+
+use super::read::{GmailMessage, MessageFormat};
+use super::Gmail;
+use crate::error::{CheckStatus, Error};
+use crate::models::ExtraFields;
+
+/// A conversation. Listing only fills in the id, snippet and history id, `get_thread` adds
+/// the messages, oldest first.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct GmailThread {
+    pub id: String,
+    pub snippet: String,
+    pub history_id: String,
+    pub messages: Vec<GmailMessage>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct ThreadList {
+    threads: Vec<GmailThread>,
+    next_page_token: Option<String>,
+}
+
+impl Gmail {
+    /// Threads with a message matching a Gmail search query, empty for every thread. Every
+    /// page is fetched.
+    pub fn list_threads(&self, query: &str) -> Result<Vec<GmailThread>, Error> {
+        let mut threads = vec![];
+        let mut page_token: Option<String> = None;
+        loop {
+            let mut params = vec![("maxResults", "500")];
+            if !query.is_empty() {
+                params.push(("q", query));
+            }
+            if let Some(token) = &page_token {
+                params.push(("pageToken", token));
+            }
+            let page: ThreadList = self
+                .request(Method::GET, &["threads"])?
+                .query(&params)
+                .send()?
+                .check_status()?
+                .json()?;
+            threads.extend(page.threads);
+
+            match page.next_page_token {
+                Some(token) => page_token = Some(token),
+                None => break,
+            }
+        }
+        Ok(threads)
+    }
+
+    /// Fetch a thread with its messages in given format.
+    pub fn get_thread(&self, thread_id: &str, format: MessageFormat) -> Result<GmailThread, Error> {
+        Ok(self
+            .request(Method::GET, &["threads", thread_id])?
+            .query(&[("format", format.as_str())])
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    /// Add and remove labels on every message in a thread.
+    pub fn modify_thread(
+        &self,
+        thread_id: &str,
+        add: &[&str],
+        remove: &[&str],
+    ) -> Result<GmailThread, Error> {
+        Ok(self
+            .request(Method::POST, &["threads", thread_id, "modify"])?
+            .json(&serde_json::json!({ "addLabelIds": add, "removeLabelIds": remove }))
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    /// Move a whole thread to the trash.
+    pub fn trash_thread(&self, thread_id: &str) -> Result<GmailThread, Error> {
+        Ok(self
+            .request(Method::POST, &["threads", thread_id, "trash"])?
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    pub fn untrash_thread(&self, thread_id: &str) -> Result<GmailThread, Error> {
+        Ok(self
+            .request(Method::POST, &["threads", thread_id, "untrash"])?
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+}
@@ -0,0 +1,244 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::error::Error;
+
+/// Match a name against a glob pattern supporting `*` (anything but `/`), `**` (anything)
+/// and `?` (one character). The same flavour ignore patterns use, exposed for filtering
+/// listings, e.g. `glob_match("*.pdf", &file.name)`.
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let p: Vec<char> = pattern.chars().collect();
+    let t: Vec<char> = text.chars().collect();
+    matches(&p, &t)
+}
+
+fn matches(p: &[char], t: &[char]) -> bool {
+    match p.first() {
+        None => t.is_empty(),
+        Some('*') if p.get(1) == Some(&'*') => {
+            let rest = &p[2..];
+            // `**/` also matches zero directories.
+            if rest.first() == Some(&'/') && matches(&rest[1..], t) {
+                return true;
+            }
+            (0..=t.len()).any(|i| matches(rest, &t[i..]))
+        }
+        Some('*') => {
+            let rest = &p[1..];
+            for i in 0..=t.len() {
+                if matches(rest, &t[i..]) {
+                    return true;
+                }
+                if i < t.len() && t[i] == '/' {
+                    break;
+                }
+            }
+            false
+        }
+        Some('?') => !t.is_empty() && t[0] != '/' && matches(&p[1..], &t[1..]),
+        Some(c) => t.first() == Some(c) && matches(&p[1..], &t[1..]),
+    }
+}
+
+/// Name of the file listing paths to leave out of folder uploads and syncs, read from the
+/// top of the local directory.
+pub const DRIVE_IGNORE_FILE: &str = ".driveignore";
+
+/// Which paths folder uploads and syncs leave out, with `.gitignore` rules:
+///
+/// - `#` starts a comment, blank lines are skipped
+/// - a pattern without a `/` matches a file or folder name at any depth
+/// - a pattern with a `/` matches the path from the top, a leading `/` only anchors it
+/// - a trailing `/` only matches folders
+/// - `!` re-includes what an earlier pattern left out, except inside a left out folder
+///
+/// The last pattern matching a path decides.
+///
+/// ```
+/// # use std::path::Path;
+/// # use google_api::*;
+/// let rules = IgnoreRules::parse("target/\n*.log\n!important.log").exclude("node_modules");
+/// assert!(rules.is_ignored(Path::new("logs/debug.log"), false));
+/// assert!(!rules.is_ignored(Path::new("logs/important.log"), false));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IgnoreRules {
+    rules: Vec<Rule>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Rule {
+    pattern: String,
+    include: bool,
+    dir_only: bool,
+    /// Matched against the whole path instead of the name.
+    anchored: bool,
+}
+
+impl IgnoreRules {
+    pub fn new() -> IgnoreRules {
+        IgnoreRules::default()
+    }
+
+    /// Rules from the lines of an ignore file.
+    pub fn parse(text: &str) -> IgnoreRules {
+        text.lines().fold(IgnoreRules::new(), IgnoreRules::exclude)
+    }
+
+    /// Rules from `DRIVE_IGNORE_FILE` in given directory, none if it has no such file.
+    pub fn load(dir: &Path) -> Result<IgnoreRules, Error> {
+        match fs::read_to_string(dir.join(DRIVE_IGNORE_FILE)) {
+            Ok(text) => Ok(IgnoreRules::parse(&text)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(IgnoreRules::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Add a line of an ignore file, e.g. `target/`, `*.tmp` or `!keep.tmp`.
+    pub fn exclude(mut self, line: &str) -> IgnoreRules {
+        let line = line.trim_end();
+        if line.is_empty() || line.starts_with('#') {
+            return self;
+        }
+        let (include, pattern) = match line.strip_prefix('!') {
+            Some(pattern) => (true, pattern),
+            None => (false, line.strip_prefix('\\').unwrap_or(line)),
+        };
+        let dir_only = pattern.ends_with('/');
+        let pattern = pattern.trim_end_matches('/');
+        let anchored = pattern.contains('/');
+        self.rules.push(Rule {
+            pattern: pattern.trim_start_matches('/').to_string(),
+            include,
+            dir_only,
+            anchored,
+        });
+        self
+    }
+
+    /// Bring back paths matching given pattern, the same as `exclude("!pattern")`.
+    pub fn include(self, pattern: &str) -> IgnoreRules {
+        self.exclude(&format!("!{}", pattern))
+    }
+
+    /// Add another set of rules after these, so they win where both match.
+    pub fn extend(mut self, other: &IgnoreRules) -> IgnoreRules {
+        self.rules.extend(other.rules.iter().cloned());
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Whether given path, relative to the directory the rules are for, is left out. `is_dir`
+    /// says whether the path itself is a folder, for patterns ending in `/`.
+    pub fn is_ignored(&self, relative: &Path, is_dir: bool) -> bool {
+        if self.rules.is_empty() {
+            return false;
+        }
+        let path = relative.to_string_lossy().replace('\\', "/");
+        let components: Vec<&str> = path.split('/').filter(|c| !c.is_empty()).collect();
+        // Every folder on the way is checked first, as nothing inside a left out folder
+        // can be brought back.
+        for end in 1..=components.len() {
+            let last = end == components.len();
+            let prefix = components[..end].join("/");
+            if self.decide(&prefix, components[end - 1], !last || is_dir) {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn decide(&self, path: &str, name: &str, is_dir: bool) -> bool {
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| {
+                (is_dir || !rule.dir_only)
+                    && glob_match(&rule.pattern, if rule.anchored { path } else { name })
+            })
+            .is_some_and(|rule| !rule.include)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn globs() {
+        assert!(glob_match("*.pdf", "report.pdf"));
+        assert!(!glob_match("*.pdf", "docs/report.pdf"));
+        assert!(!glob_match("*.pdf", "report.pdf.bak"));
+        assert!(glob_match("docs/**/*.pdf", "docs/a/b/report.pdf"));
+        assert!(glob_match("docs/**/*.pdf", "docs/report.pdf"));
+        assert!(glob_match("**", "any/path/at/all"));
+        assert!(glob_match("file?.txt", "file1.txt"));
+        assert!(!glob_match("file?.txt", "file10.txt"));
+        assert!(!glob_match("a?b", "a/b"));
+        assert!(glob_match("", ""));
+        assert!(!glob_match("", "a"));
+    }
+
+    fn ignored(rules: &IgnoreRules, path: &str) -> bool {
+        rules.is_ignored(Path::new(path), path.ends_with('/'))
+    }
+
+    #[test]
+    fn names_match_at_any_depth_and_paths_from_the_top() {
+        let rules = IgnoreRules::parse("# build output\n\n*.log\n/build\ndocs/*.tmp\n");
+        assert!(ignored(&rules, "app.log"));
+        assert!(ignored(&rules, "src/deep/app.log"));
+        assert!(ignored(&rules, "build/"));
+        assert!(ignored(&rules, "build/out.bin"));
+        assert!(!ignored(&rules, "src/build/out.bin"));
+        assert!(ignored(&rules, "docs/a.tmp"));
+        assert!(!ignored(&rules, "src/docs/a.tmp"));
+        assert!(!ignored(&rules, "# build output"));
+    }
+
+    #[test]
+    fn trailing_slash_only_matches_folders() {
+        let rules = IgnoreRules::parse("cache/");
+        assert!(ignored(&rules, "cache/"));
+        assert!(ignored(&rules, "cache/data.bin"));
+        assert!(ignored(&rules, "src/cache/"));
+        assert!(!ignored(&rules, "cache"));
+    }
+
+    #[test]
+    fn last_matching_rule_wins() {
+        let rules = IgnoreRules::parse("*.log\n!important.log");
+        assert!(ignored(&rules, "debug.log"));
+        assert!(!ignored(&rules, "important.log"));
+
+        let rules = IgnoreRules::parse("!important.log\n*.log");
+        assert!(ignored(&rules, "important.log"));
+
+        let rules = IgnoreRules::parse("*.log").include("important.log");
+        assert!(!ignored(&rules, "logs/important.log"));
+
+        let rules = IgnoreRules::new()
+            .include("*.log")
+            .extend(&IgnoreRules::parse("*.log"));
+        assert!(ignored(&rules, "debug.log"));
+    }
+
+    #[test]
+    fn nothing_inside_an_ignored_folder_comes_back() {
+        let rules = IgnoreRules::parse("target/\n!target/keep.txt\n!keep.txt");
+        assert!(ignored(&rules, "target/keep.txt"));
+        assert!(!ignored(&rules, "keep.txt"));
+    }
+
+    #[test]
+    fn escaped_bang_is_literal() {
+        let rules = IgnoreRules::parse("\\!notes.txt");
+        assert!(ignored(&rules, "!notes.txt"));
+        assert!(!ignored(&rules, "notes.txt"));
+        assert!(!IgnoreRules::new().is_ignored(Path::new("anything"), false));
+    }
+}
@@ -0,0 +1,168 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{CheckStatus, Error};
+use crate::file_id::FileId;
+use crate::models::{ExtraFields, User};
+use crate::params::Params;
+use crate::Drive;
+
+/// A Drive label applied to a file.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Label {
+    pub id: String,
+    pub revision_id: String,
+    /// Field id -> value set on the file.
+    pub fields: HashMap<String, LabelField>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+/// Value of one field of an applied label. Only the list matching `value_type` is filled in.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct LabelField {
+    pub id: String,
+    /// One of "text", "selection", "integer", "dateString" or "user".
+    pub value_type: String,
+    pub text: Vec<String>,
+    pub selection: Vec<String>,
+    /// int64 values, sent as strings by Drive.
+    pub integer: Vec<String>,
+    /// Dates formatted as YYYY-MM-DD.
+    pub date_string: Vec<String>,
+    pub user: Vec<User>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+/// New value for a label field.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LabelFieldValue {
+    Text(Vec<String>),
+    /// Choice ids.
+    Selection(Vec<String>),
+    Integer(Vec<i64>),
+    /// Dates formatted as YYYY-MM-DD.
+    Date(Vec<String>),
+    /// Email addresses.
+    User(Vec<String>),
+    /// Clear the field.
+    Unset,
+}
+
+/// A change to one label on a file, see `Drive::modify_labels`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LabelModification {
+    label_id: String,
+    remove: bool,
+    fields: Vec<(String, LabelFieldValue)>,
+}
+
+impl LabelModification {
+    /// Apply given label, or update its fields if it's already applied.
+    pub fn apply(label_id: &str) -> LabelModification {
+        LabelModification {
+            label_id: label_id.to_string(),
+            remove: false,
+            fields: vec![],
+        }
+    }
+
+    /// Remove given label from the file.
+    pub fn remove(label_id: &str) -> LabelModification {
+        LabelModification {
+            label_id: label_id.to_string(),
+            remove: true,
+            fields: vec![],
+        }
+    }
+
+    /// Set a field of the label being applied.
+    pub fn field(mut self, field_id: &str, value: LabelFieldValue) -> LabelModification {
+        self.fields.push((field_id.to_string(), value));
+        self
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        if self.remove {
+            return serde_json::json!({"labelId": self.label_id, "removeLabel": true});
+        }
+        let fields: Vec<serde_json::Value> = self
+            .fields
+            .iter()
+            .map(|(id, value)| {
+                let mut m = serde_json::json!({ "fieldId": id });
+                match value {
+                    LabelFieldValue::Text(v) => m["setTextValues"] = serde_json::json!(v),
+                    LabelFieldValue::Selection(v) => m["setSelectionValues"] = serde_json::json!(v),
+                    LabelFieldValue::Integer(v) => {
+                        let v: Vec<String> = v.iter().map(|i| i.to_string()).collect();
+                        m["setIntegerValues"] = serde_json::json!(v)
+                    }
+                    LabelFieldValue::Date(v) => m["setDateValues"] = serde_json::json!(v),
+                    LabelFieldValue::User(v) => m["setUserValues"] = serde_json::json!(v),
+                    LabelFieldValue::Unset => m["unsetValues"] = serde_json::json!(true),
+                }
+                m
+            })
+            .collect();
+        serde_json::json!({"labelId": self.label_id, "fieldModifications": fields})
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct LabelList {
+    labels: Vec<Label>,
+    modified_labels: Vec<Label>,
+    next_page_token: Option<String>,
+}
+
+impl Drive {
+    /// List labels applied to given file.
+    pub fn list_labels(&self, file_id: impl Into<FileId>) -> Result<Vec<Label>, Error> {
+        let file_id = file_id.into();
+        let endpoint = format!("/files/{}/listLabels", file_id);
+        let mut labels = vec![];
+        let mut page_token: Option<String> = None;
+        loop {
+            let mut params = Params::new().param("maxResults", 100);
+            if let Some(token) = &page_token {
+                params = params.page_token(token);
+            }
+            let page: LabelList = self
+                .get(endpoint.as_str(), params)?
+                .check_status()?
+                .json()?;
+            labels.extend(page.labels);
+            match page.next_page_token {
+                Some(token) => page_token = Some(token),
+                None => return Ok(labels),
+            }
+        }
+    }
+
+    /// Apply, update or remove labels on given file. Returns the labels that were changed.
+    pub fn modify_labels(
+        &self,
+        file_id: impl Into<FileId>,
+        modifications: &[LabelModification],
+    ) -> Result<Vec<Label>, Error> {
+        let file_id = file_id.into();
+        let body = serde_json::json!({
+            "labelModifications": modifications.iter().map(|m| m.to_json()).collect::<Vec<_>>(),
+        });
+        let resp: LabelList = self
+            .post(
+                format!("/files/{}/modifyLabels", file_id).as_str(),
+                Params::new(),
+                body,
+            )?
+            .check_status()?
+            .json()?;
+        Ok(resp.modified_labels)
+    }
+}
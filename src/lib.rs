@@ -1,9 +1,10 @@
+use std::collections::HashMap;
 use std::env;
 
 use std::fs::{create_dir_all, File, OpenOptions};
 use std::io::prelude::*;
 use std::io::{BufReader, BufWriter};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::{Duration, SystemTime};
 
 use serde::{Deserialize, Serialize};
@@ -15,20 +16,198 @@ use log;
 
 use std::net::TcpListener;
 use std::sync::mpsc::channel;
+use std::sync::{Arc, Mutex};
 use std::thread;
 
+mod about;
+mod activity;
+mod api;
+mod appdata;
+mod auth;
+mod batch;
+mod builder;
+mod calendar;
+mod changes;
+mod chat;
+mod client;
+mod concurrency;
+mod directory;
+mod docs;
+mod download;
+mod drives;
+mod duplicates;
+mod error;
+mod export;
+mod file_id;
+mod folders;
+mod forms;
+mod gmail;
+mod ignore;
+mod labels;
+mod list;
+mod mime;
+mod models;
+mod orphans;
+mod params;
+mod paths;
+mod people;
+mod permissions;
+mod photos;
+pub mod prelude;
+mod profile;
+mod properties;
+mod pubsub;
+mod query;
+mod resumable;
+mod retry;
+mod scope;
+mod script;
+mod service_account;
+mod sheets;
+mod slides;
+mod storage;
+mod sync;
+mod tasks;
+mod timestamp;
+mod translate;
+mod tree;
+mod upload;
+mod watch;
+mod youtube;
+
+pub use about::{About, StorageQuota};
+pub use activity::{Activity, ActivityAction, ActivityQuery, ActivityTarget, Actor};
+pub use appdata::APP_DATA_FOLDER;
+pub use batch::BulkReport;
+pub use builder::DriveBuilder;
+pub use calendar::{
+    Attendee, BusyPeriod, Calendar, CalendarBusy, CalendarColors, CalendarListEntry,
+    CalendarNotification, ColorDefinition, ConferenceData, EntryPoint, Event, EventQuery,
+    EventTime, FreeBusy, FreeBusyError, Frequency, NotificationSettings, Recurrence,
+    ReminderOverride, Reminders, PRIMARY_CALENDAR,
+};
+pub use changes::{Change, ChangeCursor};
+pub use chat::{Card, Chat, ChatMessage, ChatThread, ChatWebhook, SentChatMessage, Widget};
+pub use client::GoogleClient;
+pub use concurrency::run_bounded;
+pub use directory::{Directory, DirectoryUser, Group, Member, MemberRole, UserName};
+pub use docs::{
+    Body, Dimension, DocRequest, Docs, Document, DocumentUpdateResponse, EmbeddedObject,
+    FilledTemplate, ImageProperties, InlineObject, InlineObjectElement, InlineObjectProperties,
+    Link, OptionalColor, Paragraph, ParagraphElement, ParagraphStyle, RgbColor,
+    StructuralElement, Table, TableCell, TableRow, TemplateOptions, TextRun, TextStyle,
+};
+pub use download::{local_file_name, DownloadOptions};
+pub use drives::SharedDrive;
+pub use duplicates::DuplicateSet;
+pub use error::Error;
+pub use export::ExportFormat;
+pub use file_id::FileId;
+pub use labels::{Label, LabelField, LabelFieldValue, LabelModification};
+pub use folders::{FolderUploadEvent, FolderUploadOptions, FolderUploadReport};
+pub use forms::{
+    Answer, ChoiceOption, ChoiceQuestion, ChoiceType, FormInfo, FormItem, FormRequest,
+    FormResponse, FormUpdateResponse, FormWatch, FormWatchEvent, Forms, GoogleForm, Question,
+    QuestionItem, ScaleQuestion, TextAnswer, TextAnswers, TextQuestion,
+};
+pub use gmail::{
+    Gmail, GmailDraft, GmailHistory, GmailLabel, GmailMessage, GmailNotification, GmailThread,
+    GmailWatch, HistoryLabels, HistoryMessage, HistoryRecord, LabelColor, Message,
+    MessageFormat, MessageHeader, MessagePart, MessagePartBody, MessageRef,
+};
+pub use ignore::{glob_match, IgnoreRules, DRIVE_IGNORE_FILE};
+pub use list::{Corpora, ListOptions};
+pub use mime::MimeType;
+pub use models::{DriveFile, ExtraFields, User};
+pub use params::Params;
+pub use people::{EmailAddress, Organization, People, Person, PersonName, PhoneNumber, Photo};
+pub use permissions::{
+    Grantee, OwnershipTransfer, Permission, Role, ShareOptions, ShareableLink,
+};
+pub use photos::{Album, MediaItem, MediaMetadata, MediaSearch, MediaType, Photos};
+pub use profile::{current_profile, profiles, switch_profile, DEFAULT_PROFILE, PROFILE_ENV};
+pub use properties::PropertyVisibility;
+pub use pubsub::{
+    PubSub, PubsubMessage, ReceivedMessage, Subscription, Topic, FORMS_PUBLISHER, GMAIL_PUBLISHER,
+};
+pub use query::Query;
+pub use resumable::ResumableUpload;
+pub use retry::RetryPolicy;
+pub use scope::Scope;
+pub use script::{AppsScript, ScriptError, ScriptRunError, ScriptStackFrame};
+pub use service_account::ServiceAccountKey;
+pub use sheets::{
+    a1_range, column_index, column_name, A1Range, AppendValuesResponse, BatchUpdateResponse,
+    CellFormat, Color, Condition, GridProperties, GridRange, HorizontalAlign, MajorDimension,
+    MergeType, NumberFormat, Sheet, SheetProperties, SheetRequest, Sheets, Spreadsheet,
+    SpreadsheetProperties, TextFormat, UpdateValuesResponse, ValueInputOption, ValueRange,
+};
+pub use slides::{
+    Image, PageElement, PageSize, Presentation, PresentationUpdateResponse, Shape, ShapeText,
+    Slide, SlideRequest, SlideTextRun, Slides, TextElement, Thumbnail, ThumbnailSize,
+};
+pub use storage::{Bucket, Object, ObjectList, ObjectUpload, Storage};
+pub use sync::{ConflictPolicy, SyncAction, SyncOptions, SyncReport, SYNC_STATE_FILE};
+pub use tasks::{Task, TaskList, Tasks};
+pub use timestamp::Timestamp;
+pub use translate::{DetectedLanguage, Language, Translate, Translation};
+pub use tree::{FolderTree, FolderUsage};
+pub use upload::{ConvertTo, IfExists, UploadOptions};
+pub use watch::{Channel, Notification, ResourceState, WatchTarget};
+pub use youtube::{
+    ChannelStatistics, Playlist, Video, VideoDetails, VideoPrivacy, VideoSnippet, VideoStatistics,
+    VideoStatus, YouTube, YouTubeChannel,
+};
+use auth::Auth;
+use error::{check_status, CheckStatus, Context};
+use retry::send_with_policy;
+use upload::UploadTarget;
 
 const GOOGLE_AUTH_URL: &str = "https://accounts.google.com/o/oauth2/v2/auth";
 const GOOGLE_TOKEN_URL: &str = "https://oauth2.googleapis.com/token";
 const DEFAULT_REDIRECT_URI: &str = "http://127.0.0.1:3000/";
 const DEFAULT_DRIVE_SCOPE: &str = "https://www.googleapis.com/auth/drive";
 const DRIVE_BASE_URL: &str = "https://www.googleapis.com/drive/v3";
-const CONFIG_PATH: &str = "/.config/cameron-williams/google_api";
-
-fn config_dir() -> PathBuf {
-    let mut dir = env::var("HOME").expect("$HOME not set");
+const DRIVE_UPLOAD_URL: &str = "https://www.googleapis.com/upload/drive/v3";
+const CONFIG_PATH: &str = "/.config/cameron-williams";
+
+/// Directory the saved tokens and profiles are kept in, `~/.config/cameron-williams`. Programs
+/// built on the crate can keep their own settings there too.
+pub fn config_dir() -> Result<PathBuf, Error> {
+    let mut dir = env::var("HOME").map_err(|_| {
+        std::io::Error::new(std::io::ErrorKind::NotFound, "$HOME not set")
+    })?;
     dir.push_str(CONFIG_PATH);
-    PathBuf::from(dir)
+    Ok(PathBuf::from(dir))
+}
+
+/// JSON of a token endpoint response, or the reason Google gave for refusing.
+fn token_response(resp: reqwest::blocking::Response) -> Result<serde_json::Value, String> {
+    let status = resp.status();
+    let body: serde_json::Value = resp
+        .json()
+        .map_err(|e| format!("invalid token response ({}): {}", status, e))?;
+    if let Some(error) = body["error"].as_str() {
+        return Err(match body["error_description"].as_str() {
+            Some(description) => format!("token request refused: {} ({})", error, description),
+            None => format!("token request refused: {}", error),
+        });
+    }
+    Ok(body)
+}
+
+fn token_field(resp: &serde_json::Value, field: &str) -> Result<String, String> {
+    resp[field]
+        .as_str()
+        .map(String::from)
+        .ok_or_else(|| format!("no {} in token response", field))
+}
+
+fn token_lifetime(resp: &serde_json::Value) -> Result<Duration, String> {
+    resp["expires_in"]
+        .as_u64()
+        .map(Duration::from_secs)
+        .ok_or_else(|| String::from("no expires_in in token response"))
 }
 
 /// Will be stored locally in .config or something. Google OAuth savedata.
@@ -39,6 +218,9 @@ struct GoogleOAuthToken {
     code: String,
     refresh_token: String,
     scope: Vec<String>,
+    /// File the token is kept in, the current profile's when `None`.
+    #[serde(skip)]
+    path: Option<PathBuf>,
 }
 
 impl GoogleOAuthToken {
@@ -49,13 +231,21 @@ impl GoogleOAuthToken {
             code: String::new(),
             refresh_token: String::new(),
             scope: Vec::new(),
+            path: None,
         }
     }
 
-    /// Try to get existing GoogleOAuthToken from config path. Or return a new one.
-    fn from_config(client_id: &String, client_secret: &String) -> Result<GoogleOAuthToken, String> {
-        let mut auth = Self::read_from_path()?;
-        auth.ensure_validity(client_id, client_secret)?;
+    /// Try to get existing GoogleOAuthToken from given file, or the current profile's. Or
+    /// return a new one.
+    fn from_config_at(
+        client_id: &String,
+        client_secret: &String,
+        scopes: &[String],
+        path: Option<PathBuf>,
+    ) -> Result<GoogleOAuthToken, Error> {
+        let mut auth = Self::read_from_path(path).map_err(Error::Auth)?;
+        auth.ensure_validity(client_id, client_secret, scopes)
+            .map_err(Error::Auth)?;
         Ok(auth)
     }
 
@@ -64,11 +254,22 @@ impl GoogleOAuthToken {
         &mut self,
         client_id: &String,
         client_secret: &String,
+        scopes: &[String],
     ) -> Result<(), String> {
-        // No code == we need to get user consent.
-        if self.code.is_empty() {
+        // No code == we need to get user consent. Same if the saved consent doesn't cover every
+        // scope asked for, in which case the old tokens are useless too.
+        if self.code.is_empty() || !scopes.iter().all(|s| self.scope.contains(s)) {
             log::debug!("no consent");
-            self.prompt_user_authentication(client_id)?;
+            // Keep asking for whatever was granted before, the saved token is shared by every
+            // client (Drive, Sheets, ...) and one shouldn't lose access to make room for another.
+            let mut wanted = self.scope.clone();
+            for scope in scopes {
+                if !wanted.contains(scope) {
+                    wanted.push(scope.clone());
+                }
+            }
+            self.refresh_token.clear();
+            self.prompt_user_authentication(client_id, &wanted)?;
         }
 
         // Check that we even have a valid token from user auth.
@@ -86,20 +287,24 @@ impl GoogleOAuthToken {
     }
 
     /// Prompt the user to approve the app's access for whatever scopes are currently required.
-    fn prompt_user_authentication(&mut self, client_id: &String) -> Result<(), String> {
-        // Build auth url (use default values for now).
+    fn prompt_user_authentication(
+        &mut self,
+        client_id: &String,
+        scopes: &[String],
+    ) -> Result<(), String> {
+        let scope = scopes.join(" ");
         let auth_url = Url::parse_with_params(
             GOOGLE_AUTH_URL,
             &[
                 ("client_id", client_id.as_str()),
                 ("redirect_uri", DEFAULT_REDIRECT_URI),
                 ("response_type", "code"),
-                ("scope", DEFAULT_DRIVE_SCOPE),
+                ("scope", scope.as_str()),
             ],
         )
-        .unwrap();
+        .map_err(|e| format!("failed to build google consent url: {}", e))?;
 
-        self.scope = vec![String::from(DEFAULT_DRIVE_SCOPE)];
+        self.scope = scopes.to_vec();
 
         // Open auth url in user's browser for their consent.
         if let Err(e) = open::that(auth_url.as_str()) {
@@ -112,18 +317,28 @@ impl GoogleOAuthToken {
         log::info!("This application needs your consent to use Google Drive. Please check your browser and either approve or deny it.");
 
         // Create a listener that waits until we get a response from the user's consent.
+        let listener = TcpListener::bind("127.0.0.1:3000")
+            .map_err(|e| format!("failed to listen for the consent redirect: {}", e))?;
         let (tx, rx) = channel();
         thread::spawn(move || {
-            let listener = TcpListener::bind("127.0.0.1:3000").unwrap();
+            // A connection that fails half way is skipped, the browser's next try may work.
             for stream in listener.incoming() {
-                let mut stream = stream.unwrap();
+                let mut stream = match stream {
+                    Ok(stream) => stream,
+                    Err(_) => continue,
+                };
                 let mut buffer = [0; 512];
-                stream.read(&mut buffer).unwrap();
-                stream
-                    .write("HTTP/1.1 200 OK\r\n\r\n<body><p>Authenticated.</p></body>".as_bytes())
-                    .unwrap();
-                stream.flush().unwrap();
-                tx.send(buffer).unwrap();
+                if stream.read(&mut buffer).is_err() {
+                    continue;
+                }
+                let reply = "HTTP/1.1 200 OK\r\n\r\n<body><p>Authenticated.</p></body>";
+                let _ = stream
+                    .write_all(reply.as_bytes())
+                    .and_then(|_| stream.flush());
+                // Nobody is waiting any more after a timeout.
+                if tx.send(buffer).is_err() {
+                    break;
+                }
             }
         });
 
@@ -136,8 +351,9 @@ impl GoogleOAuthToken {
                 // Discard first index of split body.
                 split.next();
                 // 2nd index is the response url which we need to get the code from.
-                base_url.push_str(split.next().unwrap());
-                Url::parse(&base_url).unwrap()
+                base_url.push_str(split.next().unwrap_or_default());
+                Url::parse(&base_url)
+                    .map_err(|e| format!("invalid consent redirect {}: {}", base_url, e))?
             }
             Err(e) => {
                 // error!("error getting auth: {}", e);
@@ -149,7 +365,10 @@ impl GoogleOAuthToken {
         };
 
         // Check if our response url is valid or err.
-        let status = response_url.query_pairs().next().unwrap();
+        let status = response_url
+            .query_pairs()
+            .find(|(key, _)| key == "code" || key == "error")
+            .ok_or_else(|| format!("no consent code in redirect {}", response_url))?;
         if status.0 == "error" {
             return Err(status.1.to_string());
         } else {
@@ -180,8 +399,8 @@ impl GoogleOAuthToken {
             .header("charset", "utf-8")
             .multipart(form)
             .send();
-        let resp: serde_json::Value = match resp {
-            Ok(r) => r.json().unwrap(),
+        let resp = match resp {
+            Ok(r) => token_response(r)?,
             Err(e) => {
                 return Err(format!(
                     "response error on new token request post: {:#?}",
@@ -190,10 +409,9 @@ impl GoogleOAuthToken {
             }
         };
         // Update self values from response.
-        self.refresh_token = String::from(resp["refresh_token"].as_str().unwrap());
-        self.token = String::from(resp["access_token"].as_str().unwrap());
-        self.expires =
-            SystemTime::now() + Duration::from_secs(resp["expires_in"].as_u64().unwrap());
+        self.refresh_token = token_field(&resp, "refresh_token")?;
+        self.token = token_field(&resp, "access_token")?;
+        self.expires = SystemTime::now() + token_lifetime(&resp)?;
 
         // Write current config to path.
         self.write_to_path()
@@ -216,25 +434,27 @@ impl GoogleOAuthToken {
             .header("charset", "utf-8")
             .multipart(form)
             .send();
-        let resp: serde_json::Value = match resp {
-            Ok(r) => r.json().unwrap(),
+        let resp = match resp {
+            Ok(r) => token_response(r)?,
             Err(e) => return Err(format!("response err on refresh token request: {:#?}", e)),
         };
 
         // Update token and expire values from response.
-        self.token = String::from(resp["access_token"].as_str().unwrap());
-        self.expires =
-            SystemTime::now() + Duration::from_secs(resp["expires_in"].as_u64().unwrap());
+        self.token = token_field(&resp, "access_token")?;
+        self.expires = SystemTime::now() + token_lifetime(&resp)?;
         // Write updated auth to config.
         self.write_to_path()
     }
 
-    /// Eventually allow for custom config path.
-    fn read_from_path() -> Result<GoogleOAuthToken, String> {
-        let path = config_dir();
+    /// Read the token in given file, or the current profile's, see `current_profile`.
+    fn read_from_path(custom: Option<PathBuf>) -> Result<GoogleOAuthToken, String> {
+        let path = match &custom {
+            Some(path) => path.clone(),
+            None => profile::token_path().map_err(|e| e.to_string())?,
+        };
         // Ensure config path exists. If it doesn't create it and return a blank GoogleOAuthToken.
         if !path.exists() {
-            match create_dir_all(path.parent().unwrap()) {
+            match create_dir_all(path.parent().unwrap_or(&path)) {
                 Ok(_) => {
                     if let Err(e) = File::create(&path) {
                         return Err(format!("failed to create new config file: {:#?}", e));
@@ -242,31 +462,37 @@ impl GoogleOAuthToken {
                 }
                 Err(e) => return Err(format!("failed to create config dir: {:#?}", e)),
             }
-            return Ok(GoogleOAuthToken::new());
+            return Ok(GoogleOAuthToken {
+                path: custom,
+                ..GoogleOAuthToken::new()
+            });
         }
         match OpenOptions::new()
             .read(true)
             .write(false)
-            .open(config_dir())
+            .open(&path)
         {
             Ok(f) => {
                 let reader = BufReader::new(f);
-                match serde_json::from_reader(reader) {
-                    Ok(d) => Ok(d),
-                    Err(_) => Ok(GoogleOAuthToken::new()),
-                }
+                let auth = serde_json::from_reader(reader).unwrap_or_else(|_| GoogleOAuthToken::new());
+                Ok(GoogleOAuthToken {
+                    path: custom,
+                    ..auth
+                })
             }
             Err(e) => Err(format!("error reading from config file: {:#?}", e)),
         }
     }
 
-    /// Write the current GoogleOAuthToken state to the config file.
-    /// Eventually add parameter for custom config path.
+    /// Write the current GoogleOAuthToken state to its token file.
     fn write_to_path(&self) -> Result<(), String> {
         // Ensure config path exists. If it doesn't create it.
-        let path = config_dir();
+        let path = match &self.path {
+            Some(path) => path.clone(),
+            None => profile::token_path().map_err(|e| e.to_string())?,
+        };
         if !path.exists() {
-            match create_dir_all(path.parent().unwrap()) {
+            match create_dir_all(path.parent().unwrap_or(&path)) {
                 Ok(_) => {
                     if let Err(e) = File::create(&path) {
                         return Err(format!("failed to create new config file: {:#?}", e));
@@ -298,206 +524,335 @@ impl GoogleOAuthToken {
     }
 }
 
-#[derive(Debug)]
+/// Google Drive client. Clones are cheap and share the token, the connection pool and the
+/// path cache, so hand one to each worker thread (or `spawn_blocking` task, the client
+/// blocks) rather than signing in again.
+#[derive(Debug, Clone)]
 pub struct Drive {
-    auth: GoogleOAuthToken,
+    auth: Auth,
     client: reqwest::blocking::Client,
+    /// Ids of folders and files found by `resolve_path`, keyed by anchor and path.
+    paths: Arc<Mutex<HashMap<String, FileId>>>,
+    retry: RetryPolicy,
+}
+
+/// `Drive` has to stay shareable between threads.
+#[allow(dead_code)]
+fn assert_drive_send_sync() {
+    fn shareable<T: Clone + Send + Sync>() {}
+    shareable::<Drive>();
 }
 
 impl Drive {
+    /// Authenticate with given OAuth client. `scopes` defaults to full Drive access, see
+    /// `Scope`. The user is asked for consent again if the saved token lacks any of them.
+    /// See `Drive::builder` for more options.
     pub fn new(
         client_id: String,
         client_secret: String,
         scopes: Option<Vec<String>>,
-    ) -> Result<Drive, String> {
-        // doesn't do anything currently, eventually allow manually specified scopes.
-        let _scopes: Vec<String> = if let None = scopes {
-            vec![String::from(DEFAULT_DRIVE_SCOPE)]
-        } else {
-            scopes.unwrap()
-        };
+    ) -> Result<Drive, Error> {
+        Drive::builder()
+            .client_id(client_id)
+            .client_secret(client_secret)
+            .scopes(scopes.unwrap_or_else(|| vec![String::from(DEFAULT_DRIVE_SCOPE)]))
+            .build()
+    }
 
-        Ok(Drive {
-            auth: GoogleOAuthToken::from_config(&client_id, &client_secret)?,
-            client: reqwest::blocking::Client::new(),
-        })
+    /// Configure a client step by step: token file, request timeout and retries.
+    pub fn builder() -> DriveBuilder {
+        DriveBuilder::new()
     }
 
-    pub fn get(
-        &self,
-        endpoint: &str,
-        params: Option<Vec<(&str, &str)>>,
-    ) -> Result<reqwest::blocking::Response, reqwest::Error> {
-        // Build url with optional params.
+    /// When the current access token expires. It's refreshed before a request once it's about
+    /// to, so this moves on as the client is used.
+    pub fn token_expires(&self) -> Timestamp {
+        Timestamp::from(self.auth.expires())
+    }
+
+    /// Build url for given endpoint with optional params. File and change endpoints always get
+    /// `supportsAllDrives` so they work on items in shared drives.
+    /// One that can't be parsed is returned as it is, for sending it to fail with the reason.
+    fn build_url(endpoint: &str, mut params: Params) -> String {
         let mut url = String::from(DRIVE_BASE_URL);
         url.push_str(endpoint);
-        let url = match params {
-            Some(p) => Url::parse_with_params(&url, p).unwrap(),
-            None => Url::parse(&url).unwrap(),
-        };
+        let shared = endpoint.starts_with("/files") || endpoint.starts_with("/changes");
+        if shared && params.get("supportsAllDrives").is_none() {
+            params = params.supports_all_drives(true);
+        }
+        match Url::parse_with_params(&url, params.iter()) {
+            Ok(parsed) => parsed.into(),
+            Err(_) => url,
+        }
+    }
 
-        self.client
-            .get(url)
-            .header("Authorization", format!("Bearer {}", &self.auth.token))
-            .send()
+    /// GET given endpoint. Rate limits and server errors are retried as the retry policy
+    /// says, any other error status comes back as `Error::Api`.
+    pub fn get(
+        &self,
+        endpoint: &str,
+        params: impl Into<Params>,
+    ) -> Result<reqwest::blocking::Response, Error> {
+        let url = Drive::build_url(endpoint, params.into());
+
+        send_with_policy(&self.retry, || -> Result<_, Error> {
+            Ok(self.client
+                .get(&url)
+                .header("Authorization", self.auth.bearer()?)
+                .send()?)
+        })
     }
 
+    /// POST given JSON to given endpoint, retried like `get`.
     pub fn post(
         &self,
         endpoint: &str,
-        params: Option<Vec<(&str, &str)>>,
+        params: impl Into<Params>,
         json: serde_json::Value,
-    ) -> Result<reqwest::blocking::Response, reqwest::Error> {
-        // Build url with optional params.
-        let mut url = String::from(DRIVE_BASE_URL);
-        url.push_str(endpoint);
-        let url = match params {
-            Some(p) => Url::parse_with_params(&url, p).unwrap(),
-            None => Url::parse(&url).unwrap(),
-        };
-
-        self.client
-            .post(url)
-            .header("Authorization", format!("Bearer {}", &self.auth.token))
-            .json(&json)
-            .send()
+    ) -> Result<reqwest::blocking::Response, Error> {
+        let url = Drive::build_url(endpoint, params.into());
+
+        send_with_policy(&self.retry, || -> Result<_, Error> {
+            Ok(self.client
+                .post(&url)
+                .header("Authorization", self.auth.bearer()?)
+                .json(&json)
+                .send()?)
+        })
     }
 
+    /// PATCH given endpoint with given JSON, retried like `get`.
     pub fn patch(
         &self,
         endpoint: &str,
-        params: Option<Vec<(&str, &str)>>,
+        params: impl Into<Params>,
         json: serde_json::Value,
-    ) -> Result<reqwest::blocking::Response, reqwest::Error> {
-        // Build url with optional params.
-        let mut url = String::from(DRIVE_BASE_URL);
-        url.push_str(endpoint);
-        let url = match params {
-            Some(p) => Url::parse_with_params(&url, p).unwrap(),
-            None => Url::parse(&url).unwrap(),
-        };
-
-        self.client
-            .patch(url)
-            .header("Authorization", format!("Bearer {}", &self.auth.token))
-            .json(&json)
-            .send()
+    ) -> Result<reqwest::blocking::Response, Error> {
+        let url = Drive::build_url(endpoint, params.into());
+
+        send_with_policy(&self.retry, || -> Result<_, Error> {
+            Ok(self.client
+                .patch(&url)
+                .header("Authorization", self.auth.bearer()?)
+                .json(&json)
+                .send()?)
+        })
     }
 
+    /// DELETE given endpoint, retried like `get`.
     pub fn delete(
         &self,
         endpoint: &str,
-        params: Option<Vec<(&str, &str)>>,
-    ) -> Result<reqwest::blocking::Response, reqwest::Error> {
-        // Build url with optional params.
-        let mut url = String::from(DRIVE_BASE_URL);
-        url.push_str(endpoint);
-        let url = match params {
-            Some(p) => Url::parse_with_params(&url, p).unwrap(),
-            None => Url::parse(&url).unwrap(),
-        };
-
-        self.client
-            .delete(url)
-            .header("Authorization", format!("Bearer {}", &self.auth.token))
-            .send()
-    }
-
-    fn get_file_id_from_url(url: &str) -> Result<String, String> {
-        // Get file id from passed url.
-        let url = Url::parse(url).expect("invalid url format");
-        match url.query_pairs().next() {
-            Some(i) => Ok(String::from(i.1)),
-            None => Err(String::from("no url param specified")),
-        }
+        params: impl Into<Params>,
+    ) -> Result<reqwest::blocking::Response, Error> {
+        let url = Drive::build_url(endpoint, params.into());
+
+        send_with_policy(&self.retry, || -> Result<_, Error> {
+            Ok(self.client
+                .delete(&url)
+                .header("Authorization", self.auth.bearer()?)
+                .send()?)
+        })
     }
 
-    /// Get given file's metadata from Drive for given url.
-    pub fn file_metadata(&self, url: &str) -> Result<serde_json::Value, reqwest::Error> {
-        let id = Drive::get_file_id_from_url(url).expect("no id param in given url");
+    /// Get given file's metadata from Drive.
+    pub fn file_metadata(
+        &self,
+        file: impl Into<FileId>,
+    ) -> Result<serde_json::Value, Error> {
+        let id = file.into();
 
         let resp = self
-            .get(format!("/files/{}?fields=*", id).as_str(), None)?
+            .get(format!("/files/{}", id).as_str(), Params::new().fields("*"))?
+            .check_status()?
             .json()?;
 
         Ok(resp)
     }
 
-    /// Download file from given drive url to given path. Return the path the file was downloaded to.
-    pub fn download_file(&self, url: &str, path: PathBuf) -> Result<PathBuf, reqwest::Error> {
-        // Get file id from passed url.
-        let id = Drive::get_file_id_from_url(url).expect("no id param in given url");
+    /// Download given file to given path. Return the path the file was downloaded to.
+    pub fn download_file(
+        &self,
+        file: impl Into<FileId>,
+        path: PathBuf,
+    ) -> Result<PathBuf, Error> {
+        let id = file.into();
+        let downloading = || format!("downloading {}", id);
 
         // If path is a dir, get the name of the file we are downloading and add it to the path.
         let path = if path.is_dir() {
-            let v = self.file_metadata(url)?;
-            let mut p = PathBuf::from(path);
-            p.push(v["name"].as_str().unwrap());
-            p
+            let v = self.file_metadata(&id).with_context(downloading)?;
+            let name = v["name"].as_str().ok_or_else(|| {
+                Error::UnexpectedResponse(format!("no name in metadata of {}", id))
+            })?;
+            path.join(name)
         } else {path};
 
-        // Get the file from Drive and put to buffer.
-        let mut resp = self.get(
+        // Get the file from Drive and put to buffer. An error status leaves the path alone.
+        let resp = self.get(
             format!("/files/{}", id).as_str(),
-            Some(vec![("alt", "media")]),
+            Params::new().alt("media"),
         )?;
+        let mut resp = check_status(resp).with_context(downloading)?;
         let mut buf: Vec<u8> = vec![];
-        resp.copy_to(&mut buf)?;
+        resp.copy_to(&mut buf).with_context(downloading)?;
 
         // Write file locally.
-        let mut file = File::create(&path).unwrap();
-        file.write_all(&buf).expect("failed to write buf to file");
+        File::create(&path)
+            .and_then(|mut file| file.write_all(&buf))
+            .with_context(|| format!("writing {}", path.display()))?;
 
         Ok(path)
     }
 
-    /// Upload file at given path to Google Drive. Todo:// make it one request somehow?
-    pub fn upload_file(&self, path: &PathBuf) -> Result<String, reqwest::Error> {
-        // Google Drive file upload url has a different base url.
-        let url = "https://www.googleapis.com/upload/drive/v3/files?uploadType=multipart";
-        let file = File::open(&path).expect("failed to open file for upload");
-        let resp: serde_json::Value = self
-            .client
-            .post(url)
-            .header("Authorization", format!("Bearer {}", &self.auth.token))
-            .body(file)
-            .send()?
-            .json()?;
-        let file_id = resp["id"].as_str().unwrap().clone();
-        let url = format!("https://drive.google.com/open?id={}", file_id);
-        // Patch to update file name with one from given path.
-        self.patch(
-            format!("/files/{}", file_id).as_str(),
-            None,
-            serde_json::json!({"name": path.file_name().unwrap().to_str()}),
-        )?;
-        Ok(url)
+    /// Upload file at given path to Google Drive. Small files are sent in a single multipart
+    /// request, big ones with the resumable protocol one chunk in memory at a time. Returns the
+    /// new file, `web_view_link` has its url. When a file
+    /// of the same name is already there, `UploadOptions::if_exists` decides whether it's
+    /// overwritten, kept, or a new one created next to it.
+    pub fn upload_file(&self, path: &Path, options: &UploadOptions) -> Result<DriveFile, Error> {
+        let (file_id, name) = match self.upload_target(path, options)? {
+            UploadTarget::Existing(file) => return Ok(*file),
+            UploadTarget::Update(id) => (Some(id), None),
+            UploadTarget::Create(name) => (None, name),
+        };
+        let mut options = options.clone();
+        if let Some(name) = name {
+            options = options.name(&name);
+        }
+        self.send_file(file_id.as_deref(), path, &options)
+            .with_context(|| format!("uploading {}", path.display()))
     }
 
-    /// Update file at given drive url from local file path.reqwest
-    pub fn update_file(&self, path: PathBuf, url: &str) -> Result<(), reqwest::Error> {
-        // Google drive file update url has a different base url.
-        let file_id = Drive::get_file_id_from_url(url).unwrap();
-        let fmt_url = format!(
-            "https://www.googleapis.com/upload/drive/v3/files/{}?uploadType=multipart",
-            file_id
-        );
-        let file = File::open(&path).expect("failed to open local file for update");
-
-        self.client
-            .patch(fmt_url.as_str())
-            .header("Authorization", format!("Bearer {}", &self.auth.token))
-            .body(file)
-            .send()?;
-            // .json()?;
+    /// Update given file from local file path. Content and any metadata set in the options
+    /// are changed together, so either both apply or neither does. Big files are sent with the
+    /// resumable protocol. Returns the updated file.
+    pub fn update_file(
+        &self,
+        path: PathBuf,
+        file: impl Into<FileId>,
+        options: &UploadOptions,
+    ) -> Result<DriveFile, Error> {
+        let file_id = file.into();
+        self.send_file(Some(file_id.as_str()), &path, options)
+            .with_context(|| format!("updating {} from {}", file_id, path.display()))
+    }
+
+    /// Permanently delete given file, skipping the trash.
+    pub fn delete_file(&self, file: impl Into<FileId>) -> Result<(), Error> {
+        let file_id = file.into();
+        let endpoint = format!("/files/{}", file_id);
+        self.delete(endpoint.as_str(), Params::new())?.check_status()?;
         Ok(())
     }
 
-    /// Delete file at given drive url.
-    pub fn delete_file(&self, url: &str) -> Result<(), reqwest::Error> {
-        let file_id = Drive::get_file_id_from_url(url).unwrap();
+    /// Star or unstar given file.
+    pub fn set_starred(
+        &self,
+        file_id: impl Into<FileId>,
+        starred: bool,
+    ) -> Result<DriveFile, Error> {
+        let file_id = file_id.into();
+        Ok(self.patch(
+            format!("/files/{}", file_id).as_str(),
+            Params::new().fields(models::FILE_FIELDS),
+            serde_json::json!({ "starred": starred }),
+        )?
+        .check_status()?
+        .json()?)
+    }
+
+    /// Copy given file, Google Docs included. The copy is named `name` (Drive's "Copy of ..."
+    /// if `None`) and placed in `parent` (next to the original if `None`).
+    pub fn copy_file(
+        &self,
+        file_id: impl Into<FileId>,
+        name: Option<&str>,
+        parent: Option<FileId>,
+    ) -> Result<DriveFile, Error> {
+        let file_id = file_id.into();
+        let mut body = serde_json::json!({});
+        if let Some(name) = name {
+            body["name"] = serde_json::json!(name);
+        }
+        if let Some(parent) = parent {
+            body["parents"] = serde_json::json!([parent.to_string()]);
+        }
+        Ok(self.post(
+            format!("/files/{}/copy", file_id).as_str(),
+            Params::new().fields(models::FILE_FIELDS),
+            body,
+        )?
+        .check_status()?
+        .json()?)
+    }
+
+    /// Rename given file or folder.
+    pub fn rename_file(
+        &self,
+        file_id: impl Into<FileId>,
+        name: &str,
+    ) -> Result<DriveFile, Error> {
+        let file_id = file_id.into();
+        let file = self
+            .patch(
+                format!("/files/{}", file_id).as_str(),
+                Params::new().fields(models::FILE_FIELDS),
+                serde_json::json!({ "name": name }),
+            )?
+            .check_status()?
+            .json()?;
+        self.clear_path_cache();
+        Ok(file)
+    }
+
+    /// Move given file or folder into `folder_id`, out of whichever folders it was in.
+    pub fn move_file(
+        &self,
+        file_id: impl Into<FileId>,
+        folder_id: impl Into<FileId>,
+    ) -> Result<DriveFile, Error> {
+        let file_id = file_id.into();
+        let folder_id = folder_id.into();
         let endpoint = format!("/files/{}", file_id);
-        self.delete(endpoint.as_str(), None)?;
+        let current: DriveFile = self
+            .get(endpoint.as_str(), Params::new().fields("id,parents"))?
+            .check_status()?
+            .json()?;
+        let remove = current.parents.join(",");
+        let mut params = Params::new()
+            .fields(models::FILE_FIELDS)
+            .param("addParents", &folder_id);
+        if !remove.is_empty() {
+            params = params.param("removeParents", remove);
+        }
+        let file = self
+            .patch(endpoint.as_str(), params, serde_json::json!({}))?
+            .check_status()?
+            .json()?;
+        self.clear_path_cache();
+        Ok(file)
+    }
+
+    /// Move given file to the trash, or restore it from the trash.
+    pub fn set_trashed(
+        &self,
+        file_id: impl Into<FileId>,
+        trashed: bool,
+    ) -> Result<DriveFile, Error> {
+        let file_id = file_id.into();
+        Ok(self.patch(
+            format!("/files/{}", file_id).as_str(),
+            Params::new().fields(models::FILE_FIELDS),
+            serde_json::json!({ "trashed": trashed }),
+        )?
+        .check_status()?
+        .json()?)
+    }
+
+    /// Permanently delete everything in the user's trash.
+    pub fn empty_trash(&self) -> Result<(), Error> {
+        self.delete("/files/trash", Params::new())?.check_status()?;
         Ok(())
     }
 }
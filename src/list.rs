@@ -0,0 +1,178 @@
+use serde::Deserialize;
+
+use crate::error::{CheckStatus, Error};
+use crate::models::{DriveFile, FILE_FIELDS};
+use crate::params::Params;
+use crate::Drive;
+
+/// Escape a value for use inside a single quoted string in a search query.
+pub(crate) fn escape_query(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('\'', "\\'")
+}
+
+/// Which collection of files a listing searches.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Corpora {
+    /// Files in My Drive and shared with the user (Drive's default).
+    User,
+    /// Files shared to the user's domain.
+    Domain,
+    /// Files in the shared drive with the given id.
+    Drive(String),
+    /// Everything the user can see, including every shared drive they're a member of.
+    AllDrives,
+}
+
+/// Options for `Drive::list_files`.
+#[derive(Debug, Clone, Default)]
+pub struct ListOptions {
+    query: Option<String>,
+    order_by: Option<String>,
+    corpora: Option<Corpora>,
+    spaces: Option<String>,
+    limit: Option<usize>,
+}
+
+impl ListOptions {
+    pub fn new() -> ListOptions {
+        ListOptions::default()
+    }
+
+    /// Search query (the `q` param), e.g. `name = 'report.pdf' and trashed = false`.
+    pub fn query(mut self, query: &str) -> ListOptions {
+        self.query = Some(query.to_string());
+        self
+    }
+
+    /// Sort order, e.g. `modifiedTime desc,name`.
+    pub fn order_by(mut self, order_by: &str) -> ListOptions {
+        self.order_by = Some(order_by.to_string());
+        self
+    }
+
+    pub fn corpora(mut self, corpora: Corpora) -> ListOptions {
+        self.corpora = Some(corpora);
+        self
+    }
+
+    /// Comma separated spaces to search, `drive` and/or `appDataFolder`.
+    pub fn spaces(mut self, spaces: &str) -> ListOptions {
+        self.spaces = Some(spaces.to_string());
+        self
+    }
+
+    /// Stop after this many files instead of walking every page.
+    pub fn limit(mut self, limit: usize) -> ListOptions {
+        self.limit = Some(limit);
+        self
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct FileList {
+    files: Vec<DriveFile>,
+    next_page_token: Option<String>,
+}
+
+impl Drive {
+    /// List files matching given options, following pagination until every page (or `limit`
+    /// files) has been fetched.
+    pub fn list_files(&self, options: &ListOptions) -> Result<Vec<DriveFile>, Error> {
+        let fields = format!("nextPageToken,files({})", FILE_FIELDS);
+        let page_size = options.limit.unwrap_or(1000).min(1000) as u32;
+
+        let mut params = Params::new().fields(&fields).page_size(page_size);
+        if let Some(q) = &options.query {
+            params = params.param("q", q);
+        }
+        if let Some(order_by) = &options.order_by {
+            params = params.param("orderBy", order_by);
+        }
+        if let Some(spaces) = &options.spaces {
+            params = params.param("spaces", spaces);
+        }
+        params = match &options.corpora {
+            Some(Corpora::User) => params.param("corpora", "user"),
+            Some(Corpora::Domain) => params.param("corpora", "domain"),
+            Some(Corpora::Drive(id)) => params
+                .param("corpora", "drive")
+                .param("driveId", id)
+                .param("includeItemsFromAllDrives", true),
+            Some(Corpora::AllDrives) => params
+                .param("corpora", "allDrives")
+                .param("includeItemsFromAllDrives", true),
+            None => params,
+        };
+
+        let mut files = vec![];
+        let mut page_token: Option<String> = None;
+        loop {
+            let mut page_params = params.clone();
+            if let Some(token) = &page_token {
+                page_params = page_params.page_token(token);
+            }
+            let page: FileList = self.get("/files", page_params)?.check_status()?.json()?;
+            files.extend(page.files);
+
+            if let Some(limit) = options.limit {
+                if files.len() >= limit {
+                    files.truncate(limit);
+                    break;
+                }
+            }
+            match page.next_page_token {
+                Some(token) => page_token = Some(token),
+                None => break,
+            }
+        }
+        Ok(files)
+    }
+
+    /// List the user's starred files, most recently modified first.
+    pub fn starred(&self) -> Result<Vec<DriveFile>, Error> {
+        self.list_files(
+            &ListOptions::new()
+                .query("starred = true and trashed = false")
+                .order_by("modifiedTime desc"),
+        )
+    }
+
+    /// List the user's trashed files, most recently modified first.
+    pub fn trashed(&self) -> Result<Vec<DriveFile>, Error> {
+        self.list_files(
+            &ListOptions::new()
+                .query("trashed = true")
+                .order_by("modifiedTime desc"),
+        )
+    }
+
+    /// List files other people have shared with the user, most recently shared first.
+    pub fn shared_with_me(&self) -> Result<Vec<DriveFile>, Error> {
+        self.list_files(
+            &ListOptions::new()
+                .query("sharedWithMe = true and trashed = false")
+                .order_by("sharedWithMeTime desc"),
+        )
+    }
+
+    /// The `limit` files the user opened most recently.
+    pub fn recently_viewed(&self, limit: usize) -> Result<Vec<DriveFile>, Error> {
+        self.list_files(
+            &ListOptions::new()
+                .query("trashed = false")
+                .order_by("viewedByMeTime desc")
+                .limit(limit),
+        )
+    }
+
+    /// The `limit` files modified most recently, by anyone.
+    pub fn recently_modified(&self, limit: usize) -> Result<Vec<DriveFile>, Error> {
+        self.list_files(
+            &ListOptions::new()
+                .query("trashed = false")
+                .order_by("modifiedTime desc")
+                .limit(limit),
+        )
+    }
+}
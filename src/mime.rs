@@ -0,0 +1,167 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+/// Prefix shared by every Google-native type.
+const GOOGLE_APPS_PREFIX: &str = "application/vnd.google-apps.";
+
+/// A MIME type, with constants for the Google-native types Drive uses and common file types.
+///
+/// ```
+/// # use std::path::Path;
+/// # use google_api::*;
+/// # let file = DriveFile::default();
+/// if file.mime() == MimeType::SPREADSHEET {
+///     // export it rather than download it
+/// }
+/// let mime = MimeType::from_path(Path::new("report.pdf"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct MimeType(Cow<'static, str>);
+
+impl MimeType {
+    pub const FOLDER: MimeType = MimeType::from_static("application/vnd.google-apps.folder");
+    pub const DOCUMENT: MimeType = MimeType::from_static("application/vnd.google-apps.document");
+    pub const SPREADSHEET: MimeType =
+        MimeType::from_static("application/vnd.google-apps.spreadsheet");
+    pub const PRESENTATION: MimeType =
+        MimeType::from_static("application/vnd.google-apps.presentation");
+    pub const DRAWING: MimeType = MimeType::from_static("application/vnd.google-apps.drawing");
+    pub const FORM: MimeType = MimeType::from_static("application/vnd.google-apps.form");
+    pub const SCRIPT: MimeType = MimeType::from_static("application/vnd.google-apps.script");
+    pub const SHORTCUT: MimeType = MimeType::from_static("application/vnd.google-apps.shortcut");
+    pub const SITE: MimeType = MimeType::from_static("application/vnd.google-apps.site");
+
+    pub const PDF: MimeType = MimeType::from_static("application/pdf");
+    pub const PLAIN_TEXT: MimeType = MimeType::from_static("text/plain");
+    pub const HTML: MimeType = MimeType::from_static("text/html");
+    pub const CSV: MimeType = MimeType::from_static("text/csv");
+    pub const TSV: MimeType = MimeType::from_static("text/tab-separated-values");
+    pub const JSON: MimeType = MimeType::from_static("application/json");
+    pub const ZIP: MimeType = MimeType::from_static("application/zip");
+    pub const DOCX: MimeType = MimeType::from_static(
+        "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+    );
+    pub const XLSX: MimeType =
+        MimeType::from_static("application/vnd.openxmlformats-officedocument.spreadsheetml.sheet");
+    pub const PPTX: MimeType = MimeType::from_static(
+        "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+    );
+    pub const JPEG: MimeType = MimeType::from_static("image/jpeg");
+    pub const PNG: MimeType = MimeType::from_static("image/png");
+    pub const GIF: MimeType = MimeType::from_static("image/gif");
+    pub const SVG: MimeType = MimeType::from_static("image/svg+xml");
+    pub const MP4: MimeType = MimeType::from_static("video/mp4");
+    pub const MP3: MimeType = MimeType::from_static("audio/mpeg");
+    pub const OCTET_STREAM: MimeType = MimeType::from_static("application/octet-stream");
+
+    const fn from_static(mime_type: &'static str) -> MimeType {
+        MimeType(Cow::Borrowed(mime_type))
+    }
+
+    pub fn new(mime_type: &str) -> MimeType {
+        MimeType(Cow::Owned(mime_type.to_string()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Google Docs, Sheets, Slides, folders, shortcuts and the other types that only exist
+    /// inside Drive. Their content can't be downloaded, only exported.
+    pub fn is_google_native(&self) -> bool {
+        self.0.starts_with(GOOGLE_APPS_PREFIX)
+    }
+
+    pub fn is_folder(&self) -> bool {
+        *self == MimeType::FOLDER
+    }
+
+    /// Whether the content of a file of this type can be exported to other formats.
+    pub fn is_exportable(&self) -> bool {
+        self.is_google_native() && !self.is_folder() && *self != MimeType::SHORTCUT
+    }
+
+    /// Guess a MIME type from a file extension (without the dot, any case). Unknown
+    /// extensions give `OCTET_STREAM`.
+    pub fn from_extension(ext: &str) -> MimeType {
+        let mime_type = match ext.to_ascii_lowercase().as_str() {
+            "docx" => return MimeType::DOCX,
+            "xlsx" => return MimeType::XLSX,
+            "pptx" => return MimeType::PPTX,
+            "doc" => "application/msword",
+            "odt" => "application/vnd.oasis.opendocument.text",
+            "rtf" => "application/rtf",
+            "txt" | "log" | "md" => "text/plain",
+            "html" | "htm" => "text/html",
+            "xls" => "application/vnd.ms-excel",
+            "ods" => "application/vnd.oasis.opendocument.spreadsheet",
+            "csv" => "text/csv",
+            "tsv" => "text/tab-separated-values",
+            "ppt" => "application/vnd.ms-powerpoint",
+            "odp" => "application/vnd.oasis.opendocument.presentation",
+            "pdf" => "application/pdf",
+            "epub" => "application/epub+zip",
+            "json" => "application/json",
+            "xml" => "application/xml",
+            "zip" => "application/zip",
+            "gz" | "tgz" => "application/gzip",
+            "tar" => "application/x-tar",
+            "jpg" | "jpeg" => "image/jpeg",
+            "png" => "image/png",
+            "gif" => "image/gif",
+            "bmp" => "image/bmp",
+            "webp" => "image/webp",
+            "svg" => "image/svg+xml",
+            "mp4" => "video/mp4",
+            "mov" => "video/quicktime",
+            "webm" => "video/webm",
+            "mp3" => "audio/mpeg",
+            "wav" => "audio/wav",
+            _ => return MimeType::OCTET_STREAM,
+        };
+        MimeType::from_static(mime_type)
+    }
+
+    /// Guess a file's MIME type from its extension.
+    pub fn from_path(path: &Path) -> MimeType {
+        let ext = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or_default();
+        MimeType::from_extension(ext)
+    }
+}
+
+impl fmt::Display for MimeType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl AsRef<str> for MimeType {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&str> for MimeType {
+    fn from(mime_type: &str) -> MimeType {
+        MimeType::new(mime_type)
+    }
+}
+
+impl PartialEq<str> for MimeType {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for MimeType {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
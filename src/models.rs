@@ -0,0 +1,112 @@
+use std::collections::HashMap;
+
+use reqwest::blocking::Response;
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::error::{check_status, Error};
+use crate::mime::MimeType;
+use crate::timestamp::Timestamp;
+
+/// Fields requested whenever a `DriveFile` is fetched.
+pub(crate) const FILE_FIELDS: &str = "id,name,mimeType,parents,size,quotaBytesUsed,md5Checksum,createdTime,modifiedTime,viewedByMeTime,sharedWithMeTime,trashed,starred,webViewLink,webContentLink,owners(displayName,emailAddress),driveId,properties,appProperties";
+
+/// JSON fields of a resource that its model has no field for. Models keep them in `extra`
+/// and write them back out when serialized, so sending a fetched resource back in an update
+/// doesn't drop what the API added after this crate was written.
+pub type ExtraFields = serde_json::Map<String, serde_json::Value>;
+
+/// Drive file metadata. Only the fields in `FILE_FIELDS` are filled in, everything is defaulted
+/// so partial `fields` selections still deserialize.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct DriveFile {
+    pub id: String,
+    pub name: String,
+    pub mime_type: String,
+    pub parents: Vec<String>,
+    /// Size in bytes. Google Docs/Sheets/Slides and folders don't have one.
+    #[serde(deserialize_with = "de_int64")]
+    pub size: Option<u64>,
+    /// Storage the file counts against the owner's quota, including old revisions kept
+    /// forever. Zero for Google Docs/Sheets/Slides.
+    #[serde(deserialize_with = "de_int64")]
+    pub quota_bytes_used: Option<u64>,
+    pub md5_checksum: Option<String>,
+    pub created_time: Option<Timestamp>,
+    pub modified_time: Option<Timestamp>,
+    /// Last time the user opened the file, if ever.
+    pub viewed_by_me_time: Option<Timestamp>,
+    /// When the file was shared with the user, for files they don't own.
+    pub shared_with_me_time: Option<Timestamp>,
+    pub trashed: bool,
+    pub starred: bool,
+    pub web_view_link: Option<String>,
+    pub web_content_link: Option<String>,
+    pub owners: Vec<User>,
+    /// Shared drive the file lives in, if any.
+    pub drive_id: Option<String>,
+    /// Custom properties visible to every app.
+    pub properties: HashMap<String, String>,
+    /// Custom properties private to this app.
+    pub app_properties: HashMap<String, String>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl DriveFile {
+    /// The file a create or update request answered with. Everything being defaulted, an
+    /// answer without an id would otherwise pass for a file.
+    pub(crate) fn from_response(resp: Response) -> Result<DriveFile, Error> {
+        let file: DriveFile = check_status(resp)?.json()?;
+        if file.id.is_empty() {
+            return Err(Error::UnexpectedResponse(String::from(
+                "Drive answered without a file id",
+            )));
+        }
+        Ok(file)
+    }
+
+    pub fn mime(&self) -> MimeType {
+        MimeType::new(&self.mime_type)
+    }
+
+    pub fn is_folder(&self) -> bool {
+        self.mime().is_folder()
+    }
+
+    /// Google Docs, Sheets, Slides and the like, which have to be exported rather than
+    /// downloaded.
+    pub fn is_google_native(&self) -> bool {
+        self.mime().is_google_native()
+    }
+}
+
+/// A Google account as Drive describes it.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct User {
+    pub display_name: String,
+    pub email_address: Option<String>,
+    pub permission_id: Option<String>,
+    pub photo_link: Option<String>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+/// Google sends int64 values as JSON strings. Accept both strings and numbers.
+pub(crate) fn de_int64<'de, D>(deserializer: D) -> Result<Option<u64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Int64 {
+        Str(String),
+        Num(u64),
+    }
+    match Option::<Int64>::deserialize(deserializer)? {
+        Some(Int64::Str(s)) => s.parse().map(Some).map_err(serde::de::Error::custom),
+        Some(Int64::Num(n)) => Ok(Some(n)),
+        None => Ok(None),
+    }
+}
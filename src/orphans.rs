@@ -0,0 +1,96 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::error::{CheckStatus, Error};
+use crate::file_id::FileId;
+use crate::models::{DriveFile, FILE_FIELDS};
+use crate::params::Params;
+use crate::query::Query;
+use crate::Drive;
+
+impl Drive {
+    /// Files owned by the user that can't be reached from any folder: they either have no
+    /// parent at all, or every parent is trashed or gone. These pile up when a folder is
+    /// deleted by someone else or files are removed from their last folder.
+    pub fn orphaned_files(&self) -> Result<Vec<DriveFile>, Error> {
+        let owned = self.search(&Query::new().raw("'me' in owners").trashed(false))?;
+        let root = self.root_folder_id()?;
+        let mut known: HashSet<&str> = owned.iter().map(|f| f.id.as_str()).collect();
+        known.insert(&root);
+
+        // Parents outside the listing (shared folders, trashed folders) have to be checked one
+        // by one, remember the answer since siblings share them.
+        let mut reachable: HashMap<String, bool> = HashMap::new();
+        let mut orphans = vec![];
+        for file in &owned {
+            let mut has_parent = false;
+            for parent in &file.parents {
+                if known.contains(parent.as_str()) {
+                    has_parent = true;
+                    break;
+                }
+                let ok = match reachable.get(parent) {
+                    Some(ok) => *ok,
+                    None => {
+                        let ok = self.is_live_folder(parent)?;
+                        reachable.insert(parent.clone(), ok);
+                        ok
+                    }
+                };
+                if ok {
+                    has_parent = true;
+                    break;
+                }
+            }
+            if !has_parent {
+                orphans.push(file.clone());
+            }
+        }
+        Ok(orphans)
+    }
+
+    /// Move an orphaned file into given folder, dropping any dead parents it still lists.
+    pub fn rehome_orphan(
+        &self,
+        file: &DriveFile,
+        folder_id: impl Into<FileId>,
+    ) -> Result<DriveFile, Error> {
+        let folder_id = folder_id.into();
+        let remove = file.parents.join(",");
+        let mut params = Params::new()
+            .fields(FILE_FIELDS)
+            .param("addParents", folder_id);
+        if !remove.is_empty() {
+            params = params.param("removeParents", remove);
+        }
+        Ok(self
+            .patch(
+                format!("/files/{}", file.id).as_str(),
+                params,
+                serde_json::json!({}),
+            )?
+            .check_status()?
+            .json()?)
+    }
+
+    /// Id of the root of My Drive.
+    fn root_folder_id(&self) -> Result<String, Error> {
+        let root: DriveFile = self
+            .get("/files/root", Params::new().fields("id"))?
+            .check_status()?
+            .json()?;
+        Ok(root.id)
+    }
+
+    /// Whether given folder still exists outside the trash and is visible to the user.
+    fn is_live_folder(&self, folder_id: &str) -> Result<bool, Error> {
+        let resp = self.get(
+            format!("/files/{}", folder_id).as_str(),
+            Params::new().fields("id,trashed"),
+        );
+        let folder: DriveFile = match resp {
+            Err(e) if e.status() == Some(reqwest::StatusCode::NOT_FOUND) => return Ok(false),
+            resp => resp?.json()?,
+        };
+        Ok(!folder.trashed)
+    }
+}
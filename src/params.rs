@@ -0,0 +1,145 @@
+use std::fmt::Display;
+
+/// Query parameters for a request made with `Drive::get`, `post`, `patch` or `delete`. `param`
+/// adds a value, so a key can be given more than once, while `set` and the helpers replace
+/// whatever the key had.
+///
+/// ```no_run
+/// # use google_api::*;
+/// # let drive = Drive::new(String::new(), String::new(), None)?;
+/// let page: serde_json::Value = drive
+///     .get(
+///         "/files",
+///         Params::new()
+///             .fields("nextPageToken,files(id,name)")
+///             .page_size(100)
+///             .param("orderBy", "modifiedTime desc"),
+///     )?
+///     .error_for_status()?
+///     .json()?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Params {
+    pairs: Vec<(String, String)>,
+}
+
+impl Params {
+    pub fn new() -> Params {
+        Params::default()
+    }
+
+    /// Add any parameter, e.g. `param("orderBy", "name")`. Values already given for the key
+    /// are kept, for parameters that can repeat.
+    pub fn param(mut self, key: &str, value: impl Display) -> Params {
+        self.pairs.push((key.to_string(), value.to_string()));
+        self
+    }
+
+    /// Set any parameter, replacing the values it had.
+    pub fn set(mut self, key: &str, value: impl Display) -> Params {
+        self.pairs.retain(|(k, _)| k != key);
+        self.param(key, value)
+    }
+
+    /// Fields to return, e.g. `id,name` or `files(id,name),nextPageToken`.
+    pub fn fields(self, fields: &str) -> Params {
+        self.set("fields", fields)
+    }
+
+    /// Items per page for list requests.
+    pub fn page_size(self, size: u32) -> Params {
+        self.set("pageSize", size)
+    }
+
+    /// Page to continue a list from, the `nextPageToken` of the one before.
+    pub fn page_token(self, token: &str) -> Params {
+        self.set("pageToken", token)
+    }
+
+    /// Whether the request works on items in shared drives. File and change endpoints get
+    /// `true` unless this says otherwise.
+    pub fn supports_all_drives(self, supports: bool) -> Params {
+        self.set("supportsAllDrives", supports)
+    }
+
+    /// Response format, e.g. `media` for a file's content instead of its metadata.
+    pub fn alt(self, alt: &str) -> Params {
+        self.set("alt", alt)
+    }
+
+    /// First value given for a parameter.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.pairs
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.pairs.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+}
+
+impl From<Vec<(&str, &str)>> for Params {
+    fn from(pairs: Vec<(&str, &str)>) -> Self {
+        pairs
+            .into_iter()
+            .fold(Params::new(), |params, (key, value)| {
+                params.param(key, value)
+            })
+    }
+}
+
+/// The pairs taken before `Params` existed.
+impl From<Option<Vec<(&str, &str)>>> for Params {
+    fn from(pairs: Option<Vec<(&str, &str)>>) -> Self {
+        pairs.map(Params::from).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn param_keeps_repeated_keys() {
+        let params = Params::new()
+            .param("spaces", "drive")
+            .param("spaces", "appDataFolder");
+        let pairs: Vec<_> = params.iter().collect();
+        assert_eq!(pairs, [("spaces", "drive"), ("spaces", "appDataFolder")]);
+        assert_eq!(params.get("spaces"), Some("drive"));
+    }
+
+    #[test]
+    fn set_and_helpers_replace() {
+        let params = Params::new()
+            .param("orderBy", "name")
+            .param("orderBy", "createdTime")
+            .set("orderBy", "modifiedTime desc")
+            .page_size(10)
+            .page_size(100)
+            .fields("id");
+        let pairs: Vec<_> = params.iter().collect();
+        assert_eq!(
+            pairs,
+            [
+                ("orderBy", "modifiedTime desc"),
+                ("pageSize", "100"),
+                ("fields", "id")
+            ]
+        );
+    }
+
+    #[test]
+    fn from_pairs() {
+        let params = Params::from(vec![("q", "trashed = false"), ("q", "starred = true")]);
+        assert_eq!(params.iter().count(), 2);
+        assert_eq!(Params::from(None), Params::new());
+        assert_eq!(
+            Params::from(Some(vec![("alt", "media")])),
+            Params::new().alt("media")
+        );
+    }
+}
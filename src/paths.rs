@@ -0,0 +1,99 @@
+use std::collections::HashMap;
+use std::sync::MutexGuard;
+
+use crate::error::Error;
+use crate::file_id::FileId;
+use crate::query::Query;
+use crate::Drive;
+
+/// Names a path may start with to mean the root of My Drive.
+const ROOT_NAMES: &[&str] = &["My Drive", "root"];
+
+impl Drive {
+    /// Find the file or folder at given `/` separated path from the root of My Drive, e.g.
+    /// `My Drive/projects/report.pdf` or just `projects/report.pdf`. If several items with the
+    /// same name share a folder, the first one Drive returns is used.
+    pub fn resolve_path(&self, path: &str) -> Result<FileId, Error> {
+        self.resolve_path_from(FileId::root(), relative_to_root(path))
+    }
+
+    /// Find the folder at given path like `resolve_path` does, creating it and any folder
+    /// leading to it that doesn't exist yet, as `mkdir -p` would.
+    pub fn create_folder_path(&self, path: &str) -> Result<FileId, Error> {
+        let mut current = FileId::root();
+        for name in relative_to_root(path).split('/').filter(|n| !n.is_empty()) {
+            current = match self.resolve_path_from(&current, name) {
+                Ok(id) => id,
+                Err(Error::NotFound(_)) => {
+                    let folder = self.create_folder(name, &current)?;
+                    let id = FileId::unchecked(folder.id);
+                    let key = format!("{}:{}", current, name);
+                    self.path_cache().insert(key, id.clone());
+                    id
+                }
+                Err(e) => return Err(e),
+            };
+        }
+        Ok(current)
+    }
+
+    /// Find the file or folder at given path relative to the `anchor` folder.
+    pub fn resolve_path_from(
+        &self,
+        anchor: impl Into<FileId>,
+        path: &str,
+    ) -> Result<FileId, Error> {
+        let mut current = anchor.into();
+        let mut walked = String::new();
+        for name in path.split('/').filter(|n| !n.is_empty()) {
+            walked.push('/');
+            walked.push_str(name);
+            let key = format!("{}:{}", current, name);
+            let cached = self.path_cache().get(&key).cloned();
+            current = match cached {
+                Some(id) => id,
+                None => {
+                    let query = Query::new()
+                        .name_is(name)
+                        .in_folder(&current)
+                        .trashed(false);
+                    let found = self
+                        .search(&query)?
+                        .into_iter()
+                        .next()
+                        .ok_or_else(|| Error::NotFound(walked.clone()))?;
+                    let id = FileId::unchecked(found.id);
+                    self.path_cache().insert(key, id.clone());
+                    id
+                }
+            };
+        }
+        Ok(current)
+    }
+
+    /// The path cache, shared by clones of this client. A thread that panicked holding it
+    /// can't have left it half updated, so a poisoned lock is fine to use.
+    fn path_cache(&self) -> MutexGuard<'_, HashMap<String, FileId>> {
+        self.paths.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Forget every path looked up so far, for when files have been moved or renamed since.
+    pub fn clear_path_cache(&self) {
+        self.path_cache().clear();
+    }
+}
+
+/// Path with any leading `My Drive/` and surrounding slashes taken off.
+fn relative_to_root(path: &str) -> &str {
+    let trimmed = path.trim_matches('/');
+    ROOT_NAMES
+        .iter()
+        .find_map(|root| {
+            if trimmed == *root {
+                Some("")
+            } else {
+                trimmed.strip_prefix(&format!("{}/", root))
+            }
+        })
+        .unwrap_or(trimmed)
+}
@@ -0,0 +1,150 @@
+use reqwest::blocking::RequestBuilder;
+use reqwest::Method;
+use serde::Deserialize;
+
+use super::person::{Person, PERSON_FIELDS};
+use super::People;
+
+use crate::error::{CheckStatus, Error};
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct ConnectionList {
+    connections: Vec<Person>,
+    next_page_token: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct SearchResult {
+    person: Person,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct SearchResults {
+    results: Vec<SearchResult>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct PhotoUpdate {
+    person: Person,
+}
+
+impl People {
+    /// Request for a method on a contact, e.g. `people/c123:updateContact`. The resource
+    /// name's slash has to stay a path separator.
+    fn contact_request(
+        &self,
+        method: Method,
+        resource_name: &str,
+        verb: &str,
+    ) -> Result<RequestBuilder, Error> {
+        let mut segments: Vec<String> = resource_name.split('/').map(String::from).collect();
+        if let Some(last) = segments.last_mut() {
+            last.push_str(verb);
+        }
+        let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+        self.request(method, &segments)
+    }
+
+    /// Every contact of the user.
+    pub fn list_contacts(&self) -> Result<Vec<Person>, Error> {
+        let mut contacts = vec![];
+        let mut page_token: Option<String> = None;
+        loop {
+            let mut params = vec![("personFields", PERSON_FIELDS), ("pageSize", "1000")];
+            if let Some(token) = &page_token {
+                params.push(("pageToken", token));
+            }
+            let page: ConnectionList = self
+                .request(Method::GET, &["people", "me", "connections"])?
+                .query(&params)
+                .send()?
+                .check_status()?
+                .json()?;
+            contacts.extend(page.connections);
+
+            match page.next_page_token {
+                Some(token) => page_token = Some(token),
+                None => break,
+            }
+        }
+        Ok(contacts)
+    }
+
+    /// Contacts whose names, email addresses, phone numbers or organizations start with
+    /// `query`, at most 30. The search index is only loaded on the first query of a
+    /// session, which comes back empty, so a first search of `""` is a good idea.
+    pub fn search_contacts(&self, query: &str) -> Result<Vec<Person>, Error> {
+        let results: SearchResults = self
+            .request(Method::GET, &["people:searchContacts"])?
+            .query(&[
+                ("query", query),
+                ("readMask", PERSON_FIELDS),
+                ("pageSize", "30"),
+            ])
+            .send()?
+            .check_status()?
+            .json()?;
+        Ok(results.results.into_iter().map(|r| r.person).collect())
+    }
+
+    /// Fetch a contact by resource name (`people/c123`).
+    pub fn get_contact(&self, resource_name: &str) -> Result<Person, Error> {
+        Ok(self
+            .contact_request(Method::GET, resource_name, "")?
+            .query(&[("personFields", PERSON_FIELDS)])
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    pub fn create_contact(&self, person: &Person) -> Result<Person, Error> {
+        Ok(self
+            .request(Method::POST, &["people:createContact"])?
+            .query(&[("personFields", PERSON_FIELDS)])
+            .json(person)
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    /// Save changes to a contact that was fetched (or created) earlier. The fields that have
+    /// values replace the contact's, empty ones are left as they are. Fails if the contact
+    /// was changed since, going by its `etag`.
+    pub fn update_contact(&self, person: &Person) -> Result<Person, Error> {
+        Ok(self
+            .contact_request(Method::PATCH, &person.resource_name, ":updateContact")?
+            .query(&[
+                ("updatePersonFields", person.update_fields().as_str()),
+                ("personFields", PERSON_FIELDS),
+            ])
+            .json(person)
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    /// Set a contact's picture from JPEG or PNG bytes.
+    pub fn update_contact_photo(&self, resource_name: &str, image: &[u8]) -> Result<Person, Error> {
+        let update: PhotoUpdate = self
+            .contact_request(Method::PATCH, resource_name, ":updateContactPhoto")?
+            .json(&serde_json::json!({
+                "photoBytes": base64::encode(image),
+                "personFields": PERSON_FIELDS,
+            }))
+            .send()?
+            .check_status()?
+            .json()?;
+        Ok(update.person)
+    }
+
+    pub fn delete_contact(&self, resource_name: &str) -> Result<(), Error> {
+        self.contact_request(Method::DELETE, resource_name, ":deleteContact")?
+            .send()?
+            .check_status()?;
+        Ok(())
+    }
+}
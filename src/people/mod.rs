@@ -0,0 +1,54 @@
+use reqwest::blocking::RequestBuilder;
+use reqwest::Method;
+
+use crate::api;
+use crate::auth::Auth;
+use crate::error::Error;
+use crate::scope::Scope;
+
+mod contacts;
+mod person;
+
+pub use person::{EmailAddress, Organization, Person, PersonName, PhoneNumber, Photo};
+
+const PEOPLE_BASE_URL: &str = "https://people.googleapis.com/v1";
+
+/// Google People client, for the user's contacts. Shares its saved token with the other
+/// clients.
+///
+/// ```no_run
+/// # use google_api::*;
+/// # let (client_id, client_secret) = (String::new(), String::new());
+/// let people = People::new(client_id, client_secret, None)?;
+/// let ada = people.create_contact(
+///     &Person::new("Ada", "Lovelace").email("ada@example.com").phone("+44 20 7946 0000"),
+/// )?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug)]
+pub struct People {
+    auth: Auth,
+    client: reqwest::blocking::Client,
+}
+
+impl People {
+    /// Authenticate with given OAuth client. `scopes` defaults to `Scope::Contacts`.
+    pub fn new(
+        client_id: String,
+        client_secret: String,
+        scopes: Option<Vec<String>>,
+    ) -> Result<People, Error> {
+        let scopes = scopes.unwrap_or_else(|| vec![Scope::Contacts.into()]);
+
+        Ok(People {
+            auth: Auth::from_config(&client_id, &client_secret, &scopes)?,
+            client: reqwest::blocking::Client::new(),
+        })
+    }
+
+    /// Authorized request for the endpoint made of given path segments, e.g.
+    /// `["people", "me", "connections"]`.
+    fn request(&self, method: Method, segments: &[&str]) -> Result<RequestBuilder, Error> {
+        api::request(&self.client, &self.auth, PEOPLE_BASE_URL, method, segments)
+    }
+}
@@ -0,0 +1,168 @@
+use crate::models::ExtraFields;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct PersonName {
+    #[serde(skip_serializing)]
+    pub display_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub given_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub family_name: Option<String>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct EmailAddress {
+    pub value: String,
+    /// `home`, `work`, `other` or anything custom.
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct PhoneNumber {
+    pub value: String,
+    /// `mobile`, `home`, `work`, ... or anything custom.
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    /// The number in E.164 form, when Google could work it out.
+    #[serde(skip_serializing)]
+    pub canonical_form: Option<String>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+/// A contact's picture. Change it with `People::update_contact_photo`.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Photo {
+    pub url: String,
+    /// Set when it's a generated placeholder rather than an actual picture.
+    pub default: bool,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Organization {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Job title.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+/// A contact, with the fields this crate reads and writes. Only non-empty fields are sent,
+/// so a `Person` built with the setters can be used to create a contact.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Person {
+    /// `people/<id>`, which the contact methods take.
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub resource_name: String,
+    /// Version of the contact, required to update it.
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub etag: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub names: Vec<PersonName>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub email_addresses: Vec<EmailAddress>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub phone_numbers: Vec<PhoneNumber>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub organizations: Vec<Organization>,
+    #[serde(skip_serializing)]
+    pub photos: Vec<Photo>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+/// Person fields fetched by default: everything `Person` has.
+pub(crate) const PERSON_FIELDS: &str = "names,emailAddresses,phoneNumbers,organizations,photos";
+
+impl Person {
+    pub fn new(given_name: &str, family_name: &str) -> Person {
+        Person {
+            names: vec![PersonName {
+                given_name: Some(given_name.to_string()),
+                family_name: Some(family_name.to_string()),
+                ..PersonName::default()
+            }],
+            ..Person::default()
+        }
+    }
+
+    /// Add an email address. Can be given more than once.
+    pub fn email(mut self, address: &str) -> Person {
+        self.email_addresses.push(EmailAddress {
+            value: address.to_string(),
+            kind: None,
+            ..EmailAddress::default()
+        });
+        self
+    }
+
+    /// Add a phone number. Can be given more than once.
+    pub fn phone(mut self, number: &str) -> Person {
+        self.phone_numbers.push(PhoneNumber {
+            value: number.to_string(),
+            ..PhoneNumber::default()
+        });
+        self
+    }
+
+    pub fn organization(mut self, name: &str, title: &str) -> Person {
+        self.organizations.push(Organization {
+            name: Some(name.to_string()),
+            title: Some(title.to_string()),
+            ..Organization::default()
+        });
+        self
+    }
+
+    pub fn display_name(&self) -> Option<&str> {
+        self.names
+            .first()
+            .map(|n| n.display_name.as_str())
+            .filter(|n| !n.is_empty())
+    }
+
+    /// The first email address, which is the primary one.
+    pub fn primary_email(&self) -> Option<&str> {
+        self.email_addresses.first().map(|e| e.value.as_str())
+    }
+
+    /// The first phone number, which is the primary one.
+    pub fn primary_phone(&self) -> Option<&str> {
+        self.phone_numbers.first().map(|p| p.value.as_str())
+    }
+
+    /// Update mask naming the fields this person has values for, so an update only
+    /// touches those.
+    pub(crate) fn update_fields(&self) -> String {
+        let mut fields = vec![];
+        if !self.names.is_empty() {
+            fields.push("names");
+        }
+        if !self.email_addresses.is_empty() {
+            fields.push("emailAddresses");
+        }
+        if !self.phone_numbers.is_empty() {
+            fields.push("phoneNumbers");
+        }
+        if !self.organizations.is_empty() {
+            fields.push("organizations");
+        }
+        fields.join(",")
+    }
+}
@@ -0,0 +1,341 @@
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{CheckStatus, Error};
+use crate::file_id::FileId;
+use crate::models::ExtraFields;
+use crate::params::Params;
+use crate::timestamp::format_rfc3339;
+use crate::Drive;
+
+/// Fields requested whenever a `Permission` is fetched.
+const PERMISSION_FIELDS: &str =
+    "id,type,role,emailAddress,domain,displayName,expirationTime,allowFileDiscovery,pendingOwner";
+
+/// Outcome of a `Drive::transfer_ownership` call.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OwnershipTransfer {
+    /// The new owner owns the file now.
+    Completed,
+    /// The new owner was set as pending owner and has to accept the transfer in their Drive.
+    /// This is the only flow consumer (gmail.com) accounts support.
+    Pending,
+}
+
+/// Role granted by a permission.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Role {
+    Owner,
+    Organizer,
+    FileOrganizer,
+    Writer,
+    Commenter,
+    Reader,
+}
+
+impl Role {
+    /// Value Drive uses for this role.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Owner => "owner",
+            Role::Organizer => "organizer",
+            Role::FileOrganizer => "fileOrganizer",
+            Role::Writer => "writer",
+            Role::Commenter => "commenter",
+            Role::Reader => "reader",
+        }
+    }
+}
+
+/// Who a permission is granted to.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Grantee {
+    /// A Google account, by email.
+    User(String),
+    /// A Google group, by email.
+    Group(String),
+    /// Everyone in a Workspace domain.
+    Domain(String),
+    /// Anyone, with or without a Google account.
+    Anyone,
+}
+
+/// A permission on a file, as Drive returns it.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Permission {
+    pub id: String,
+    /// "user", "group", "domain" or "anyone".
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub role: String,
+    pub email_address: Option<String>,
+    pub domain: Option<String>,
+    pub display_name: Option<String>,
+    /// When the permission lapses, only set for temporary shares.
+    pub expiration_time: Option<String>,
+    /// For domain and anyone permissions, whether the file shows up in search.
+    pub allow_file_discovery: Option<bool>,
+    pub pending_owner: bool,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+/// Options for `Drive::share`.
+///
+/// ```no_run
+/// # use std::time::{Duration, SystemTime};
+/// # use google_api::*;
+/// # let drive = Drive::new(String::new(), String::new(), None)?;
+/// # let (file_id, email) = (FileId::unchecked("1aBc"), String::from("ada@example.com"));
+/// let week = SystemTime::now() + Duration::from_secs(7 * 24 * 60 * 60);
+/// let options = ShareOptions::new().expires(week).message("Here's the draft");
+/// drive.share(file_id, Grantee::User(email), Role::Reader, &options)?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ShareOptions {
+    expiration_time: Option<SystemTime>,
+    allow_file_discovery: Option<bool>,
+    send_notification_email: Option<bool>,
+    email_message: Option<String>,
+}
+
+impl ShareOptions {
+    pub fn new() -> ShareOptions {
+        ShareOptions::default()
+    }
+
+    /// Revoke access automatically at given time. Only user and group permissions can expire.
+    pub fn expires(mut self, time: SystemTime) -> ShareOptions {
+        self.expiration_time = Some(time);
+        self
+    }
+
+    /// Let a domain or anyone permission be found through search instead of only the link.
+    pub fn allow_file_discovery(mut self, allow: bool) -> ShareOptions {
+        self.allow_file_discovery = Some(allow);
+        self
+    }
+
+    /// Whether to email users and groups about the share. Drive sends one by default.
+    pub fn send_notification_email(mut self, send: bool) -> ShareOptions {
+        self.send_notification_email = Some(send);
+        self
+    }
+
+    /// Custom text to include in the notification email.
+    pub fn message(mut self, message: &str) -> ShareOptions {
+        self.email_message = Some(message.to_string());
+        self
+    }
+}
+
+/// Links to a file that was shared with anyone who has the link.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShareableLink {
+    /// Link to open the file in the browser.
+    pub web_view_link: String,
+    /// Direct download link. Folders and Google Docs/Sheets/Slides don't have one.
+    pub web_content_link: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct PermissionList {
+    permissions: Vec<Permission>,
+    next_page_token: Option<String>,
+}
+
+impl Drive {
+    /// Transfer ownership of given file to the user with the given email.
+    /// Tries a direct transfer first, falling back to the pending owner flow when Google
+    /// requires the new owner's consent.
+    pub fn transfer_ownership(
+        &self,
+        file_id: impl Into<FileId>,
+        new_owner_email: &str,
+    ) -> Result<OwnershipTransfer, Error> {
+        let file_id = file_id.into();
+        let endpoint = format!("/files/{}/permissions", file_id);
+        let sent = self.post(
+            endpoint.as_str(),
+            Params::new().param("transferOwnership", true),
+            serde_json::json!({
+                "role": "owner",
+                "type": "user",
+                "emailAddress": new_owner_email,
+            }),
+        );
+
+        // Anything other than a consent error is a real failure.
+        let error = match sent {
+            Ok(_) => return Ok(OwnershipTransfer::Completed),
+            Err(error) => error,
+        };
+        match error.reason() {
+            Some("consentRequiredForOwnershipTransfer") | Some("pendingOwnerWriterRequired") => {}
+            _ => return Err(error),
+        }
+
+        // Pending owners have to be writers, so update their existing permission if they have one.
+        let existing: serde_json::Value = self
+            .get(
+                endpoint.as_str(),
+                Params::new().fields("permissions(id,emailAddress)"),
+            )?
+            .check_status()?
+            .json()?;
+        let permission_id = existing["permissions"]
+            .as_array()
+            .and_then(|perms| {
+                perms.iter().find(|p| {
+                    matches!(p["emailAddress"].as_str(), Some(e) if e.eq_ignore_ascii_case(new_owner_email))
+                })
+            })
+            .and_then(|p| p["id"].as_str());
+
+        match permission_id {
+            Some(id) => self.patch(
+                format!("{}/{}", endpoint, id).as_str(),
+                Params::new(),
+                serde_json::json!({"role": "writer", "pendingOwner": true}),
+            )?,
+            None => self.post(
+                endpoint.as_str(),
+                Params::new(),
+                serde_json::json!({
+                    "role": "writer",
+                    "type": "user",
+                    "emailAddress": new_owner_email,
+                    "pendingOwner": true,
+                }),
+            )?,
+        }
+        .check_status()?;
+
+        Ok(OwnershipTransfer::Pending)
+    }
+
+    /// Grant given role on a file. Returns the created permission.
+    pub fn share(
+        &self,
+        file_id: impl Into<FileId>,
+        grantee: Grantee,
+        role: Role,
+        options: &ShareOptions,
+    ) -> Result<Permission, Error> {
+        let file_id = file_id.into();
+        let mut body = serde_json::json!({ "role": role.as_str() });
+        match &grantee {
+            Grantee::User(email) => {
+                body["type"] = "user".into();
+                body["emailAddress"] = email.as_str().into();
+            }
+            Grantee::Group(email) => {
+                body["type"] = "group".into();
+                body["emailAddress"] = email.as_str().into();
+            }
+            Grantee::Domain(domain) => {
+                body["type"] = "domain".into();
+                body["domain"] = domain.as_str().into();
+            }
+            Grantee::Anyone => body["type"] = "anyone".into(),
+        }
+        if let Some(time) = options.expiration_time {
+            body["expirationTime"] = format_rfc3339(time).into();
+        }
+        if let Some(allow) = options.allow_file_discovery {
+            body["allowFileDiscovery"] = allow.into();
+        }
+
+        let mut params = Params::new().fields(PERMISSION_FIELDS);
+        // Drive rejects notification params for domain and anyone permissions.
+        if let Grantee::User(_) | Grantee::Group(_) = grantee {
+            if let Some(send) = options.send_notification_email {
+                params = params.param("sendNotificationEmail", send);
+            }
+            if let Some(message) = &options.email_message {
+                params = params.param("emailMessage", message);
+            }
+        }
+        Ok(self
+            .post(
+                format!("/files/{}/permissions", file_id).as_str(),
+                params,
+                body,
+            )?
+            .check_status()?
+            .json()?)
+    }
+
+    /// Share given file with anyone who has the link, using the given role.
+    /// Returns the links that can be handed out.
+    pub fn make_link_shareable(
+        &self,
+        file_id: impl Into<FileId>,
+        role: Role,
+    ) -> Result<ShareableLink, Error> {
+        let file_id = file_id.into();
+        self.post(
+            format!("/files/{}/permissions", file_id).as_str(),
+            Params::new(),
+            serde_json::json!({"role": role.as_str(), "type": "anyone"}),
+        )?
+        .check_status()?;
+
+        let file: serde_json::Value = self
+            .get(
+                format!("/files/{}", file_id).as_str(),
+                Params::new().fields("webViewLink,webContentLink"),
+            )?
+            .check_status()?
+            .json()?;
+        Ok(ShareableLink {
+            web_view_link: file["webViewLink"].as_str().unwrap_or_default().to_string(),
+            web_content_link: file["webContentLink"].as_str().map(String::from),
+        })
+    }
+
+    /// Every permission on given file.
+    pub fn list_permissions(&self, file_id: impl Into<FileId>) -> Result<Vec<Permission>, Error> {
+        let file_id = file_id.into();
+        let endpoint = format!("/files/{}/permissions", file_id);
+        let fields = format!("nextPageToken,permissions({})", PERMISSION_FIELDS);
+        let mut permissions = vec![];
+        let mut page_token: Option<String> = None;
+        loop {
+            let mut params = Params::new().fields(&fields);
+            if let Some(token) = &page_token {
+                params = params.page_token(token);
+            }
+            let page: PermissionList = self
+                .get(endpoint.as_str(), params)?
+                .check_status()?
+                .json()?;
+            permissions.extend(page.permissions);
+
+            match page.next_page_token {
+                Some(token) => page_token = Some(token),
+                None => break,
+            }
+        }
+        Ok(permissions)
+    }
+
+    /// Revoke a permission, by the id `list_permissions` or `share` returned.
+    pub fn remove_permission(
+        &self,
+        file_id: impl Into<FileId>,
+        permission_id: &str,
+    ) -> Result<(), Error> {
+        let file_id = file_id.into();
+        self.delete(
+            format!("/files/{}/permissions/{}", file_id, permission_id).as_str(),
+            Params::new(),
+        )?
+        .check_status()?;
+        Ok(())
+    }
+}
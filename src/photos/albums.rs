@@ -0,0 +1,88 @@
+use reqwest::Method;
+use serde::Deserialize;
+
+use super::Photos;
+use crate::error::{CheckStatus, Error};
+use crate::models::{de_int64, ExtraFields};
+
+/// An album in the user's library.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Album {
+    pub id: String,
+    pub title: String,
+    /// Link to the album in Google Photos.
+    pub product_url: String,
+    #[serde(deserialize_with = "de_int64")]
+    pub media_items_count: Option<u64>,
+    /// Media item id of the cover photo.
+    pub cover_photo_media_item_id: Option<String>,
+    /// Whether this app created the album. Apps can only add to albums they created.
+    pub is_writeable: bool,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct AlbumList {
+    albums: Vec<Album>,
+    next_page_token: Option<String>,
+}
+
+impl Photos {
+    /// Albums in the user's library, shared albums left out.
+    pub fn list_albums(&self) -> Result<Vec<Album>, Error> {
+        let mut albums = vec![];
+        let mut page_token: Option<String> = None;
+        loop {
+            let mut params = vec![("pageSize", "50")];
+            if let Some(token) = &page_token {
+                params.push(("pageToken", token));
+            }
+            let page: AlbumList = self
+                .request(Method::GET, &["albums"])?
+                .query(&params)
+                .send()?
+                .check_status()?
+                .json()?;
+            albums.extend(page.albums);
+
+            match page.next_page_token {
+                Some(token) => page_token = Some(token),
+                None => break,
+            }
+        }
+        Ok(albums)
+    }
+
+    pub fn get_album(&self, album_id: &str) -> Result<Album, Error> {
+        Ok(self
+            .request(Method::GET, &["albums", album_id])?
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    pub fn create_album(&self, title: &str) -> Result<Album, Error> {
+        Ok(self
+            .request(Method::POST, &["albums"])?
+            .json(&serde_json::json!({ "album": { "title": title } }))
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    /// Add media items already in the library to an album this app created. Sent 50 at a
+    /// time, the most the API takes per request.
+    pub fn add_to_album(&self, album_id: &str, media_item_ids: &[&str]) -> Result<(), Error> {
+        for chunk in media_item_ids.chunks(50) {
+            let endpoint = format!("{}:batchAddMediaItems", album_id);
+            self.request(Method::POST, &["albums", &endpoint])?
+                .json(&serde_json::json!({ "mediaItemIds": chunk }))
+                .send()?
+                .check_status()?;
+        }
+        Ok(())
+    }
+}
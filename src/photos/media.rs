@@ -0,0 +1,350 @@
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+
+use super::Photos;
+use crate::error::{CheckStatus, Error};
+use crate::mime::MimeType;
+use crate::models::{de_int64, ExtraFields};
+
+/// Most media items `mediaItems:batchCreate` takes at once.
+const BATCH_CREATE_LIMIT: usize = 50;
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct MediaMetadata {
+    /// When the photo or video was taken, as an RFC 3339 timestamp.
+    pub creation_time: String,
+    #[serde(deserialize_with = "de_int64")]
+    pub width: Option<u64>,
+    #[serde(deserialize_with = "de_int64")]
+    pub height: Option<u64>,
+    /// Set for videos.
+    pub video: Option<serde_json::Value>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+/// A photo or video in the user's library.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct MediaItem {
+    pub id: String,
+    pub description: Option<String>,
+    /// Link to the item in Google Photos.
+    pub product_url: String,
+    /// Short-lived link to the content, valid for about an hour. `Photos::download` uses it.
+    pub base_url: String,
+    pub mime_type: String,
+    pub filename: String,
+    pub media_metadata: MediaMetadata,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl MediaItem {
+    pub fn is_video(&self) -> bool {
+        self.media_metadata.video.is_some() || self.mime_type.starts_with("video/")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum MediaType {
+    Photo,
+    Video,
+}
+
+/// Which media items `Photos::search` returns, built up with chained setters. The API
+/// doesn't combine an album with the other filters.
+///
+/// ```
+/// # use google_api::*;
+/// let search = MediaSearch::new()
+///     .dates("2024-07-01", "2024-07-31")
+///     .category("LANDSCAPES")
+///     .media_type(MediaType::Photo);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MediaSearch {
+    album_id: Option<String>,
+    date_ranges: Vec<(String, String)>,
+    categories: Vec<String>,
+    media_type: Option<MediaType>,
+}
+
+impl MediaSearch {
+    pub fn new() -> MediaSearch {
+        MediaSearch::default()
+    }
+
+    /// Items in given album.
+    pub fn in_album(mut self, album_id: &str) -> MediaSearch {
+        self.album_id = Some(album_id.to_string());
+        self
+    }
+
+    /// Items taken between two days (`yyyy-mm-dd`), both included. Can be given more than
+    /// once, up to 5 ranges, to match any of them.
+    pub fn dates(mut self, start: &str, end: &str) -> MediaSearch {
+        self.date_ranges.push((start.to_string(), end.to_string()));
+        self
+    }
+
+    /// Items Google classified in given content category, e.g. `LANDSCAPES`, `PEOPLE`,
+    /// `RECEIPTS` or `DOCUMENTS`. Can be given more than once to match any of them.
+    pub fn category(mut self, category: &str) -> MediaSearch {
+        self.categories.push(category.to_uppercase());
+        self
+    }
+
+    pub fn media_type(mut self, media_type: MediaType) -> MediaSearch {
+        self.media_type = Some(media_type);
+        self
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        let mut body = serde_json::json!({ "pageSize": 100 });
+        if let Some(album_id) = &self.album_id {
+            body["albumId"] = serde_json::json!(album_id);
+        }
+        let mut filters = serde_json::json!({});
+        if !self.date_ranges.is_empty() {
+            let ranges: Vec<serde_json::Value> = self
+                .date_ranges
+                .iter()
+                .map(|(start, end)| {
+                    serde_json::json!({ "startDate": date_json(start), "endDate": date_json(end) })
+                })
+                .collect();
+            filters["dateFilter"] = serde_json::json!({ "ranges": ranges });
+        }
+        if !self.categories.is_empty() {
+            filters["contentFilter"] =
+                serde_json::json!({ "includedContentCategories": self.categories });
+        }
+        if let Some(media_type) = self.media_type {
+            filters["mediaTypeFilter"] = serde_json::json!({ "mediaTypes": [media_type] });
+        }
+        if filters != serde_json::json!({}) {
+            body["filters"] = filters;
+        }
+        body
+    }
+}
+
+/// `yyyy-mm-dd` as the API's `Date`. Parts that don't parse are 0, which the API reads as
+/// "any".
+fn date_json(date: &str) -> serde_json::Value {
+    let mut parts = date.splitn(3, '-').map(|p| p.parse::<u32>().unwrap_or(0));
+    serde_json::json!({
+        "year": parts.next().unwrap_or(0),
+        "month": parts.next().unwrap_or(0),
+        "day": parts.next().unwrap_or(0),
+    })
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct MediaItemList {
+    media_items: Vec<MediaItem>,
+    next_page_token: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct Status {
+    code: i32,
+    message: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct NewMediaItemResult {
+    status: Status,
+    media_item: Option<MediaItem>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct NewMediaItemResults {
+    new_media_item_results: Vec<NewMediaItemResult>,
+}
+
+impl Photos {
+    /// Media items matching the search, following every page. An empty search lists the
+    /// whole library, newest first.
+    pub fn search(&self, search: &MediaSearch) -> Result<Vec<MediaItem>, Error> {
+        let body = search.to_json();
+        let mut items = vec![];
+        let mut page_token: Option<String> = None;
+        loop {
+            let mut page_body = body.clone();
+            if let Some(token) = &page_token {
+                page_body["pageToken"] = serde_json::json!(token);
+            }
+            let page: MediaItemList = self
+                .request(Method::POST, &["mediaItems:search"])?
+                .json(&page_body)
+                .send()?
+                .check_status()?
+                .json()?;
+            items.extend(page.media_items);
+
+            match page.next_page_token {
+                Some(token) => page_token = Some(token),
+                None => break,
+            }
+        }
+        Ok(items)
+    }
+
+    pub fn get_media_item(&self, media_item_id: &str) -> Result<MediaItem, Error> {
+        Ok(self
+            .request(Method::GET, &["mediaItems", media_item_id])?
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    /// Send a file's bytes to Photos. The token answered is what `mediaItems:batchCreate`
+    /// takes to turn the bytes into a media item, within a day.
+    fn upload_bytes(&self, path: &Path) -> Result<String, Error> {
+        let content = fs::read(path)?;
+        let mime_type = MimeType::from_path(path);
+        Ok(self
+            .request(Method::POST, &["uploads"])?
+            .header("Content-Type", "application/octet-stream")
+            .header("X-Goog-Upload-Content-Type", mime_type.as_str())
+            .header("X-Goog-Upload-Protocol", "raw")
+            .body(content)
+            .send()?
+            .check_status()?
+            .text()?)
+    }
+
+    /// Upload a photo or video to the library, optionally into an album this app created.
+    pub fn upload(
+        &self,
+        path: &Path,
+        album_id: Option<&str>,
+        description: Option<&str>,
+    ) -> Result<MediaItem, Error> {
+        let token = self.upload_bytes(path)?;
+        let mut results =
+            self.create_media_items(&[(token, file_name(path))], album_id, description)?;
+        results.remove(0)
+    }
+
+    /// Upload many files, optionally into an album this app created. Each file gets its own
+    /// result, in the order given, so one failure doesn't stop the rest.
+    pub fn upload_all(
+        &self,
+        paths: &[PathBuf],
+        album_id: Option<&str>,
+    ) -> Vec<Result<MediaItem, Error>> {
+        let mut results = vec![];
+        for chunk in paths.chunks(BATCH_CREATE_LIMIT) {
+            let tokens: Vec<Result<String, Error>> =
+                chunk.iter().map(|path| self.upload_bytes(path)).collect();
+            let uploads: Vec<(String, String)> = tokens
+                .iter()
+                .zip(chunk)
+                .filter_map(|(token, path)| {
+                    token.as_ref().ok().map(|t| (t.clone(), file_name(path)))
+                })
+                .collect();
+            let mut created = match self.create_media_items(&uploads, album_id, None) {
+                Ok(created) => created,
+                Err(e) => {
+                    let message = e.to_string();
+                    uploads
+                        .iter()
+                        .map(|_| Err(Error::UnexpectedResponse(message.clone())))
+                        .collect()
+                }
+            }
+            .into_iter();
+            for token in tokens {
+                results.push(match token {
+                    Ok(_) => created.next().unwrap_or_else(|| {
+                        Err(Error::UnexpectedResponse(String::from(
+                            "missing batch create result",
+                        )))
+                    }),
+                    Err(e) => Err(e),
+                });
+            }
+        }
+        results
+    }
+
+    /// Turn upload tokens into media items, one result per token.
+    fn create_media_items(
+        &self,
+        uploads: &[(String, String)],
+        album_id: Option<&str>,
+        description: Option<&str>,
+    ) -> Result<Vec<Result<MediaItem, Error>>, Error> {
+        if uploads.is_empty() {
+            return Ok(vec![]);
+        }
+        let new_items: Vec<serde_json::Value> = uploads
+            .iter()
+            .map(|(token, name)| {
+                serde_json::json!({
+                    "description": description.unwrap_or_default(),
+                    "simpleMediaItem": { "uploadToken": token, "fileName": name },
+                })
+            })
+            .collect();
+        let mut body = serde_json::json!({ "newMediaItems": new_items });
+        if let Some(album_id) = album_id {
+            body["albumId"] = serde_json::json!(album_id);
+        }
+        let resp: NewMediaItemResults = self
+            .request(Method::POST, &["mediaItems:batchCreate"])?
+            .json(&body)
+            .send()?
+            .check_status()?
+            .json()?;
+        if resp.new_media_item_results.len() != uploads.len() {
+            return Err(Error::UnexpectedResponse(format!(
+                "batch create answered {} results for {} uploads",
+                resp.new_media_item_results.len(),
+                uploads.len()
+            )));
+        }
+        Ok(resp
+            .new_media_item_results
+            .into_iter()
+            .map(|result| match result.media_item {
+                Some(item) if result.status.code == 0 => Ok(item),
+                _ => Err(Error::UnexpectedResponse(result.status.message)),
+            })
+            .collect())
+    }
+
+    /// Download a media item's original content to given path: the full photo, or the
+    /// video file.
+    pub fn download(&self, item: &MediaItem, path: &Path) -> Result<(), Error> {
+        let suffix = if item.is_video() { "=dv" } else { "=d" };
+        let mut resp = self
+            .client
+            .get(&format!("{}{}", item.base_url, suffix))
+            .send()?
+            .check_status()?;
+        let mut file = File::create(path)?;
+        resp.copy_to(&mut file)?;
+        Ok(())
+    }
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("upload")
+        .to_string()
+}
@@ -0,0 +1,56 @@
+use reqwest::blocking::RequestBuilder;
+use reqwest::Method;
+
+use crate::api;
+use crate::auth::Auth;
+use crate::error::Error;
+use crate::scope::Scope;
+
+mod albums;
+mod media;
+
+pub use albums::Album;
+pub use media::{MediaItem, MediaMetadata, MediaSearch, MediaType};
+
+const PHOTOS_BASE_URL: &str = "https://photoslibrary.googleapis.com/v1";
+
+/// Google Photos Library client. Shares its saved token with the other clients.
+///
+/// ```no_run
+/// # use std::path::Path;
+/// # use google_api::*;
+/// # let (client_id, client_secret) = (String::new(), String::new());
+/// let photos = Photos::new(client_id, client_secret, None)?;
+/// let album = photos.create_album("Holiday 2024")?;
+/// photos.upload(Path::new("beach.jpg"), Some(&album.id), None)?;
+/// let july = MediaSearch::new().dates("2024-07-01", "2024-07-31").category("LANDSCAPES");
+/// let landscapes = photos.search(&july)?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug)]
+pub struct Photos {
+    auth: Auth,
+    client: reqwest::blocking::Client,
+}
+
+impl Photos {
+    /// Authenticate with given OAuth client. `scopes` defaults to `Scope::PhotosLibrary`.
+    pub fn new(
+        client_id: String,
+        client_secret: String,
+        scopes: Option<Vec<String>>,
+    ) -> Result<Photos, Error> {
+        let scopes = scopes.unwrap_or_else(|| vec![Scope::PhotosLibrary.into()]);
+
+        Ok(Photos {
+            auth: Auth::from_config(&client_id, &client_secret, &scopes)?,
+            client: reqwest::blocking::Client::new(),
+        })
+    }
+
+    /// Authorized request for the endpoint made of given path segments, e.g.
+    /// `["albums", id]`.
+    fn request(&self, method: Method, segments: &[&str]) -> Result<RequestBuilder, Error> {
+        api::request(&self.client, &self.auth, PHOTOS_BASE_URL, method, segments)
+    }
+}
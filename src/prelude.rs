@@ -0,0 +1,21 @@
+//! The types most programs need, in one import:
+//!
+//! ```no_run
+//! use google_api::prelude::*;
+//!
+//! # let (client_id, client_secret) = (String::new(), String::new());
+//! let drive = Drive::builder()
+//!     .client_id(client_id)
+//!     .client_secret(client_secret)
+//!     .scope(Scope::DriveReadonly)
+//!     .build()?;
+//! let reports = drive.search(&Query::new().name_contains("report").trashed(false))?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! Everything else is exported from the crate root.
+
+pub use crate::{
+    Calendar, DownloadOptions, Drive, DriveBuilder, DriveFile, Error, FileId, Gmail, GoogleClient,
+    ListOptions, MimeType, Params, Query, RetryPolicy, Scope, Sheets, Timestamp, UploadOptions,
+};
@@ -0,0 +1,91 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::config_dir;
+use crate::error::{Context, Error};
+
+/// Environment variable naming the profile to sign in as. It wins over `switch_profile`.
+pub const PROFILE_ENV: &str = "GOOGLE_API_PROFILE";
+/// Profile used when none is picked, the token saved before profiles existed.
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Profiles are separate saved tokens, one per Google account, e.g. `work` and `personal`.
+/// Every client signs in as the current profile: `GOOGLE_API_PROFILE` if set, else the last
+/// one passed to `switch_profile`, else `default`.
+pub fn current_profile() -> String {
+    if let Ok(name) = env::var(PROFILE_ENV) {
+        if !name.is_empty() {
+            return name;
+        }
+    }
+    config_dir()
+        .ok()
+        .and_then(|dir| fs::read_to_string(dir.join("profile")).ok())
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| String::from(DEFAULT_PROFILE))
+}
+
+/// Make given profile the current one for this and later runs. A new profile asks the user
+/// to sign in the first time a client is created with it.
+pub fn switch_profile(name: &str) -> Result<(), Error> {
+    check_name(name)?;
+    let dir = config_dir()?;
+    fs::create_dir_all(&dir).with_context(|| "creating config dir")?;
+    fs::write(dir.join("profile"), name).with_context(|| "saving current profile")
+}
+
+/// Profiles with a saved token, sorted.
+pub fn profiles() -> Result<Vec<String>, Error> {
+    let dir = config_dir()?;
+    let mut names = vec![];
+    if is_saved(&dir.join("google_api")) {
+        names.push(String::from(DEFAULT_PROFILE));
+    }
+    let entries = match fs::read_dir(dir.join("profiles")) {
+        Ok(entries) => entries,
+        Err(_) => return Ok(names),
+    };
+    for entry in entries {
+        let entry = entry.with_context(|| "reading profiles dir")?;
+        if is_saved(&entry.path()) {
+            names.push(entry.file_name().to_string_lossy().into_owned());
+        }
+    }
+    names.sort();
+    Ok(names)
+}
+
+/// File the current profile's token is saved in.
+pub(crate) fn token_path() -> Result<PathBuf, Error> {
+    let name = current_profile();
+    check_name(&name)?;
+    let dir = config_dir()?;
+    if name == DEFAULT_PROFILE {
+        Ok(dir.join("google_api"))
+    } else {
+        Ok(dir.join("profiles").join(name))
+    }
+}
+
+/// Names end up as file names, keep them to something safe on any filesystem.
+fn check_name(name: &str) -> Result<(), Error> {
+    let valid = |c: char| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.';
+    if name.is_empty() || name.starts_with('.') || !name.chars().all(valid) {
+        let message = format!(
+            "invalid profile name {:?}, use letters, digits, '-', '_' and '.'",
+            name
+        );
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, message).into());
+    }
+    Ok(())
+}
+
+/// The token file is created empty before the first sign in.
+fn is_saved(path: &Path) -> bool {
+    fs::metadata(path)
+        .map(|m| m.is_file() && m.len() > 0)
+        .unwrap_or(false)
+}
@@ -0,0 +1,83 @@
+use std::collections::HashMap;
+
+use crate::error::{CheckStatus, Error};
+use crate::file_id::FileId;
+use crate::list::{escape_query, ListOptions};
+use crate::models::DriveFile;
+use crate::params::Params;
+use crate::Drive;
+
+/// Which set of custom key/value properties to work with.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PropertyVisibility {
+    /// `properties`, visible to every app.
+    Public,
+    /// `appProperties`, only visible to the app (client id) that set them.
+    Private,
+}
+
+impl PropertyVisibility {
+    fn field(&self) -> &'static str {
+        match self {
+            PropertyVisibility::Public => "properties",
+            PropertyVisibility::Private => "appProperties",
+        }
+    }
+}
+
+impl Drive {
+    /// Get custom properties of given file.
+    pub fn properties(
+        &self,
+        file_id: impl Into<FileId>,
+        visibility: PropertyVisibility,
+    ) -> Result<HashMap<String, String>, Error> {
+        let file_id = file_id.into();
+        let resp: serde_json::Value = self
+            .get(
+                format!("/files/{}", file_id).as_str(),
+                Params::new().fields(visibility.field()),
+            )?
+            .check_status()?
+            .json()?;
+        Ok(serde_json::from_value(resp[visibility.field()].clone()).unwrap_or_default())
+    }
+
+    /// Set custom properties on given file. Keys mapped to `None` are removed, keys that
+    /// aren't in the map are left alone. Returns the file's properties after the update.
+    pub fn set_properties(
+        &self,
+        file_id: impl Into<FileId>,
+        visibility: PropertyVisibility,
+        properties: &HashMap<String, Option<String>>,
+    ) -> Result<HashMap<String, String>, Error> {
+        let file_id = file_id.into();
+        let mut body = serde_json::json!({});
+        body[visibility.field()] = serde_json::json!(properties);
+        let resp: serde_json::Value = self
+            .patch(
+                format!("/files/{}", file_id).as_str(),
+                Params::new().fields(visibility.field()),
+                body,
+            )?
+            .check_status()?
+            .json()?;
+        Ok(serde_json::from_value(resp[visibility.field()].clone()).unwrap_or_default())
+    }
+
+    /// Find non-trashed files with given custom property set to given value.
+    pub fn find_by_property(
+        &self,
+        key: &str,
+        value: &str,
+        visibility: PropertyVisibility,
+    ) -> Result<Vec<DriveFile>, Error> {
+        let query = format!(
+            "{} has {{ key='{}' and value='{}' }} and trashed = false",
+            visibility.field(),
+            escape_query(key),
+            escape_query(value)
+        );
+        self.list_files(&ListOptions::new().query(&query))
+    }
+}
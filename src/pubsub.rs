@@ -0,0 +1,279 @@
+use std::collections::HashMap;
+
+use reqwest::blocking::RequestBuilder;
+use reqwest::Method;
+use serde::Deserialize;
+
+use crate::api;
+use crate::auth::Auth;
+use crate::error::{CheckStatus, Error};
+use crate::gmail::GmailNotification;
+use crate::models::ExtraFields;
+use crate::scope::Scope;
+
+const PUBSUB_BASE_URL: &str = "https://pubsub.googleapis.com/v1";
+
+/// Service account Gmail publishes mailbox changes as, see `PubSub::allow_publisher`.
+pub const GMAIL_PUBLISHER: &str = "gmail-api-push@system.gserviceaccount.com";
+/// Service account Forms publishes form watch events as.
+pub const FORMS_PUBLISHER: &str = "forms-notifications@system.gserviceaccount.com";
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct Topic {
+    /// `projects/<project>/topics/<topic>`, which `Gmail::watch` and `Forms::watch` take.
+    pub name: String,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Subscription {
+    /// `projects/<project>/subscriptions/<subscription>`.
+    pub name: String,
+    pub topic: String,
+    /// Seconds a pulled message stays hidden from other pulls before it's delivered again,
+    /// unless acknowledged.
+    pub ack_deadline_seconds: u32,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct PubsubMessage {
+    pub message_id: String,
+    pub publish_time: String,
+    pub attributes: HashMap<String, String>,
+    /// Payload, base64 encoded. `payload` decodes it.
+    pub data: String,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl PubsubMessage {
+    pub fn payload(&self) -> Result<Vec<u8>, Error> {
+        base64::decode(&self.data)
+            .map_err(|e| Error::UnexpectedResponse(format!("message data isn't base64: {}", e)))
+    }
+
+    /// Read the message as what Gmail publishes for a watched mailbox.
+    pub fn gmail_notification(&self) -> Result<GmailNotification, Error> {
+        Ok(serde_json::from_slice(&self.payload()?)?)
+    }
+}
+
+/// A message handed out by `PubSub::pull`. Acknowledge it with its `ack_id` once handled,
+/// or it's delivered again.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ReceivedMessage {
+    pub ack_id: String,
+    pub message: PubsubMessage,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct PullResponse {
+    received_messages: Vec<ReceivedMessage>,
+}
+
+/// Minimal Cloud Pub/Sub client: enough to create the topic Gmail or Forms watches publish
+/// to, and pull the notifications from a subscription without running a web server.
+/// Topics and subscriptions are given by their short names, within the client's project.
+///
+/// ```no_run
+/// # use google_api::*;
+/// # let (client_id, client_secret) = (String::new(), String::new());
+/// # let gmail = Gmail::new(String::new(), String::new(), None)?;
+/// let pubsub = PubSub::new(client_id, client_secret, "my-project", None)?;
+/// let topic = pubsub.create_topic("gmail")?;
+/// pubsub.allow_publisher("gmail", GMAIL_PUBLISHER)?;
+/// pubsub.create_subscription("gmail-pull", "gmail", None)?;
+/// gmail.watch(&topic.name, &[])?;
+/// loop {
+///     let received = pubsub.pull("gmail-pull", 10)?;
+///     for r in &received {
+///         let changed = r.message.gmail_notification()?;
+///         // gmail.list_history(...) from the last history id seen
+///     }
+///     let ack_ids: Vec<&str> = received.iter().map(|r| r.ack_id.as_str()).collect();
+///     pubsub.acknowledge("gmail-pull", &ack_ids)?;
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug)]
+pub struct PubSub {
+    auth: Auth,
+    client: reqwest::blocking::Client,
+    project: String,
+}
+
+impl PubSub {
+    /// Authenticate with given OAuth client, working in given Cloud project. `scopes`
+    /// defaults to `Scope::Pubsub`.
+    pub fn new(
+        client_id: String,
+        client_secret: String,
+        project_id: &str,
+        scopes: Option<Vec<String>>,
+    ) -> Result<PubSub, Error> {
+        let scopes = scopes.unwrap_or_else(|| vec![Scope::Pubsub.into()]);
+
+        Ok(PubSub {
+            auth: Auth::from_config(&client_id, &client_secret, &scopes)?,
+            client: reqwest::blocking::Client::new(),
+            project: project_id.to_string(),
+        })
+    }
+
+    /// Full name of a topic of this project, as the watch methods want it.
+    pub fn topic_path(&self, topic: &str) -> String {
+        format!("projects/{}/topics/{}", self.project, topic)
+    }
+
+    /// Request for a topic or subscription of the project, e.g. `("topics", "gmail")`, with
+    /// an optional verb like `:pull`.
+    fn request(
+        &self,
+        method: Method,
+        kind: &str,
+        name: &str,
+        verb: &str,
+    ) -> Result<RequestBuilder, Error> {
+        let name = format!("{}{}", name, verb);
+        api::request(
+            &self.client,
+            &self.auth,
+            PUBSUB_BASE_URL,
+            method,
+            &["projects", &self.project, kind, &name],
+        )
+    }
+
+    pub fn create_topic(&self, topic: &str) -> Result<Topic, Error> {
+        Ok(self
+            .request(Method::PUT, "topics", topic, "")?
+            .json(&serde_json::json!({}))
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    pub fn delete_topic(&self, topic: &str) -> Result<(), Error> {
+        self.request(Method::DELETE, "topics", topic, "")?
+            .send()?
+            .check_status()?;
+        Ok(())
+    }
+
+    /// Let given account publish to a topic, e.g. `GMAIL_PUBLISHER` so Gmail can deliver
+    /// mailbox changes. Other bindings on the topic are kept.
+    pub fn allow_publisher(&self, topic: &str, account_email: &str) -> Result<(), Error> {
+        let mut policy: serde_json::Value = self
+            .request(Method::GET, "topics", topic, ":getIamPolicy")?
+            .send()?
+            .check_status()?
+            .json()?;
+        let member = format!("serviceAccount:{}", account_email);
+        let mut bindings = policy["bindings"].as_array().cloned().unwrap_or_default();
+        match bindings
+            .iter_mut()
+            .find(|b| b["role"] == "roles/pubsub.publisher")
+        {
+            Some(binding) => match binding["members"].as_array_mut() {
+                Some(members) if members.iter().any(|m| m == &member) => return Ok(()),
+                Some(members) => members.push(serde_json::json!(member)),
+                None => binding["members"] = serde_json::json!([member]),
+            },
+            None => bindings.push(serde_json::json!({
+                "role": "roles/pubsub.publisher",
+                "members": [member],
+            })),
+        }
+        policy["bindings"] = serde_json::json!(bindings);
+
+        self.request(Method::POST, "topics", topic, ":setIamPolicy")?
+            .json(&serde_json::json!({ "policy": policy }))
+            .send()?
+            .check_status()?;
+        Ok(())
+    }
+
+    /// Publish a message to a topic. Returns its message id.
+    pub fn publish(
+        &self,
+        topic: &str,
+        payload: &[u8],
+        attributes: &HashMap<String, String>,
+    ) -> Result<String, Error> {
+        let resp: serde_json::Value = self
+            .request(Method::POST, "topics", topic, ":publish")?
+            .json(&serde_json::json!({
+                "messages": [{ "data": base64::encode(payload), "attributes": attributes }],
+            }))
+            .send()?
+            .check_status()?
+            .json()?;
+        resp["messageIds"][0]
+            .as_str()
+            .map(String::from)
+            .ok_or_else(|| Error::UnexpectedResponse(String::from("publish returned no id")))
+    }
+
+    /// Create a pull subscription to a topic. `ack_deadline_seconds` defaults to 10.
+    pub fn create_subscription(
+        &self,
+        subscription: &str,
+        topic: &str,
+        ack_deadline_seconds: Option<u32>,
+    ) -> Result<Subscription, Error> {
+        Ok(self
+            .request(Method::PUT, "subscriptions", subscription, "")?
+            .json(&serde_json::json!({
+                "topic": self.topic_path(topic),
+                "ackDeadlineSeconds": ack_deadline_seconds.unwrap_or(10),
+            }))
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    pub fn delete_subscription(&self, subscription: &str) -> Result<(), Error> {
+        self.request(Method::DELETE, "subscriptions", subscription, "")?
+            .send()?
+            .check_status()?;
+        Ok(())
+    }
+
+    /// Up to `max_messages` waiting messages. May return fewer, or none, even when more are
+    /// waiting.
+    pub fn pull(
+        &self,
+        subscription: &str,
+        max_messages: u32,
+    ) -> Result<Vec<ReceivedMessage>, Error> {
+        let resp: PullResponse = self
+            .request(Method::POST, "subscriptions", subscription, ":pull")?
+            .json(&serde_json::json!({ "maxMessages": max_messages }))
+            .send()?
+            .check_status()?
+            .json()?;
+        Ok(resp.received_messages)
+    }
+
+    /// Mark pulled messages as handled so they aren't delivered again.
+    pub fn acknowledge(&self, subscription: &str, ack_ids: &[&str]) -> Result<(), Error> {
+        if ack_ids.is_empty() {
+            return Ok(());
+        }
+        self.request(Method::POST, "subscriptions", subscription, ":acknowledge")?
+            .json(&serde_json::json!({ "ackIds": ack_ids }))
+            .send()?
+            .check_status()?;
+        Ok(())
+    }
+}
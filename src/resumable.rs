@@ -0,0 +1,473 @@
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::thread;
+
+use reqwest::blocking::{RequestBuilder, Response};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{CheckStatus, Context, Error};
+use crate::models::{DriveFile, FILE_FIELDS};
+use crate::retry::RetryPolicy;
+use crate::upload::UploadOptions;
+use crate::{Drive, DRIVE_UPLOAD_URL};
+
+/// Chunks have to be a multiple of this, except for the last one.
+const CHUNK_GRANULARITY: usize = 256 * 1024;
+const DEFAULT_CHUNK_SIZE: usize = 32 * CHUNK_GRANULARITY;
+/// Files bigger than this are sent with the resumable protocol rather than in one request.
+pub(crate) const RESUMABLE_THRESHOLD: u64 = 5 * 1024 * 1024;
+
+/// An in-progress resumable upload. Persist it with `persist_to` and it can be picked up
+/// again with `ResumableUpload::load` after the process restarts.
+/// Drive keeps upload sessions for about a week.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ResumableUpload {
+    pub session_uri: String,
+    pub path: PathBuf,
+    pub size: u64,
+    /// Bytes Drive has confirmed receiving.
+    pub offset: u64,
+    chunk_size: usize,
+    #[serde(skip)]
+    state_file: Option<PathBuf>,
+}
+
+impl ResumableUpload {
+    /// Set how many bytes are sent per request. Rounded down to a multiple of 256KiB.
+    pub fn chunk_size(mut self, bytes: usize) -> ResumableUpload {
+        self.chunk_size = (bytes / CHUNK_GRANULARITY).max(1) * CHUNK_GRANULARITY;
+        self
+    }
+
+    /// Save upload state to given file, and keep it updated after every chunk. The file is
+    /// removed once the upload completes.
+    pub fn persist_to(mut self, state_file: PathBuf) -> Result<ResumableUpload, Error> {
+        self.state_file = Some(state_file);
+        self.save()?;
+        Ok(self)
+    }
+
+    /// Load an upload saved with `persist_to`.
+    pub fn load(state_file: PathBuf) -> Result<ResumableUpload, Error> {
+        let f = File::open(&state_file)?;
+        let mut upload: ResumableUpload = serde_json::from_reader(BufReader::new(f))?;
+        upload.state_file = Some(state_file);
+        Ok(upload)
+    }
+
+    fn save(&self) -> Result<(), Error> {
+        if let Some(state_file) = &self.state_file {
+            if let Some(dir) = state_file.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            let f = File::create(state_file)?;
+            serde_json::to_writer_pretty(BufWriter::new(f), self)?;
+        }
+        Ok(())
+    }
+
+    fn finish(&self) -> Result<(), Error> {
+        if let Some(state_file) = &self.state_file {
+            if state_file.exists() {
+                fs::remove_file(state_file)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Parse the last confirmed byte out of a `Range: bytes=0-1234` header.
+pub(crate) fn confirmed_offset(resp: &reqwest::blocking::Response) -> u64 {
+    resp.headers()
+        .get("Range")
+        .and_then(|r| r.to_str().ok())
+        .and_then(|r| r.rsplit('-').next())
+        .and_then(|end| end.parse::<u64>().ok())
+        .map_or(0, |end| end + 1)
+}
+
+/// Send a local file to an open resumable session chunk by chunk, starting at `*offset` and
+/// keeping it at the last byte the server confirmed. Returns the final response, which carries
+/// the created resource.
+pub(crate) fn send_file_chunks<F>(
+    put: F,
+    retry: &RetryPolicy,
+    path: &Path,
+    size: u64,
+    offset: &mut u64,
+) -> Result<Response, Error>
+where
+    F: Fn() -> RequestBuilder,
+{
+    loop {
+        if let Some(resp) = send_chunk(&put, retry, path, size, DEFAULT_CHUNK_SIZE, offset)? {
+            return Ok(resp);
+        }
+        log::debug!("uploaded {}/{} bytes", offset, size);
+    }
+}
+
+/// Send the chunk of the file at `path` starting at `*offset` with a PUT made by `put`, and
+/// move `*offset` to the last byte the server confirmed. Server errors and dropped connections
+/// are retried as `retry` says, after asking the server how much of the chunk arrived. Returns
+/// the final response once the server has the whole file, `None` while there's more to send.
+fn send_chunk<F>(
+    put: F,
+    retry: &RetryPolicy,
+    path: &Path,
+    size: u64,
+    chunk_size: usize,
+    offset: &mut u64,
+) -> Result<Option<Response>, Error>
+where
+    F: Fn() -> RequestBuilder,
+{
+    let mut failures = 0;
+    loop {
+        let mut file = File::open(path)?;
+        file.seek(SeekFrom::Start(*offset))?;
+        let mut chunk = Vec::with_capacity(chunk_size);
+        file.take(chunk_size as u64).read_to_end(&mut chunk)?;
+        let end = *offset + chunk.len() as u64;
+        let content_range = if chunk.is_empty() {
+            format!("bytes */{}", size)
+        } else {
+            format!("bytes {}-{}/{}", offset, end - 1, size)
+        };
+
+        let resp = put()
+            .header("Content-Range", content_range)
+            .body(chunk)
+            .send();
+        match resp {
+            Ok(r) if r.status() == StatusCode::PERMANENT_REDIRECT => {
+                *offset = confirmed_offset(&r);
+                return Ok(None);
+            }
+            Ok(r) if r.status().is_success() => return Ok(Some(r)),
+            // Server errors and dropped connections are worth retrying, anything else isn't.
+            Ok(ref r) if r.status().is_server_error() && failures < retry.max_retries => {}
+            Err(_) if failures < retry.max_retries => {}
+            Ok(r) => return Err(Error::from_status(r)),
+            Err(e) => return Err(Error::Http(e)),
+        }
+
+        failures += 1;
+        log::debug!("chunk upload failed, retry {}/{}", failures, retry.max_retries);
+        thread::sleep(retry.delay(failures));
+        let status = put()
+            .header("Content-Range", format!("bytes */{}", size))
+            .header("Content-Length", 0)
+            .send()?;
+        if status.status().is_success() {
+            return Ok(Some(status));
+        }
+        if status.status() != StatusCode::PERMANENT_REDIRECT {
+            return Err(Error::from_status(status));
+        }
+        *offset = confirmed_offset(&status);
+    }
+}
+
+impl Drive {
+    /// Open a resumable upload session for file at given path. Nothing is uploaded until
+    /// `resume_upload` is called.
+    pub fn start_resumable_upload(
+        &self,
+        path: &Path,
+        options: &UploadOptions,
+    ) -> Result<ResumableUpload, Error> {
+        let size = fs::metadata(path)
+            .with_context(|| format!("reading {}", path.display()))?
+            .len();
+        let session_uri = self
+            .open_session(None, path, options, &options.media_type(path), Some(size))
+            .with_context(|| format!("starting upload of {}", path.display()))?;
+        Ok(ResumableUpload {
+            session_uri,
+            path: path.to_path_buf(),
+            size,
+            offset: 0,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            state_file: None,
+        })
+    }
+
+    /// Upload a local file as a new file, or as new content of `file_id`, in one request if
+    /// it's small and with the resumable protocol, one chunk in memory at a time, if it's big.
+    pub(crate) fn send_file(
+        &self,
+        file_id: Option<&str>,
+        path: &Path,
+        options: &UploadOptions,
+    ) -> Result<DriveFile, Error> {
+        let size = fs::metadata(path)?.len();
+        if size <= RESUMABLE_THRESHOLD {
+            let content = fs::read(path)?;
+            return self.send_multipart(file_id, path, &content, options);
+        }
+        let session_uri =
+            self.open_session(file_id, path, options, &options.media_type(path), Some(size))?;
+        let mut upload = ResumableUpload {
+            session_uri,
+            path: path.to_path_buf(),
+            size,
+            offset: 0,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            state_file: None,
+        };
+        self.resume_upload(&mut upload)
+    }
+
+    /// Start a resumable session creating a file, or updating `file_id`, and return its URI.
+    /// `path` only provides the default name. The size can be left out when it isn't known
+    /// up front.
+    fn open_session(
+        &self,
+        file_id: Option<&str>,
+        path: &Path,
+        options: &UploadOptions,
+        media_type: &str,
+        size: Option<u64>,
+    ) -> Result<String, Error> {
+        let mut params = vec![
+            ("uploadType", String::from("resumable")),
+            ("supportsAllDrives", String::from("true")),
+            ("fields", String::from(FILE_FIELDS)),
+        ];
+        params.extend(options.query_params(file_id.is_some()));
+        let req = match file_id {
+            Some(id) => self
+                .client
+                .patch(format!("{}/files/{}", DRIVE_UPLOAD_URL, id).as_str()),
+            None => self
+                .client
+                .post(format!("{}/files", DRIVE_UPLOAD_URL).as_str()),
+        };
+        let mut req = req
+            .query(&params)
+            .header("Authorization", self.auth.bearer()?)
+            .header("X-Upload-Content-Type", media_type);
+        if let Some(size) = size {
+            req = req.header("X-Upload-Content-Length", size);
+        }
+        let resp = req
+            .json(&options.metadata(path, file_id.is_some()))
+            .send()?
+            .check_status()?;
+
+        match resp.headers().get("Location").map(|l| l.to_str()) {
+            Some(Ok(uri)) => Ok(uri.to_string()),
+            _ => Err(Error::UnexpectedResponse(String::from(
+                "resumable upload session response had no Location header",
+            ))),
+        }
+    }
+
+    /// Ask Drive how much of given upload it has received, updating `upload.offset`.
+    /// Returns the file if the upload has already completed.
+    pub fn upload_status(&self, upload: &mut ResumableUpload) -> Result<Option<DriveFile>, Error> {
+        let resp = self
+            .client
+            .put(upload.session_uri.as_str())
+            .header("Authorization", self.auth.bearer()?)
+            .header("Content-Range", format!("bytes */{}", upload.size))
+            .header("Content-Length", 0)
+            .send()?;
+        if resp.status() == StatusCode::PERMANENT_REDIRECT {
+            upload.offset = confirmed_offset(&resp);
+            upload.save()?;
+            return Ok(None);
+        }
+        Ok(Some(DriveFile::from_response(resp)?))
+    }
+
+    /// Upload the rest of given upload chunk by chunk, from the last confirmed byte.
+    /// Failed chunks are retried after checking with Drive how much actually arrived.
+    pub fn resume_upload(&self, upload: &mut ResumableUpload) -> Result<DriveFile, Error> {
+        loop {
+            if let Some(file) = self.upload_chunk(upload)? {
+                return Ok(file);
+            }
+        }
+    }
+
+    /// Send the next chunk of given upload, retrying it if it fails. Returns the file once
+    /// Drive has all of it, `None` while there's more to send. `upload.offset` is kept up to
+    /// date, so calling this in a loop lets the caller report progress between chunks.
+    pub fn upload_chunk(&self, upload: &mut ResumableUpload) -> Result<Option<DriveFile>, Error> {
+        let session_uri = upload.session_uri.clone();
+        let bearer = self.auth.bearer()?;
+        let put = || {
+            self.client
+                .put(session_uri.as_str())
+                .header("Authorization", bearer.as_str())
+        };
+        let path = upload.path.clone();
+        let done = send_chunk(
+            put,
+            &self.retry,
+            &path,
+            upload.size,
+            upload.chunk_size,
+            &mut upload.offset,
+        )?;
+        match done {
+            Some(resp) => {
+                let file = DriveFile::from_response(resp)?;
+                upload.finish()?;
+                Ok(Some(file))
+            }
+            None => {
+                upload.save()?;
+                log::debug!("uploaded {}/{} bytes", upload.offset, upload.size);
+                Ok(None)
+            }
+        }
+    }
+
+    /// Upload file at given path with the resumable protocol, for files too big or connections
+    /// too flaky for a single request.
+    pub fn upload_file_resumable(
+        &self,
+        path: &Path,
+        options: &UploadOptions,
+    ) -> Result<DriveFile, Error> {
+        let mut upload = self.start_resumable_upload(path, options)?;
+        self.resume_upload(&mut upload)
+    }
+
+    /// Stream a file from any HTTP(S) URL into Drive, one chunk in memory at a time. The name
+    /// defaults to the last segment of the URL and the MIME type to the source's
+    /// `Content-Type`. No credentials are sent to the source.
+    pub fn upload_from_url(
+        &self,
+        source_url: &str,
+        options: &UploadOptions,
+    ) -> Result<DriveFile, Error> {
+        let source = reqwest::blocking::get(source_url)?.check_status()?;
+        let name = source
+            .url()
+            .path_segments()
+            .and_then(|mut s| s.rfind(|s| !s.is_empty()))
+            .unwrap_or("download")
+            .to_string();
+        let path = Path::new(&name);
+        let media_type = match source
+            .headers()
+            .get("Content-Type")
+            .and_then(|t| t.to_str().ok())
+        {
+            Some(t) if !options.has_mime_type() => t.to_string(),
+            _ => options.media_type(path),
+        };
+        let size = source.content_length();
+        self.stream_upload(source, path, &media_type, size, options)
+    }
+
+    /// Stream anything readable into a new Drive file, one chunk in memory at a time, e.g.
+    /// stdin or a pipe whose length isn't known up front. `name` is the default name, and the
+    /// MIME type is guessed from it unless set in the options.
+    pub fn upload_from_reader<R: Read>(
+        &self,
+        reader: R,
+        name: &str,
+        options: &UploadOptions,
+    ) -> Result<DriveFile, Error> {
+        let path = Path::new(name);
+        self.stream_upload(reader, path, &options.media_type(path), None, options)
+    }
+
+    /// Open a session and send `source` through it chunk by chunk until it runs dry.
+    fn stream_upload<R: Read>(
+        &self,
+        mut source: R,
+        path: &Path,
+        media_type: &str,
+        size: Option<u64>,
+        options: &UploadOptions,
+    ) -> Result<DriveFile, Error> {
+        let session_uri = self.open_session(None, path, options, media_type, size)?;
+
+        let mut offset = 0;
+        loop {
+            let mut chunk = Vec::with_capacity(DEFAULT_CHUNK_SIZE);
+            (&mut source)
+                .take(DEFAULT_CHUNK_SIZE as u64)
+                .read_to_end(&mut chunk)?;
+            // A short read means the source is done, so the total is known.
+            let total = if chunk.len() < DEFAULT_CHUNK_SIZE {
+                Some(offset + chunk.len() as u64)
+            } else {
+                size
+            };
+            if let Some(file) = self.send_stream_chunk(&session_uri, &chunk, offset, total)? {
+                return Ok(file);
+            }
+            offset += chunk.len() as u64;
+            log::debug!("streamed {} bytes", offset);
+        }
+    }
+
+    /// Send one chunk of a streamed upload, retrying from whatever part of it Drive confirmed.
+    /// Returns the file once Drive has received everything.
+    fn send_stream_chunk(
+        &self,
+        session_uri: &str,
+        chunk: &[u8],
+        offset: u64,
+        total: Option<u64>,
+    ) -> Result<Option<DriveFile>, Error> {
+        let total_str = total.map_or_else(|| String::from("*"), |t| t.to_string());
+        let end = offset + chunk.len() as u64;
+        let mut sent = 0;
+        let mut failures = 0;
+        // After a failure, ask how much arrived before sending anything again.
+        let mut check_status = false;
+        loop {
+            let part = if check_status {
+                &[][..]
+            } else {
+                &chunk[sent..]
+            };
+            let content_range = if part.is_empty() {
+                format!("bytes */{}", total_str)
+            } else {
+                format!("bytes {}-{}/{}", offset + sent as u64, end - 1, total_str)
+            };
+            let resp = self
+                .client
+                .put(session_uri)
+                .header("Authorization", self.auth.bearer()?)
+                .header("Content-Range", content_range)
+                .body(part.to_vec())
+                .send();
+            match resp {
+                Ok(r) if r.status() == StatusCode::PERMANENT_REDIRECT => {
+                    check_status = false;
+                    let confirmed = confirmed_offset(&r);
+                    if confirmed >= end {
+                        return Ok(None);
+                    }
+                    // Drive kept only part of the chunk, send the rest.
+                    sent = confirmed.saturating_sub(offset) as usize;
+                }
+                Ok(r) if r.status().is_success() => return Ok(Some(DriveFile::from_response(r)?)),
+                Ok(ref r) if r.status().is_server_error() && failures < self.retry.max_retries => {
+                    failures += 1;
+                    check_status = true;
+                    thread::sleep(self.retry.delay(failures));
+                }
+                Err(_) if failures < self.retry.max_retries => {
+                    failures += 1;
+                    check_status = true;
+                    thread::sleep(self.retry.delay(failures));
+                }
+                Ok(r) => return Err(Error::from_status(r)),
+                Err(e) => return Err(Error::Http(e)),
+            }
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use std::thread;
+use std::time::Duration;
+
+use reqwest::blocking::Response;
+
+use crate::error::Error;
+
+/// Attempts after the first before giving up on a rate limited or failing request.
+const MAX_RETRIES: u32 = 5;
+
+/// How often and how patiently requests failing with a rate limit or server error are tried
+/// again. Each wait is twice as long as the one before.
+///
+/// ```no_run
+/// # use std::time::Duration;
+/// # use google_api::*;
+/// # let (client_id, client_secret) = (String::new(), String::new());
+/// let drive = Drive::builder()
+///     .client_id(client_id)
+///     .client_secret(client_secret)
+///     .retry_policy(RetryPolicy::new(8, Duration::from_millis(500)))
+///     .build()?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Attempts after the first before giving up.
+    pub max_retries: u32,
+    /// Wait before the first retry.
+    pub base_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    /// 5 retries, waiting 2s, 4s, 8s, 16s and 32s.
+    fn default() -> Self {
+        RetryPolicy {
+            max_retries: MAX_RETRIES,
+            base_delay: Duration::from_secs(2),
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn new(max_retries: u32, base_delay: Duration) -> RetryPolicy {
+        RetryPolicy {
+            max_retries,
+            base_delay,
+        }
+    }
+
+    /// Fail on the first error.
+    pub fn none() -> RetryPolicy {
+        RetryPolicy::new(0, Duration::from_secs(0))
+    }
+
+    /// How long to wait after given number of consecutive failures, from 1.
+    pub(crate) fn delay(&self, failures: u32) -> Duration {
+        self.base_delay * 2u32.saturating_pow(failures.saturating_sub(1))
+    }
+}
+
+/// Send a request, backing off and sending it again as given policy says while Google answers
+/// with a rate limit or server error. Other error statuses are returned straight away with the
+/// API's message.
+pub(crate) fn send_with_policy<F, E>(policy: &RetryPolicy, send: F) -> Result<Response, Error>
+where
+    F: Fn() -> Result<Response, E>,
+    E: Into<Error>,
+{
+    let mut failures = 0;
+    loop {
+        let resp = send().map_err(Into::into)?;
+        let status = resp.status();
+        if status.is_success() {
+            return Ok(resp);
+        }
+        // Drive reports most rate limits as 403 with a reason in the body.
+        let error = Error::from_api(resp);
+        let retryable = status.is_server_error() || error.is_rate_limited();
+        if retryable && failures < policy.max_retries {
+            failures += 1;
+            log::debug!("{}, retry {}/{}", status, failures, policy.max_retries);
+            thread::sleep(policy.delay(failures));
+            continue;
+        }
+        return Err(error);
+    }
+}
@@ -0,0 +1,162 @@
+use std::fmt;
+
+/// OAuth scopes the user can be asked to grant. Pass them to `Drive::new` (or any other
+/// client's `new`), e.g.
+/// `Some(vec![Scope::Drive.into(), Scope::DriveActivityReadonly.into()])`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Scope {
+    /// Full access to the user's Drive. The default.
+    Drive,
+    DriveReadonly,
+    /// Only files created or opened by this app.
+    DriveFile,
+    /// The hidden app data folder.
+    DriveAppData,
+    DriveMetadataReadonly,
+    /// Drive Activity API, read only.
+    DriveActivityReadonly,
+    /// Google Sheets, read and write.
+    Spreadsheets,
+    SpreadsheetsReadonly,
+    /// Send mail only, no access to the mailbox.
+    GmailSend,
+    GmailReadonly,
+    /// Drafts and sending.
+    GmailCompose,
+    /// Read, send, label and trash mail, but not delete it permanently.
+    GmailModify,
+    /// Calendars and events, read and write.
+    Calendar,
+    CalendarReadonly,
+    /// Events only, not the calendars themselves.
+    CalendarEvents,
+    /// Google Docs, read and write.
+    Documents,
+    DocumentsReadonly,
+    /// Google Slides, read and write.
+    Presentations,
+    PresentationsReadonly,
+    /// Google Forms: create and edit forms.
+    FormsBody,
+    FormsBodyReadonly,
+    /// Google Forms: read responses.
+    FormsResponsesReadonly,
+    /// Google Tasks, read and write.
+    Tasks,
+    TasksReadonly,
+    /// The user's contacts, read and write.
+    Contacts,
+    ContactsReadonly,
+    /// Google Photos library, read and upload.
+    PhotosLibrary,
+    /// Upload to the library only.
+    PhotosLibraryAppendOnly,
+    PhotosLibraryReadonly,
+    /// Cloud Storage buckets and objects, read and write.
+    CloudStorage,
+    CloudStorageReadonly,
+    /// Cloud Storage, including bucket and object ACLs.
+    CloudStorageFullControl,
+    /// YouTube account, read and manage.
+    Youtube,
+    YoutubeReadonly,
+    /// Upload videos only.
+    YoutubeUpload,
+    /// Cloud Translation.
+    CloudTranslation,
+    /// Workspace users, for domain admins.
+    AdminDirectoryUser,
+    AdminDirectoryUserReadonly,
+    /// Workspace groups and their members, for domain admins.
+    AdminDirectoryGroup,
+    AdminDirectoryGroupReadonly,
+    /// Google Chat as an app, for service accounts.
+    ChatBot,
+    /// Google Chat messages as the user.
+    ChatMessages,
+    /// Cloud Pub/Sub topics and subscriptions.
+    Pubsub,
+    /// Any other scope, by its full URL.
+    Custom(String),
+}
+
+impl Scope {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Scope::Drive => "https://www.googleapis.com/auth/drive",
+            Scope::DriveReadonly => "https://www.googleapis.com/auth/drive.readonly",
+            Scope::DriveFile => "https://www.googleapis.com/auth/drive.file",
+            Scope::DriveAppData => "https://www.googleapis.com/auth/drive.appdata",
+            Scope::DriveMetadataReadonly => {
+                "https://www.googleapis.com/auth/drive.metadata.readonly"
+            }
+            Scope::DriveActivityReadonly => {
+                "https://www.googleapis.com/auth/drive.activity.readonly"
+            }
+            Scope::Spreadsheets => "https://www.googleapis.com/auth/spreadsheets",
+            Scope::SpreadsheetsReadonly => "https://www.googleapis.com/auth/spreadsheets.readonly",
+            Scope::GmailSend => "https://www.googleapis.com/auth/gmail.send",
+            Scope::GmailReadonly => "https://www.googleapis.com/auth/gmail.readonly",
+            Scope::GmailCompose => "https://www.googleapis.com/auth/gmail.compose",
+            Scope::GmailModify => "https://www.googleapis.com/auth/gmail.modify",
+            Scope::Calendar => "https://www.googleapis.com/auth/calendar",
+            Scope::CalendarReadonly => "https://www.googleapis.com/auth/calendar.readonly",
+            Scope::CalendarEvents => "https://www.googleapis.com/auth/calendar.events",
+            Scope::Documents => "https://www.googleapis.com/auth/documents",
+            Scope::DocumentsReadonly => "https://www.googleapis.com/auth/documents.readonly",
+            Scope::Presentations => "https://www.googleapis.com/auth/presentations",
+            Scope::PresentationsReadonly => {
+                "https://www.googleapis.com/auth/presentations.readonly"
+            }
+            Scope::FormsBody => "https://www.googleapis.com/auth/forms.body",
+            Scope::FormsBodyReadonly => "https://www.googleapis.com/auth/forms.body.readonly",
+            Scope::FormsResponsesReadonly => {
+                "https://www.googleapis.com/auth/forms.responses.readonly"
+            }
+            Scope::Tasks => "https://www.googleapis.com/auth/tasks",
+            Scope::TasksReadonly => "https://www.googleapis.com/auth/tasks.readonly",
+            Scope::Contacts => "https://www.googleapis.com/auth/contacts",
+            Scope::ContactsReadonly => "https://www.googleapis.com/auth/contacts.readonly",
+            Scope::PhotosLibrary => "https://www.googleapis.com/auth/photoslibrary",
+            Scope::PhotosLibraryAppendOnly => {
+                "https://www.googleapis.com/auth/photoslibrary.appendonly"
+            }
+            Scope::PhotosLibraryReadonly => {
+                "https://www.googleapis.com/auth/photoslibrary.readonly"
+            }
+            Scope::CloudStorage => "https://www.googleapis.com/auth/devstorage.read_write",
+            Scope::CloudStorageReadonly => "https://www.googleapis.com/auth/devstorage.read_only",
+            Scope::CloudStorageFullControl => {
+                "https://www.googleapis.com/auth/devstorage.full_control"
+            }
+            Scope::Youtube => "https://www.googleapis.com/auth/youtube",
+            Scope::YoutubeReadonly => "https://www.googleapis.com/auth/youtube.readonly",
+            Scope::YoutubeUpload => "https://www.googleapis.com/auth/youtube.upload",
+            Scope::CloudTranslation => "https://www.googleapis.com/auth/cloud-translation",
+            Scope::AdminDirectoryUser => "https://www.googleapis.com/auth/admin.directory.user",
+            Scope::AdminDirectoryUserReadonly => {
+                "https://www.googleapis.com/auth/admin.directory.user.readonly"
+            }
+            Scope::AdminDirectoryGroup => "https://www.googleapis.com/auth/admin.directory.group",
+            Scope::AdminDirectoryGroupReadonly => {
+                "https://www.googleapis.com/auth/admin.directory.group.readonly"
+            }
+            Scope::ChatBot => "https://www.googleapis.com/auth/chat.bot",
+            Scope::ChatMessages => "https://www.googleapis.com/auth/chat.messages",
+            Scope::Pubsub => "https://www.googleapis.com/auth/pubsub",
+            Scope::Custom(url) => url,
+        }
+    }
+}
+
+impl fmt::Display for Scope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl From<Scope> for String {
+    fn from(scope: Scope) -> String {
+        scope.as_str().to_string()
+    }
+}
@@ -0,0 +1,119 @@
+use std::fmt;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use ring::rand::SystemRandom;
+use ring::signature::{RsaKeyPair, RSA_PKCS1_SHA256};
+use serde::Deserialize;
+
+use crate::error::{CheckStatus, Error};
+use crate::{GoogleOAuthToken, GOOGLE_TOKEN_URL};
+
+/// A service account's JSON key, as downloaded from the Cloud console. Signs Cloud Storage
+/// URLs, and gets tokens for clients that act on a Workspace domain's behalf.
+///
+/// ```no_run
+/// # use std::path::Path;
+/// # use std::time::Duration;
+/// # use google_api::*;
+/// let key = ServiceAccountKey::from_file(Path::new("service-account.json"))?;
+/// let url = key.signed_url("GET", "my-bucket", "reports/q3.pdf", Duration::from_secs(3600))?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Clone, Deserialize)]
+pub struct ServiceAccountKey {
+    pub client_email: String,
+    private_key: String,
+}
+
+impl fmt::Debug for ServiceAccountKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ServiceAccountKey")
+            .field("client_email", &self.client_email)
+            .finish()
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct TokenResponse {
+    access_token: String,
+    expires_in: u64,
+}
+
+impl ServiceAccountKey {
+    pub fn from_file(path: &Path) -> Result<ServiceAccountKey, Error> {
+        let f = File::open(path)?;
+        Ok(serde_json::from_reader(BufReader::new(f))?)
+    }
+
+    /// RSA-SHA256 signature of `message` with the account's private key.
+    pub(crate) fn sign(&self, message: &[u8]) -> Result<Vec<u8>, Error> {
+        let der: String = self
+            .private_key
+            .lines()
+            .filter(|line| !line.starts_with("-----"))
+            .collect();
+        let der = base64::decode(der.trim())
+            .map_err(|e| Error::UnexpectedResponse(format!("private key isn't base64: {}", e)))?;
+        let key = RsaKeyPair::from_pkcs8(&der)
+            .map_err(|e| Error::UnexpectedResponse(format!("invalid private key: {}", e)))?;
+        let mut signature = vec![0; key.public().modulus_len()];
+        key.sign(
+            &RSA_PKCS1_SHA256,
+            &SystemRandom::new(),
+            message,
+            &mut signature,
+        )
+        .map_err(|_| Error::UnexpectedResponse(String::from("signing failed")))?;
+        Ok(signature)
+    }
+
+    /// Access token for given scopes, impersonating `subject` when set. Impersonation needs
+    /// the account to have domain-wide delegation for those scopes in the Admin console.
+    /// Tokens last an hour and aren't saved anywhere.
+    pub(crate) fn access_token(
+        &self,
+        scopes: &[String],
+        subject: Option<&str>,
+    ) -> Result<GoogleOAuthToken, Error> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let mut claims = serde_json::json!({
+            "iss": self.client_email,
+            "scope": scopes.join(" "),
+            "aud": GOOGLE_TOKEN_URL,
+            "iat": now,
+            "exp": now + 3600,
+        });
+        if let Some(subject) = subject {
+            claims["sub"] = serde_json::json!(subject);
+        }
+        let header =
+            base64::encode_config(r#"{"alg":"RS256","typ":"JWT"}"#, base64::URL_SAFE_NO_PAD);
+        let claims = base64::encode_config(claims.to_string(), base64::URL_SAFE_NO_PAD);
+        let unsigned = format!("{}.{}", header, claims);
+        let signature =
+            base64::encode_config(self.sign(unsigned.as_bytes())?, base64::URL_SAFE_NO_PAD);
+
+        let resp: TokenResponse = reqwest::blocking::Client::new()
+            .post(GOOGLE_TOKEN_URL)
+            .form(&[
+                ("grant_type", "urn:ietf:params:oauth:grant-type:jwt-bearer"),
+                ("assertion", &format!("{}.{}", unsigned, signature)),
+            ])
+            .send()?
+            .check_status()?
+            .json()?;
+
+        let mut auth = GoogleOAuthToken::new();
+        auth.token = resp.access_token;
+        auth.expires = SystemTime::now() + Duration::from_secs(resp.expires_in);
+        auth.scope = scopes.to_vec();
+        Ok(auth)
+    }
+}
@@ -0,0 +1,63 @@
+use reqwest::blocking::RequestBuilder;
+use reqwest::Method;
+
+use crate::api;
+use crate::auth::Auth;
+use crate::error::Error;
+use crate::scope::Scope;
+
+mod range;
+mod requests;
+mod spreadsheet;
+mod values;
+
+pub use range::{a1_range, column_index, column_name, A1Range, GridRange};
+pub use requests::{
+    BatchUpdateResponse, CellFormat, Color, Condition, HorizontalAlign, MergeType, NumberFormat,
+    SheetRequest, TextFormat,
+};
+pub use spreadsheet::{GridProperties, Sheet, SheetProperties, Spreadsheet, SpreadsheetProperties};
+pub use values::{
+    AppendValuesResponse, MajorDimension, UpdateValuesResponse, ValueInputOption, ValueRange,
+};
+
+const SHEETS_BASE_URL: &str = "https://sheets.googleapis.com/v4/spreadsheets";
+
+/// Google Sheets client. Shares its saved token with `Drive`, so a user who already gave
+/// consent for Drive is only asked again to add the Sheets scope.
+///
+/// ```no_run
+/// # use google_api::*;
+/// # let (client_id, client_secret) = (String::new(), String::new());
+/// # let spreadsheet_id = "1aBc";
+/// let sheets = Sheets::new(client_id, client_secret, None)?;
+/// let values = sheets.get_values(spreadsheet_id, "Data!A1:C10")?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug)]
+pub struct Sheets {
+    auth: Auth,
+    client: reqwest::blocking::Client,
+}
+
+impl Sheets {
+    /// Authenticate with given OAuth client. `scopes` defaults to `Scope::Spreadsheets`.
+    pub fn new(
+        client_id: String,
+        client_secret: String,
+        scopes: Option<Vec<String>>,
+    ) -> Result<Sheets, Error> {
+        let scopes = scopes.unwrap_or_else(|| vec![Scope::Spreadsheets.into()]);
+
+        Ok(Sheets {
+            auth: Auth::from_config(&client_id, &client_secret, &scopes)?,
+            client: reqwest::blocking::Client::new(),
+        })
+    }
+
+    /// Authorized request for the endpoint below `/spreadsheets` made of given path segments,
+    /// e.g. `[id, "values", range]`.
+    fn request(&self, method: Method, segments: &[&str]) -> Result<RequestBuilder, Error> {
+        api::request(&self.client, &self.auth, SHEETS_BASE_URL, method, segments)
+    }
+}
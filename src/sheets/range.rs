@@ -0,0 +1,587 @@
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::ExtraFields;
+
+/// Letters of the column at given index, counting from 0: `A`, ..., `Z`, `AA`, ...
+pub fn column_name(index: u32) -> String {
+    let mut name = vec![];
+    let mut n = u64::from(index) + 1;
+    while n > 0 {
+        let rem = (n - 1) % 26;
+        name.push(b'A' + rem as u8);
+        n = (n - 1) / 26;
+    }
+    name.reverse();
+    String::from_utf8(name).unwrap_or_default()
+}
+
+/// Index of the column with given letters (any case), counting from 0. `None` if they
+/// aren't all letters or name a column past `u32::MAX`.
+pub fn column_index(name: &str) -> Option<u32> {
+    if name.is_empty() {
+        return None;
+    }
+    let mut n: u64 = 0;
+    for c in name.chars() {
+        if !c.is_ascii_alphabetic() {
+            return None;
+        }
+        let digit = u64::from(c.to_ascii_uppercase() as u8 - b'A') + 1;
+        n = n.checked_mul(26)?.checked_add(digit)?;
+    }
+    u32::try_from(n - 1).ok()
+}
+
+/// A sheet name as it has to appear in A1 notation, quoted unless it's plain letters,
+/// digits and underscores.
+fn quote_sheet(sheet: &str) -> String {
+    let plain = !sheet.is_empty() && sheet.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if plain {
+        sheet.to_string()
+    } else {
+        format!("'{}'", sheet.replace('\'', "''"))
+    }
+}
+
+/// A1 notation for `cells` (e.g. `A1:C10`) on the named sheet, quoting the name when it needs
+/// it. Empty `cells` means the whole sheet.
+pub fn a1_range(sheet: &str, cells: &str) -> String {
+    if cells.is_empty() {
+        quote_sheet(sheet)
+    } else {
+        format!("{}!{}", quote_sheet(sheet), cells)
+    }
+}
+
+/// Start and exclusive end of a span of rows or columns, `None` running to the sheet's edge.
+type Span = (Option<u32>, Option<u32>);
+
+/// Where two spans overlap, if they do.
+fn intersect_span(a: Span, b: Span) -> Option<Span> {
+    let start = match (a.0, b.0) {
+        (Some(x), Some(y)) => Some(x.max(y)),
+        (x, None) | (None, x) => x,
+    };
+    let end = match (a.1, b.1) {
+        (Some(x), Some(y)) => Some(x.min(y)),
+        (x, None) | (None, x) => x,
+    };
+    match (start, end) {
+        (Some(start), Some(end)) if start >= end => None,
+        (None, Some(0)) => None,
+        _ => Some((start, end)),
+    }
+}
+
+/// A span moved by `by`, `None` if that takes it before the first row or column. Open ends
+/// stay open.
+fn offset_span(span: Span, by: i32) -> Option<Span> {
+    let start = match span.0 {
+        Some(start) => Some(start.checked_add_signed(by)?),
+        None => None,
+    };
+    let end = match span.1 {
+        Some(end) => Some(end.checked_add_signed(by)?),
+        None => None,
+    };
+    Some((start, end))
+}
+
+/// Parse one side of a range, e.g. `B3`, `$B$3`, `B` or `3`, into zero-based column and row.
+fn parse_cell(cell: &str) -> Result<(Option<u32>, Option<u32>), String> {
+    let cell = cell.replace('$', "");
+    let split = cell
+        .find(|c: char| !c.is_ascii_alphabetic())
+        .unwrap_or(cell.len());
+    let (letters, digits) = cell.split_at(split);
+    if letters.is_empty() && digits.is_empty() {
+        return Err(String::from("empty cell reference"));
+    }
+    let column = match letters {
+        "" => None,
+        letters => Some(column_index(letters).ok_or_else(|| format!("bad column {}", letters))?),
+    };
+    let row = match digits {
+        "" => None,
+        digits => Some(
+            digits
+                .parse::<u32>()
+                .ok()
+                .filter(|row| *row > 0)
+                .ok_or_else(|| format!("bad row {}", digits))?
+                - 1,
+        ),
+    };
+    Ok((column, row))
+}
+
+/// Split the sheet name off a range, unquoting it. Without a `!` the whole string is taken as
+/// cells when it looks like them (has a digit or `:`), otherwise as a sheet name.
+fn split_sheet(s: &str) -> Result<(Option<String>, &str), String> {
+    if let Some(rest) = s.strip_prefix('\'') {
+        let mut name = String::new();
+        let mut chars = rest.char_indices().peekable();
+        while let Some((i, c)) = chars.next() {
+            if c != '\'' {
+                name.push(c);
+                continue;
+            }
+            if let Some((_, '\'')) = chars.peek() {
+                chars.next();
+                name.push('\'');
+                continue;
+            }
+            let after = &rest[i + 1..];
+            return match after.strip_prefix('!') {
+                Some(cells) => Ok((Some(name), cells)),
+                None if after.is_empty() => Ok((Some(name), "")),
+                None => Err(format!("unexpected {} after sheet name", after)),
+            };
+        }
+        return Err(String::from("unterminated sheet name"));
+    }
+    match s.rfind('!') {
+        Some(i) => Ok((Some(s[..i].to_string()), &s[i + 1..])),
+        None if s.contains(':') || s.chars().any(|c| c.is_ascii_digit()) => Ok((None, s)),
+        None => Ok((Some(s.to_string()), "")),
+    }
+}
+
+/// A range in A1 notation, e.g. `'Q1 Sales'!B2:D10`, `A:C` or `Data`. Bounds are stored like
+/// `GridRange`'s: zero-based, exclusive ends, `None` running to the edge of the sheet.
+///
+/// ```no_run
+/// # use google_api::*;
+/// # let sheets = Sheets::new(String::new(), String::new(), None)?;
+/// # let spreadsheet_id = "1aBc";
+/// let range: A1Range = "Data!B2:D10".parse()?;
+/// let below = range.offset(9, 0).unwrap(); // Data!B11:D19
+/// sheets.get_values(spreadsheet_id, &below.to_string())?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct A1Range {
+    /// Sheet the range is on. Without one the API uses the first sheet.
+    pub sheet: Option<String>,
+    pub start_row: Option<u32>,
+    pub end_row: Option<u32>,
+    pub start_column: Option<u32>,
+    pub end_column: Option<u32>,
+}
+
+impl A1Range {
+    /// Parse A1 notation. Absolute markers (`$`) are accepted and dropped.
+    pub fn parse(s: &str) -> Result<A1Range, String> {
+        let (sheet, cells) = split_sheet(s.trim())?;
+        if sheet.as_deref() == Some("") {
+            return Err(String::from("empty sheet name"));
+        }
+        let mut range = A1Range {
+            sheet,
+            ..A1Range::default()
+        };
+        if cells.is_empty() {
+            return match range.sheet {
+                Some(_) => Ok(range),
+                None => Err(String::from("empty range")),
+            };
+        }
+        let mut sides = cells.splitn(2, ':');
+        let (start_column, start_row) = parse_cell(sides.next().unwrap_or_default())?;
+        let (end_column, end_row) = match sides.next() {
+            Some(end) => parse_cell(end)?,
+            None => (start_column, start_row),
+        };
+        range.start_column = start_column;
+        range.start_row = start_row;
+        range.end_column = end_column.map(|c| c.saturating_add(1));
+        range.end_row = end_row.map(|r| r + 1);
+        range.validate()?;
+        Ok(range)
+    }
+
+    /// A single cell, by zero-based row and column.
+    pub fn cell(row: u32, column: u32) -> A1Range {
+        A1Range::cells(
+            (row, row.saturating_add(1)),
+            (column, column.saturating_add(1)),
+        )
+    }
+
+    /// Rows `rows.0..rows.1` of columns `columns.0..columns.1`.
+    pub fn cells(rows: (u32, u32), columns: (u32, u32)) -> A1Range {
+        A1Range {
+            sheet: None,
+            start_row: Some(rows.0),
+            end_row: Some(rows.1),
+            start_column: Some(columns.0),
+            end_column: Some(columns.1),
+        }
+    }
+
+    /// The same range on the named sheet.
+    pub fn on_sheet(mut self, sheet: &str) -> A1Range {
+        self.sheet = Some(sheet.to_string());
+        self
+    }
+
+    /// Check that the range isn't empty or backwards.
+    pub fn validate(&self) -> Result<(), String> {
+        for (start, end) in [self.rows(), self.columns()] {
+            if let (Some(start), Some(end)) = (start, end) {
+                if start >= end {
+                    return Err(format!("{} ends before it starts", self));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn rows(&self) -> Span {
+        (self.start_row, self.end_row)
+    }
+
+    fn columns(&self) -> Span {
+        (self.start_column, self.end_column)
+    }
+
+    /// Number of rows, if the range has both ends.
+    pub fn height(&self) -> Option<u32> {
+        self.end_row?
+            .checked_sub(self.start_row.unwrap_or_default())
+    }
+
+    /// Number of columns, if the range has both ends.
+    pub fn width(&self) -> Option<u32> {
+        self.end_column?
+            .checked_sub(self.start_column.unwrap_or_default())
+    }
+
+    /// The range moved down and right (negative for up and left). `None` if that would take
+    /// it off the top or left of the sheet.
+    pub fn offset(&self, rows: i32, columns: i32) -> Option<A1Range> {
+        let (start_row, end_row) = offset_span(self.rows(), rows)?;
+        let (start_column, end_column) = offset_span(self.columns(), columns)?;
+        Some(A1Range {
+            sheet: self.sheet.clone(),
+            start_row,
+            end_row,
+            start_column,
+            end_column,
+        })
+    }
+
+    /// Cells in both ranges, `None` if they don't overlap or are on different sheets.
+    pub fn intersect(&self, other: &A1Range) -> Option<A1Range> {
+        if self.sheet != other.sheet {
+            return None;
+        }
+        let (start_row, end_row) = intersect_span(self.rows(), other.rows())?;
+        let (start_column, end_column) = intersect_span(self.columns(), other.columns())?;
+        Some(A1Range {
+            sheet: self.sheet.clone(),
+            start_row,
+            end_row,
+            start_column,
+            end_column,
+        })
+    }
+
+    /// Whether the cell at given zero-based row and column is in the range.
+    pub fn contains(&self, row: u32, column: u32) -> bool {
+        let within =
+            |(start, end): Span, i: u32| start.map_or(true, |s| i >= s) && end.map_or(true, |e| i < e);
+        within(self.rows(), row) && within(self.columns(), column)
+    }
+
+    /// The same cells as a `GridRange`, for batch updates. The sheet is given by id since
+    /// grid ranges don't use titles.
+    pub fn to_grid(&self, sheet_id: u32) -> GridRange {
+        GridRange {
+            sheet_id,
+            start_row_index: self.start_row,
+            end_row_index: self.end_row,
+            start_column_index: self.start_column,
+            end_column_index: self.end_column,
+            ..GridRange::default()
+        }
+    }
+
+    /// Just the cells part, e.g. `B2:D10`.
+    fn cells_notation(&self) -> String {
+        let side = |column: Option<u32>, row: Option<u32>| {
+            let mut side = column.map(column_name).unwrap_or_default();
+            if let Some(row) = row {
+                side.push_str(&(u64::from(row) + 1).to_string());
+            }
+            side
+        };
+        let start = side(self.start_column, self.start_row);
+        let end = side(
+            self.end_column.map(|c| c.saturating_sub(1)),
+            self.end_row.map(|r| r.saturating_sub(1)),
+        );
+        let single = self.height() == Some(1)
+            && self.width() == Some(1)
+            && self.start_row.is_some()
+            && self.start_column.is_some();
+        match (start.is_empty() && end.is_empty(), single) {
+            (true, _) => String::new(),
+            (false, true) => start,
+            (false, false) => format!("{}:{}", start, end),
+        }
+    }
+}
+
+impl FromStr for A1Range {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<A1Range, String> {
+        A1Range::parse(s)
+    }
+}
+
+impl fmt::Display for A1Range {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cells = self.cells_notation();
+        match &self.sheet {
+            Some(sheet) => write!(f, "{}", a1_range(sheet, &cells)),
+            None => write!(f, "{}", cells),
+        }
+    }
+}
+
+/// A block of cells on one sheet, by zero-based indexes with exclusive ends. Unset bounds run
+/// to the edge of the sheet, so `GridRange::sheet(id)` is the whole sheet.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct GridRange {
+    pub sheet_id: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_row_index: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_row_index: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_column_index: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_column_index: Option<u32>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl GridRange {
+    /// Every cell of given sheet.
+    pub fn sheet(sheet_id: u32) -> GridRange {
+        GridRange {
+            sheet_id,
+            ..GridRange::default()
+        }
+    }
+
+    /// Rows `start..end` across all columns.
+    pub fn rows(sheet_id: u32, start: u32, end: u32) -> GridRange {
+        GridRange {
+            start_row_index: Some(start),
+            end_row_index: Some(end),
+            ..GridRange::sheet(sheet_id)
+        }
+    }
+
+    /// Columns `start..end` across all rows.
+    pub fn columns(sheet_id: u32, start: u32, end: u32) -> GridRange {
+        GridRange {
+            start_column_index: Some(start),
+            end_column_index: Some(end),
+            ..GridRange::sheet(sheet_id)
+        }
+    }
+
+    /// Rows `rows.0..rows.1` of columns `columns.0..columns.1`.
+    pub fn cells(sheet_id: u32, rows: (u32, u32), columns: (u32, u32)) -> GridRange {
+        GridRange {
+            sheet_id,
+            start_row_index: Some(rows.0),
+            end_row_index: Some(rows.1),
+            start_column_index: Some(columns.0),
+            end_column_index: Some(columns.1),
+            ..GridRange::default()
+        }
+    }
+
+    /// The range moved down and right (negative for up and left). `None` if that would take
+    /// it off the top or left of the sheet.
+    pub fn offset(&self, rows: i32, columns: i32) -> Option<GridRange> {
+        let (start_row_index, end_row_index) =
+            offset_span((self.start_row_index, self.end_row_index), rows)?;
+        let (start_column_index, end_column_index) =
+            offset_span((self.start_column_index, self.end_column_index), columns)?;
+        Some(GridRange {
+            sheet_id: self.sheet_id,
+            start_row_index,
+            end_row_index,
+            start_column_index,
+            end_column_index,
+            ..GridRange::default()
+        })
+    }
+
+    /// Cells in both ranges, `None` if they don't overlap or are on different sheets.
+    pub fn intersect(&self, other: &GridRange) -> Option<GridRange> {
+        if self.sheet_id != other.sheet_id {
+            return None;
+        }
+        let (start_row_index, end_row_index) = intersect_span(
+            (self.start_row_index, self.end_row_index),
+            (other.start_row_index, other.end_row_index),
+        )?;
+        let (start_column_index, end_column_index) = intersect_span(
+            (self.start_column_index, self.end_column_index),
+            (other.start_column_index, other.end_column_index),
+        )?;
+        Some(GridRange {
+            sheet_id: self.sheet_id,
+            start_row_index,
+            end_row_index,
+            start_column_index,
+            end_column_index,
+            ..GridRange::default()
+        })
+    }
+
+    /// The same cells in A1 notation on the sheet with given title.
+    pub fn to_a1(&self, sheet: &str) -> A1Range {
+        A1Range {
+            sheet: Some(sheet.to_string()),
+            start_row: self.start_row_index,
+            end_row: self.end_row_index,
+            start_column: self.start_column_index,
+            end_column: self.end_column_index,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn column_names_round_trip() {
+        for (index, name) in &[(0, "A"), (25, "Z"), (26, "AA"), (701, "ZZ"), (702, "AAA")] {
+            assert_eq!(column_name(*index), *name);
+            assert_eq!(column_index(name), Some(*index));
+        }
+        assert_eq!(column_name(u32::MAX), "MWLQKWV");
+        assert_eq!(column_index("mwlqkwv"), Some(u32::MAX));
+        assert_eq!(column_index("MWLQKWW"), None);
+        assert_eq!(column_index(""), None);
+        assert_eq!(column_index("A1"), None);
+    }
+
+    #[test]
+    fn parses_a1_notation() {
+        let range: A1Range = "Data!$B$2:D10".parse().unwrap();
+        assert_eq!(range, A1Range::cells((1, 10), (1, 4)).on_sheet("Data"));
+
+        let range = A1Range::parse("'It''s'!A:C").unwrap();
+        assert_eq!(range.sheet.as_deref(), Some("It's"));
+        assert_eq!((range.start_row, range.end_row), (None, None));
+        assert_eq!((range.start_column, range.end_column), (Some(0), Some(3)));
+
+        let range = A1Range::parse("3:5").unwrap();
+        assert_eq!(range.sheet, None);
+        assert_eq!((range.start_row, range.end_row), (Some(2), Some(5)));
+        assert_eq!((range.start_column, range.end_column), (None, None));
+
+        assert_eq!(A1Range::parse("b3"), Ok(A1Range::cell(2, 1)));
+        assert_eq!(
+            A1Range::parse("Data"),
+            Ok(A1Range::default().on_sheet("Data"))
+        );
+    }
+
+    #[test]
+    fn formats_a1_notation() {
+        for s in &[
+            "Data!B2:D10",
+            "'Q1 Sales'!A1",
+            "'It''s'!A:C",
+            "B3",
+            "3:5",
+            "Data",
+        ] {
+            assert_eq!(A1Range::parse(s).unwrap().to_string(), *s);
+        }
+    }
+
+    #[test]
+    fn rejects_bad_a1_notation() {
+        for s in &[
+            "",
+            "!",
+            "D10:B2",
+            "A0",
+            "1A",
+            "A1:B2:C3",
+            "'Open!A1",
+            "'Q1'x!A1",
+            "A99999999999",
+        ] {
+            assert!(A1Range::parse(s).is_err(), "{} parsed", s);
+        }
+    }
+
+    #[test]
+    fn offsets_and_intersects_a1_ranges() {
+        let range = A1Range::parse("A1:C3").unwrap();
+        assert_eq!(range.offset(1, 1), A1Range::parse("B2:D4").ok());
+        assert_eq!(range.offset(-1, 0), None);
+        assert_eq!(
+            range.intersect(&A1Range::parse("B2:D4").unwrap()),
+            A1Range::parse("B2:C3").ok()
+        );
+        assert_eq!(
+            range.intersect(&A1Range::parse("A:A").unwrap()),
+            A1Range::parse("A1:A3").ok()
+        );
+        assert_eq!(range.intersect(&A1Range::parse("D1:E3").unwrap()), None);
+        assert_eq!(range.intersect(&range.clone().on_sheet("Data")), None);
+        assert!(range.contains(2, 2));
+        assert!(!range.contains(3, 0));
+        assert_eq!((range.height(), range.width()), (Some(3), Some(3)));
+    }
+
+    #[test]
+    fn converts_grid_ranges() {
+        let a1 = A1Range::parse("Data!B2:D10").unwrap();
+        let grid = a1.to_grid(7);
+        assert_eq!(grid, GridRange::cells(7, (1, 10), (1, 4)));
+        assert_eq!(grid.to_a1("Data"), a1);
+        assert_eq!(
+            serde_json::to_value(GridRange::rows(7, 2, 5)).unwrap(),
+            serde_json::json!({ "sheetId": 7, "startRowIndex": 2, "endRowIndex": 5 })
+        );
+    }
+
+    #[test]
+    fn offsets_and_intersects_grid_ranges() {
+        let rows = GridRange::rows(7, 2, 5);
+        let columns = GridRange::columns(7, 1, 3);
+        assert_eq!(
+            rows.intersect(&columns),
+            Some(GridRange::cells(7, (2, 5), (1, 3)))
+        );
+        assert_eq!(rows.intersect(&GridRange::rows(7, 5, 9)), None);
+        assert_eq!(rows.intersect(&GridRange::columns(8, 1, 3)), None);
+        assert_eq!(
+            GridRange::sheet(7).intersect(&columns),
+            Some(columns.clone())
+        );
+        assert_eq!(rows.offset(-2, 4), Some(GridRange::rows(7, 0, 3)));
+        assert_eq!(rows.offset(-3, 0), None);
+        assert_eq!(columns.offset(0, 1), Some(GridRange::columns(7, 2, 4)));
+    }
+}
@@ -0,0 +1,447 @@
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+
+use super::range::GridRange;
+use super::Sheets;
+use crate::error::{CheckStatus, Error};
+use crate::models::ExtraFields;
+
+/// A colour, each channel from 0 to 1.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Color {
+    pub red: f32,
+    pub green: f32,
+    pub blue: f32,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl Color {
+    pub fn rgb(red: u8, green: u8, blue: u8) -> Color {
+        Color {
+            red: f32::from(red) / 255.0,
+            green: f32::from(green) / 255.0,
+            blue: f32::from(blue) / 255.0,
+            ..Color::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum HorizontalAlign {
+    Left,
+    Center,
+    Right,
+}
+
+/// Font settings. Only what's set is changed.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct TextFormat {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bold: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub italic: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub font_size: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub foreground_color: Option<Color>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+/// How numbers and dates are displayed, e.g. `#,##0.00` or `yyyy-mm-dd`.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct NumberFormat {
+    /// `NUMBER`, `CURRENCY`, `PERCENT`, `DATE`, `TIME`, `DATE_TIME`, `TEXT` or `SCIENTIFIC`.
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub pattern: String,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+/// Formatting for a range of cells, built up with chained setters:
+///
+/// ```
+/// # use google_api::*;
+/// let header = CellFormat::new().bold().background(Color::rgb(230, 230, 230));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct CellFormat {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub background_color: Option<Color>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text_format: Option<TextFormat>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub horizontal_alignment: Option<HorizontalAlign>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub number_format: Option<NumberFormat>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl CellFormat {
+    pub fn new() -> CellFormat {
+        CellFormat::default()
+    }
+
+    pub fn background(mut self, color: Color) -> CellFormat {
+        self.background_color = Some(color);
+        self
+    }
+
+    pub fn bold(mut self) -> CellFormat {
+        self.text_format
+            .get_or_insert_with(TextFormat::default)
+            .bold = Some(true);
+        self
+    }
+
+    pub fn italic(mut self) -> CellFormat {
+        self.text_format
+            .get_or_insert_with(TextFormat::default)
+            .italic = Some(true);
+        self
+    }
+
+    pub fn font_size(mut self, size: u32) -> CellFormat {
+        self.text_format
+            .get_or_insert_with(TextFormat::default)
+            .font_size = Some(size);
+        self
+    }
+
+    pub fn text_color(mut self, color: Color) -> CellFormat {
+        self.text_format
+            .get_or_insert_with(TextFormat::default)
+            .foreground_color = Some(color);
+        self
+    }
+
+    pub fn align(mut self, align: HorizontalAlign) -> CellFormat {
+        self.horizontal_alignment = Some(align);
+        self
+    }
+
+    /// Number format by type and pattern, e.g. `("CURRENCY", "$#,##0.00")`.
+    pub fn number_format(mut self, kind: &str, pattern: &str) -> CellFormat {
+        self.number_format = Some(NumberFormat {
+            kind: kind.to_string(),
+            pattern: pattern.to_string(),
+            ..NumberFormat::default()
+        });
+        self
+    }
+
+    /// Field mask naming only what's set, so anything else on the cells is left alone.
+    fn fields(&self) -> String {
+        let mut fields = vec![];
+        if self.background_color.is_some() {
+            fields.push("backgroundColor");
+        }
+        if self.text_format.is_some() {
+            fields.push("textFormat");
+        }
+        if self.horizontal_alignment.is_some() {
+            fields.push("horizontalAlignment");
+        }
+        if self.number_format.is_some() {
+            fields.push("numberFormat");
+        }
+        format!("userEnteredFormat({})", fields.join(","))
+    }
+}
+
+/// A condition on a cell's value, for conditional formatting and data validation. The
+/// helpers cover the common ones, `Condition::new` takes any of the API's condition types.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Condition {
+    kind: String,
+    values: Vec<String>,
+}
+
+impl Condition {
+    /// Condition of given type (e.g. `NUMBER_BETWEEN`, `DATE_BEFORE`) with its values.
+    pub fn new(kind: &str, values: &[&str]) -> Condition {
+        Condition {
+            kind: kind.to_string(),
+            values: values.iter().map(|v| v.to_string()).collect(),
+        }
+    }
+
+    pub fn number_greater(value: f64) -> Condition {
+        Condition::new("NUMBER_GREATER", &[&value.to_string()])
+    }
+
+    pub fn number_less(value: f64) -> Condition {
+        Condition::new("NUMBER_LESS", &[&value.to_string()])
+    }
+
+    pub fn text_contains(text: &str) -> Condition {
+        Condition::new("TEXT_CONTAINS", &[text])
+    }
+
+    /// True where the formula is, e.g. `=$C2>$B2`. Relative references move with each cell.
+    pub fn custom_formula(formula: &str) -> Condition {
+        Condition::new("CUSTOM_FORMULA", &[formula])
+    }
+
+    /// Value is one of the given options. As a validation rule it shows as a dropdown.
+    pub fn one_of(options: &[&str]) -> Condition {
+        Condition::new("ONE_OF_LIST", options)
+    }
+
+    pub fn checkbox() -> Condition {
+        Condition::new("BOOLEAN", &[])
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        let values: Vec<serde_json::Value> = self
+            .values
+            .iter()
+            .map(|v| serde_json::json!({ "userEnteredValue": v }))
+            .collect();
+        serde_json::json!({ "type": self.kind, "values": values })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeType {
+    /// One cell covering the whole range.
+    All,
+    /// One cell per column.
+    Columns,
+    /// One cell per row.
+    Rows,
+}
+
+impl MergeType {
+    fn as_str(&self) -> &'static str {
+        match self {
+            MergeType::All => "MERGE_ALL",
+            MergeType::Columns => "MERGE_COLUMNS",
+            MergeType::Rows => "MERGE_ROWS",
+        }
+    }
+}
+
+/// One change to a spreadsheet's structure or formatting, sent with `Sheets::batch_update`.
+/// `Raw` takes any request the API supports that isn't covered here.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SheetRequest {
+    /// Add a sheet with given title at the end.
+    AddSheet {
+        title: String,
+    },
+    DeleteSheet {
+        sheet_id: u32,
+    },
+    RenameSheet {
+        sheet_id: u32,
+        title: String,
+    },
+    /// Copy a sheet, with its values and formatting, under a new title.
+    DuplicateSheet {
+        sheet_id: u32,
+        title: String,
+    },
+    /// Move a sheet to given tab position, counting from 0.
+    MoveSheet {
+        sheet_id: u32,
+        index: u32,
+    },
+    /// Set the width in pixels of columns `start..end`.
+    ResizeColumns {
+        sheet_id: u32,
+        start: u32,
+        end: u32,
+        pixels: u32,
+    },
+    /// Fit columns `start..end` to their content.
+    AutoResizeColumns {
+        sheet_id: u32,
+        start: u32,
+        end: u32,
+    },
+    /// Freeze the first rows and columns so they stay in view while scrolling.
+    Freeze {
+        sheet_id: u32,
+        rows: u32,
+        columns: u32,
+    },
+    FormatCells {
+        range: GridRange,
+        format: CellFormat,
+    },
+    MergeCells {
+        range: GridRange,
+        merge_type: MergeType,
+    },
+    UnmergeCells {
+        range: GridRange,
+    },
+    /// Apply `format` to cells in `range` that meet `condition`.
+    AddConditionalFormat {
+        range: GridRange,
+        condition: Condition,
+        format: CellFormat,
+    },
+    /// Only accept values meeting `condition` in `range`, `None` removes validation. When not
+    /// `strict`, invalid values are flagged instead of rejected.
+    SetDataValidation {
+        range: GridRange,
+        condition: Option<Condition>,
+        strict: bool,
+    },
+    Raw(serde_json::Value),
+}
+
+impl SheetRequest {
+    fn to_json(&self) -> serde_json::Value {
+        match self {
+            SheetRequest::AddSheet { title } => serde_json::json!({
+                "addSheet": { "properties": { "title": title } }
+            }),
+            SheetRequest::DeleteSheet { sheet_id } => serde_json::json!({
+                "deleteSheet": { "sheetId": sheet_id }
+            }),
+            SheetRequest::RenameSheet { sheet_id, title } => serde_json::json!({
+                "updateSheetProperties": {
+                    "properties": { "sheetId": sheet_id, "title": title },
+                    "fields": "title",
+                }
+            }),
+            SheetRequest::DuplicateSheet { sheet_id, title } => serde_json::json!({
+                "duplicateSheet": { "sourceSheetId": sheet_id, "newSheetName": title }
+            }),
+            SheetRequest::MoveSheet { sheet_id, index } => serde_json::json!({
+                "updateSheetProperties": {
+                    "properties": { "sheetId": sheet_id, "index": index },
+                    "fields": "index",
+                }
+            }),
+            SheetRequest::ResizeColumns {
+                sheet_id,
+                start,
+                end,
+                pixels,
+            } => serde_json::json!({
+                "updateDimensionProperties": {
+                    "range": dimension_range(*sheet_id, *start, *end),
+                    "properties": { "pixelSize": pixels },
+                    "fields": "pixelSize",
+                }
+            }),
+            SheetRequest::AutoResizeColumns {
+                sheet_id,
+                start,
+                end,
+            } => serde_json::json!({
+                "autoResizeDimensions": { "dimensions": dimension_range(*sheet_id, *start, *end) }
+            }),
+            SheetRequest::Freeze {
+                sheet_id,
+                rows,
+                columns,
+            } => serde_json::json!({
+                "updateSheetProperties": {
+                    "properties": {
+                        "sheetId": sheet_id,
+                        "gridProperties": { "frozenRowCount": rows, "frozenColumnCount": columns },
+                    },
+                    "fields": "gridProperties(frozenRowCount,frozenColumnCount)",
+                }
+            }),
+            SheetRequest::FormatCells { range, format } => serde_json::json!({
+                "repeatCell": {
+                    "range": range,
+                    "cell": { "userEnteredFormat": format },
+                    "fields": format.fields(),
+                }
+            }),
+            SheetRequest::MergeCells { range, merge_type } => serde_json::json!({
+                "mergeCells": { "range": range, "mergeType": merge_type.as_str() }
+            }),
+            SheetRequest::UnmergeCells { range } => serde_json::json!({
+                "unmergeCells": { "range": range }
+            }),
+            SheetRequest::AddConditionalFormat {
+                range,
+                condition,
+                format,
+            } => serde_json::json!({
+                "addConditionalFormatRule": {
+                    "rule": {
+                        "ranges": [range],
+                        "booleanRule": { "condition": condition.to_json(), "format": format },
+                    },
+                    "index": 0,
+                }
+            }),
+            SheetRequest::SetDataValidation {
+                range,
+                condition,
+                strict,
+            } => {
+                let rule = condition.as_ref().map(|c| {
+                    serde_json::json!({
+                        "condition": c.to_json(),
+                        "strict": strict,
+                        "showCustomUi": true,
+                    })
+                });
+                let mut request = serde_json::json!({ "range": range });
+                if let Some(rule) = rule {
+                    request["rule"] = rule;
+                }
+                serde_json::json!({ "setDataValidation": request })
+            }
+            SheetRequest::Raw(request) => request.clone(),
+        }
+    }
+}
+
+/// Columns `start..end` of a sheet, as the API's `DimensionRange`.
+fn dimension_range(sheet_id: u32, start: u32, end: u32) -> serde_json::Value {
+    serde_json::json!({
+        "sheetId": sheet_id,
+        "dimension": "COLUMNS",
+        "startIndex": start,
+        "endIndex": end,
+    })
+}
+
+/// Result of a batch update, one reply per request. Most replies are empty, `AddSheet`'s
+/// holds the new sheet's properties.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct BatchUpdateResponse {
+    pub spreadsheet_id: String,
+    pub replies: Vec<serde_json::Value>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl Sheets {
+    /// Apply the requests to the spreadsheet in order, as one change. If any of them fails,
+    /// none are applied.
+    pub fn batch_update(
+        &self,
+        spreadsheet_id: &str,
+        requests: &[SheetRequest],
+    ) -> Result<BatchUpdateResponse, Error> {
+        let requests: Vec<serde_json::Value> = requests.iter().map(|r| r.to_json()).collect();
+        let endpoint = format!("{}:batchUpdate", spreadsheet_id);
+        Ok(self
+            .request(Method::POST, &[&endpoint])?
+            .json(&serde_json::json!({ "requests": requests }))
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+}
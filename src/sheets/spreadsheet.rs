@@ -0,0 +1,163 @@
+use reqwest::Method;
+use serde::Deserialize;
+
+use super::requests::SheetRequest;
+use super::Sheets;
+use crate::error::{CheckStatus, Error};
+use crate::models::ExtraFields;
+
+/// A spreadsheet's metadata and tabs, without cell data.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Spreadsheet {
+    pub spreadsheet_id: String,
+    pub properties: SpreadsheetProperties,
+    pub sheets: Vec<Sheet>,
+    pub spreadsheet_url: String,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl Spreadsheet {
+    /// The tab with given title, if there is one.
+    pub fn sheet(&self, title: &str) -> Option<&SheetProperties> {
+        self.sheets
+            .iter()
+            .map(|s| &s.properties)
+            .find(|p| p.title == title)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct SpreadsheetProperties {
+    pub title: String,
+    pub locale: String,
+    pub time_zone: String,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+/// One tab of a spreadsheet.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Sheet {
+    pub properties: SheetProperties,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct SheetProperties {
+    /// Stable id of the sheet, unlike its title and index. The first sheet's is 0.
+    pub sheet_id: u32,
+    pub title: String,
+    /// Position among the tabs, from 0.
+    pub index: u32,
+    pub grid_properties: GridProperties,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct GridProperties {
+    pub row_count: u32,
+    pub column_count: u32,
+    pub frozen_row_count: u32,
+    pub frozen_column_count: u32,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl Sheets {
+    /// Create an empty spreadsheet with given title, in the root of the user's Drive.
+    pub fn create(&self, title: &str) -> Result<Spreadsheet, Error> {
+        Ok(self
+            .request(Method::POST, &[])?
+            .json(&serde_json::json!({ "properties": { "title": title } }))
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    /// Get a spreadsheet's metadata and list of tabs.
+    pub fn get_spreadsheet(&self, spreadsheet_id: &str) -> Result<Spreadsheet, Error> {
+        Ok(self
+            .request(Method::GET, &[spreadsheet_id])?
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    /// Add a tab with given title at the end. Returns the new sheet.
+    pub fn add_sheet(&self, spreadsheet_id: &str, title: &str) -> Result<SheetProperties, Error> {
+        let request = SheetRequest::AddSheet {
+            title: title.to_string(),
+        };
+        self.update_one(spreadsheet_id, request, "addSheet")
+    }
+
+    /// Copy a tab under a new title. Returns the copy.
+    pub fn duplicate_sheet(
+        &self,
+        spreadsheet_id: &str,
+        sheet_id: u32,
+        title: &str,
+    ) -> Result<SheetProperties, Error> {
+        let request = SheetRequest::DuplicateSheet {
+            sheet_id,
+            title: title.to_string(),
+        };
+        self.update_one(spreadsheet_id, request, "duplicateSheet")
+    }
+
+    pub fn rename_sheet(
+        &self,
+        spreadsheet_id: &str,
+        sheet_id: u32,
+        title: &str,
+    ) -> Result<(), Error> {
+        let request = SheetRequest::RenameSheet {
+            sheet_id,
+            title: title.to_string(),
+        };
+        self.batch_update(spreadsheet_id, &[request])?;
+        Ok(())
+    }
+
+    /// Move a tab to given position, counting from 0.
+    pub fn move_sheet(&self, spreadsheet_id: &str, sheet_id: u32, index: u32) -> Result<(), Error> {
+        self.batch_update(
+            spreadsheet_id,
+            &[SheetRequest::MoveSheet { sheet_id, index }],
+        )?;
+        Ok(())
+    }
+
+    /// Delete a tab. A spreadsheet must keep at least one.
+    pub fn delete_sheet(&self, spreadsheet_id: &str, sheet_id: u32) -> Result<(), Error> {
+        self.batch_update(spreadsheet_id, &[SheetRequest::DeleteSheet { sheet_id }])?;
+        Ok(())
+    }
+
+    /// Send a single request whose reply holds a sheet's properties under `reply`.
+    fn update_one(
+        &self,
+        spreadsheet_id: &str,
+        request: SheetRequest,
+        reply: &str,
+    ) -> Result<SheetProperties, Error> {
+        let resp = self.batch_update(spreadsheet_id, &[request])?;
+        match resp.replies.first().map(|r| &r[reply]["properties"]) {
+            Some(properties) if properties.is_object() => {
+                Ok(serde_json::from_value(properties.clone())?)
+            }
+            _ => Err(Error::UnexpectedResponse(format!(
+                "batch update reply had no {} properties",
+                reply
+            ))),
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+
+use super::Sheets;
+use crate::error::{CheckStatus, Error};
+use crate::models::ExtraFields;
+
+/// Whether values are read and written row by row or column by column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum MajorDimension {
+    #[default]
+    Rows,
+    Columns,
+}
+
+/// How written values are interpreted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueInputOption {
+    /// Stored exactly as given, `=SUM(A1:A3)` stays a string.
+    Raw,
+    /// Parsed as if typed into the UI, so formulas, numbers and dates are recognised.
+    UserEntered,
+}
+
+impl ValueInputOption {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ValueInputOption::Raw => "RAW",
+            ValueInputOption::UserEntered => "USER_ENTERED",
+        }
+    }
+}
+
+/// Values in a range of a sheet. Trailing empty rows and cells are left out by the API.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ValueRange {
+    /// Range in A1 notation, e.g. `Data!A1:C10`.
+    pub range: String,
+    pub major_dimension: MajorDimension,
+    pub values: Vec<Vec<serde_json::Value>>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl ValueRange {
+    /// The value at given row and column, counted from the start of the range.
+    pub fn get(&self, row: usize, column: usize) -> Option<&serde_json::Value> {
+        self.values.get(row).and_then(|r| r.get(column))
+    }
+}
+
+/// What an update wrote.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct UpdateValuesResponse {
+    pub spreadsheet_id: String,
+    pub updated_range: String,
+    pub updated_rows: u32,
+    pub updated_columns: u32,
+    pub updated_cells: u32,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+/// What an append wrote, and where.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct AppendValuesResponse {
+    pub spreadsheet_id: String,
+    /// The table the values were appended to, before the append.
+    pub table_range: Option<String>,
+    pub updates: UpdateValuesResponse,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl Sheets {
+    /// Read the values in given range (A1 notation, see `A1Range`), as shown in the UI.
+    pub fn get_values(&self, spreadsheet_id: &str, range: &str) -> Result<ValueRange, Error> {
+        Ok(self
+            .request(Method::GET, &[spreadsheet_id, "values", range])?
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    /// Overwrite the values in given range, row by row, starting at its top left cell.
+    pub fn update_values(
+        &self,
+        spreadsheet_id: &str,
+        range: &str,
+        values: &[Vec<serde_json::Value>],
+        input: ValueInputOption,
+    ) -> Result<UpdateValuesResponse, Error> {
+        Ok(self
+            .request(Method::PUT, &[spreadsheet_id, "values", range])?
+            .query(&[("valueInputOption", input.as_str())])
+            .json(&serde_json::json!({ "range": range, "values": values }))
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    /// Append rows after the table found in given range, inserting new rows rather than
+    /// writing over anything below it.
+    pub fn append_values(
+        &self,
+        spreadsheet_id: &str,
+        range: &str,
+        values: &[Vec<serde_json::Value>],
+        input: ValueInputOption,
+    ) -> Result<AppendValuesResponse, Error> {
+        let endpoint = format!("{}:append", range);
+        Ok(self
+            .request(Method::POST, &[spreadsheet_id, "values", &endpoint])?
+            .query(&[
+                ("valueInputOption", input.as_str()),
+                ("insertDataOption", "INSERT_ROWS"),
+            ])
+            .json(&serde_json::json!({ "range": range, "values": values }))
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    /// Clear the values in given range, leaving formatting alone. Returns the range cleared.
+    pub fn clear_values(&self, spreadsheet_id: &str, range: &str) -> Result<String, Error> {
+        let endpoint = format!("{}:clear", range);
+        let resp: serde_json::Value = self
+            .request(Method::POST, &[spreadsheet_id, "values", &endpoint])?
+            .json(&serde_json::json!({}))
+            .send()?
+            .check_status()?
+            .json()?;
+        Ok(resp["clearedRange"].as_str().unwrap_or(range).to_string())
+    }
+}
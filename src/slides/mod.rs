@@ -0,0 +1,62 @@
+use reqwest::blocking::RequestBuilder;
+use reqwest::Method;
+
+use crate::api;
+use crate::auth::Auth;
+use crate::error::Error;
+use crate::scope::Scope;
+
+mod presentation;
+mod requests;
+
+pub use presentation::{
+    Image, PageElement, PageSize, Presentation, Shape, ShapeText, Slide, SlideTextRun, TextElement,
+    Thumbnail, ThumbnailSize,
+};
+pub use requests::{PresentationUpdateResponse, SlideRequest};
+
+const SLIDES_BASE_URL: &str = "https://slides.googleapis.com/v1/presentations";
+
+/// Google Slides client. Shares its saved token with the other clients.
+///
+/// ```no_run
+/// # use google_api::*;
+/// # let (client_id, client_secret) = (String::new(), String::new());
+/// # let deck_id = "1aBc";
+/// let slides = Slides::new(client_id, client_secret, None)?;
+/// let deck = slides.get_presentation(deck_id)?;
+/// let copy = slides.duplicate_slide(deck_id, &deck.slides[0].object_id)?;
+/// slides.batch_update(deck_id, &[SlideRequest::ReplaceAllText {
+///     find: String::from("{{quarter}}"),
+///     replace: String::from("Q3"),
+///     page_ids: vec![copy],
+/// }])?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug)]
+pub struct Slides {
+    auth: Auth,
+    client: reqwest::blocking::Client,
+}
+
+impl Slides {
+    /// Authenticate with given OAuth client. `scopes` defaults to `Scope::Presentations`.
+    pub fn new(
+        client_id: String,
+        client_secret: String,
+        scopes: Option<Vec<String>>,
+    ) -> Result<Slides, Error> {
+        let scopes = scopes.unwrap_or_else(|| vec![Scope::Presentations.into()]);
+
+        Ok(Slides {
+            auth: Auth::from_config(&client_id, &client_secret, &scopes)?,
+            client: reqwest::blocking::Client::new(),
+        })
+    }
+
+    /// Authorized request for the endpoint below `/presentations` made of given path
+    /// segments, e.g. `[id, "pages", page_id]`.
+    fn request(&self, method: Method, segments: &[&str]) -> Result<RequestBuilder, Error> {
+        api::request(&self.client, &self.auth, SLIDES_BASE_URL, method, segments)
+    }
+}
@@ -0,0 +1,219 @@
+use std::fs;
+use std::path::Path;
+
+use reqwest::Method;
+use serde::Deserialize;
+
+use super::Slides;
+use crate::docs::Dimension;
+use crate::error::{CheckStatus, Error};
+use crate::models::ExtraFields;
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct PageSize {
+    pub width: Dimension,
+    pub height: Dimension,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+/// A presentation and its slides.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Presentation {
+    pub presentation_id: String,
+    pub title: String,
+    pub revision_id: String,
+    pub page_size: PageSize,
+    pub slides: Vec<Slide>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Slide {
+    /// Id that `SlideRequest`s and `Slides::slide_thumbnail` take.
+    pub object_id: String,
+    pub page_elements: Vec<PageElement>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl Slide {
+    /// Text of every shape on the slide, in order.
+    pub fn text(&self) -> String {
+        self.page_elements
+            .iter()
+            .filter_map(|e| e.shape.as_ref())
+            .map(|s| s.text())
+            .collect()
+    }
+}
+
+/// Something on a slide. One of `shape` and `image` is set for those kinds, anything else
+/// (tables, videos, groups, ...) only has its id.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct PageElement {
+    pub object_id: String,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub shape: Option<Shape>,
+    pub image: Option<Image>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+/// A text box, or any other shape that can hold text.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Shape {
+    /// `TEXT_BOX`, `RECTANGLE`, ...
+    pub shape_type: String,
+    pub text: Option<ShapeText>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl Shape {
+    pub fn text(&self) -> String {
+        self.text
+            .iter()
+            .flat_map(|t| &t.text_elements)
+            .filter_map(|e| e.text_run.as_ref())
+            .map(|run| run.content.as_str())
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ShapeText {
+    pub text_elements: Vec<TextElement>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+/// A piece of a shape's text. Paragraph markers and autotext have no `text_run`.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct TextElement {
+    pub start_index: u32,
+    pub end_index: u32,
+    pub text_run: Option<SlideTextRun>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct SlideTextRun {
+    pub content: String,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Image {
+    /// Short-lived link to the image content.
+    pub content_url: String,
+    pub source_url: Option<String>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+/// Width of a slide thumbnail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThumbnailSize {
+    /// 200 pixels.
+    Small,
+    /// 800 pixels.
+    Medium,
+    /// 1600 pixels.
+    Large,
+}
+
+impl ThumbnailSize {
+    fn as_str(&self) -> &'static str {
+        match self {
+            ThumbnailSize::Small => "SMALL",
+            ThumbnailSize::Medium => "MEDIUM",
+            ThumbnailSize::Large => "LARGE",
+        }
+    }
+}
+
+/// A rendered slide, as a short-lived link to a PNG.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Thumbnail {
+    pub width: u32,
+    pub height: u32,
+    pub content_url: String,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl Slides {
+    /// Create a presentation with given title and one blank slide, in the root of the
+    /// user's Drive.
+    pub fn create_presentation(&self, title: &str) -> Result<Presentation, Error> {
+        Ok(self
+            .request(Method::POST, &[])?
+            .json(&serde_json::json!({ "title": title }))
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    pub fn get_presentation(&self, presentation_id: &str) -> Result<Presentation, Error> {
+        Ok(self
+            .request(Method::GET, &[presentation_id])?
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    /// Render a slide to PNG. The link in the answer works for about half an hour.
+    pub fn slide_thumbnail(
+        &self,
+        presentation_id: &str,
+        slide_id: &str,
+        size: ThumbnailSize,
+    ) -> Result<Thumbnail, Error> {
+        Ok(self
+            .request(
+                Method::GET,
+                &[presentation_id, "pages", slide_id, "thumbnail"],
+            )?
+            .query(&[
+                ("thumbnailProperties.mimeType", "PNG"),
+                ("thumbnailProperties.thumbnailSize", size.as_str()),
+            ])
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    /// Render a slide and write the PNG to given path.
+    pub fn save_slide_png(
+        &self,
+        presentation_id: &str,
+        slide_id: &str,
+        size: ThumbnailSize,
+        path: &Path,
+    ) -> Result<(), Error> {
+        let thumbnail = self.slide_thumbnail(presentation_id, slide_id, size)?;
+        let png = self
+            .client
+            .get(&thumbnail.content_url)
+            .send()?
+            .check_status()?
+            .bytes()?;
+        fs::write(path, png)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,190 @@
+use reqwest::Method;
+use serde::Deserialize;
+
+use super::Slides;
+use crate::error::{CheckStatus, Error};
+use crate::models::ExtraFields;
+
+/// One change to a presentation, sent with `Slides::batch_update`. Empty `page_ids` means
+/// every slide. `Raw` takes any request the API supports that isn't covered here.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SlideRequest {
+    /// Add a slide with a predefined layout (`BLANK`, `TITLE`, `TITLE_AND_BODY`, ...) at
+    /// given position, or at the end.
+    CreateSlide {
+        layout: String,
+        index: Option<u32>,
+    },
+    /// Copy a slide, or any other object, right after the original.
+    Duplicate {
+        object_id: String,
+    },
+    /// Delete a slide or an object on one.
+    Delete {
+        object_id: String,
+    },
+    /// Move slides to given position, counting from 0, keeping their order.
+    MoveSlides {
+        slide_ids: Vec<String>,
+        index: u32,
+    },
+    /// Replace every occurrence of `find`, matching case, e.g. a `{{title}}` placeholder.
+    ReplaceAllText {
+        find: String,
+        replace: String,
+        page_ids: Vec<String>,
+    },
+    /// Replace every shape containing `find` with the image at `image_url`, fitted inside
+    /// the shape.
+    ReplaceShapesWithImage {
+        find: String,
+        image_url: String,
+        page_ids: Vec<String>,
+    },
+    /// Swap an existing image for the one at `image_url`, keeping its size and position.
+    ReplaceImage {
+        image_id: String,
+        image_url: String,
+    },
+    /// Insert text into a shape at given index.
+    InsertText {
+        object_id: String,
+        index: u32,
+        text: String,
+    },
+    Raw(serde_json::Value),
+}
+
+impl SlideRequest {
+    fn to_json(&self) -> serde_json::Value {
+        match self {
+            SlideRequest::CreateSlide { layout, index } => {
+                let mut request = serde_json::json!({
+                    "slideLayoutReference": { "predefinedLayout": layout }
+                });
+                if let Some(index) = index {
+                    request["insertionIndex"] = serde_json::json!(index);
+                }
+                serde_json::json!({ "createSlide": request })
+            }
+            SlideRequest::Duplicate { object_id } => serde_json::json!({
+                "duplicateObject": { "objectId": object_id }
+            }),
+            SlideRequest::Delete { object_id } => serde_json::json!({
+                "deleteObject": { "objectId": object_id }
+            }),
+            SlideRequest::MoveSlides { slide_ids, index } => serde_json::json!({
+                "updateSlidesPosition": { "slideObjectIds": slide_ids, "insertionIndex": index }
+            }),
+            SlideRequest::ReplaceAllText {
+                find,
+                replace,
+                page_ids,
+            } => serde_json::json!({
+                "replaceAllText": {
+                    "containsText": { "text": find, "matchCase": true },
+                    "replaceText": replace,
+                    "pageObjectIds": page_ids,
+                }
+            }),
+            SlideRequest::ReplaceShapesWithImage {
+                find,
+                image_url,
+                page_ids,
+            } => serde_json::json!({
+                "replaceAllShapesWithImage": {
+                    "containsText": { "text": find, "matchCase": true },
+                    "imageUrl": image_url,
+                    "imageReplaceMethod": "CENTER_INSIDE",
+                    "pageObjectIds": page_ids,
+                }
+            }),
+            SlideRequest::ReplaceImage {
+                image_id,
+                image_url,
+            } => serde_json::json!({
+                "replaceImage": { "imageObjectId": image_id, "url": image_url }
+            }),
+            SlideRequest::InsertText {
+                object_id,
+                index,
+                text,
+            } => serde_json::json!({
+                "insertText": { "objectId": object_id, "insertionIndex": index, "text": text }
+            }),
+            SlideRequest::Raw(request) => request.clone(),
+        }
+    }
+}
+
+/// Result of a batch update, one reply per request. `CreateSlide` and `Duplicate` replies
+/// hold the new object's id.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct PresentationUpdateResponse {
+    pub presentation_id: String,
+    pub replies: Vec<serde_json::Value>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl Slides {
+    /// Apply the requests to the presentation in order, as one change. If any of them fails,
+    /// none are applied.
+    pub fn batch_update(
+        &self,
+        presentation_id: &str,
+        requests: &[SlideRequest],
+    ) -> Result<PresentationUpdateResponse, Error> {
+        let requests: Vec<serde_json::Value> = requests.iter().map(|r| r.to_json()).collect();
+        let endpoint = format!("{}:batchUpdate", presentation_id);
+        Ok(self
+            .request(Method::POST, &[&endpoint])?
+            .json(&serde_json::json!({ "requests": requests }))
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    /// Copy a slide, e.g. one from a template deck, right after it. Returns the copy's id.
+    pub fn duplicate_slide(&self, presentation_id: &str, slide_id: &str) -> Result<String, Error> {
+        let request = SlideRequest::Duplicate {
+            object_id: slide_id.to_string(),
+        };
+        self.create_one(presentation_id, request, "duplicateObject")
+    }
+
+    /// Add a slide with given predefined layout at the end. Returns its id.
+    pub fn add_slide(&self, presentation_id: &str, layout: &str) -> Result<String, Error> {
+        let request = SlideRequest::CreateSlide {
+            layout: layout.to_string(),
+            index: None,
+        };
+        self.create_one(presentation_id, request, "createSlide")
+    }
+
+    pub fn delete_slide(&self, presentation_id: &str, slide_id: &str) -> Result<(), Error> {
+        let request = SlideRequest::Delete {
+            object_id: slide_id.to_string(),
+        };
+        self.batch_update(presentation_id, &[request])?;
+        Ok(())
+    }
+
+    /// Send a single request whose reply holds the new object's id under `reply`.
+    fn create_one(
+        &self,
+        presentation_id: &str,
+        request: SlideRequest,
+        reply: &str,
+    ) -> Result<String, Error> {
+        let resp = self.batch_update(presentation_id, &[request])?;
+        resp.replies
+            .first()
+            .and_then(|r| r[reply]["objectId"].as_str())
+            .map(String::from)
+            .ok_or_else(|| {
+                Error::UnexpectedResponse(format!("batch update reply had no {} object id", reply))
+            })
+    }
+}
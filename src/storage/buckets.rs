@@ -0,0 +1,83 @@
+use reqwest::Method;
+use serde::Deserialize;
+
+use super::Storage;
+use crate::error::Error;
+use crate::models::ExtraFields;
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Bucket {
+    pub id: String,
+    pub name: String,
+    /// Where the bucket's data lives, e.g. `US` or `EUROPE-WEST1`.
+    pub location: String,
+    /// Default storage class of new objects, e.g. `STANDARD` or `NEARLINE`.
+    pub storage_class: String,
+    pub time_created: String,
+    pub updated: String,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct BucketList {
+    items: Vec<Bucket>,
+    next_page_token: Option<String>,
+}
+
+impl Storage {
+    /// Buckets of given Cloud project.
+    pub fn list_buckets(&self, project: &str) -> Result<Vec<Bucket>, Error> {
+        let mut buckets = vec![];
+        let mut page_token: Option<String> = None;
+        loop {
+            let mut params = vec![("project", project)];
+            if let Some(token) = &page_token {
+                params.push(("pageToken", token));
+            }
+            let page: BucketList = self
+                .send(|| Ok(self.request(Method::GET, &["b"])?.query(&params)))?
+                .json()?;
+            buckets.extend(page.items);
+
+            match page.next_page_token {
+                Some(token) => page_token = Some(token),
+                None => break,
+            }
+        }
+        Ok(buckets)
+    }
+
+    pub fn get_bucket(&self, bucket: &str) -> Result<Bucket, Error> {
+        Ok(self
+            .send(|| self.request(Method::GET, &["b", bucket]))?
+            .json()?)
+    }
+
+    /// Create a bucket in given project. Bucket names are global, so this fails if anyone
+    /// already has one by that name. `location` defaults to `US`.
+    pub fn create_bucket(
+        &self,
+        project: &str,
+        name: &str,
+        location: Option<&str>,
+    ) -> Result<Bucket, Error> {
+        let body = serde_json::json!({ "name": name, "location": location.unwrap_or("US") });
+        Ok(self
+            .send(|| {
+                Ok(self
+                    .request(Method::POST, &["b"])?
+                    .query(&[("project", project)])
+                    .json(&body))
+            })?
+            .json()?)
+    }
+
+    /// Delete a bucket, which has to be empty.
+    pub fn delete_bucket(&self, bucket: &str) -> Result<(), Error> {
+        self.send(|| self.request(Method::DELETE, &["b", bucket]))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,89 @@
+use reqwest::blocking::{RequestBuilder, Response};
+use reqwest::Method;
+
+use crate::api;
+use crate::auth::Auth;
+use crate::error::Error;
+use crate::retry::{send_with_policy, RetryPolicy};
+use crate::scope::Scope;
+
+mod buckets;
+mod objects;
+mod signing;
+mod upload;
+
+pub use buckets::Bucket;
+pub use objects::{Object, ObjectList};
+pub use upload::ObjectUpload;
+
+const STORAGE_BASE_URL: &str = "https://storage.googleapis.com/storage/v1";
+const STORAGE_UPLOAD_URL: &str = "https://storage.googleapis.com/upload/storage/v1";
+
+/// Google Cloud Storage client for buckets and objects. Shares its saved token with the
+/// other clients. Signed URLs don't go through the API, see `ServiceAccountKey`.
+///
+/// ```no_run
+/// # use std::path::Path;
+/// # use google_api::*;
+/// # let (client_id, client_secret) = (String::new(), String::new());
+/// let storage = Storage::new(client_id, client_secret, None)?;
+/// storage.upload_file("my-bucket", "backups/db.tar.gz", Path::new("db.tar.gz"), None)?;
+/// for object in storage.list_objects("my-bucket", Some("backups/"))? {
+///     println!("{} {:?}", object.name, object.size);
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug)]
+pub struct Storage {
+    auth: Auth,
+    client: reqwest::blocking::Client,
+    retry: RetryPolicy,
+}
+
+impl Storage {
+    /// Authenticate with given OAuth client. `scopes` defaults to `Scope::CloudStorage`.
+    pub fn new(
+        client_id: String,
+        client_secret: String,
+        scopes: Option<Vec<String>>,
+    ) -> Result<Storage, Error> {
+        let scopes = scopes.unwrap_or_else(|| vec![Scope::CloudStorage.into()]);
+
+        Ok(Storage {
+            auth: Auth::from_config(&client_id, &client_secret, &scopes)?,
+            client: reqwest::blocking::Client::new(),
+            retry: RetryPolicy::default(),
+        })
+    }
+
+    /// Set how rate limited and failing requests, upload chunks included, are retried.
+    pub fn retry_policy(mut self, retry: RetryPolicy) -> Storage {
+        self.retry = retry;
+        self
+    }
+
+    /// Authorized request for the endpoint made of given path segments, e.g.
+    /// `["b", bucket, "o", object]`. Object names are encoded whole, slashes included.
+    fn request(&self, method: Method, segments: &[&str]) -> Result<RequestBuilder, Error> {
+        api::request(&self.client, &self.auth, STORAGE_BASE_URL, method, segments)
+    }
+
+    /// Authorized request against the media upload endpoint.
+    fn upload_request(&self, method: Method, segments: &[&str]) -> Result<RequestBuilder, Error> {
+        api::request(
+            &self.client,
+            &self.auth,
+            STORAGE_UPLOAD_URL,
+            method,
+            segments,
+        )
+    }
+
+    /// Send a request built by `build`, retrying rate limits and server errors.
+    fn send<F>(&self, build: F) -> Result<Response, Error>
+    where
+        F: Fn() -> Result<RequestBuilder, Error>,
+    {
+        send_with_policy(&self.retry, || Ok::<_, Error>(build()?.send()?))
+    }
+}
@@ -0,0 +1,190 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufWriter, Read, Write};
+use std::path::Path;
+
+use reqwest::blocking::Body;
+use reqwest::Method;
+use serde::Deserialize;
+
+use super::Storage;
+use crate::error::{CheckStatus, Error};
+use crate::models::{de_int64, ExtraFields};
+
+/// An object's metadata. The content itself is fetched with `Storage::download_object`.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Object {
+    pub name: String,
+    pub bucket: String,
+    #[serde(deserialize_with = "de_int64")]
+    pub size: Option<u64>,
+    pub content_type: String,
+    /// Base64 MD5 of the content. Not set for objects put together with compose.
+    pub md5_hash: Option<String>,
+    /// Base64 CRC32C of the content.
+    pub crc32c: String,
+    /// Version of the content, changes every time the object is overwritten.
+    #[serde(deserialize_with = "de_int64")]
+    pub generation: Option<u64>,
+    pub storage_class: String,
+    pub time_created: String,
+    pub updated: String,
+    pub cache_control: Option<String>,
+    pub content_disposition: Option<String>,
+    /// Custom key/value metadata.
+    pub metadata: HashMap<String, String>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+/// One level of a bucket, as if `/` separated directories: the objects directly under a
+/// prefix and the prefixes one level deeper.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ObjectList {
+    #[serde(rename = "items")]
+    pub objects: Vec<Object>,
+    /// Deeper prefixes, each ending in `/`.
+    pub prefixes: Vec<String>,
+    next_page_token: Option<String>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl Storage {
+    /// Objects in given bucket, optionally only those whose names start with `prefix`.
+    pub fn list_objects(&self, bucket: &str, prefix: Option<&str>) -> Result<Vec<Object>, Error> {
+        Ok(self.list_page_by_page(bucket, prefix, None)?.objects)
+    }
+
+    /// Objects directly under `prefix` and the prefixes below it, the way a file browser
+    /// would show a directory. Give `""` for the top of the bucket.
+    pub fn list_directory(&self, bucket: &str, prefix: &str) -> Result<ObjectList, Error> {
+        self.list_page_by_page(bucket, Some(prefix), Some("/"))
+    }
+
+    fn list_page_by_page(
+        &self,
+        bucket: &str,
+        prefix: Option<&str>,
+        delimiter: Option<&str>,
+    ) -> Result<ObjectList, Error> {
+        let mut list = ObjectList::default();
+        let mut page_token: Option<String> = None;
+        loop {
+            let mut params = vec![];
+            if let Some(prefix) = prefix {
+                params.push(("prefix", prefix));
+            }
+            if let Some(delimiter) = delimiter {
+                params.push(("delimiter", delimiter));
+            }
+            if let Some(token) = &page_token {
+                params.push(("pageToken", token));
+            }
+            let page: ObjectList = self
+                .send(|| {
+                    Ok(self
+                        .request(Method::GET, &["b", bucket, "o"])?
+                        .query(&params))
+                })?
+                .json()?;
+            list.objects.extend(page.objects);
+            list.prefixes.extend(page.prefixes);
+
+            match page.next_page_token {
+                Some(token) => page_token = Some(token),
+                None => break,
+            }
+        }
+        Ok(list)
+    }
+
+    pub fn get_object(&self, bucket: &str, name: &str) -> Result<Object, Error> {
+        Ok(self
+            .send(|| self.request(Method::GET, &["b", bucket, "o", name]))?
+            .json()?)
+    }
+
+    /// Merge given keys into the object's custom metadata. Keys not given are kept.
+    pub fn set_object_metadata(
+        &self,
+        bucket: &str,
+        name: &str,
+        metadata: &HashMap<String, String>,
+    ) -> Result<Object, Error> {
+        let body = serde_json::json!({ "metadata": metadata });
+        Ok(self
+            .send(|| {
+                Ok(self
+                    .request(Method::PATCH, &["b", bucket, "o", name])?
+                    .json(&body))
+            })?
+            .json()?)
+    }
+
+    /// Change the headers the object is served with.
+    pub fn set_object_content_type(
+        &self,
+        bucket: &str,
+        name: &str,
+        content_type: &str,
+    ) -> Result<Object, Error> {
+        let body = serde_json::json!({ "contentType": content_type });
+        Ok(self
+            .send(|| {
+                Ok(self
+                    .request(Method::PATCH, &["b", bucket, "o", name])?
+                    .json(&body))
+            })?
+            .json()?)
+    }
+
+    pub fn delete_object(&self, bucket: &str, name: &str) -> Result<(), Error> {
+        self.send(|| self.request(Method::DELETE, &["b", bucket, "o", name]))?;
+        Ok(())
+    }
+
+    /// Upload an object from any reader in one request, without holding it in memory. A
+    /// reader can't be rewound, so this isn't retried; `upload_file` is for files.
+    pub fn upload_object<R: Read + Send + 'static>(
+        &self,
+        bucket: &str,
+        name: &str,
+        content: R,
+        content_type: &str,
+    ) -> Result<Object, Error> {
+        Ok(self
+            .upload_request(Method::POST, &["b", bucket, "o"])?
+            .query(&[("uploadType", "media"), ("name", name)])
+            .header("Content-Type", content_type)
+            .body(Body::new(content))
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    /// Stream an object's content into `out`. Returns the number of bytes written.
+    pub fn download_object<W: Write>(
+        &self,
+        bucket: &str,
+        name: &str,
+        out: &mut W,
+    ) -> Result<u64, Error> {
+        let mut resp = self.send(|| {
+            Ok(self
+                .request(Method::GET, &["b", bucket, "o", name])?
+                .query(&[("alt", "media")]))
+        })?;
+        Ok(resp.copy_to(out)?)
+    }
+
+    /// Download an object to a local file.
+    pub fn download_object_to(&self, bucket: &str, name: &str, path: &Path) -> Result<u64, Error> {
+        let mut file = BufWriter::new(File::create(path)?);
+        let written = self.download_object(bucket, name, &mut file)?;
+        file.flush()?;
+        Ok(written)
+    }
+}
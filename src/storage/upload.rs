@@ -0,0 +1,102 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use reqwest::{Method, StatusCode};
+use serde::{Deserialize, Serialize};
+
+use super::{Object, Storage};
+use crate::error::{CheckStatus, Error};
+use crate::mime::MimeType;
+use crate::resumable::{confirmed_offset, send_file_chunks};
+
+/// An in-progress resumable upload to Cloud Storage. It can be serialized and handed back to
+/// `Storage::resume_upload` later, as the session stays open for a week.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ObjectUpload {
+    pub session_uri: String,
+    pub path: PathBuf,
+    pub size: u64,
+    /// Bytes Cloud Storage has confirmed receiving.
+    pub offset: u64,
+}
+
+impl Storage {
+    /// Open a resumable upload of a local file to `bucket/name`. Nothing is sent until
+    /// `resume_upload` is called. `content_type` defaults to one guessed from the extension.
+    pub fn start_upload(
+        &self,
+        bucket: &str,
+        name: &str,
+        path: &Path,
+        content_type: Option<&str>,
+    ) -> Result<ObjectUpload, Error> {
+        let size = fs::metadata(path)?.len();
+        let content_type = content_type
+            .map(String::from)
+            .unwrap_or_else(|| MimeType::from_path(path).as_str().to_string());
+        let body = serde_json::json!({ "name": name, "contentType": content_type });
+        let resp = self.send(|| {
+            Ok(self
+                .upload_request(Method::POST, &["b", bucket, "o"])?
+                .query(&[("uploadType", "resumable")])
+                .header("X-Upload-Content-Type", content_type.as_str())
+                .header("X-Upload-Content-Length", size)
+                .json(&body))
+        })?;
+
+        match resp.headers().get("Location").map(|l| l.to_str()) {
+            Some(Ok(uri)) => Ok(ObjectUpload {
+                session_uri: uri.to_string(),
+                path: path.to_path_buf(),
+                size,
+                offset: 0,
+            }),
+            _ => Err(Error::UnexpectedResponse(String::from(
+                "resumable upload session response had no Location header",
+            ))),
+        }
+    }
+
+    /// Ask Cloud Storage how much of given upload it has, updating `upload.offset`. Returns
+    /// the object if the upload has already completed.
+    pub fn upload_status(&self, upload: &mut ObjectUpload) -> Result<Option<Object>, Error> {
+        let resp = self
+            .client
+            .put(upload.session_uri.as_str())
+            .header("Content-Range", format!("bytes */{}", upload.size))
+            .header("Content-Length", 0)
+            .send()?;
+        if resp.status() == StatusCode::PERMANENT_REDIRECT {
+            upload.offset = confirmed_offset(&resp);
+            return Ok(None);
+        }
+        Ok(Some(resp.check_status()?.json()?))
+    }
+
+    /// Send the rest of given upload chunk by chunk, from the last confirmed byte. Failed
+    /// chunks are retried from whatever part of them arrived.
+    pub fn resume_upload(&self, upload: &mut ObjectUpload) -> Result<Object, Error> {
+        let session_uri = upload.session_uri.clone();
+        let resp = send_file_chunks(
+            || self.client.put(session_uri.as_str()),
+            &self.retry,
+            &upload.path,
+            upload.size,
+            &mut upload.offset,
+        )?;
+        Ok(resp.json()?)
+    }
+
+    /// Upload a local file to `bucket/name` with the resumable protocol, so a dropped
+    /// connection only costs the chunk in flight.
+    pub fn upload_file(
+        &self,
+        bucket: &str,
+        name: &str,
+        path: &Path,
+        content_type: Option<&str>,
+    ) -> Result<Object, Error> {
+        let mut upload = self.start_upload(bucket, name, path, content_type)?;
+        self.resume_upload(&mut upload)
+    }
+}
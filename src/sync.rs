@@ -0,0 +1,1208 @@
+use std::collections::{BTreeSet, HashMap};
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+use std::time::UNIX_EPOCH;
+
+use serde::{Deserialize, Serialize};
+
+use crate::changes::Change;
+use crate::concurrency::run_bounded;
+use crate::download::{local_file_name, write_replacing};
+use crate::error::{CheckStatus, Error};
+use crate::file_id::FileId;
+use crate::ignore::IgnoreRules;
+use crate::models::{DriveFile, FILE_FIELDS};
+use crate::params::Params;
+use crate::query::Query;
+use crate::timestamp::Timestamp;
+use crate::upload::UploadOptions;
+use crate::Drive;
+
+/// Default name of the state file, kept in the root of the local directory.
+pub const SYNC_STATE_FILE: &str = ".google_api_sync.json";
+
+/// What to do when a file changed both locally and on Drive since the last sync.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConflictPolicy {
+    /// Keep whichever side was modified last.
+    NewestWins,
+    /// Keep the Drive version at the original path and the local version as a renamed copy
+    /// on both sides.
+    KeepBoth,
+    /// Don't touch either side. Conflicts are returned so the caller can ask the user.
+    Ask,
+}
+
+/// Options for `Drive::sync`.
+#[derive(Debug, Clone)]
+pub struct SyncOptions {
+    delete: bool,
+    conflict: ConflictPolicy,
+    state_file: Option<PathBuf>,
+    ignore: IgnoreRules,
+    full_scan: bool,
+    jobs: usize,
+}
+
+impl Default for SyncOptions {
+    fn default() -> Self {
+        SyncOptions {
+            delete: false,
+            conflict: ConflictPolicy::NewestWins,
+            state_file: None,
+            ignore: IgnoreRules::new(),
+            full_scan: false,
+            jobs: 1,
+        }
+    }
+}
+
+impl SyncOptions {
+    pub fn new() -> SyncOptions {
+        SyncOptions::default()
+    }
+
+    /// Propagate deletions. Off by default, files deleted on one side are left alone on the
+    /// other. Remote deletions go to Drive's trash.
+    pub fn delete(mut self, delete: bool) -> SyncOptions {
+        self.delete = delete;
+        self
+    }
+
+    pub fn conflict_policy(mut self, policy: ConflictPolicy) -> SyncOptions {
+        self.conflict = policy;
+        self
+    }
+
+    /// Where to keep sync state. Defaults to `SYNC_STATE_FILE` in the local directory.
+    pub fn state_file(mut self, path: PathBuf) -> SyncOptions {
+        self.state_file = Some(path);
+        self
+    }
+
+    /// Skip paths matching given pattern on both sides, a line of an ignore file (see
+    /// `IgnoreRules`). Added after the rules in the directory's `DRIVE_IGNORE_FILE`.
+    pub fn ignore(mut self, pattern: &str) -> SyncOptions {
+        self.ignore = self.ignore.exclude(pattern);
+        self
+    }
+
+    /// Sync paths matching given pattern even if an ignore rule before it leaves them out.
+    pub fn include(mut self, pattern: &str) -> SyncOptions {
+        self.ignore = self.ignore.include(pattern);
+        self
+    }
+
+    /// Add a whole set of ignore rules.
+    pub fn ignore_rules(mut self, rules: &IgnoreRules) -> SyncOptions {
+        self.ignore = self.ignore.extend(rules);
+        self
+    }
+
+    /// List the whole Drive folder instead of only looking at what changed since the last
+    /// sync. Useful if the state file is suspected to be out of date.
+    pub fn full_scan(mut self, full_scan: bool) -> SyncOptions {
+        self.full_scan = full_scan;
+        self
+    }
+
+    /// Number of files to transfer at once.
+    pub fn jobs(mut self, jobs: usize) -> SyncOptions {
+        self.jobs = jobs.max(1);
+        self
+    }
+}
+
+/// One step of a sync. Paths are relative to the synced directory.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyncAction {
+    /// Upload a local file, updating `file_id` if it already exists on Drive.
+    Upload {
+        path: PathBuf,
+        file_id: Option<String>,
+    },
+    Download {
+        path: PathBuf,
+        file_id: String,
+    },
+    DeleteLocal {
+        path: PathBuf,
+    },
+    /// Move a Drive file to the trash.
+    DeleteRemote {
+        path: PathBuf,
+        file_id: String,
+    },
+    /// Rename the local file to `copy`, upload it as a new file, and download the Drive
+    /// version to `path`.
+    KeepBoth {
+        path: PathBuf,
+        copy: PathBuf,
+        file_id: String,
+    },
+    /// Changed on both sides and left alone (`ConflictPolicy::Ask`).
+    Conflict {
+        path: PathBuf,
+        file_id: String,
+    },
+}
+
+/// Outcome of `Drive::sync`.
+#[derive(Debug, Clone, Default)]
+pub struct SyncReport {
+    /// Actions that were carried out.
+    pub done: Vec<SyncAction>,
+    /// Conflicts left for the caller to resolve.
+    pub conflicts: Vec<SyncAction>,
+    /// Actions that failed, with the reason.
+    pub failed: Vec<(SyncAction, String)>,
+}
+
+/// A file as it was after the last sync.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub(crate) struct SyncedFile {
+    pub(crate) file_id: String,
+    pub(crate) md5: Option<String>,
+    pub(crate) remote_modified: Option<Timestamp>,
+    pub(crate) size: u64,
+    pub(crate) local_mtime: u64,
+}
+
+/// Persisted state of a synced directory, keyed by `/` separated relative path.
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub(crate) struct SyncState {
+    pub(crate) files: HashMap<String, SyncedFile>,
+    /// Relative folder path -> Drive folder id.
+    pub(crate) folders: HashMap<String, String>,
+    /// Changes feed position, used for delta syncs.
+    #[serde(default)]
+    pub(crate) page_token: Option<String>,
+}
+
+impl SyncState {
+    pub(crate) fn load(path: &Path) -> Result<SyncState, Error> {
+        if !path.exists() {
+            return Ok(SyncState::default());
+        }
+        Ok(serde_json::from_reader(BufReader::new(File::open(path)?))?)
+    }
+
+    pub(crate) fn save(&self, path: &Path) -> Result<(), Error> {
+        let f = File::create(path)?;
+        serde_json::to_writer_pretty(BufWriter::new(f), self)?;
+        Ok(())
+    }
+}
+
+/// Files and folders under a Drive folder, keyed by relative path.
+#[derive(Debug, Default)]
+pub(crate) struct RemoteTree {
+    pub(crate) files: HashMap<String, DriveFile>,
+    pub(crate) folders: HashMap<String, String>,
+}
+
+impl RemoteTree {
+    /// The Drive folder as it was after the last sync.
+    fn from_state(state: &SyncState) -> RemoteTree {
+        let files = state
+            .files
+            .iter()
+            .map(|(key, synced)| {
+                let file = DriveFile {
+                    id: synced.file_id.clone(),
+                    name: key.rsplit('/').next().unwrap_or_default().to_string(),
+                    size: Some(synced.size),
+                    md5_checksum: synced.md5.clone(),
+                    modified_time: synced.remote_modified,
+                    ..DriveFile::default()
+                };
+                (key.clone(), file)
+            })
+            .collect();
+        RemoteTree {
+            files,
+            folders: state.folders.clone(),
+        }
+    }
+
+    /// Drop the file or folder with given id, along with everything under a dropped folder.
+    fn remove_id(&mut self, id: &str) {
+        self.files.retain(|_, f| f.id != id);
+        let removed: Vec<String> = self
+            .folders
+            .iter()
+            .filter(|(_, folder_id)| folder_id.as_str() == id)
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in removed {
+            let prefix = format!("{}/", key);
+            self.folders
+                .retain(|k, _| k != &key && !k.starts_with(&prefix));
+            self.files.retain(|k, _| !k.starts_with(&prefix));
+        }
+    }
+
+    /// Relative path of the known folder with given id.
+    fn folder_path(&self, id: &str) -> Option<String> {
+        self.folders
+            .iter()
+            .find(|(_, folder_id)| folder_id.as_str() == id)
+            .map(|(key, _)| key.clone())
+    }
+
+    /// Update a tree from the last sync with changes from the feed. The feed covers the whole
+    /// Drive, anything outside the synced folder is dropped. `list` gives the contents of a
+    /// folder, for folders that were moved or renamed.
+    fn apply_changes<F>(
+        &mut self,
+        changes: Vec<Change>,
+        options: &SyncOptions,
+        mut list: F,
+    ) -> Result<(), Error>
+    where
+        F: FnMut(&str) -> Result<RemoteTree, Error>,
+    {
+        // Folders first so files in new folders can be placed.
+        let (folders, files): (Vec<Change>, Vec<Change>) = changes
+            .into_iter()
+            .partition(|c| matches!(&c.file, Some(f) if f.is_folder()));
+        for change in folders.into_iter().chain(files) {
+            let file = match change.file {
+                Some(file) if !change.removed && !file.trashed => file,
+                _ => {
+                    if let Some(id) = &change.file_id {
+                        self.remove_id(id);
+                    }
+                    continue;
+                }
+            };
+            // Moved or renamed files show up as a change, forget the old path.
+            let known_folder = self.folder_path(&file.id);
+            self.remove_id(&file.id);
+            let parent = file.parents.iter().find_map(|p| self.folder_path(p));
+            // Named the way a full listing names it, see `Drive::remote_tree`.
+            let name = local_file_name(&file.name);
+            let key = match parent {
+                Some(p) if p.is_empty() => name,
+                Some(p) => format!("{}/{}", p, name),
+                None => continue,
+            };
+            let is_folder = file.is_folder();
+            if options.ignore.is_ignored(Path::new(&key), is_folder) {
+                continue;
+            }
+            if is_folder {
+                self.folders.insert(key.clone(), file.id.clone());
+                // Children of a moved or renamed folder don't change themselves, so list them.
+                if known_folder.as_deref() != Some(key.as_str()) {
+                    let subtree = list(&file.id)?;
+                    self.graft(&key, subtree);
+                }
+            } else if !file.is_google_native() {
+                self.files.insert(key, file);
+            }
+        }
+        Ok(())
+    }
+
+    /// Add everything from a subtree listed separately, rooted at given relative path.
+    fn graft(&mut self, at: &str, subtree: RemoteTree) {
+        let join = |key: String| format!("{}/{}", at, key);
+        self.folders
+            .extend(subtree.folders.into_iter().map(|(k, v)| (join(k), v)));
+        self.files
+            .extend(subtree.files.into_iter().map(|(k, v)| (join(k), v)));
+    }
+}
+
+/// Size and modification time of a local file.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct LocalFile {
+    pub(crate) size: u64,
+    pub(crate) mtime: u64,
+}
+
+impl LocalFile {
+    pub(crate) fn stat(path: &Path) -> io::Result<LocalFile> {
+        let meta = fs::metadata(path)?;
+        let mtime = meta
+            .modified()?
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        Ok(LocalFile {
+            size: meta.len(),
+            mtime,
+        })
+    }
+}
+
+/// Hex md5 of a local file, comparable with Drive's `md5Checksum`.
+pub(crate) fn md5_file(path: &Path) -> io::Result<String> {
+    let mut file = File::open(path)?;
+    let mut context = md5::Context::new();
+    let mut buf = vec![0; 64 * 1024];
+    loop {
+        let n = file.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        context.consume(&buf[..n]);
+    }
+    Ok(format!("{:x}", context.compute()))
+}
+
+/// Relative path to the `/` separated key used in the state file.
+pub(crate) fn state_key(relative: &Path) -> String {
+    relative.to_string_lossy().replace('\\', "/")
+}
+
+/// Name for the local copy kept by `ConflictPolicy::KeepBoth`.
+fn conflict_copy(path: &Path) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or_default();
+    let name = match path.extension().and_then(|e| e.to_str()) {
+        Some(ext) => format!("{} (conflict).{}", stem, ext),
+        None => format!("{} (conflict)", stem),
+    };
+    path.with_file_name(name)
+}
+
+/// The options with the rules of the local directory's ignore file put first.
+fn with_ignore_file(local: &Path, options: &SyncOptions) -> Result<SyncOptions, Error> {
+    let mut options = options.clone();
+    options.ignore = IgnoreRules::load(local)?.extend(&options.ignore);
+    Ok(options)
+}
+
+fn state_file_path(local: &Path, options: &SyncOptions) -> PathBuf {
+    options
+        .state_file
+        .clone()
+        .unwrap_or_else(|| local.join(SYNC_STATE_FILE))
+}
+
+/// What to do with each path, from its local and Drive versions and how it was after the
+/// last sync.
+pub(crate) fn plan(
+    local: &Path,
+    local_files: &HashMap<String, LocalFile>,
+    remote: &HashMap<String, DriveFile>,
+    state: &SyncState,
+    options: &SyncOptions,
+) -> Result<Vec<SyncAction>, Error> {
+    let keys: BTreeSet<&String> = local_files.keys().chain(remote.keys()).collect();
+    let mut actions = vec![];
+    for key in keys {
+        let path = PathBuf::from(key);
+        let l = local_files.get(key);
+        let r = remote.get(key);
+        let s = state.files.get(key);
+        let action = match (l, r, s) {
+            (Some(l), Some(r), Some(s)) => {
+                let local_changed = l.size != s.size || l.mtime != s.local_mtime;
+                let remote_changed =
+                    r.md5_checksum != s.md5 || r.modified_time != s.remote_modified;
+                match (local_changed, remote_changed) {
+                    (false, false) => None,
+                    (true, false) => Some(SyncAction::Upload {
+                        path,
+                        file_id: Some(r.id.clone()),
+                    }),
+                    (false, true) => Some(SyncAction::Download {
+                        path,
+                        file_id: r.id.clone(),
+                    }),
+                    (true, true) => conflict(local, path, l, r, options)?,
+                }
+            }
+            // Never synced but on both sides, only a conflict if the contents differ.
+            (Some(l), Some(r), None) => conflict(local, path, l, r, options)?,
+            (Some(_), None, Some(_)) if options.delete => Some(SyncAction::DeleteLocal { path }),
+            (Some(_), None, Some(_)) => None,
+            (Some(_), None, None) => Some(SyncAction::Upload {
+                path,
+                file_id: None,
+            }),
+            (None, Some(r), Some(_)) if options.delete => Some(SyncAction::DeleteRemote {
+                path,
+                file_id: r.id.clone(),
+            }),
+            (None, Some(_), Some(_)) => None,
+            (None, Some(r), None) => Some(SyncAction::Download {
+                path,
+                file_id: r.id.clone(),
+            }),
+            (None, None, _) => None,
+        };
+        actions.extend(action);
+    }
+    Ok(actions)
+}
+
+/// Resolve a file that changed on both sides according to the conflict policy.
+fn conflict(
+    local: &Path,
+    path: PathBuf,
+    l: &LocalFile,
+    r: &DriveFile,
+    options: &SyncOptions,
+) -> Result<Option<SyncAction>, Error> {
+    // Same content on both sides isn't a conflict.
+    if Some(l.size) == r.size && r.md5_checksum.as_deref() == Some(&md5_file(&local.join(&path))?) {
+        return Ok(None);
+    }
+    let file_id = r.id.clone();
+    Ok(Some(match options.conflict {
+        ConflictPolicy::NewestWins => {
+            let remote_mtime = r
+                .modified_time
+                .and_then(|t| t.system_time().duration_since(UNIX_EPOCH).ok())
+                .map(|d| d.as_secs())
+                .unwrap_or_default();
+            if l.mtime > remote_mtime {
+                SyncAction::Upload {
+                    path,
+                    file_id: Some(file_id),
+                }
+            } else {
+                SyncAction::Download { path, file_id }
+            }
+        }
+        ConflictPolicy::KeepBoth => SyncAction::KeepBoth {
+            copy: conflict_copy(&path),
+            path,
+            file_id,
+        },
+        ConflictPolicy::Ask => SyncAction::Conflict { path, file_id },
+    }))
+}
+
+impl Drive {
+    /// Work out what `sync` would do, without changing anything.
+    pub fn sync_plan(
+        &self,
+        local: &Path,
+        folder_id: impl Into<FileId>,
+        options: &SyncOptions,
+    ) -> Result<Vec<SyncAction>, Error> {
+        let folder_id = folder_id.into();
+        let options = &with_ignore_file(local, options)?;
+        let state = SyncState::load(&state_file_path(local, options))?;
+        let (remote, _) = self.remote_state(folder_id.as_str(), &state, options)?;
+        let remote = remote.files;
+        let local_files = local_tree(local, options)?;
+        plan(local, &local_files, &remote, &state, options)
+    }
+
+    /// Sync the local directory with given Drive folder: new and changed files are copied to
+    /// the other side, deletions are propagated if enabled, and conflicts are handled per the
+    /// conflict policy. State is saved after the run, even if some actions failed.
+    ///
+    /// The first run lists the whole Drive folder. Later runs only look at the changes feed
+    /// since the previous run, unless `SyncOptions::full_scan` is set.
+    pub fn sync(
+        &self,
+        local: &Path,
+        folder_id: impl Into<FileId>,
+        options: &SyncOptions,
+    ) -> Result<SyncReport, Error> {
+        let folder_id = folder_id.into();
+        let options = &with_ignore_file(local, options)?;
+        let state_path = state_file_path(local, options);
+        let mut state = SyncState::load(&state_path)?;
+        let (
+            RemoteTree {
+                files: remote,
+                folders,
+            },
+            page_token,
+        ) = self.remote_state(folder_id.as_str(), &state, options)?;
+        state.folders = folders;
+        state.folders.insert(String::new(), folder_id.to_string());
+        let local_files = local_tree(local, options)?;
+        let actions = plan(local, &local_files, &remote, &state, options)?;
+
+        // Forget files that are gone from both sides.
+        state
+            .files
+            .retain(|key, _| remote.contains_key(key) || local_files.contains_key(key));
+
+        let report = self.apply(local, actions, &mut state, options.jobs);
+        // Files that failed would be missed by the next delta, rescan next time instead.
+        state.page_token = if report.failed.is_empty() {
+            Some(page_token)
+        } else {
+            None
+        };
+        state.save(&state_path)?;
+        Ok(report)
+    }
+
+    /// Current contents of the synced Drive folder and the change feed token they're as of.
+    /// Applies the changes since the last sync to the saved state when possible, falls back to
+    /// listing everything.
+    fn remote_state(
+        &self,
+        folder_id: &str,
+        state: &SyncState,
+        options: &SyncOptions,
+    ) -> Result<(RemoteTree, String), Error> {
+        if let (Some(token), false) = (&state.page_token, options.full_scan) {
+            let (changes, next) = self.list_changes(token)?;
+            let mut tree = RemoteTree::from_state(state);
+            tree.folders.insert(String::new(), folder_id.to_string());
+            tree.apply_changes(changes, options, |id| self.remote_tree(id, options))?;
+            return Ok((tree, next));
+        }
+        // Take the token first so nothing changed during the listing gets missed.
+        let token = self.start_page_token()?;
+        Ok((self.remote_tree(folder_id, options)?, token))
+    }
+
+    /// List every file and folder under given folder. Google-native files are skipped since
+    /// they can't be downloaded as-is.
+    pub(crate) fn remote_tree(
+        &self,
+        folder_id: &str,
+        options: &SyncOptions,
+    ) -> Result<RemoteTree, Error> {
+        let mut tree = RemoteTree::default();
+        let mut pending = vec![(PathBuf::new(), folder_id.to_string())];
+        while let Some((dir, id)) = pending.pop() {
+            for file in self.search(
+                &Query::new()
+                    .in_folder(FileId::unchecked(&id))
+                    .trashed(false),
+            )? {
+                let relative = dir.join(local_file_name(&file.name));
+                let is_folder = file.is_folder();
+                if options.ignore.is_ignored(&relative, is_folder) {
+                    continue;
+                }
+                if is_folder {
+                    tree.folders.insert(state_key(&relative), file.id.clone());
+                    pending.push((relative, file.id));
+                } else if !file.is_google_native() {
+                    tree.files.insert(state_key(&relative), file);
+                }
+            }
+        }
+        Ok(tree)
+    }
+
+    /// Carry out planned actions, recording each success in the state.
+    pub(crate) fn apply(
+        &self,
+        local: &Path,
+        actions: Vec<SyncAction>,
+        state: &mut SyncState,
+        jobs: usize,
+    ) -> SyncReport {
+        let mut report = SyncReport::default();
+        let shared = Mutex::new(std::mem::take(state));
+        let (conflicts, actions): (Vec<SyncAction>, Vec<SyncAction>) = actions
+            .into_iter()
+            .partition(|a| matches!(a, SyncAction::Conflict { .. }));
+        report.conflicts = conflicts;
+
+        // Create the folders new files go into up front, so files in the same new folder
+        // don't race to create it. A failure here shows up again for the file.
+        for action in &actions {
+            let new_file = match action {
+                SyncAction::Upload {
+                    path,
+                    file_id: None,
+                } => path,
+                SyncAction::KeepBoth { copy, .. } => copy,
+                _ => continue,
+            };
+            let _ = self.ensure_remote_folder(new_file.parent().unwrap_or(Path::new("")), &shared);
+        }
+
+        let results = run_bounded(actions, jobs, |action| {
+            let result = self.apply_one(local, &action, &shared);
+            (action, result)
+        });
+        *state = shared.into_inner().unwrap_or_else(|e| e.into_inner());
+        for (action, result) in results {
+            match result {
+                Ok(()) => report.done.push(action),
+                Err(e) => report.failed.push((action, e.to_string())),
+            }
+        }
+        report
+    }
+
+    /// Apply one action. The state is only locked to read and record, not during transfers.
+    fn apply_one(
+        &self,
+        local: &Path,
+        action: &SyncAction,
+        state: &Mutex<SyncState>,
+    ) -> Result<(), Error> {
+        match action {
+            SyncAction::Upload { path, file_id } => {
+                let file = self.sync_upload(local, path, file_id.as_deref(), state)?;
+                record(&mut lock(state), local, path, &file)?;
+            }
+            SyncAction::Download { path, file_id } => {
+                let file = self.sync_download(local, path, file_id)?;
+                record(&mut lock(state), local, path, &file)?;
+            }
+            SyncAction::DeleteLocal { path } => {
+                fs::remove_file(local.join(path))?;
+                lock(state).files.remove(&state_key(path));
+            }
+            SyncAction::DeleteRemote { path, file_id } => {
+                self.set_trashed(FileId::unchecked(file_id), true)?;
+                lock(state).files.remove(&state_key(path));
+            }
+            SyncAction::KeepBoth {
+                path,
+                copy,
+                file_id,
+            } => {
+                fs::rename(local.join(path), local.join(copy))?;
+                let uploaded = self.sync_upload(local, copy, None, state)?;
+                record(&mut lock(state), local, copy, &uploaded)?;
+                let downloaded = self.sync_download(local, path, file_id)?;
+                record(&mut lock(state), local, path, &downloaded)?;
+            }
+            SyncAction::Conflict { .. } => {}
+        }
+        Ok(())
+    }
+
+    fn sync_upload(
+        &self,
+        local: &Path,
+        path: &Path,
+        file_id: Option<&str>,
+        state: &Mutex<SyncState>,
+    ) -> Result<DriveFile, Error> {
+        let mut options = UploadOptions::new();
+        if file_id.is_none() {
+            let parent =
+                self.ensure_remote_folder(path.parent().unwrap_or(Path::new("")), state)?;
+            options = options.parent(FileId::unchecked(parent));
+        }
+        self.send_file(file_id, &local.join(path), &options)
+    }
+
+    fn sync_download(&self, local: &Path, path: &Path, file_id: &str) -> Result<DriveFile, Error> {
+        let target = local.join(path);
+        if let Some(dir) = target.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let meta: DriveFile = self
+            .get(
+                format!("/files/{}", file_id).as_str(),
+                Params::new().fields(FILE_FIELDS),
+            )?
+            .check_status()?
+            .json()?;
+        let mut resp = self.get_media(file_id, None)?.check_status()?;
+        write_replacing(&target, |partial| {
+            resp.copy_to(&mut File::create(partial)?)?;
+            Ok(())
+        })?;
+        Ok(meta)
+    }
+
+    /// Id of the Drive folder for given relative folder path, creating any missing folders.
+    /// The state is only locked to look folders up and record them, not while creating them.
+    fn ensure_remote_folder(
+        &self,
+        relative: &Path,
+        state: &Mutex<SyncState>,
+    ) -> Result<String, Error> {
+        let key = state_key(relative);
+        if let Some(id) = lock(state).folders.get(&key) {
+            return Ok(id.clone());
+        }
+        let parent =
+            self.ensure_remote_folder(relative.parent().unwrap_or(Path::new("")), state)?;
+        let name = relative
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or_default();
+        let folder = self.create_folder(name, FileId::unchecked(parent))?;
+        Ok(lock(state).folders.entry(key).or_insert(folder.id).clone())
+    }
+}
+
+fn lock(state: &Mutex<SyncState>) -> MutexGuard<'_, SyncState> {
+    state.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Record a file as in sync on both sides.
+fn record(state: &mut SyncState, local: &Path, path: &Path, file: &DriveFile) -> Result<(), Error> {
+    let stat = LocalFile::stat(&local.join(path))?;
+    state.files.insert(
+        state_key(path),
+        SyncedFile {
+            file_id: file.id.clone(),
+            md5: file.md5_checksum.clone(),
+            remote_modified: file.modified_time,
+            size: stat.size,
+            local_mtime: stat.mtime,
+        },
+    );
+    Ok(())
+}
+
+/// Every local file under given directory, keyed by relative path.
+pub(crate) fn local_tree(
+    local: &Path,
+    options: &SyncOptions,
+) -> Result<HashMap<String, LocalFile>, Error> {
+    let state_file = state_file_path(local, options);
+    let mut files = HashMap::new();
+    let mut pending = vec![local.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path == state_file {
+                continue;
+            }
+            let relative = path.strip_prefix(local).unwrap_or(&path).to_path_buf();
+            let is_dir = path.is_dir();
+            if options.ignore.is_ignored(&relative, is_dir) {
+                continue;
+            }
+            if is_dir {
+                pending.push(path);
+            } else {
+                files.insert(state_key(&relative), LocalFile::stat(&path)?);
+            }
+        }
+    }
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mime::MimeType;
+
+    fn file(id: &str, name: &str, parent: &str) -> DriveFile {
+        DriveFile {
+            id: id.to_string(),
+            name: name.to_string(),
+            parents: vec![parent.to_string()],
+            ..DriveFile::default()
+        }
+    }
+
+    fn folder(id: &str, name: &str, parent: &str) -> DriveFile {
+        DriveFile {
+            mime_type: MimeType::FOLDER.as_str().to_string(),
+            ..file(id, name, parent)
+        }
+    }
+
+    fn changed(file: DriveFile) -> Change {
+        Change {
+            file_id: Some(file.id.clone()),
+            file: Some(file),
+            ..Change::default()
+        }
+    }
+
+    fn removed(id: &str) -> Change {
+        Change {
+            removed: true,
+            file_id: Some(id.to_string()),
+            ..Change::default()
+        }
+    }
+
+    fn keys<V>(map: &HashMap<String, V>) -> BTreeSet<&str> {
+        map.keys().map(String::as_str).collect()
+    }
+
+    /// The synced folder `root` with a `docs` folder, as after a sync.
+    fn synced_tree() -> RemoteTree {
+        let mut tree = RemoteTree::default();
+        tree.folders.insert(String::new(), String::from("root"));
+        tree.folders
+            .insert(String::from("docs"), String::from("docs"));
+        for (key, f) in [
+            ("a_b", file("slash", "a/b", "root")),
+            ("notes.txt", file("notes", "notes.txt", "root")),
+            ("docs/old.txt", file("old", "old.txt", "docs")),
+        ] {
+            tree.files.insert(key.to_string(), f);
+        }
+        tree
+    }
+
+    #[test]
+    fn changes_are_keyed_like_a_full_listing() {
+        let mut tree = synced_tree();
+        let changes = vec![
+            changed(file("slash", "a/b", "root")),
+            changed(file("back", "c\\d", "docs")),
+        ];
+        tree.apply_changes(changes, &SyncOptions::new(), |_| unreachable!())
+            .unwrap();
+        assert_eq!(
+            keys(&tree.files),
+            ["a_b", "docs/c_d", "docs/old.txt", "notes.txt"]
+                .iter()
+                .copied()
+                .collect()
+        );
+    }
+
+    #[test]
+    fn renames_moves_and_removals() {
+        let mut tree = synced_tree();
+        let mut listed = vec![];
+        let changes = vec![
+            // Renamed and moved into `docs`.
+            changed(file("notes", "report.txt", "docs")),
+            removed("old"),
+            // Moved out of the synced folder.
+            changed(file("slash", "a/b", "elsewhere")),
+            // A new folder, whose contents have to be listed.
+            changed(folder("pics", "Pics", "root")),
+            changed(file("skip", "debug.log", "root")),
+        ];
+        let options = SyncOptions::new().ignore("*.log");
+        tree.apply_changes(changes, &options, |id| {
+            listed.push(id.to_string());
+            let mut subtree = RemoteTree::default();
+            subtree
+                .files
+                .insert(String::from("beach.jpg"), file("beach", "beach.jpg", id));
+            Ok(subtree)
+        })
+        .unwrap();
+        assert_eq!(listed, ["pics"]);
+        assert_eq!(
+            keys(&tree.files),
+            ["Pics/beach.jpg", "docs/report.txt"]
+                .iter()
+                .copied()
+                .collect()
+        );
+        assert_eq!(
+            keys(&tree.folders),
+            ["", "Pics", "docs"].iter().copied().collect()
+        );
+    }
+
+    #[test]
+    fn renamed_folder_takes_its_files_along() {
+        let mut tree = synced_tree();
+        let changes = vec![changed(folder("docs", "Docs/2020", "root"))];
+        tree.apply_changes(changes, &SyncOptions::new(), |id| {
+            assert_eq!(id, "docs");
+            let mut subtree = RemoteTree::default();
+            subtree
+                .files
+                .insert(String::from("old.txt"), file("old", "old.txt", id));
+            Ok(subtree)
+        })
+        .unwrap();
+        assert_eq!(
+            keys(&tree.files),
+            ["Docs_2020/old.txt", "a_b", "notes.txt"]
+                .iter()
+                .copied()
+                .collect()
+        );
+        assert_eq!(tree.folder_path("docs").as_deref(), Some("Docs_2020"));
+    }
+
+    #[test]
+    fn removing_a_folder_drops_what_is_under_it() {
+        let mut tree = synced_tree();
+        tree.folders
+            .insert(String::from("docs/deep"), String::from("deep"));
+        tree.folders
+            .insert(String::from("docs2"), String::from("docs2"));
+        tree.files
+            .insert(String::from("docs/deep/x.txt"), file("x", "x.txt", "deep"));
+        tree.files
+            .insert(String::from("docs2/y.txt"), file("y", "y.txt", "docs2"));
+        tree.remove_id("docs");
+        assert_eq!(keys(&tree.folders), ["", "docs2"].iter().copied().collect());
+        assert_eq!(
+            keys(&tree.files),
+            ["a_b", "docs2/y.txt", "notes.txt"]
+                .iter()
+                .copied()
+                .collect()
+        );
+        tree.remove_id("notes");
+        assert!(!tree.files.contains_key("notes.txt"));
+    }
+
+    #[test]
+    fn conflict_copy_names() {
+        for (path, copy) in &[
+            ("a/b/report.pdf", "a/b/report (conflict).pdf"),
+            ("Makefile", "Makefile (conflict)"),
+            (".bashrc", ".bashrc (conflict)"),
+            ("archive.tar.gz", "archive.tar (conflict).gz"),
+        ] {
+            assert_eq!(conflict_copy(Path::new(path)), PathBuf::from(copy));
+        }
+    }
+
+    const HELLO_MD5: &str = "5d41402abc4b2a76b9719d911017c592";
+    const SYNCED_AT: u64 = 1_600_000_000;
+
+    fn local_file(size: u64, mtime: u64) -> LocalFile {
+        LocalFile { size, mtime }
+    }
+
+    fn remote_file(id: &str, size: u64, md5: &str, modified: u64) -> DriveFile {
+        DriveFile {
+            id: id.to_string(),
+            size: Some(size),
+            md5_checksum: Some(md5.to_string()),
+            modified_time: Some(Timestamp::from(
+                UNIX_EPOCH + std::time::Duration::from_secs(modified),
+            )),
+            ..DriveFile::default()
+        }
+    }
+
+    fn synced(id: &str, size: u64, md5: &str) -> SyncedFile {
+        SyncedFile {
+            file_id: id.to_string(),
+            md5: Some(md5.to_string()),
+            remote_modified: remote_file(id, size, md5, SYNCED_AT).modified_time,
+            size,
+            local_mtime: SYNCED_AT,
+        }
+    }
+
+    /// A local directory holding `same.txt` with the content Drive has for it.
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("google_api_sync_{}_{}", name, std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("same.txt"), "hello").unwrap();
+        dir
+    }
+
+    /// Every case of (local, remote, state), keyed by what happened to the file.
+    fn sides() -> (
+        HashMap<String, LocalFile>,
+        HashMap<String, DriveFile>,
+        SyncState,
+    ) {
+        let mut local = HashMap::new();
+        let mut remote = HashMap::new();
+        let mut state = SyncState::default();
+        let mut add =
+            |key: &str, l: Option<LocalFile>, r: Option<DriveFile>, s: Option<SyncedFile>| {
+                let key = key.to_string();
+                local.extend(l.map(|l| (key.clone(), l)));
+                remote.extend(r.map(|r| (key.clone(), r)));
+                state.files.extend(s.map(|s| (key.clone(), s)));
+            };
+        let at = SYNCED_AT;
+        add("new_local", Some(local_file(5, at)), None, None);
+        add(
+            "new_remote",
+            None,
+            Some(remote_file("nr", 5, "aa", at)),
+            None,
+        );
+        add(
+            "unchanged",
+            Some(local_file(5, at)),
+            Some(remote_file("u", 5, "aa", at)),
+            Some(synced("u", 5, "aa")),
+        );
+        add(
+            "local_changed",
+            Some(local_file(6, at + 10)),
+            Some(remote_file("lc", 5, "aa", at)),
+            Some(synced("lc", 5, "aa")),
+        );
+        add(
+            "remote_changed",
+            Some(local_file(5, at)),
+            Some(remote_file("rc", 7, "bb", at + 10)),
+            Some(synced("rc", 5, "aa")),
+        );
+        add(
+            "local_newer",
+            Some(local_file(6, at + 20)),
+            Some(remote_file("ln", 7, "bb", at + 10)),
+            Some(synced("ln", 5, "aa")),
+        );
+        add(
+            "remote_newer",
+            Some(local_file(6, at + 10)),
+            Some(remote_file("rn", 7, "bb", at + 20)),
+            Some(synced("rn", 5, "aa")),
+        );
+        add(
+            "same.txt",
+            Some(local_file(5, at + 10)),
+            Some(remote_file("same", 5, HELLO_MD5, at + 20)),
+            None,
+        );
+        add(
+            "differs.txt",
+            Some(local_file(6, at + 10)),
+            Some(remote_file("diff", 5, "aa", at + 20)),
+            None,
+        );
+        add(
+            "deleted_locally",
+            None,
+            Some(remote_file("dl", 5, "aa", at)),
+            Some(synced("dl", 5, "aa")),
+        );
+        add(
+            "deleted_remotely",
+            Some(local_file(5, at)),
+            None,
+            Some(synced("dr", 5, "aa")),
+        );
+        add("deleted_both", None, None, Some(synced("db", 5, "aa")));
+        (local, remote, state)
+    }
+
+    fn plan_with(options: &SyncOptions, dir: &str) -> HashMap<String, SyncAction> {
+        let local = scratch_dir(dir);
+        let (local_files, remote, state) = sides();
+        let actions = plan(&local, &local_files, &remote, &state, options).unwrap();
+        fs::remove_dir_all(&local).unwrap();
+        actions
+            .into_iter()
+            .map(|action| {
+                let path = match &action {
+                    SyncAction::Upload { path, .. }
+                    | SyncAction::Download { path, .. }
+                    | SyncAction::DeleteLocal { path }
+                    | SyncAction::DeleteRemote { path, .. }
+                    | SyncAction::KeepBoth { path, .. }
+                    | SyncAction::Conflict { path, .. } => path,
+                };
+                (state_key(path), action)
+            })
+            .collect()
+    }
+
+    fn upload(path: &str, file_id: Option<&str>) -> SyncAction {
+        SyncAction::Upload {
+            path: PathBuf::from(path),
+            file_id: file_id.map(String::from),
+        }
+    }
+
+    fn download(path: &str, file_id: &str) -> SyncAction {
+        SyncAction::Download {
+            path: PathBuf::from(path),
+            file_id: file_id.to_string(),
+        }
+    }
+
+    fn conflict_with(path: &str, file_id: &str) -> SyncAction {
+        SyncAction::Conflict {
+            path: PathBuf::from(path),
+            file_id: file_id.to_string(),
+        }
+    }
+
+    #[test]
+    fn plan_one_sided_changes() {
+        let actions = plan_with(&SyncOptions::new(), "one_sided");
+        assert_eq!(actions["new_local"], upload("new_local", None));
+        assert_eq!(actions["new_remote"], download("new_remote", "nr"));
+        assert_eq!(
+            actions["local_changed"],
+            upload("local_changed", Some("lc"))
+        );
+        assert_eq!(actions["remote_changed"], download("remote_changed", "rc"));
+        for untouched in &[
+            "unchanged",
+            "same.txt",
+            "deleted_locally",
+            "deleted_remotely",
+            "deleted_both",
+        ] {
+            assert!(!actions.contains_key(*untouched), "{} planned", untouched);
+        }
+    }
+
+    #[test]
+    fn plan_deletions_when_enabled() {
+        let actions = plan_with(&SyncOptions::new().delete(true), "deletions");
+        assert_eq!(
+            actions["deleted_locally"],
+            SyncAction::DeleteRemote {
+                path: PathBuf::from("deleted_locally"),
+                file_id: String::from("dl"),
+            }
+        );
+        assert_eq!(
+            actions["deleted_remotely"],
+            SyncAction::DeleteLocal {
+                path: PathBuf::from("deleted_remotely"),
+            }
+        );
+        assert!(!actions.contains_key("deleted_both"));
+    }
+
+    #[test]
+    fn plan_conflicts_newest_wins() {
+        let options = SyncOptions::new().conflict_policy(ConflictPolicy::NewestWins);
+        let actions = plan_with(&options, "newest_wins");
+        assert_eq!(actions["local_newer"], upload("local_newer", Some("ln")));
+        assert_eq!(actions["remote_newer"], download("remote_newer", "rn"));
+        assert_eq!(actions["differs.txt"], download("differs.txt", "diff"));
+        assert!(!actions.contains_key("same.txt"));
+    }
+
+    #[test]
+    fn plan_conflicts_keep_both() {
+        let options = SyncOptions::new().conflict_policy(ConflictPolicy::KeepBoth);
+        let actions = plan_with(&options, "keep_both");
+        assert_eq!(
+            actions["local_newer"],
+            SyncAction::KeepBoth {
+                path: PathBuf::from("local_newer"),
+                copy: PathBuf::from("local_newer (conflict)"),
+                file_id: String::from("ln"),
+            }
+        );
+        assert_eq!(
+            actions["differs.txt"],
+            SyncAction::KeepBoth {
+                path: PathBuf::from("differs.txt"),
+                copy: PathBuf::from("differs (conflict).txt"),
+                file_id: String::from("diff"),
+            }
+        );
+        assert!(!actions.contains_key("same.txt"));
+    }
+
+    #[test]
+    fn plan_conflicts_ask() {
+        let options = SyncOptions::new().conflict_policy(ConflictPolicy::Ask);
+        let actions = plan_with(&options, "ask");
+        assert_eq!(actions["local_newer"], conflict_with("local_newer", "ln"));
+        assert_eq!(actions["remote_newer"], conflict_with("remote_newer", "rn"));
+        assert_eq!(actions["differs.txt"], conflict_with("differs.txt", "diff"));
+        assert!(!actions.contains_key("same.txt"));
+        assert_eq!(
+            actions["local_changed"],
+            upload("local_changed", Some("lc"))
+        );
+    }
+}
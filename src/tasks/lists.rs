@@ -0,0 +1,78 @@
+use reqwest::Method;
+use serde::Deserialize;
+
+use super::Tasks;
+use crate::error::{CheckStatus, Error};
+use crate::models::ExtraFields;
+
+/// A list of tasks. Every user has at least their default one.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct TaskList {
+    pub id: String,
+    pub title: String,
+    /// RFC 3339 timestamp.
+    pub updated: String,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct TaskListList {
+    items: Vec<TaskList>,
+    next_page_token: Option<String>,
+}
+
+impl Tasks {
+    /// The user's task lists, the default one first.
+    pub fn list_task_lists(&self) -> Result<Vec<TaskList>, Error> {
+        let mut lists = vec![];
+        let mut page_token: Option<String> = None;
+        loop {
+            let mut params = vec![("maxResults", "100")];
+            if let Some(token) = &page_token {
+                params.push(("pageToken", token));
+            }
+            let page: TaskListList = self
+                .request(Method::GET, &["users", "@me", "lists"])?
+                .query(&params)
+                .send()?
+                .check_status()?
+                .json()?;
+            lists.extend(page.items);
+
+            match page.next_page_token {
+                Some(token) => page_token = Some(token),
+                None => break,
+            }
+        }
+        Ok(lists)
+    }
+
+    pub fn create_task_list(&self, title: &str) -> Result<TaskList, Error> {
+        Ok(self
+            .request(Method::POST, &["users", "@me", "lists"])?
+            .json(&serde_json::json!({ "title": title }))
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    pub fn rename_task_list(&self, list_id: &str, title: &str) -> Result<TaskList, Error> {
+        Ok(self
+            .request(Method::PATCH, &["users", "@me", "lists", list_id])?
+            .json(&serde_json::json!({ "title": title }))
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    /// Delete a task list and every task in it.
+    pub fn delete_task_list(&self, list_id: &str) -> Result<(), Error> {
+        self.request(Method::DELETE, &["users", "@me", "lists", list_id])?
+            .send()?
+            .check_status()?;
+        Ok(())
+    }
+}
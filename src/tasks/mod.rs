@@ -0,0 +1,54 @@
+use reqwest::blocking::RequestBuilder;
+use reqwest::Method;
+
+use crate::api;
+use crate::auth::Auth;
+use crate::error::Error;
+use crate::scope::Scope;
+
+mod lists;
+mod task;
+
+pub use lists::TaskList;
+pub use task::Task;
+
+const TASKS_BASE_URL: &str = "https://tasks.googleapis.com/tasks/v1";
+
+/// Google Tasks client. Shares its saved token with the other clients.
+///
+/// ```no_run
+/// # use google_api::*;
+/// # let (client_id, client_secret) = (String::new(), String::new());
+/// let tasks = Tasks::new(client_id, client_secret, None)?;
+/// let list = &tasks.list_task_lists()?[0];
+/// let trip = tasks.create_task(&list.id, &Task::new("Plan trip").due_date("2024-06-01"))?;
+/// tasks.create_subtask(&list.id, &trip.id, &Task::new("Book flights"))?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug)]
+pub struct Tasks {
+    auth: Auth,
+    client: reqwest::blocking::Client,
+}
+
+impl Tasks {
+    /// Authenticate with given OAuth client. `scopes` defaults to `Scope::Tasks`.
+    pub fn new(
+        client_id: String,
+        client_secret: String,
+        scopes: Option<Vec<String>>,
+    ) -> Result<Tasks, Error> {
+        let scopes = scopes.unwrap_or_else(|| vec![Scope::Tasks.into()]);
+
+        Ok(Tasks {
+            auth: Auth::from_config(&client_id, &client_secret, &scopes)?,
+            client: reqwest::blocking::Client::new(),
+        })
+    }
+
+    /// Authorized request for the endpoint made of given path segments, e.g.
+    /// `["lists", id, "tasks"]`.
+    fn request(&self, method: Method, segments: &[&str]) -> Result<RequestBuilder, Error> {
+        api::request(&self.client, &self.auth, TASKS_BASE_URL, method, segments)
+    }
+}
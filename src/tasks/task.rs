@@ -0,0 +1,215 @@
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+
+use super::Tasks;
+use crate::error::{CheckStatus, Error};
+use crate::models::ExtraFields;
+
+const NEEDS_ACTION: &str = "needsAction";
+const COMPLETED: &str = "completed";
+
+/// A task. Only fields that are set are sent, so the same type serves to create tasks and
+/// to change some of their fields with `Tasks::update_task`.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Task {
+    #[serde(skip_serializing)]
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    /// `needsAction` or `completed`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    /// Due date as an RFC 3339 timestamp. Only the date counts, the time is dropped.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub due: Option<String>,
+    /// When the task was completed, as an RFC 3339 timestamp.
+    #[serde(skip_serializing)]
+    pub completed: Option<String>,
+    /// Id of the task this one is a subtask of.
+    #[serde(skip_serializing)]
+    pub parent: Option<String>,
+    /// Sorts the task among its siblings. Change it with `Tasks::move_task`.
+    #[serde(skip_serializing)]
+    pub position: String,
+    /// Completed tasks cleared from the list.
+    #[serde(skip_serializing)]
+    pub hidden: bool,
+    #[serde(skip_serializing)]
+    pub deleted: bool,
+    #[serde(skip_serializing)]
+    pub updated: String,
+    #[serde(skip_serializing)]
+    pub web_view_link: String,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl Task {
+    pub fn new(title: &str) -> Task {
+        Task {
+            title: Some(title.to_string()),
+            ..Task::default()
+        }
+    }
+
+    pub fn notes(mut self, notes: &str) -> Task {
+        self.notes = Some(notes.to_string());
+        self
+    }
+
+    /// Due on given day, `yyyy-mm-dd`.
+    pub fn due_date(mut self, date: &str) -> Task {
+        self.due = Some(format!("{}T00:00:00.000Z", date));
+        self
+    }
+
+    pub fn is_completed(&self) -> bool {
+        self.status.as_deref() == Some(COMPLETED)
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct TaskPage {
+    items: Vec<Task>,
+    next_page_token: Option<String>,
+}
+
+impl Tasks {
+    /// Tasks in a list, subtasks included (see `Task::parent`), in no particular order.
+    /// Completed and cleared tasks are only included with `include_completed`.
+    pub fn list_tasks(&self, list_id: &str, include_completed: bool) -> Result<Vec<Task>, Error> {
+        let show = if include_completed { "true" } else { "false" };
+        let mut tasks = vec![];
+        let mut page_token: Option<String> = None;
+        loop {
+            let mut params = vec![
+                ("maxResults", "100"),
+                ("showCompleted", show),
+                ("showHidden", show),
+            ];
+            if let Some(token) = &page_token {
+                params.push(("pageToken", token));
+            }
+            let page: TaskPage = self
+                .request(Method::GET, &["lists", list_id, "tasks"])?
+                .query(&params)
+                .send()?
+                .check_status()?
+                .json()?;
+            tasks.extend(page.items);
+
+            match page.next_page_token {
+                Some(token) => page_token = Some(token),
+                None => break,
+            }
+        }
+        Ok(tasks)
+    }
+
+    pub fn get_task(&self, list_id: &str, task_id: &str) -> Result<Task, Error> {
+        Ok(self
+            .request(Method::GET, &["lists", list_id, "tasks", task_id])?
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    /// Add a task at the top of a list.
+    pub fn create_task(&self, list_id: &str, task: &Task) -> Result<Task, Error> {
+        Ok(self
+            .request(Method::POST, &["lists", list_id, "tasks"])?
+            .json(task)
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    /// Add a task as the first subtask of `parent_id`.
+    pub fn create_subtask(
+        &self,
+        list_id: &str,
+        parent_id: &str,
+        task: &Task,
+    ) -> Result<Task, Error> {
+        Ok(self
+            .request(Method::POST, &["lists", list_id, "tasks"])?
+            .query(&[("parent", parent_id)])
+            .json(task)
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    /// Change the fields that are set in `changes`.
+    pub fn update_task(&self, list_id: &str, task_id: &str, changes: &Task) -> Result<Task, Error> {
+        Ok(self
+            .request(Method::PATCH, &["lists", list_id, "tasks", task_id])?
+            .json(changes)
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    pub fn complete_task(&self, list_id: &str, task_id: &str) -> Result<Task, Error> {
+        let changes = Task {
+            status: Some(String::from(COMPLETED)),
+            ..Task::default()
+        };
+        self.update_task(list_id, task_id, &changes)
+    }
+
+    /// Mark a completed task as not done again.
+    pub fn reopen_task(&self, list_id: &str, task_id: &str) -> Result<Task, Error> {
+        // `completed` has to be cleared explicitly, which `Task` never sends.
+        Ok(self
+            .request(Method::PATCH, &["lists", list_id, "tasks", task_id])?
+            .json(&serde_json::json!({ "status": NEEDS_ACTION, "completed": null }))
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    /// Move a task under `parent` (top level if `None`), right after sibling `previous`
+    /// (first if `None`). Its subtasks move with it.
+    pub fn move_task(
+        &self,
+        list_id: &str,
+        task_id: &str,
+        parent: Option<&str>,
+        previous: Option<&str>,
+    ) -> Result<Task, Error> {
+        let mut params = vec![];
+        if let Some(parent) = parent {
+            params.push(("parent", parent));
+        }
+        if let Some(previous) = previous {
+            params.push(("previous", previous));
+        }
+        Ok(self
+            .request(Method::POST, &["lists", list_id, "tasks", task_id, "move"])?
+            .query(&params)
+            .send()?
+            .check_status()?
+            .json()?)
+    }
+
+    /// Delete a task and its subtasks.
+    pub fn delete_task(&self, list_id: &str, task_id: &str) -> Result<(), Error> {
+        self.request(Method::DELETE, &["lists", list_id, "tasks", task_id])?
+            .send()?
+            .check_status()?;
+        Ok(())
+    }
+
+    /// Hide every completed task in a list, as the "clear completed" button does.
+    pub fn clear_completed(&self, list_id: &str) -> Result<(), Error> {
+        self.request(Method::POST, &["lists", list_id, "clear"])?
+            .send()?
+            .check_status()?;
+        Ok(())
+    }
+}
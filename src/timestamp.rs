@@ -0,0 +1,181 @@
+use std::fmt;
+use std::str::FromStr;
+use std::time::SystemTime;
+
+use chrono::{DateTime, SecondsFormat, Timelike, Utc};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+/// A point in time as Google APIs write it, an RFC 3339 timestamp such as
+/// `2020-03-01T12:30:00.123Z`, which is also how it (de)serializes. Converts to and from
+/// `chrono::DateTime<Utc>` and `SystemTime`, and times before 1970 are fine:
+///
+/// ```no_run
+/// # use google_api::*;
+/// # let drive = Drive::new(String::new(), String::new(), None)?;
+/// # let file = DriveFile::default();
+/// let modified: chrono::DateTime<chrono::Utc> = file.modified_time.unwrap().into();
+/// let options = UploadOptions::new().modified_time(modified + chrono::Duration::hours(1));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp(DateTime<Utc>);
+
+impl Timestamp {
+    pub fn now() -> Timestamp {
+        Timestamp::from(SystemTime::now())
+    }
+
+    pub fn date_time(self) -> DateTime<Utc> {
+        self.0
+    }
+
+    pub fn system_time(self) -> SystemTime {
+        self.0.into()
+    }
+}
+
+impl From<DateTime<Utc>> for Timestamp {
+    fn from(time: DateTime<Utc>) -> Self {
+        Timestamp(time)
+    }
+}
+
+impl From<Timestamp> for DateTime<Utc> {
+    fn from(time: Timestamp) -> Self {
+        time.0
+    }
+}
+
+impl From<SystemTime> for Timestamp {
+    fn from(time: SystemTime) -> Self {
+        Timestamp(time.into())
+    }
+}
+
+impl From<Timestamp> for SystemTime {
+    fn from(time: Timestamp) -> Self {
+        time.0.into()
+    }
+}
+
+/// UTC, with milliseconds when there are any.
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let precision = match self.0.nanosecond() / 1_000_000 {
+            0 => SecondsFormat::Secs,
+            _ => SecondsFormat::Millis,
+        };
+        f.write_str(&self.0.to_rfc3339_opts(precision, true))
+    }
+}
+
+impl FromStr for Timestamp {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        DateTime::parse_from_rfc3339(s)
+            .map(|time| Timestamp(time.with_timezone(&Utc)))
+            .map_err(|e| format!("invalid RFC 3339 timestamp {}: {}", s, e))
+    }
+}
+
+impl Serialize for Timestamp {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Timestamp {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
+/// Format a time as an RFC 3339 UTC timestamp, the format Drive expects in queries.
+pub(crate) fn format_rfc3339(time: SystemTime) -> String {
+    DateTime::<Utc>::from(time).to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// Parse an RFC 3339 timestamp as Google returns them (e.g. `2020-03-01T12:30:00.000Z`).
+/// Fractional seconds are kept to the nanosecond. Returns `None` for anything malformed or
+/// out of range.
+pub(crate) fn parse_rfc3339(s: &str) -> Option<SystemTime> {
+    s.parse::<Timestamp>().ok().map(Timestamp::system_time)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::*;
+
+    #[test]
+    fn round_trips() {
+        for s in &[
+            "1601-01-01T00:00:00Z",
+            "1969-07-20T20:17:40.500Z",
+            "1970-01-01T00:00:00Z",
+            "2020-02-29T23:59:59Z",
+            "2020-03-01T12:30:00.123Z",
+            "2038-01-19T03:14:08Z",
+            "9999-12-31T23:59:59.999Z",
+        ] {
+            let parsed: Timestamp = s.parse().unwrap();
+            assert_eq!(parsed.to_string(), *s);
+        }
+    }
+
+    #[test]
+    fn applies_offsets_and_fractions() {
+        let utc: Timestamp = "2020-03-01T10:30:00Z".parse().unwrap();
+        assert_eq!("2020-03-01T12:30:00+02:00".parse(), Ok(utc));
+        assert_eq!("2020-03-01T05:30:00-05:00".parse(), Ok(utc));
+        let precise: Timestamp = "2020-03-01T10:30:00.123456789Z".parse().unwrap();
+        let nanos = precise
+            .system_time()
+            .duration_since(utc.system_time())
+            .unwrap();
+        assert_eq!(nanos, Duration::from_nanos(123_456_789));
+    }
+
+    #[test]
+    fn handles_times_before_1970() {
+        let landing = UNIX_EPOCH - Duration::from_secs(14_182_940);
+        assert_eq!(format_rfc3339(landing), "1969-07-20T20:17:40Z");
+        assert_eq!(parse_rfc3339("1969-07-20T20:17:40Z"), Some(landing));
+        let file: crate::DriveFile =
+            serde_json::from_value(serde_json::json!({ "modifiedTime": "1969-07-20T20:17:40Z" }))
+                .unwrap();
+        assert_eq!(
+            file.modified_time.map(Timestamp::system_time),
+            Some(landing)
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_input() {
+        for s in &[
+            "",
+            "2020-03-01",
+            "2020-03-01T12:30Z",
+            "2020-13-01T00:00:00Z",
+            "2020-00-01T00:00:00Z",
+            "2021-02-29T00:00:00Z",
+            "2020-04-31T00:00:00Z",
+            "2020-03-01T24:00:00Z",
+            "2020-03-01T12:60:00Z",
+            "2020-03-01T12:30:61Z",
+            "2020-03-01T12:30:00+24:00",
+            "2020-03-01T12:30:00+02",
+            "2020-03-01T12:30:00.12aZ",
+            "2020-3-1T12:30:00Z",
+            "+020-03-01T12:30:00Z",
+            "99999999999999-01-01T00:00:00Z",
+            "2020-03-01T9223372036854775807:00:00Z",
+        ] {
+            assert!(s.parse::<Timestamp>().is_err(), "{} parsed", s);
+        }
+    }
+}
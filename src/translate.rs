@@ -0,0 +1,312 @@
+use reqwest::blocking::RequestBuilder;
+use reqwest::Method;
+use serde::Deserialize;
+
+use crate::api;
+use crate::auth::Auth;
+use crate::error::{CheckStatus, Error};
+use crate::scope::Scope;
+
+const TRANSLATE_V2_URL: &str = "https://translation.googleapis.com/language/translate/v2";
+const TRANSLATE_V3_URL: &str = "https://translation.googleapis.com/v3";
+
+/// One translated text.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Translation {
+    pub text: String,
+    /// Language Google detected in the source, when none was given.
+    pub detected_source_language: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DetectedLanguage {
+    /// BCP-47 code, e.g. `en` or `zh-CN`.
+    pub language: String,
+    /// From 0 to 1. Not always filled in by the API key (v2) endpoint.
+    pub confidence: f64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Language {
+    pub code: String,
+    /// Name of the language, in the display language asked for.
+    pub name: String,
+}
+
+#[derive(Debug)]
+enum Backend {
+    /// Cloud Translation v3, which needs OAuth and a Cloud project.
+    V3 { auth: Auth, parent: String },
+    /// Translation v2 with an API key.
+    V2 { api_key: String },
+}
+
+/// Google Cloud Translation client. With OAuth it talks to the v3 API under given project;
+/// with only an API key it uses v2. Both answer the same way.
+///
+/// ```no_run
+/// # use google_api::*;
+/// # let api_key = String::new();
+/// let translate = Translate::with_api_key(api_key);
+/// let out = translate.translate_text(&["Où est la gare ?"], "en", None)?;
+/// println!("{} ({:?})", out[0].text, out[0].detected_source_language);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug)]
+pub struct Translate {
+    backend: Backend,
+    client: reqwest::blocking::Client,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct V2Translation {
+    translated_text: String,
+    detected_source_language: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct V2Detection {
+    language: String,
+    confidence: f64,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct V2Language {
+    language: String,
+    name: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct V2Data {
+    translations: Vec<V2Translation>,
+    detections: Vec<Vec<V2Detection>>,
+    languages: Vec<V2Language>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct V2Response {
+    data: V2Data,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct V3Translation {
+    translated_text: String,
+    detected_language_code: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct V3Language {
+    language_code: String,
+    display_name: String,
+    confidence: f64,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct V3Response {
+    translations: Vec<V3Translation>,
+    languages: Vec<V3Language>,
+}
+
+impl Translate {
+    /// Authenticate with given OAuth client and use the v3 API, billed to given Cloud
+    /// project. `scopes` defaults to `Scope::CloudTranslation`.
+    pub fn new(
+        client_id: String,
+        client_secret: String,
+        project_id: &str,
+        scopes: Option<Vec<String>>,
+    ) -> Result<Translate, Error> {
+        let scopes = scopes.unwrap_or_else(|| vec![Scope::CloudTranslation.into()]);
+
+        Ok(Translate {
+            backend: Backend::V3 {
+                auth: Auth::from_config(&client_id, &client_secret, &scopes)?,
+                parent: format!("projects/{}/locations/global", project_id),
+            },
+            client: reqwest::blocking::Client::new(),
+        })
+    }
+
+    /// Use the v2 API with an API key, no user sign-in needed.
+    pub fn with_api_key(api_key: String) -> Translate {
+        Translate {
+            backend: Backend::V2 { api_key },
+            client: reqwest::blocking::Client::new(),
+        }
+    }
+
+    /// Request for a v3 method on the project, e.g. `:translateText`, or a v2 endpoint.
+    fn request(&self, method: Method, endpoint: &str) -> Result<RequestBuilder, Error> {
+        match &self.backend {
+            Backend::V3 { auth, parent } => {
+                let mut segments: Vec<String> = parent.split('/').map(String::from).collect();
+                if let Some(verb) = endpoint.strip_prefix(':') {
+                    if let Some(last) = segments.last_mut() {
+                        last.push(':');
+                        last.push_str(verb);
+                    }
+                } else {
+                    segments.push(endpoint.to_string());
+                }
+                let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+                api::request(&self.client, auth, TRANSLATE_V3_URL, method, &segments)
+            }
+            Backend::V2 { api_key } => {
+                let url = if endpoint.is_empty() {
+                    TRANSLATE_V2_URL.to_string()
+                } else {
+                    format!("{}/{}", TRANSLATE_V2_URL, endpoint)
+                };
+                Ok(self
+                    .client
+                    .request(method, url.as_str())
+                    .query(&[("key", api_key)]))
+            }
+        }
+    }
+
+    /// Translate plain texts into `target`. The source language is detected when not given.
+    /// Results are in the order of `texts`.
+    pub fn translate_text(
+        &self,
+        texts: &[&str],
+        target: &str,
+        source: Option<&str>,
+    ) -> Result<Vec<Translation>, Error> {
+        match self.backend {
+            Backend::V3 { .. } => {
+                let mut body = serde_json::json!({
+                    "contents": texts,
+                    "targetLanguageCode": target,
+                    "mimeType": "text/plain",
+                });
+                if let Some(source) = source {
+                    body["sourceLanguageCode"] = serde_json::json!(source);
+                }
+                let resp: V3Response = self
+                    .request(Method::POST, ":translateText")?
+                    .json(&body)
+                    .send()?
+                    .check_status()?
+                    .json()?;
+                Ok(resp
+                    .translations
+                    .into_iter()
+                    .map(|t| Translation {
+                        text: t.translated_text,
+                        detected_source_language: t.detected_language_code,
+                    })
+                    .collect())
+            }
+            Backend::V2 { .. } => {
+                let mut body =
+                    serde_json::json!({ "q": texts, "target": target, "format": "text" });
+                if let Some(source) = source {
+                    body["source"] = serde_json::json!(source);
+                }
+                let resp: V2Response = self
+                    .request(Method::POST, "")?
+                    .json(&body)
+                    .send()?
+                    .check_status()?
+                    .json()?;
+                Ok(resp
+                    .data
+                    .translations
+                    .into_iter()
+                    .map(|t| Translation {
+                        text: t.translated_text,
+                        detected_source_language: t.detected_source_language,
+                    })
+                    .collect())
+            }
+        }
+    }
+
+    /// Languages the text could be in, most likely first.
+    pub fn detect_language(&self, text: &str) -> Result<Vec<DetectedLanguage>, Error> {
+        match self.backend {
+            Backend::V3 { .. } => {
+                let resp: V3Response = self
+                    .request(Method::POST, ":detectLanguage")?
+                    .json(&serde_json::json!({ "content": text, "mimeType": "text/plain" }))
+                    .send()?
+                    .check_status()?
+                    .json()?;
+                Ok(resp
+                    .languages
+                    .into_iter()
+                    .map(|l| DetectedLanguage {
+                        language: l.language_code,
+                        confidence: l.confidence,
+                    })
+                    .collect())
+            }
+            Backend::V2 { .. } => {
+                let resp: V2Response = self
+                    .request(Method::POST, "detect")?
+                    .json(&serde_json::json!({ "q": [text] }))
+                    .send()?
+                    .check_status()?
+                    .json()?;
+                Ok(resp
+                    .data
+                    .detections
+                    .into_iter()
+                    .flatten()
+                    .map(|d| DetectedLanguage {
+                        language: d.language,
+                        confidence: d.confidence,
+                    })
+                    .collect())
+            }
+        }
+    }
+
+    /// Languages Google can translate, named in `display_language` (e.g. `en`).
+    pub fn supported_languages(&self, display_language: &str) -> Result<Vec<Language>, Error> {
+        match self.backend {
+            Backend::V3 { .. } => {
+                let resp: V3Response = self
+                    .request(Method::GET, "supportedLanguages")?
+                    .query(&[("displayLanguageCode", display_language)])
+                    .send()?
+                    .check_status()?
+                    .json()?;
+                Ok(resp
+                    .languages
+                    .into_iter()
+                    .map(|l| Language {
+                        code: l.language_code,
+                        name: l.display_name,
+                    })
+                    .collect())
+            }
+            Backend::V2 { .. } => {
+                let resp: V2Response = self
+                    .request(Method::GET, "languages")?
+                    .query(&[("target", display_language)])
+                    .send()?
+                    .check_status()?
+                    .json()?;
+                Ok(resp
+                    .data
+                    .languages
+                    .into_iter()
+                    .map(|l| Language {
+                        code: l.language,
+                        name: l.name,
+                    })
+                    .collect())
+            }
+        }
+    }
+}
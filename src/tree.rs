@@ -0,0 +1,196 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::error::{CheckStatus, Error};
+use crate::file_id::FileId;
+use crate::list::{escape_query, ListOptions};
+use crate::models::{DriveFile, FILE_FIELDS};
+use crate::params::Params;
+use crate::Drive;
+
+/// Folders whose children are fetched in a single query. Keeps the `q` param well under
+/// Drive's length limit.
+const FOLDERS_PER_QUERY: usize = 50;
+
+/// A folder with everything below it, as returned by `Drive::tree`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FolderTree {
+    pub folder: DriveFile,
+    pub folders: Vec<FolderTree>,
+    pub files: Vec<DriveFile>,
+    /// Total size in bytes of the files listed in this folder and all subfolders.
+    pub size: u64,
+    /// Number of files listed in this folder and all subfolders.
+    pub file_count: usize,
+    /// The depth limit was reached here, so this folder's contents weren't listed.
+    pub truncated: bool,
+}
+
+impl FolderTree {
+    /// Every folder in the tree with its path relative to the root, subfolders before their
+    /// parent.
+    pub fn walk(&self) -> Vec<(String, &FolderTree)> {
+        let mut out = vec![];
+        self.walk_into(String::new(), &mut out);
+        out
+    }
+
+    fn walk_into<'a>(&'a self, path: String, out: &mut Vec<(String, &'a FolderTree)>) {
+        for folder in &self.folders {
+            let child = if path.is_empty() {
+                folder.folder.name.clone()
+            } else {
+                format!("{}/{}", path, folder.folder.name)
+            };
+            folder.walk_into(child, out);
+        }
+        out.push((path, self));
+    }
+}
+
+/// Storage used below one folder, as reported by `Drive::folder_usage`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FolderUsage {
+    pub folder_id: String,
+    /// Path relative to the folder the report was made for, empty for that folder itself.
+    pub path: String,
+    /// Bytes used by every file in this folder and its subfolders.
+    pub bytes: u64,
+    pub file_count: usize,
+    /// Google Docs/Sheets/Slides and other native files, which have no size of their own.
+    pub native_count: usize,
+}
+
+/// Bytes a file takes up. Native files have no `size`, fall back on the quota figure.
+fn file_bytes(file: &DriveFile) -> u64 {
+    file.size.or(file.quota_bytes_used).unwrap_or_default()
+}
+
+/// Usage of every folder below (and including) `tree`, appended to `out`. Returns the
+/// totals for `tree` itself.
+fn usage_into(tree: &FolderTree, path: String, out: &mut Vec<FolderUsage>) -> FolderUsage {
+    let mut usage = FolderUsage {
+        folder_id: tree.folder.id.clone(),
+        ..FolderUsage::default()
+    };
+    for file in &tree.files {
+        usage.bytes += file_bytes(file);
+        usage.file_count += 1;
+        if file.size.is_none() {
+            usage.native_count += 1;
+        }
+    }
+    for folder in &tree.folders {
+        let child_path = if path.is_empty() {
+            folder.folder.name.clone()
+        } else {
+            format!("{}/{}", path, folder.folder.name)
+        };
+        let child = usage_into(folder, child_path, out);
+        usage.bytes += child.bytes;
+        usage.file_count += child.file_count;
+        usage.native_count += child.native_count;
+    }
+    usage.path = path;
+    out.push(usage.clone());
+    usage
+}
+
+impl Drive {
+    /// Fetch given folder and everything below it, down to `depth` levels (`None` for no
+    /// limit). Each level is listed with as few queries as possible, by asking for the
+    /// children of many folders at once.
+    pub fn tree(
+        &self,
+        folder_id: impl Into<FileId>,
+        depth: Option<usize>,
+    ) -> Result<FolderTree, Error> {
+        let folder_id = folder_id.into();
+        let root: DriveFile = self
+            .get(
+                format!("/files/{}", folder_id).as_str(),
+                Params::new().fields(FILE_FIELDS),
+            )?
+            .check_status()?
+            .json()?;
+
+        let mut children: HashMap<String, Vec<DriveFile>> = HashMap::new();
+        let mut level = vec![root.id.clone()];
+        let mut listed = 0;
+        while !level.is_empty() && depth.map_or(true, |d| listed < d) {
+            let mut next = vec![];
+            // Listed folders get an entry even when empty, so they aren't taken as truncated.
+            for id in &level {
+                children.entry(id.clone()).or_default();
+            }
+            for ids in level.chunks(FOLDERS_PER_QUERY) {
+                for file in self.children_of(ids)? {
+                    if file.is_folder() {
+                        next.push(file.id.clone());
+                    }
+                    for parent in file.parents.iter().filter(|p| ids.contains(p)) {
+                        children
+                            .entry(parent.clone())
+                            .or_default()
+                            .push(file.clone());
+                    }
+                }
+            }
+            level = next;
+            listed += 1;
+        }
+        Ok(build(root, &mut children))
+    }
+
+    /// Storage used by given folder and each folder below it, biggest first.
+    pub fn folder_usage(&self, folder_id: impl Into<FileId>) -> Result<Vec<FolderUsage>, Error> {
+        let tree = self.tree(folder_id, None)?;
+        let mut usage = vec![];
+        usage_into(&tree, String::new(), &mut usage);
+        usage.sort_by_key(|u| std::cmp::Reverse(u.bytes));
+        Ok(usage)
+    }
+
+    /// Non-trashed children of any of the given folders.
+    fn children_of(&self, folder_ids: &[String]) -> Result<Vec<DriveFile>, Error> {
+        let parents: Vec<String> = folder_ids
+            .iter()
+            .map(|id| format!("'{}' in parents", escape_query(id)))
+            .collect();
+        let query = format!("({}) and trashed = false", parents.join(" or "));
+        self.list_files(&ListOptions::new().query(&query).order_by("folder,name"))
+    }
+}
+
+/// Assemble the tree below `folder` from the listed children, totalling sizes on the way up.
+fn build(folder: DriveFile, children: &mut HashMap<String, Vec<DriveFile>>) -> FolderTree {
+    let listed = match children.remove(&folder.id) {
+        Some(listed) => listed,
+        None => {
+            return FolderTree {
+                truncated: true,
+                folder,
+                ..FolderTree::default()
+            }
+        }
+    };
+    let mut tree = FolderTree {
+        folder,
+        ..FolderTree::default()
+    };
+    for child in listed {
+        if child.is_folder() {
+            let sub = build(child, children);
+            tree.size += sub.size;
+            tree.file_count += sub.file_count;
+            tree.folders.push(sub);
+        } else {
+            tree.size += child.size.unwrap_or_default();
+            tree.file_count += 1;
+            tree.files.push(child);
+        }
+    }
+    tree
+}
@@ -0,0 +1,477 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::error::{CheckStatus, Error};
+use crate::file_id::FileId;
+use crate::mime::MimeType;
+use crate::models::{DriveFile, FILE_FIELDS};
+use crate::params::Params;
+use crate::query::Query;
+use crate::timestamp::Timestamp;
+use crate::{Drive, DRIVE_UPLOAD_URL};
+
+/// Google-native types a file can be converted to when it's uploaded.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConvertTo {
+    /// From docx, doc, odt, rtf, txt, html, or (with OCR) pdf and images.
+    Document,
+    /// From xlsx, xls, ods, csv, tsv.
+    Spreadsheet,
+    /// From pptx, ppt, odp.
+    Presentation,
+    Drawing,
+}
+
+impl ConvertTo {
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            ConvertTo::Document => "application/vnd.google-apps.document",
+            ConvertTo::Spreadsheet => "application/vnd.google-apps.spreadsheet",
+            ConvertTo::Presentation => "application/vnd.google-apps.presentation",
+            ConvertTo::Drawing => "application/vnd.google-apps.drawing",
+        }
+    }
+}
+
+/// What an upload does when its folder already has a file with the same name. Drive allows
+/// duplicate names, so without a policy every upload creates a new file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IfExists {
+    /// Create another file next to the existing one.
+    #[default]
+    AlwaysCreate,
+    /// Replace the existing file's content, keeping its id, sharing and history.
+    Overwrite,
+    /// Leave the existing file alone and return it.
+    Skip,
+    /// Upload under the first free name of `name (1).ext`, `name (2).ext`, ...
+    Rename,
+}
+
+/// Where an upload ends up once its `IfExists` policy has been applied.
+pub(crate) enum UploadTarget {
+    /// A new file, under a different name when one is given.
+    Create(Option<String>),
+    /// An existing file whose content gets replaced.
+    Update(String),
+    /// An existing file left as it is.
+    Existing(Box<DriveFile>),
+}
+
+/// Split a file name into its stem and extension (with the dot). Names starting with a dot
+/// are all stem.
+fn split_extension(name: &str) -> (&str, &str) {
+    match name.rfind('.') {
+        Some(dot) if dot > 0 => name.split_at(dot),
+        _ => (name, ""),
+    }
+}
+
+/// Metadata and behaviour for uploads and updates.
+///
+/// ```no_run
+/// # use std::path::Path;
+/// # use google_api::*;
+/// # let drive = Drive::new(String::new(), String::new(), None)?;
+/// # let (folder_id, path) = (FileId::unchecked("1aBc"), Path::new("backup.tar.gz"));
+/// let options = UploadOptions::new()
+///     .parent(folder_id)
+///     .description("nightly backup")
+///     .keep_revision_forever(true);
+/// drive.upload_file(&path, &options)?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct UploadOptions {
+    name: Option<String>,
+    id: Option<String>,
+    parents: Vec<String>,
+    mime_type: Option<String>,
+    description: Option<String>,
+    starred: Option<bool>,
+    keep_revision_forever: bool,
+    app_properties: HashMap<String, String>,
+    convert_to: Option<ConvertTo>,
+    ocr_language: Option<String>,
+    modified_time: Option<SystemTime>,
+    created_time: Option<SystemTime>,
+    preserve_times: bool,
+    if_exists: IfExists,
+}
+
+impl UploadOptions {
+    pub fn new() -> UploadOptions {
+        UploadOptions::default()
+    }
+
+    /// Name on Drive. Defaults to the local file name on upload, unchanged on update.
+    pub fn name(mut self, name: &str) -> UploadOptions {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    /// Pre-generated id (see `Drive::generate_ids`) to create the file with.
+    pub fn id(mut self, id: &str) -> UploadOptions {
+        self.id = Some(id.to_string());
+        self
+    }
+
+    /// Folder to put the file in. Can be given more than once. Defaults to My Drive's root.
+    pub fn parent(mut self, folder_id: impl Into<FileId>) -> UploadOptions {
+        self.parents.push(folder_id.into().to_string());
+        self
+    }
+
+    /// MIME type of the content. Guessed from the file extension when not set.
+    pub fn mime_type(mut self, mime_type: &str) -> UploadOptions {
+        self.mime_type = Some(mime_type.to_string());
+        self
+    }
+
+    pub fn description(mut self, description: &str) -> UploadOptions {
+        self.description = Some(description.to_string());
+        self
+    }
+
+    pub fn starred(mut self, starred: bool) -> UploadOptions {
+        self.starred = Some(starred);
+        self
+    }
+
+    /// Keep this revision even once newer ones push it past Drive's revision limit.
+    pub fn keep_revision_forever(mut self, keep: bool) -> UploadOptions {
+        self.keep_revision_forever = keep;
+        self
+    }
+
+    /// Set a private app property on the file. Can be given more than once.
+    pub fn app_property(mut self, key: &str, value: &str) -> UploadOptions {
+        self.app_properties
+            .insert(key.to_string(), value.to_string());
+        self
+    }
+
+    /// Convert the file to a Google-native type as it's uploaded.
+    pub fn convert_to(mut self, convert_to: ConvertTo) -> UploadOptions {
+        self.convert_to = Some(convert_to);
+        self
+    }
+
+    /// ISO 639-1 language hint for OCR when converting images and PDFs.
+    pub fn ocr_language(mut self, language: &str) -> UploadOptions {
+        self.ocr_language = Some(language.to_string());
+        self
+    }
+
+    /// Modification time to record on Drive instead of the time of the request. Takes a
+    /// `SystemTime`, `Timestamp`, or chrono or time crate date-time.
+    pub fn modified_time(mut self, time: impl Into<SystemTime>) -> UploadOptions {
+        self.modified_time = Some(time.into());
+        self
+    }
+
+    /// Creation time to record on Drive. Only applies to new files.
+    pub fn created_time(mut self, time: impl Into<SystemTime>) -> UploadOptions {
+        self.created_time = Some(time.into());
+        self
+    }
+
+    /// Record the local file's modification and creation times on Drive, unless set
+    /// explicitly. Creation time is skipped on filesystems that don't track it.
+    pub fn preserve_times(mut self, preserve: bool) -> UploadOptions {
+        self.preserve_times = preserve;
+        self
+    }
+
+    /// What to do when the target folder already has a file with this name. Checked with a
+    /// query before uploading, so two uploads racing each other can still both create.
+    pub fn if_exists(mut self, policy: IfExists) -> UploadOptions {
+        self.if_exists = policy;
+        self
+    }
+
+    pub(crate) fn has_mime_type(&self) -> bool {
+        self.mime_type.is_some()
+    }
+
+    /// MIME type of the media being sent.
+    pub(crate) fn media_type(&self, path: &Path) -> String {
+        match &self.mime_type {
+            Some(m) => m.clone(),
+            None => MimeType::from_path(path).to_string(),
+        }
+    }
+
+    /// File metadata for the request body. Updates leave anything that wasn't set alone.
+    pub(crate) fn metadata(&self, path: &Path, is_update: bool) -> serde_json::Value {
+        let mut metadata = serde_json::json!({});
+        let name = match (&self.name, is_update) {
+            (Some(name), _) => Some(name.as_str()),
+            (None, true) => None,
+            (None, false) => self.default_name(path),
+        };
+        if let Some(name) = name {
+            metadata["name"] = serde_json::json!(name);
+        }
+        match (self.convert_to, &self.mime_type) {
+            (Some(convert_to), _) => {
+                metadata["mimeType"] = serde_json::json!(convert_to.mime_type())
+            }
+            (None, Some(mime_type)) => metadata["mimeType"] = serde_json::json!(mime_type),
+            (None, None) if !is_update => {
+                metadata["mimeType"] = serde_json::json!(MimeType::from_path(path))
+            }
+            (None, None) => {}
+        }
+        if !is_update {
+            if let Some(id) = &self.id {
+                metadata["id"] = serde_json::json!(id);
+            }
+            if !self.parents.is_empty() {
+                metadata["parents"] = serde_json::json!(self.parents);
+            }
+        }
+        if let Some(description) = &self.description {
+            metadata["description"] = serde_json::json!(description);
+        }
+        if let Some(starred) = self.starred {
+            metadata["starred"] = serde_json::json!(starred);
+        }
+        if !self.app_properties.is_empty() {
+            metadata["appProperties"] = serde_json::json!(self.app_properties);
+        }
+        let local = if self.preserve_times {
+            fs::metadata(path).ok()
+        } else {
+            None
+        };
+        let modified = self
+            .modified_time
+            .or_else(|| local.as_ref().and_then(|m| m.modified().ok()));
+        if let Some(time) = modified {
+            metadata["modifiedTime"] = serde_json::json!(Timestamp::from(time));
+        }
+        let created = self
+            .created_time
+            .or_else(|| local.as_ref().and_then(|m| m.created().ok()));
+        if let (Some(time), false) = (created, is_update) {
+            metadata["createdTime"] = serde_json::json!(Timestamp::from(time));
+        }
+        metadata
+    }
+
+    /// Name a new file gets when none is set. Converted files don't carry an extension.
+    fn default_name<'a>(&self, path: &'a Path) -> Option<&'a str> {
+        if self.convert_to.is_some() {
+            path.file_stem().and_then(|n| n.to_str())
+        } else {
+            path.file_name().and_then(|n| n.to_str())
+        }
+    }
+
+    /// Query params the options map to. Parents can't be set in an update's body.
+    pub(crate) fn query_params(&self, is_update: bool) -> Vec<(&'static str, String)> {
+        let mut params = vec![];
+        if self.keep_revision_forever {
+            params.push(("keepRevisionForever", String::from("true")));
+        }
+        if let Some(lang) = &self.ocr_language {
+            params.push(("ocrLanguage", lang.clone()));
+        }
+        if is_update && !self.parents.is_empty() {
+            params.push(("addParents", self.parents.join(",")));
+        }
+        params
+    }
+}
+
+/// Build a multipart/related body with a JSON metadata part followed by the media part.
+/// Returns the body's Content-Type header value and the body.
+pub(crate) fn multipart_related(
+    metadata: &serde_json::Value,
+    media_type: &str,
+    media: &[u8],
+) -> (String, Vec<u8>) {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos();
+    let boundary = format!("google_api_{:x}", nanos);
+
+    let mut body = Vec::with_capacity(media.len() + 512);
+    body.extend_from_slice(
+        format!(
+            "--{}\r\nContent-Type: application/json; charset=UTF-8\r\n\r\n{}\r\n",
+            boundary, metadata
+        )
+        .as_bytes(),
+    );
+    body.extend_from_slice(
+        format!("--{}\r\nContent-Type: {}\r\n\r\n", boundary, media_type).as_bytes(),
+    );
+    body.extend_from_slice(media);
+    body.extend_from_slice(format!("\r\n--{}--", boundary).as_bytes());
+
+    (format!("multipart/related; boundary={}", boundary), body)
+}
+
+impl Drive {
+    /// Allocate `count` (at most 1000) file ids ahead of time. Uploading with a pre-generated
+    /// id is idempotent, a retried upload fails with a conflict instead of creating a duplicate.
+    pub fn generate_ids(&self, count: u32) -> Result<Vec<String>, Error> {
+        let resp: serde_json::Value = self
+            .get(
+                "/files/generateIds",
+                Params::new()
+                    .param("count", count.min(1000))
+                    .param("space", "drive")
+                    .param("type", "files"),
+            )?
+            .check_status()?
+            .json()?;
+        Ok(resp["ids"]
+            .as_array()
+            .map(|ids| {
+                ids.iter()
+                    .filter_map(|id| id.as_str().map(String::from))
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    /// Upload file at given path and convert it to a Google Doc/Sheet/Slides file.
+    /// `ocr_language` is an ISO 639-1 code used when text is extracted from images and PDFs.
+    pub fn import_file(
+        &self,
+        path: &Path,
+        convert_to: ConvertTo,
+        ocr_language: Option<&str>,
+    ) -> Result<DriveFile, Error> {
+        let mut options = UploadOptions::new().convert_to(convert_to);
+        if let Some(lang) = ocr_language {
+            options = options.ocr_language(lang);
+        }
+        self.send_file(None, path, &options)
+    }
+
+    /// Look for a file already named like the upload of `path` in its folder, and decide from
+    /// the options' `IfExists` policy what the upload should do.
+    pub(crate) fn upload_target(
+        &self,
+        path: &Path,
+        options: &UploadOptions,
+    ) -> Result<UploadTarget, Error> {
+        let name = match options
+            .name
+            .as_deref()
+            .or_else(|| options.default_name(path))
+        {
+            Some(name) if options.if_exists != IfExists::AlwaysCreate => name.to_string(),
+            _ => return Ok(UploadTarget::Create(None)),
+        };
+        let parent = options.parents.first().map_or("root", String::as_str);
+        let in_parent = || {
+            Query::new()
+                .in_folder(FileId::unchecked(parent))
+                .not_mime_type(MimeType::FOLDER.as_str())
+                .trashed(false)
+        };
+        let existing = self.search(&in_parent().name_is(&name))?;
+        let existing = match existing.into_iter().next() {
+            Some(file) => file,
+            None => return Ok(UploadTarget::Create(None)),
+        };
+        match options.if_exists {
+            IfExists::Overwrite => Ok(UploadTarget::Update(existing.id)),
+            IfExists::Skip => Ok(UploadTarget::Existing(Box::new(existing))),
+            _ => {
+                let (stem, ext) = split_extension(&name);
+                let taken: HashSet<String> = self
+                    .search(&in_parent().name_contains(stem))?
+                    .into_iter()
+                    .map(|f| f.name)
+                    .collect();
+                let free = (1..)
+                    .map(|n| format!("{} ({}){}", stem, n, ext))
+                    .find(|candidate| !taken.contains(candidate))
+                    .unwrap_or_default();
+                Ok(UploadTarget::Create(Some(free)))
+            }
+        }
+    }
+
+    /// Create (or update, when given a file id) a file with a single multipart request.
+    pub(crate) fn send_multipart(
+        &self,
+        file_id: Option<&str>,
+        path: &Path,
+        content: &[u8],
+        options: &UploadOptions,
+    ) -> Result<DriveFile, Error> {
+        let is_update = file_id.is_some();
+        let metadata = options.metadata(path, is_update);
+        let (content_type, body) = multipart_related(&metadata, &options.media_type(path), content);
+
+        let mut params = vec![
+            ("uploadType", String::from("multipart")),
+            ("supportsAllDrives", String::from("true")),
+            ("fields", String::from(FILE_FIELDS)),
+        ];
+        params.extend(options.query_params(is_update));
+        let req = match file_id {
+            Some(id) => self
+                .client
+                .patch(format!("{}/files/{}", DRIVE_UPLOAD_URL, id).as_str()),
+            None => self
+                .client
+                .post(format!("{}/files", DRIVE_UPLOAD_URL).as_str()),
+        };
+        let resp = req
+            .query(&params)
+            .header("Authorization", self.auth.bearer()?)
+            .header("Content-Type", content_type)
+            .body(body)
+            .send()?;
+        DriveFile::from_response(resp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn multipart_related_layout() {
+        let metadata = serde_json::json!({ "name": "notes.txt" });
+        let (content_type, body) = multipart_related(&metadata, "text/plain", b"hello\r\nworld");
+        let boundary = content_type
+            .strip_prefix("multipart/related; boundary=")
+            .unwrap();
+        assert!(!boundary.is_empty());
+        let expected = format!(
+            "--{b}\r\n\
+             Content-Type: application/json; charset=UTF-8\r\n\r\n\
+             {{\"name\":\"notes.txt\"}}\r\n\
+             --{b}\r\n\
+             Content-Type: text/plain\r\n\r\n\
+             hello\r\nworld\r\n\
+             --{b}--",
+            b = boundary
+        );
+        assert_eq!(String::from_utf8(body).unwrap(), expected);
+    }
+
+    #[test]
+    fn multipart_related_keeps_binary_media() {
+        let media = [0u8, 159, 146, 150, 255];
+        let (content_type, body) =
+            multipart_related(&serde_json::json!({}), "application/octet-stream", &media);
+        let boundary = content_type.rsplit('=').next().unwrap();
+        let end = format!("\r\n--{}--", boundary);
+        assert!(body.ends_with(end.as_bytes()));
+        let media_at = body.len() - end.len() - media.len();
+        assert_eq!(&body[media_at..media_at + media.len()], &media[..]);
+    }
+}
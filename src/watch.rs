@@ -0,0 +1,242 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{CheckStatus, Error};
+use crate::file_id::FileId;
+use crate::models::{de_int64, ExtraFields};
+use crate::params::Params;
+use crate::Drive;
+
+/// What a notification channel is watching.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub enum WatchTarget {
+    /// A single file, by id.
+    File(String),
+    /// The change feed, starting at the given page token.
+    Changes(String),
+}
+
+/// An open notification channel. Keep it around (it serializes) to renew or stop it later.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Channel {
+    pub id: String,
+    /// Opaque id of the watched resource, needed to stop the channel.
+    pub resource_id: String,
+    pub resource_uri: Option<String>,
+    /// When the channel expires, in milliseconds since the unix epoch.
+    pub expiration: Option<u64>,
+    pub address: String,
+    pub target: WatchTarget,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ChannelResponse {
+    id: String,
+    resource_id: String,
+    resource_uri: Option<String>,
+    #[serde(default, deserialize_with = "de_int64")]
+    expiration: Option<u64>,
+}
+
+/// State reported by `X-Goog-Resource-State`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ResourceState {
+    /// Sent once when a channel is created.
+    Sync,
+    Add,
+    Remove,
+    Update,
+    Trash,
+    Untrash,
+    /// Something in the change feed changed.
+    Change,
+    Other(String),
+}
+
+impl From<&str> for ResourceState {
+    fn from(s: &str) -> Self {
+        match s {
+            "sync" => ResourceState::Sync,
+            "add" => ResourceState::Add,
+            "remove" => ResourceState::Remove,
+            "update" => ResourceState::Update,
+            "trash" => ResourceState::Trash,
+            "untrash" => ResourceState::Untrash,
+            "change" => ResourceState::Change,
+            other => ResourceState::Other(other.to_string()),
+        }
+    }
+}
+
+/// A push notification, parsed from the `X-Goog-*` headers of the webhook request.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Notification {
+    pub channel_id: String,
+    pub channel_token: Option<String>,
+    /// Channel expiration as sent by Google (RFC 1123 date).
+    pub channel_expiration: Option<String>,
+    pub message_number: Option<u64>,
+    pub resource_id: String,
+    pub resource_uri: Option<String>,
+    pub resource_state: ResourceState,
+    /// What changed on an `Update`, e.g. "content", "parents", "permissions".
+    pub changed: Vec<String>,
+}
+
+impl Notification {
+    /// Parse a notification from the webhook request headers. Header names are matched
+    /// case-insensitively so headers from any http server can be passed straight through.
+    pub fn from_headers<'a, I>(headers: I) -> Result<Notification, String>
+    where
+        I: IntoIterator<Item = (&'a str, &'a str)>,
+    {
+        let mut channel_id = None;
+        let mut resource_id = None;
+        let mut resource_state = None;
+        let mut n = Notification {
+            channel_id: String::new(),
+            channel_token: None,
+            channel_expiration: None,
+            message_number: None,
+            resource_id: String::new(),
+            resource_uri: None,
+            resource_state: ResourceState::Sync,
+            changed: vec![],
+        };
+        for (name, value) in headers {
+            match name.to_ascii_lowercase().as_str() {
+                "x-goog-channel-id" => channel_id = Some(value.to_string()),
+                "x-goog-channel-token" => n.channel_token = Some(value.to_string()),
+                "x-goog-channel-expiration" => n.channel_expiration = Some(value.to_string()),
+                "x-goog-message-number" => n.message_number = value.parse().ok(),
+                "x-goog-resource-id" => resource_id = Some(value.to_string()),
+                "x-goog-resource-uri" => n.resource_uri = Some(value.to_string()),
+                "x-goog-resource-state" => resource_state = Some(ResourceState::from(value)),
+                "x-goog-changed" => {
+                    n.changed = value.split(',').map(|c| c.trim().to_string()).collect()
+                }
+                _ => {}
+            }
+        }
+        match (channel_id, resource_id, resource_state) {
+            (Some(c), Some(r), Some(s)) => {
+                n.channel_id = c;
+                n.resource_id = r;
+                n.resource_state = s;
+                Ok(n)
+            }
+            _ => Err(String::from(
+                "missing X-Goog-Channel-ID, X-Goog-Resource-ID or X-Goog-Resource-State header",
+            )),
+        }
+    }
+}
+
+impl Drive {
+    /// Open a channel that posts to given https address whenever given file changes.
+    /// `channel_id` has to be unique per channel (a UUID is a good choice).
+    pub fn watch_file(
+        &self,
+        file_id: impl Into<FileId>,
+        channel_id: &str,
+        address: &str,
+        ttl: Option<Duration>,
+    ) -> Result<Channel, Error> {
+        let file_id = file_id.into();
+        self.watch(
+            WatchTarget::File(file_id.to_string()),
+            channel_id,
+            address,
+            ttl,
+        )
+    }
+
+    /// Open a channel that posts to given https address whenever the change feed has changes
+    /// after given page token.
+    pub fn watch_changes(
+        &self,
+        page_token: &str,
+        channel_id: &str,
+        address: &str,
+        ttl: Option<Duration>,
+    ) -> Result<Channel, Error> {
+        self.watch(
+            WatchTarget::Changes(page_token.to_string()),
+            channel_id,
+            address,
+            ttl,
+        )
+    }
+
+    /// Channels can't be extended, so open a new one on the same target and stop the old one.
+    /// The new channel is opened first so no notifications are missed.
+    pub fn renew_channel(
+        &self,
+        channel: &Channel,
+        new_channel_id: &str,
+        ttl: Option<Duration>,
+    ) -> Result<Channel, Error> {
+        let renewed = self.watch(
+            channel.target.clone(),
+            new_channel_id,
+            &channel.address,
+            ttl,
+        )?;
+        self.stop_channel(channel)?;
+        Ok(renewed)
+    }
+
+    /// Stop notifications for given channel.
+    pub fn stop_channel(&self, channel: &Channel) -> Result<(), Error> {
+        self.post(
+            "/channels/stop",
+            Params::new(),
+            serde_json::json!({"id": channel.id, "resourceId": channel.resource_id}),
+        )?
+        .check_status()?;
+        Ok(())
+    }
+
+    fn watch(
+        &self,
+        target: WatchTarget,
+        channel_id: &str,
+        address: &str,
+        ttl: Option<Duration>,
+    ) -> Result<Channel, Error> {
+        let mut body = serde_json::json!({
+            "id": channel_id,
+            "type": "web_hook",
+            "address": address,
+        });
+        if let Some(ttl) = ttl {
+            let expires = (SystemTime::now() + ttl)
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default();
+            body["expiration"] = serde_json::json!(expires.as_millis().to_string());
+        }
+
+        let resp = match &target {
+            WatchTarget::File(id) => {
+                self.post(format!("/files/{}/watch", id).as_str(), Params::new(), body)
+            }
+            WatchTarget::Changes(token) => {
+                self.post("/changes/watch", Params::new().page_token(token), body)
+            }
+        }?;
+        let resp: ChannelResponse = resp.check_status()?.json()?;
+        Ok(Channel {
+            id: resp.id,
+            resource_id: resp.resource_id,
+            resource_uri: resp.resource_uri,
+            expiration: resp.expiration,
+            address: address.to_string(),
+            target,
+            extra: ExtraFields::new(),
+        })
+    }
+}
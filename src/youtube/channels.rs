@@ -0,0 +1,227 @@
+use reqwest::Method;
+use serde::Deserialize;
+
+use super::{Video, YouTube};
+use crate::error::{CheckStatus, Error};
+use crate::models::{de_int64, ExtraFields};
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct ChannelSnippet {
+    title: String,
+    description: String,
+    custom_url: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ChannelStatistics {
+    #[serde(deserialize_with = "de_int64")]
+    pub view_count: Option<u64>,
+    /// `None` when the channel hides it.
+    #[serde(deserialize_with = "de_int64")]
+    pub subscriber_count: Option<u64>,
+    #[serde(deserialize_with = "de_int64")]
+    pub video_count: Option<u64>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+struct RelatedPlaylists {
+    uploads: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct ChannelContentDetails {
+    related_playlists: RelatedPlaylists,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct YouTubeChannel {
+    pub id: String,
+    snippet: ChannelSnippet,
+    pub statistics: ChannelStatistics,
+    content_details: ChannelContentDetails,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl YouTubeChannel {
+    pub fn title(&self) -> &str {
+        &self.snippet.title
+    }
+
+    pub fn description(&self) -> &str {
+        &self.snippet.description
+    }
+
+    /// The channel's `@handle`, if it has one.
+    pub fn handle(&self) -> Option<&str> {
+        self.snippet.custom_url.as_deref()
+    }
+
+    /// Id of the playlist holding every video the channel uploaded.
+    pub fn uploads_playlist(&self) -> &str {
+        &self.content_details.related_playlists.uploads
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct PlaylistSnippet {
+    title: String,
+    description: String,
+    published_at: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct PlaylistContentDetails {
+    item_count: u64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Playlist {
+    pub id: String,
+    snippet: PlaylistSnippet,
+    content_details: PlaylistContentDetails,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl Playlist {
+    pub fn title(&self) -> &str {
+        &self.snippet.title
+    }
+
+    pub fn description(&self) -> &str {
+        &self.snippet.description
+    }
+
+    pub fn published_at(&self) -> &str {
+        &self.snippet.published_at
+    }
+
+    pub fn item_count(&self) -> u64 {
+        self.content_details.item_count
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct ItemList<T> {
+    items: Vec<T>,
+    next_page_token: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct PlaylistItemDetails {
+    video_id: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct PlaylistItem {
+    content_details: PlaylistItemDetails,
+}
+
+impl YouTube {
+    fn fetch_channel(&self, filter: (&str, &str)) -> Result<YouTubeChannel, Error> {
+        let mut page: ItemList<YouTubeChannel> = self
+            .request(Method::GET, &["channels"])?
+            .query(&[("part", "snippet,statistics,contentDetails"), filter])
+            .send()?
+            .check_status()?
+            .json()?;
+        page.items
+            .pop()
+            .ok_or_else(|| Error::NotFound(format!("channel {}", filter.1)))
+    }
+
+    /// The authenticated user's channel.
+    pub fn my_channel(&self) -> Result<YouTubeChannel, Error> {
+        self.fetch_channel(("mine", "true"))
+    }
+
+    pub fn get_channel(&self, channel_id: &str) -> Result<YouTubeChannel, Error> {
+        self.fetch_channel(("id", channel_id))
+    }
+
+    /// Public playlists of given channel, or every playlist for the user's own channel.
+    pub fn list_playlists(&self, channel_id: &str) -> Result<Vec<Playlist>, Error> {
+        let mut playlists = vec![];
+        let mut page_token: Option<String> = None;
+        loop {
+            let mut params = vec![
+                ("part", "snippet,contentDetails"),
+                ("channelId", channel_id),
+                ("maxResults", "50"),
+            ];
+            if let Some(token) = &page_token {
+                params.push(("pageToken", token));
+            }
+            let page: ItemList<Playlist> = self
+                .request(Method::GET, &["playlists"])?
+                .query(&params)
+                .send()?
+                .check_status()?
+                .json()?;
+            playlists.extend(page.items);
+
+            match page.next_page_token {
+                Some(token) => page_token = Some(token),
+                None => break,
+            }
+        }
+        Ok(playlists)
+    }
+
+    /// Ids of the videos in a playlist, in playlist order.
+    pub fn playlist_video_ids(&self, playlist_id: &str) -> Result<Vec<String>, Error> {
+        let mut ids = vec![];
+        let mut page_token: Option<String> = None;
+        loop {
+            let mut params = vec![
+                ("part", "contentDetails"),
+                ("playlistId", playlist_id),
+                ("maxResults", "50"),
+            ];
+            if let Some(token) = &page_token {
+                params.push(("pageToken", token));
+            }
+            let page: ItemList<PlaylistItem> = self
+                .request(Method::GET, &["playlistItems"])?
+                .query(&params)
+                .send()?
+                .check_status()?
+                .json()?;
+            ids.extend(page.items.into_iter().map(|i| i.content_details.video_id));
+
+            match page.next_page_token {
+                Some(token) => page_token = Some(token),
+                None => break,
+            }
+        }
+        Ok(ids)
+    }
+
+    /// Videos of a playlist with their metadata and statistics, in playlist order.
+    pub fn list_playlist_videos(&self, playlist_id: &str) -> Result<Vec<Video>, Error> {
+        let ids = self.playlist_video_ids(playlist_id)?;
+        let ids: Vec<&str> = ids.iter().map(String::as_str).collect();
+        self.get_videos(&ids)
+    }
+
+    /// Every video a channel uploaded, newest first. Private videos only show up for the
+    /// user's own channel.
+    pub fn list_channel_videos(&self, channel_id: &str) -> Result<Vec<Video>, Error> {
+        let channel = self.get_channel(channel_id)?;
+        self.list_playlist_videos(channel.uploads_playlist())
+    }
+}
@@ -0,0 +1,68 @@
+use reqwest::blocking::RequestBuilder;
+use reqwest::Method;
+
+use crate::api;
+use crate::auth::Auth;
+use crate::error::Error;
+use crate::retry::RetryPolicy;
+use crate::scope::Scope;
+
+mod channels;
+mod upload;
+mod videos;
+
+pub use channels::{ChannelStatistics, Playlist, YouTubeChannel};
+pub use upload::VideoDetails;
+pub use videos::{Video, VideoPrivacy, VideoSnippet, VideoStatistics, VideoStatus};
+
+const YOUTUBE_BASE_URL: &str = "https://www.googleapis.com/youtube/v3";
+const YOUTUBE_UPLOAD_URL: &str = "https://www.googleapis.com/upload/youtube/v3";
+
+/// YouTube Data API client. Shares its saved token with the other clients.
+///
+/// ```no_run
+/// # use std::path::Path;
+/// # use google_api::*;
+/// # let (client_id, client_secret) = (String::new(), String::new());
+/// let youtube = YouTube::new(client_id, client_secret, None)?;
+/// let details = VideoDetails::new("Trip recap").privacy(VideoPrivacy::Unlisted);
+/// let video = youtube.upload_video(Path::new("recap.mp4"), &details)?;
+/// for video in youtube.list_channel_videos(&youtube.my_channel()?.id)? {
+///     println!("{}: {:?} views", video.snippet.title, video.statistics.view_count);
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug)]
+pub struct YouTube {
+    auth: Auth,
+    client: reqwest::blocking::Client,
+    retry: RetryPolicy,
+}
+
+impl YouTube {
+    /// Authenticate with given OAuth client. `scopes` defaults to `Scope::Youtube`.
+    pub fn new(
+        client_id: String,
+        client_secret: String,
+        scopes: Option<Vec<String>>,
+    ) -> Result<YouTube, Error> {
+        let scopes = scopes.unwrap_or_else(|| vec![Scope::Youtube.into()]);
+
+        Ok(YouTube {
+            auth: Auth::from_config(&client_id, &client_secret, &scopes)?,
+            client: reqwest::blocking::Client::new(),
+            retry: RetryPolicy::default(),
+        })
+    }
+
+    /// Set how failed upload chunks are retried.
+    pub fn retry_policy(mut self, retry: RetryPolicy) -> YouTube {
+        self.retry = retry;
+        self
+    }
+
+    /// Authorized request for the endpoint made of given path segments, e.g. `["videos"]`.
+    fn request(&self, method: Method, segments: &[&str]) -> Result<RequestBuilder, Error> {
+        api::request(&self.client, &self.auth, YOUTUBE_BASE_URL, method, segments)
+    }
+}
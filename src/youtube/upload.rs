@@ -0,0 +1,120 @@
+use std::fs;
+use std::path::Path;
+
+use reqwest::Method;
+use serde::Serialize;
+
+use super::{Video, VideoPrivacy, YouTube, YOUTUBE_UPLOAD_URL};
+use crate::api;
+use crate::error::{CheckStatus, Error};
+use crate::mime::MimeType;
+use crate::resumable::send_file_chunks;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct UploadSnippet {
+    title: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    description: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    category_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct UploadStatus {
+    privacy_status: VideoPrivacy,
+    self_declared_made_for_kids: bool,
+}
+
+/// Title, description and settings of a video to upload. Videos are private unless set
+/// otherwise.
+///
+/// ```
+/// # use google_api::*;
+/// let details = VideoDetails::new("Trip recap")
+///     .description("Two weeks in two minutes")
+///     .tags(&["travel", "timelapse"])
+///     .privacy(VideoPrivacy::Unlisted);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct VideoDetails {
+    snippet: UploadSnippet,
+    status: UploadStatus,
+}
+
+impl VideoDetails {
+    pub fn new(title: &str) -> VideoDetails {
+        let mut details = VideoDetails::default();
+        details.snippet.title = title.to_string();
+        details
+    }
+
+    pub fn description(mut self, description: &str) -> VideoDetails {
+        self.snippet.description = description.to_string();
+        self
+    }
+
+    pub fn tags(mut self, tags: &[&str]) -> VideoDetails {
+        self.snippet.tags = tags.iter().map(|t| t.to_string()).collect();
+        self
+    }
+
+    /// Numeric video category, e.g. `"22"` for People & Blogs.
+    pub fn category(mut self, category_id: &str) -> VideoDetails {
+        self.snippet.category_id = Some(category_id.to_string());
+        self
+    }
+
+    pub fn privacy(mut self, privacy: VideoPrivacy) -> VideoDetails {
+        self.status.privacy_status = privacy;
+        self
+    }
+
+    /// Declare the video as made for children, which turns off comments and personalized ads.
+    pub fn made_for_kids(mut self) -> VideoDetails {
+        self.status.self_declared_made_for_kids = true;
+        self
+    }
+}
+
+impl YouTube {
+    /// Upload a video file with the resumable protocol. The returned video is still being
+    /// processed; its `status.upload_status` turns `processed` once YouTube is done.
+    pub fn upload_video(&self, path: &Path, details: &VideoDetails) -> Result<Video, Error> {
+        let size = fs::metadata(path)?.len();
+        let resp = api::request(
+            &self.client,
+            &self.auth,
+            YOUTUBE_UPLOAD_URL,
+            Method::POST,
+            &["videos"],
+        )?
+        .query(&[("uploadType", "resumable"), ("part", "snippet,status")])
+        .header("X-Upload-Content-Type", MimeType::from_path(path).as_str())
+        .header("X-Upload-Content-Length", size)
+        .json(details)
+        .send()?
+        .check_status()?;
+        let session_uri = match resp.headers().get("Location").map(|l| l.to_str()) {
+            Some(Ok(uri)) => uri.to_string(),
+            _ => {
+                return Err(Error::UnexpectedResponse(String::from(
+                    "resumable upload session response had no Location header",
+                )))
+            }
+        };
+
+        let mut offset = 0;
+        let resp = send_file_chunks(
+            || self.client.put(session_uri.as_str()),
+            &self.retry,
+            path,
+            size,
+            &mut offset,
+        )?;
+        Ok(resp.json()?)
+    }
+}
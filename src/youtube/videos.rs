@@ -0,0 +1,123 @@
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+
+use super::YouTube;
+use crate::error::{CheckStatus, Error};
+use crate::models::{de_int64, ExtraFields};
+
+/// Parts fetched for every video.
+pub(super) const VIDEO_PARTS: &str = "snippet,statistics,status,contentDetails";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VideoPrivacy {
+    #[default]
+    Private,
+    Unlisted,
+    Public,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct VideoSnippet {
+    pub title: String,
+    pub description: String,
+    pub channel_id: String,
+    pub channel_title: String,
+    pub published_at: String,
+    pub tags: Vec<String>,
+    pub category_id: String,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+/// Counts are `None` when the owner hides them.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct VideoStatistics {
+    #[serde(deserialize_with = "de_int64")]
+    pub view_count: Option<u64>,
+    #[serde(deserialize_with = "de_int64")]
+    pub like_count: Option<u64>,
+    #[serde(deserialize_with = "de_int64")]
+    pub comment_count: Option<u64>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct VideoStatus {
+    pub privacy_status: VideoPrivacy,
+    /// `uploaded` while processing, then `processed`, or `failed`/`rejected`.
+    pub upload_status: String,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct ContentDetails {
+    duration: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Video {
+    pub id: String,
+    pub snippet: VideoSnippet,
+    pub statistics: VideoStatistics,
+    pub status: VideoStatus,
+    content_details: ContentDetails,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl Video {
+    /// Length as an ISO 8601 duration, e.g. `PT4M13S`.
+    pub fn duration(&self) -> &str {
+        &self.content_details.duration
+    }
+
+    pub fn url(&self) -> String {
+        format!("https://www.youtube.com/watch?v={}", self.id)
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct VideoList {
+    items: Vec<Video>,
+}
+
+impl YouTube {
+    /// Metadata and statistics of given videos, fetched 50 at a time. Ids that don't exist or
+    /// aren't visible to the user are left out.
+    pub fn get_videos(&self, video_ids: &[&str]) -> Result<Vec<Video>, Error> {
+        let mut videos = vec![];
+        for chunk in video_ids.chunks(50) {
+            let page: VideoList = self
+                .request(Method::GET, &["videos"])?
+                .query(&[("part", VIDEO_PARTS), ("id", &chunk.join(","))])
+                .send()?
+                .check_status()?
+                .json()?;
+            videos.extend(page.items);
+        }
+        Ok(videos)
+    }
+
+    pub fn get_video(&self, video_id: &str) -> Result<Video, Error> {
+        self.get_videos(&[video_id])?
+            .pop()
+            .ok_or_else(|| Error::NotFound(format!("video {}", video_id)))
+    }
+
+    pub fn delete_video(&self, video_id: &str) -> Result<(), Error> {
+        self.request(Method::DELETE, &["videos"])?
+            .query(&[("id", video_id)])
+            .send()?
+            .check_status()?;
+        Ok(())
+    }
+}